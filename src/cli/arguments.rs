@@ -0,0 +1,368 @@
+//! Flag-style tool argument parsing with schema-aware type coercion.
+//!
+//! `--key value` arguments arrive as strings. When the tool's `inputSchema`
+//! is known, each value is converted to the type its property declares:
+//!
+//! - `integer` / `number` — `--count 5` → `5`
+//! - `boolean` — `true/false/yes/no`; a bare `--flag` is `true` and
+//!   `--no-flag` is `false`
+//! - `array` — repeated flags (`--tag a --tag b`) or a comma list
+//!   (`--tag a,b`), with items converted by the `items` schema
+//! - `object` — dotted keys (`--filter.owner bob`) build nested objects
+//!
+//! Properties the schema doesn't describe keep the schema-less behaviour:
+//! bare flags are `true`, `--key=value` is parsed as JSON when possible and
+//! `--key value` stays a string unless it is a JSON object.
+
+use crate::error::{McpError, Result};
+use serde_json::{Map, Value};
+
+/// Value given for one flag on the command line.
+#[derive(Debug, Clone, PartialEq)]
+enum FlagValue {
+    /// `--key` with no value
+    Bare,
+    /// `--key value`
+    Separate(String),
+    /// `--key=value`
+    Inline(String),
+}
+
+/// Parse tool arguments, converting flag values using `schema` if given.
+///
+/// Arguments starting with `{` are parsed as a single JSON object and are
+/// never converted.
+///
+/// # Errors
+/// Returns McpError::InvalidJson for malformed JSON arguments
+/// Returns McpError::UsageError for malformed flags or values that don't
+/// match the type the schema declares
+pub(crate) fn parse_tool_arguments(args: Vec<String>, schema: Option<&Value>) -> Result<Value> {
+    if args.is_empty() {
+        return Ok(Value::Object(Map::new()));
+    }
+
+    // If first arg starts with '{', treat as JSON (backward compatible)
+    if args.first().map(|s| s.starts_with('{')).unwrap_or(false) {
+        let json_str = args.join(" ");
+        return serde_json::from_str(&json_str).map_err(|e| McpError::InvalidJson { source: e });
+    }
+
+    let mut arguments = Value::Object(Map::new());
+    for (key, value) in parse_flags(args)? {
+        let Some(schema) = schema else {
+            insert(&mut arguments, &[key.as_str()], untyped_value(value), false)?;
+            continue;
+        };
+
+        if let Some(property) = property_schema(schema, &[key.as_str()]) {
+            let is_array = schema_type(property) == Some("array");
+            let value = coerce(&key, value, property)?;
+            insert(&mut arguments, &[key.as_str()], value, is_array)?;
+        } else if let Some(name) = key.strip_prefix("no-")
+            && value == FlagValue::Bare
+            && property_schema(schema, &[name]).map(schema_type) == Some(Some("boolean"))
+        {
+            insert(&mut arguments, &[name], Value::Bool(false), false)?;
+        } else if let Some(path) = dotted_path(schema, &key) {
+            let property = property_schema(schema, &path).unwrap_or(&Value::Null);
+            let is_array = schema_type(property) == Some("array");
+            let value = coerce(&key, value, property)?;
+            insert(&mut arguments, &path, value, is_array)?;
+        } else {
+            insert(&mut arguments, &[key.as_str()], untyped_value(value), false)?;
+        }
+    }
+    Ok(arguments)
+}
+
+/// Convert the text given for one property to the type `property` declares.
+///
+/// Accepts the same forms as `--key value`, including comma lists for arrays.
+///
+/// # Errors
+/// Returns McpError::UsageError if the text doesn't match the declared type
+pub(crate) fn parse_property_value(key: &str, text: &str, property: &Value) -> Result<Value> {
+    coerce(key, FlagValue::Separate(text.to_string()), property)
+}
+
+/// Split `--key value`, `--key=value` and bare `--key` flags.
+fn parse_flags(args: Vec<String>) -> Result<Vec<(String, FlagValue)>> {
+    let mut flags = Vec::new();
+    let mut args = args.into_iter().peekable();
+    while let Some(arg) = args.next() {
+        let Some(key) = arg.strip_prefix("--") else {
+            return Err(McpError::usage_error(format!(
+                "Invalid argument: {}. Use JSON format: {{\"key\": \"value\"}} or flags: --key value",
+                arg
+            )));
+        };
+
+        if let Some((key, value)) = key.split_once('=') {
+            flags.push((key.to_string(), FlagValue::Inline(value.to_string())));
+        } else if let Some(value) = args.next_if(|next| !next.starts_with("--")) {
+            flags.push((key.to_string(), FlagValue::Separate(value)));
+        } else {
+            flags.push((key.to_string(), FlagValue::Bare));
+        }
+    }
+    Ok(flags)
+}
+
+/// Value of a flag whose property type is unknown.
+fn untyped_value(value: FlagValue) -> Value {
+    match value {
+        FlagValue::Bare => Value::Bool(true),
+        FlagValue::Separate(text) if text.starts_with('{') => parse_json_or_string(&text),
+        FlagValue::Separate(text) => Value::String(text),
+        FlagValue::Inline(text) => parse_json_or_string(&text),
+    }
+}
+
+/// Parse a value string as JSON, falling back to a plain string.
+fn parse_json_or_string(text: &str) -> Value {
+    let trimmed = text.trim();
+    serde_json::from_str(trimmed).unwrap_or_else(|_| Value::String(trimmed.to_string()))
+}
+
+/// Convert a flag value to the type `property` declares.
+fn coerce(key: &str, value: FlagValue, property: &Value) -> Result<Value> {
+    let Some(expected) = schema_type(property) else {
+        return Ok(untyped_value(value));
+    };
+    let text = match value {
+        FlagValue::Bare if expected == "boolean" => return Ok(Value::Bool(true)),
+        FlagValue::Bare => {
+            return Err(McpError::usage_error(format!(
+                "--{} expects {} value",
+                key,
+                type_article(expected)
+            )));
+        }
+        FlagValue::Separate(text) | FlagValue::Inline(text) => text,
+    };
+
+    if expected == "array" {
+        let items = property.get("items").unwrap_or(&Value::Null);
+        if text.trim_start().starts_with('[') {
+            let value: Value = serde_json::from_str(&text).map_err(|e| {
+                McpError::usage_error(format!("--{} expects a JSON array: {}", key, e))
+            })?;
+            return Ok(value);
+        }
+        return text
+            .split(',')
+            .map(|item| coerce_text(key, item.trim(), items))
+            .collect::<Result<Vec<_>>>()
+            .map(Value::Array);
+    }
+    coerce_text(key, &text, property)
+}
+
+/// Convert a single text value to the scalar or object type `property` declares.
+fn coerce_text(key: &str, text: &str, property: &Value) -> Result<Value> {
+    let mismatch = |expected: &str| {
+        McpError::usage_error(format!(
+            "--{} expects {}, got '{}'",
+            key,
+            type_article(expected),
+            text
+        ))
+    };
+
+    match schema_type(property) {
+        Some("string") => Ok(Value::String(text.to_string())),
+        Some("integer") => text
+            .parse::<i64>()
+            .map(Value::from)
+            .map_err(|_| mismatch("integer")),
+        Some("number") => {
+            if let Ok(integer) = text.parse::<i64>() {
+                return Ok(Value::from(integer));
+            }
+            text.parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(Value::Number)
+                .ok_or_else(|| mismatch("number"))
+        }
+        Some("boolean") => match text.to_ascii_lowercase().as_str() {
+            "true" | "yes" => Ok(Value::Bool(true)),
+            "false" | "no" => Ok(Value::Bool(false)),
+            _ => Err(mismatch("boolean")),
+        },
+        Some("object") => match serde_json::from_str::<Value>(text) {
+            Ok(value @ Value::Object(_)) => Ok(value),
+            _ => Err(mismatch("object")),
+        },
+        _ => Ok(parse_json_or_string(text)),
+    }
+}
+
+/// Type a property declares, ignoring `null` in type unions.
+fn schema_type(property: &Value) -> Option<&str> {
+    match property.get("type")? {
+        Value::String(t) => Some(t.as_str()),
+        Value::Array(types) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|t| *t != "null"),
+        _ => None,
+    }
+}
+
+/// "an integer", "a string", ...
+fn type_article(schema_type: &str) -> String {
+    match schema_type {
+        "integer" | "array" | "object" => format!("an {}", schema_type),
+        _ => format!("a {}", schema_type),
+    }
+}
+
+/// Schema of the property at `path`, following nested `properties`.
+fn property_schema<'a>(schema: &'a Value, path: &[&str]) -> Option<&'a Value> {
+    path.iter()
+        .try_fold(schema, |schema, name| schema.get("properties")?.get(*name))
+}
+
+/// Split a dotted key into a path if it names a nested property.
+fn dotted_path<'a>(schema: &Value, key: &'a str) -> Option<Vec<&'a str>> {
+    let path: Vec<&str> = key.split('.').collect();
+    if path.len() < 2 {
+        return None;
+    }
+    // Every segment but the last must be an object property
+    let parent = property_schema(schema, &path[..path.len() - 1])?;
+    (schema_type(parent) == Some("object")).then_some(path)
+}
+
+/// Set the value at `path`, creating intermediate objects.
+///
+/// Repeated array flags are appended to the values already given.
+fn insert(arguments: &mut Value, path: &[&str], value: Value, append: bool) -> Result<()> {
+    let conflict = || McpError::usage_error(format!("Conflicting values for --{}", path.join(".")));
+
+    let (last, parents) = path.split_last().expect("argument path is never empty");
+    let mut target = arguments;
+    for name in parents {
+        target = target
+            .as_object_mut()
+            .ok_or_else(conflict)?
+            .entry(name.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+    let object = target.as_object_mut().ok_or_else(conflict)?;
+
+    match (object.get_mut(*last), value) {
+        (Some(Value::Array(existing)), Value::Array(items)) if append => existing.extend(items),
+        (_, value) => {
+            object.insert(last.to_string(), value);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "count": { "type": "integer" },
+                "ratio": { "type": "number" },
+                "verbose": { "type": "boolean" },
+                "name": { "type": "string" },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "ids": { "type": "array", "items": { "type": "integer" } },
+                "filter": {
+                    "type": "object",
+                    "properties": {
+                        "owner": { "type": "string" },
+                        "limit": { "type": "integer" }
+                    }
+                }
+            }
+        })
+    }
+
+    fn parse(flags: &[&str]) -> Result<Value> {
+        parse_tool_arguments(args(flags), Some(&schema()))
+    }
+
+    #[test]
+    fn test_coerces_numbers_and_strings() {
+        let result = parse(&["--count", "5", "--ratio=0.5", "--name", "42"]).unwrap();
+        assert_eq!(result, json!({ "count": 5, "ratio": 0.5, "name": "42" }));
+    }
+
+    #[test]
+    fn test_coerces_booleans() {
+        assert_eq!(parse(&["--verbose"]).unwrap(), json!({ "verbose": true }));
+        assert_eq!(
+            parse(&["--verbose", "no"]).unwrap(),
+            json!({ "verbose": false })
+        );
+        assert_eq!(
+            parse(&["--verbose=Yes"]).unwrap(),
+            json!({ "verbose": true })
+        );
+        assert_eq!(
+            parse(&["--no-verbose"]).unwrap(),
+            json!({ "verbose": false })
+        );
+    }
+
+    #[test]
+    fn test_collects_arrays() {
+        let result = parse(&["--tags", "a", "--tags", "b,c", "--ids", "1,2"]).unwrap();
+        assert_eq!(result, json!({ "tags": ["a", "b", "c"], "ids": [1, 2] }));
+
+        let result = parse(&["--tags", r#"["x, y"]"#]).unwrap();
+        assert_eq!(result, json!({ "tags": ["x, y"] }));
+    }
+
+    #[test]
+    fn test_builds_nested_objects_from_dotted_keys() {
+        let result = parse(&["--filter.owner", "bob", "--filter.limit", "3"]).unwrap();
+        assert_eq!(result, json!({ "filter": { "owner": "bob", "limit": 3 } }));
+    }
+
+    #[test]
+    fn test_reports_mismatches_as_usage_errors() {
+        for flags in [
+            &["--count", "five"][..],
+            &["--verbose", "maybe"],
+            &["--ids", "1,x"],
+            &["--count"],
+        ] {
+            let error = parse(flags).unwrap_err();
+            assert!(matches!(error, McpError::UsageError { .. }), "{:?}", flags);
+        }
+        assert_eq!(
+            parse(&["--count", "five"]).unwrap_err().to_string(),
+            "Usage error: --count expects an integer, got 'five'"
+        );
+    }
+
+    #[test]
+    fn test_unknown_properties_keep_untyped_behaviour() {
+        let result = parse(&["--other", "5", "--raw=5", "--flag", "--a.b", "x"]).unwrap();
+        assert_eq!(
+            result,
+            json!({ "other": "5", "raw": 5, "flag": true, "a.b": "x" })
+        );
+    }
+
+    #[test]
+    fn test_without_schema_values_are_untyped() {
+        let result = parse_tool_arguments(args(&["--count", "5", "--no-verbose"]), None).unwrap();
+        assert_eq!(result, json!({ "count": "5", "no-verbose": true }));
+    }
+}
@@ -1,14 +1,20 @@
 //! Execute tool command implementation.
 
+use crate::cli::arguments::parse_tool_arguments;
 use crate::cli::formatters;
-use crate::cli::models::CallResultModel;
+use crate::cli::interactive;
+use crate::cli::models::{CallResultModel, ProgressModel};
+use crate::config::ServerConfig;
+use crate::daemon::protocol::ProgressUpdate;
 use crate::error::{McpError, Result};
 use crate::format::OutputMode;
+use crate::format::schema::validate_against_schema;
 use crate::ipc::ProtocolClient;
 use crate::output::print_error;
 use crate::retry::{RetryConfig, retry_with_backoff};
+use colored::Colorize;
 use futures_util::FutureExt;
-use std::io::{self, Read};
+use std::io::{self, IsTerminal, Read};
 use std::sync::Arc;
 
 /// Parse command-line arguments into a JSON object.
@@ -19,80 +25,11 @@ use std::sync::Arc;
 /// - --key value → {"key": "value"}
 /// - --key=value → {"key": "value"}
 /// - --key {"a":1} → parse JSON value → {"key": {"a": 1}}
-fn parse_arguments(args: Vec<String>) -> Result<serde_json::Value> {
-    // Empty args → empty object (don't try to read stdin - that happens at a higher level)
-    if args.is_empty() {
-        return Ok(serde_json::Value::Object(serde_json::Map::new()));
-    }
-
-    // If first arg starts with '{', treat as JSON (backward compatible)
-    if args.first().map(|s| s.starts_with('{')).unwrap_or(false) {
-        let json_str = args.join(" ");
-        return serde_json::from_str(&json_str).map_err(|e| McpError::InvalidJson { source: e });
-    }
-
-    // Parse as --key value pairs
-    let mut map = serde_json::Map::new();
-    let mut i = 0;
-    while i < args.len() {
-        let arg = &args[i];
-        if !arg.starts_with("--") {
-            return Err(McpError::usage_error(&format!(
-                "Invalid argument: {}. Use JSON format: {{\"key\": \"value\"}} or flags: --key value",
-                arg
-            )));
-        }
-
-        // Strip -- prefix
-        let key_with_value = arg.trim_start_matches("--");
-
-        // Check for = separator in the argument itself (--key=value)
-        if let Some((k, v)) = key_with_value.split_once('=') {
-            // --key=value format
-            let value = parse_json_value(v)?;
-            map.insert(k.to_string(), value);
-            i += 1;
-            continue;
-        }
-
-        // No = separator
-        let key = key_with_value;
-        
-        // Check if next arg exists and is not a flag
-        let has_value = i + 1 < args.len() && !args[i + 1].starts_with("--");
-        
-        if has_value {
-            let next_arg = &args[i + 1];
-            if next_arg.starts_with('{') {
-                // Next arg is JSON → use as value directly
-                let value = parse_json_value(next_arg)?;
-                map.insert(key.to_string(), value);
-                i += 2;
-            } else {
-                // --key value format
-                map.insert(key.to_string(), serde_json::Value::String(next_arg.clone()));
-                i += 2;
-            }
-        } else {
-            // Boolean flag (no value or next arg is another flag)
-            map.insert(key.to_string(), serde_json::Value::Bool(true));
-            i += 1;
-        }
-    }
-
-    Ok(serde_json::Value::Object(map))
-}
-
-/// Parse a value string into JSON, handling both plain strings and JSON.
-fn parse_json_value(value: &str) -> Result<serde_json::Value> {
-    let trimmed = value.trim();
-    // Try to parse as JSON first
-    if let Ok(parsed) = serde_json::from_str(trimmed) {
-        Ok(parsed)
-    } else {
-        // Treat as plain string
-        Ok(serde_json::Value::String(trimmed.to_string()))
-    }
+///
+/// Values are not converted to schema types; see
+/// [`parse_tool_arguments`] for schema-aware parsing.
+pub(crate) fn parse_arguments(args: Vec<String>) -> Result<serde_json::Value> {
+    parse_tool_arguments(args, None)
 }
 
 /// Execute tool call command.
@@ -103,13 +40,21 @@ fn parse_json_value(value: &str) -> Result<serde_json::Value> {
 /// Implements TASK-03: colored output for stdin and error cases.
 /// Implements OUTP-07, OUTP-08: JSON output mode
 ///
+/// On a terminal in human output mode, required arguments that weren't given
+/// are prompted for and the equivalent command line is printed.
+///
 /// # Arguments
 /// * `daemon` - Daemon IPC client (will be wrapped in `Arc<Mutex>`)
 /// * `tool_id` - Tool identifier in format "server/tool" or "server tool"
-/// * `args` - Arguments as Vec<String>, supports: JSON, --key value, --key=value, --key {"a":1}
+/// * `args` - Arguments as Vec<String>, supports: JSON, --key value, --key=value, --key {"a":1};
+///   flag values are converted to the types the tool's inputSchema declares
+/// * `timeout` - Seconds to wait for the result, overriding the configured timeout
+/// * `validate` - Check arguments against the tool's inputSchema before sending
 /// * `output_mode` - Output format (human or JSON)
 ///
 /// # Errors
+/// Returns McpError::UsageError if a flag value doesn't match its schema type
+/// Returns McpError::InvalidArguments if arguments don't match the tool's inputSchema
 /// Returns McpError::InvalidProtocol for malformed response
 /// Returns McpError::Timeout if timeout exceeded (EXEC-06)
 /// Returns McpError::MaxRetriesExceeded if max retries exceeded (EXEC-07)
@@ -117,6 +62,8 @@ pub async fn cmd_call_tool(
     mut daemon: Box<dyn ProtocolClient>,
     tool_id: &str,
     args: Vec<String>,
+    timeout: Option<u64>,
+    validate: bool,
     output_mode: OutputMode,
 ) -> Result<()> {
     let (server_name, tool_name) = crate::cli::info::parse_tool_id(tool_id)?;
@@ -135,8 +82,43 @@ pub async fn cmd_call_tool(
         }
     })?;
 
+    // Check if tool is disabled (FILT-04)
+    let server_config = config.get_server(&server_name);
+    if let Some(server_config) = server_config
+        && let Some(error_msg) = disabled_tool_message(server_config, &tool_name)
+    {
+        if output_mode == OutputMode::Json {
+            let model = CallResultModel {
+                server_name: server_name.clone(),
+                tool_name: tool_name.clone(),
+                success: false,
+                result: None,
+                error: Some(error_msg.clone()),
+                execution_time_ms: None,
+                retries: 0,
+            };
+            formatters::format_call_result(&model, output_mode);
+        } else {
+            print_error(&error_msg);
+        }
+
+        return Err(McpError::UsageError {
+            message: "Tool execution blocked by disabled_tools configuration. Remove patterns from disabled_tools list to allow this tool.".to_string(),
+        });
+    }
+
+    // The inputSchema types flag values and checks the arguments before sending
+    let flag_style = args.first().is_some_and(|arg| !arg.starts_with('{'));
+    // Missing required arguments are asked for only when a person is there to answer
+    let interactive = output_mode == OutputMode::Human && io::stdin().is_terminal();
+    let input_schema = if validate || flag_style || interactive {
+        fetch_input_schema(daemon.as_mut(), &server_name, &tool_name).await
+    } else {
+        None
+    };
+
     // Parse arguments - supports JSON, --key value, --key=value, --key {"a":1}
-    let arguments: serde_json::Value = match parse_arguments(args) {
+    let mut arguments: serde_json::Value = match parse_tool_arguments(args, input_schema.as_ref()) {
         Ok(parsed) => parsed,
         Err(e) => {
             print_error(&format!("Failed to parse arguments: {}", e));
@@ -144,44 +126,48 @@ pub async fn cmd_call_tool(
         }
     };
 
-    // Check if tool is disabled (FILT-04)
-    let server_config = config.get_server(&server_name);
-    if let Some(server_config) = server_config {
-        // Check if tool matches disabled_tools patterns
-        if let Some(disabled_patterns) = &server_config.disabled_tools {
-            let is_disabled = crate::cli::filter::tools_match_any(&tool_name, disabled_patterns);
-            if is_disabled.is_some() {
-                let patterns_str = disabled_patterns.join(", ");
-                let error_msg = format!(
-                    "Tool '{}' on server '{}' is disabled (blocked by patterns: {})",
-                    tool_name, server_name, patterns_str
-                );
-
-                if output_mode == OutputMode::Json {
-                    let model = CallResultModel {
-                        server_name: server_name.clone(),
-                        tool_name: tool_name.clone(),
-                        success: false,
-                        result: None,
-                        error: Some(error_msg.clone()),
-                        execution_time_ms: None,
-                        retries: 0,
-                    };
-                    formatters::format_call_result(&model, output_mode);
-                } else {
-                    print_error(&error_msg);
-                }
-
-                return Err(McpError::UsageError {
-                    message: "Tool execution blocked by disabled_tools configuration. Remove patterns from disabled_tools list to allow this tool.".to_string(),
-                });
-            }
+    if interactive
+        && let Some(schema) = &input_schema
+        && interactive::prompt_missing_arguments(
+            schema,
+            &mut arguments,
+            &mut io::stdin().lock(),
+            &mut io::stderr(),
+        )?
+    {
+        eprintln!(
+            "{} {}",
+            "Equivalent command:".dimmed(),
+            interactive::command_line(tool_id, timeout, validate, &arguments)
+        );
+    }
+
+    // Reject arguments the tool's inputSchema doesn't allow before sending them
+    if validate
+        && let Some(schema) = &input_schema
+        && let Err(e) = validate_arguments(&tool_name, schema, &arguments)
+    {
+        if output_mode == OutputMode::Json {
+            let model = CallResultModel {
+                server_name: server_name.clone(),
+                tool_name: tool_name.clone(),
+                success: false,
+                result: None,
+                error: Some(e.to_string()),
+                execution_time_ms: None,
+                retries: 0,
+            };
+            formatters::format_call_result(&model, output_mode);
         }
+        return Err(e);
     }
 
     // Execute tool with retry logic (EXEC-05, EXEC-07)
     let retry_config = RetryConfig::from_config(&config);
 
+    // --timeout overrides the server's timeout_secs, which overrides the global one (EXEC-06)
+    let timeout_secs = timeout.unwrap_or_else(|| config.server_timeout_secs(&server_name));
+
     // Create shared access for operation closure
     let daemon_shared = Arc::new(tokio::sync::Mutex::new(daemon));
 
@@ -194,10 +180,29 @@ pub async fn cmd_call_tool(
 
         // Convert async block to boxed trait object Future using futures-util
         Box::new(async move {
+            // Progress goes to stderr so stdout carries only the result
+            let mut on_progress = |update: ProgressUpdate| {
+                let model = ProgressModel {
+                    server_name: server_name_clone.clone(),
+                    tool_name: tool_name_clone.clone(),
+                    progress: update.progress,
+                    total: update.total,
+                    message: update.message,
+                };
+                formatters::format_progress(&model, output_mode);
+            };
             let mut daemon_guard = daemon_shared.lock().await;
-            daemon_guard
-                .execute_tool(&server_name_clone, &tool_name_clone, arguments_clone)
-                .await
+            let result = daemon_guard
+                .execute_tool_with_progress(
+                    &server_name_clone,
+                    &tool_name_clone,
+                    arguments_clone,
+                    Some(timeout_secs),
+                    &mut on_progress,
+                )
+                .await;
+            formatters::clear_progress(output_mode);
+            result
         })
         .boxed()
     };
@@ -232,15 +237,12 @@ pub async fn cmd_call_tool(
             execution_time_ms: Some(execution_time_ms),
             retries: attempts,
         },
-        Err(McpError::OperationCancelled { timeout }) => CallResultModel {
+        Err(McpError::Timeout { timeout }) => CallResultModel {
             server_name: server_name.clone(),
             tool_name: tool_name.clone(),
             success: false,
             result: None,
-            error: Some(format!(
-                "Tool execution cancelled after {}s timeout",
-                timeout
-            )),
+            error: Some(format!("Tool execution timed out after {}s", timeout)),
             execution_time_ms: Some(execution_time_ms),
             retries: 0,
         },
@@ -265,15 +267,75 @@ pub async fn cmd_call_tool(
             return Err(McpError::MaxRetriesExceeded {
                 attempts: model.retries,
             });
-        } else if err.contains("timeout") {
-            // Extract timeout value from error message
-            return Err(McpError::OperationCancelled { timeout: 30 }); // Default timeout
+        } else if err.contains("timed out") {
+            return Err(McpError::Timeout {
+                timeout: timeout_secs,
+            });
         }
     }
 
     Ok(())
 }
 
+/// Explain why a tool is blocked, if it matches the server's `disabled_tools`
+/// patterns (FILT-04).
+pub(crate) fn disabled_tool_message(
+    server_config: &ServerConfig,
+    tool_name: &str,
+) -> Option<String> {
+    let disabled_patterns = server_config.disabled_tools.as_ref()?;
+    crate::cli::filter::tools_match_any(tool_name, disabled_patterns)?;
+    Some(format!(
+        "Tool '{}' on server '{}' is disabled (blocked by patterns: {})",
+        tool_name,
+        server_config.name,
+        disabled_patterns.join(", ")
+    ))
+}
+
+/// Fetch the tool's `inputSchema` from the server.
+///
+/// Returns `None` if the tool list can't be fetched or doesn't include the
+/// tool; arguments are then sent untyped and unchecked and the server has
+/// the final say.
+async fn fetch_input_schema(
+    daemon: &mut dyn ProtocolClient,
+    server_name: &str,
+    tool_name: &str,
+) -> Option<serde_json::Value> {
+    let tools = match daemon.list_tools(server_name).await {
+        Ok(tools) => tools,
+        Err(e) => {
+            tracing::debug!("Skipping inputSchema lookup, listing tools failed: {}", e);
+            return None;
+        }
+    };
+    let Some(tool) = tools.into_iter().find(|tool| tool.name == tool_name) else {
+        tracing::debug!("Skipping inputSchema lookup, '{}' is not listed", tool_name);
+        return None;
+    };
+    Some(tool.input_schema)
+}
+
+/// Check tool arguments against the tool's `inputSchema`.
+///
+/// Every violation is reported with its JSON pointer path.
+pub(crate) fn validate_arguments(
+    tool_name: &str,
+    schema: &serde_json::Value,
+    arguments: &serde_json::Value,
+) -> Result<()> {
+    let violations = validate_against_schema(schema, arguments);
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(McpError::InvalidArguments {
+            tool: tool_name.to_string(),
+            violations: violations.iter().map(ToString::to_string).collect(),
+        })
+    }
+}
+
 /// Get current timestamp in seconds since epoch.
 fn get_timestamp() -> String {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -5,15 +5,23 @@
 
 use crate::cli::DetailLevel;
 use crate::cli::call::cmd_call_tool;
+use crate::cli::completions::{CompletionKind, cmd_complete};
 use crate::cli::daemon_lifecycle::{
     create_auto_daemon_client, create_direct_client, create_require_daemon_client,
 };
+use crate::cli::docs::{DocsFormat, cmd_docs};
+use crate::cli::export::cmd_export;
 use crate::cli::info::{cmd_server_info, cmd_tool_info};
-use crate::cli::list::cmd_list_servers;
+use crate::cli::list::{cmd_list_servers, cmd_list_tools_page};
+use crate::cli::prompts::{cmd_get_prompt, cmd_list_prompts};
+use crate::cli::resources::{cmd_list_resource_templates, cmd_list_resources, cmd_read_resource};
 use crate::cli::search::cmd_search_tools;
+use crate::cli::shell::cmd_shell;
+use crate::cli::snapshot::{cmd_diff, cmd_snapshot};
 use crate::config::Config;
 use crate::error::Result;
 use crate::format::OutputMode;
+use crate::format::export::ExportFormat;
 use crate::ipc::ProtocolClient;
 use clap::Subcommand;
 use std::sync::Arc;
@@ -48,6 +56,8 @@ pub enum Commands {
     ///   mcp list                     # List all servers
     ///   mcp list -d                  # List with tool descriptions
     ///   mcp list -v                  # List with full schema
+    ///   mcp list --json --page-size 50                        # First page of tools
+    ///   mcp list --json --page-size 50 --cursor <next_cursor> # Next page
     List {
         /// Show detailed descriptions and parameters
         #[arg(short = 'd', long)]
@@ -56,6 +66,14 @@ pub enum Commands {
         /// Show verbose output with full schema
         #[arg(short = 'v', long)]
         verbose: bool,
+
+        /// Return tools one page at a time, with a cursor for the next page
+        #[arg(long, value_name = "N")]
+        page_size: Option<usize>,
+
+        /// Cursor returned by the previous page
+        #[arg(long, requires = "page_size")]
+        cursor: Option<String>,
     },
 
     /// Show details for a specific server (DISC-02)
@@ -104,12 +122,90 @@ pub enum Commands {
         #[arg(value_name = "TOOL")]
         tool: String,
 
+        /// Seconds to wait for the result (overrides timeout_secs in the config)
+        #[arg(long, value_name = "SECS")]
+        timeout: Option<u64>,
+
+        /// Send arguments without checking them against the tool's inputSchema
+        #[arg(long)]
+        no_validate: bool,
+
         /// Arguments: JSON or --key value pairs
         /// Supports: --key value, --key=value, --key {"json": "value"}
         #[arg(last = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
 
+    /// Serve all configured servers as one MCP server (gateway mode)
+    ///
+    /// Tools are advertised as `server__tool`, filtered by each server's
+    /// allowed_tools and disabled_tools, and calls are forwarded over
+    /// pooled connections.
+    ///
+    /// Examples:
+    ///   mcp serve --stdio             # MCP over stdin/stdout
+    ///   mcp serve --http 127.0.0.1:8808 --token s3cret
+    ///                                 # Streamable HTTP at /mcp
+    ///   mcp serve --rest 127.0.0.1:8809
+    ///                                 # POST /servers/{server}/tools/{tool}
+    #[command(group(clap::ArgGroup::new("listener").required(true)))]
+    Serve {
+        /// Speak MCP over stdin/stdout
+        #[arg(long, group = "listener")]
+        stdio: bool,
+
+        /// Serve Streamable HTTP on this address (e.g. 127.0.0.1:8808)
+        #[arg(long, value_name = "ADDR", group = "listener")]
+        http: Option<std::net::SocketAddr>,
+
+        /// Serve each tool as a plain HTTP endpoint on this address, with an
+        /// OpenAPI document at /openapi.json
+        #[arg(long, value_name = "ADDR", group = "listener")]
+        rest: Option<std::net::SocketAddr>,
+
+        /// Require `Authorization: Bearer <TOKEN>` on HTTP requests
+        /// (defaults to $MCP_GATEWAY_TOKEN)
+        #[arg(long, conflicts_with = "stdio")]
+        token: Option<String>,
+    },
+
+    /// Start an interactive shell that reuses one connection
+    ///
+    /// Runs list, info, tool, call and search with line editing, history and
+    /// tab completion of server and tool names. `use <server>` sets a
+    /// default server for `call` and `tool`.
+    ///
+    /// Examples:
+    ///   mcp shell                     # Start the shell
+    ///   mcp --no-daemon shell         # Without the daemon
+    Shell,
+
+    /// Print a shell completion script
+    ///
+    /// Bash, zsh and fish scripts also complete server and tool names.
+    ///
+    /// Examples:
+    ///   source <(mcp completions bash)                      # Current bash session
+    ///   mcp completions zsh > ~/.zfunc/_mcp                 # Install for zsh
+    ///   mcp completions fish > ~/.config/fish/completions/mcp.fish
+    Completions {
+        /// Shell to generate the script for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+
+    /// Print completion candidates (used by the completion scripts)
+    #[command(name = "__complete", hide = true)]
+    Complete {
+        /// What to complete
+        #[arg(value_enum)]
+        kind: CompletionKind,
+
+        /// Text typed so far
+        #[arg(default_value = "")]
+        word: String,
+    },
+
     /// Search for tools by name pattern (DISC-04)
     ///
     /// Examples:
@@ -130,6 +226,140 @@ pub enum Commands {
         #[arg(short = 'v', long)]
         verbose: bool,
     },
+
+    /// Export the tool catalog as LLM function declarations
+    ///
+    /// Tools are named `server__tool`, adjusted to each vendor's naming
+    /// rules; schema keywords a vendor rejects are removed with a warning.
+    ///
+    /// Examples:
+    ///   mcp export --format openai > tools.json
+    ///   mcp export --format gemini --mapping names.json -o tools.json
+    Export {
+        /// Target format
+        #[arg(long, value_enum)]
+        format: ExportFormat,
+
+        /// Write the exported name to `server/tool` mapping to this file
+        #[arg(long, value_name = "FILE")]
+        mapping: Option<std::path::PathBuf>,
+
+        /// Write the declarations to this file instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<std::path::PathBuf>,
+    },
+
+    /// Generate Markdown or HTML documentation for every tool
+    ///
+    /// Writes an index page and one page per server with each tool's
+    /// description, parameter table and an example invocation.
+    ///
+    /// Examples:
+    ///   mcp docs --out docs/
+    ///   mcp docs --out site/ --format html
+    Docs {
+        /// Directory to write the pages to
+        #[arg(long, value_name = "DIR")]
+        out: std::path::PathBuf,
+
+        /// Page format
+        #[arg(long, value_enum, default_value_t = DocsFormat::Markdown)]
+        format: DocsFormat,
+    },
+
+    /// Print a snapshot of every server's tools and schemas as JSON
+    ///
+    /// Examples:
+    ///   mcp snapshot > tools.lock.json
+    Snapshot,
+
+    /// Compare the current tools against a snapshot
+    ///
+    /// Lists added and removed tools and schema changes, marking those that
+    /// can break existing calls. Exits with code 4 if any change is breaking.
+    ///
+    /// Examples:
+    ///   mcp diff tools.lock.json
+    ///   mcp --json diff tools.lock.json
+    Diff {
+        /// Snapshot file written by `mcp snapshot`
+        #[arg(value_name = "FILE")]
+        snapshot: std::path::PathBuf,
+    },
+
+    /// List and read resources exposed by a server
+    ///
+    /// Examples:
+    ///   mcp resources list filesystem                           # List resources
+    ///   mcp resources templates filesystem                      # List URI templates
+    ///   mcp resources read filesystem file:///tmp/notes.txt     # Print contents
+    ///   mcp resources read db db://images/logo -o logo.png      # Save to file
+    Resources {
+        #[command(subcommand)]
+        command: ResourcesCommand,
+    },
+
+    /// List and render prompt templates exposed by a server
+    ///
+    /// Examples:
+    ///   mcp prompts list github                                 # List prompts
+    ///   mcp prompts get github/review_pr -- --number 42         # Render with --key value
+    ///   mcp prompts get github/review_pr -- '{"number": "42"}'  # Render with JSON args
+    Prompts {
+        #[command(subcommand)]
+        command: PromptsCommand,
+    },
+}
+
+/// Subcommands of `mcp resources`
+#[derive(Clone, Subcommand)]
+pub enum ResourcesCommand {
+    /// List resources available on a server
+    List {
+        /// Server name
+        server: String,
+    },
+
+    /// List resource templates available on a server
+    Templates {
+        /// Server name
+        server: String,
+    },
+
+    /// Read a resource by URI
+    Read {
+        /// Server name
+        server: String,
+
+        /// Resource URI
+        uri: String,
+
+        /// Write the content to a file (binary blobs are decoded)
+        #[arg(short = 'o', long, value_name = "FILE")]
+        output: Option<std::path::PathBuf>,
+    },
+}
+
+/// Subcommands of `mcp prompts`
+#[derive(Clone, Subcommand)]
+pub enum PromptsCommand {
+    /// List prompts available on a server
+    List {
+        /// Server name
+        server: String,
+    },
+
+    /// Render a prompt and print its messages
+    Get {
+        /// Prompt identifier (server/prompt or server prompt)
+        #[arg(value_name = "PROMPT")]
+        prompt: String,
+
+        /// Arguments: JSON or --key value pairs
+        /// Supports: --key value, --key=value, --key {"json": "value"}
+        #[arg(last = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
 }
 
 /// Run mode for command execution
@@ -175,7 +405,23 @@ pub async fn execute_command(
             // Shutdown subcommand is handled separately in main.rs
             Ok(())
         }
-        Commands::List { describe, verbose } => {
+        Commands::Serve { .. } => {
+            // Serve subcommand is handled separately in main.rs
+            Ok(())
+        }
+        Commands::Completions { .. } => {
+            // Completions subcommand is handled separately in main.rs
+            Ok(())
+        }
+        Commands::Complete { kind, word } => cmd_complete(client, kind, &word).await,
+        Commands::List {
+            page_size: Some(page_size),
+            cursor,
+            ..
+        } => cmd_list_tools_page(client, page_size, cursor.as_deref(), output_mode).await,
+        Commands::List {
+            describe, verbose, ..
+        } => {
             let detail_level = if verbose {
                 DetailLevel::Verbose
             } else if describe {
@@ -200,9 +446,13 @@ pub async fn execute_command(
             };
             cmd_tool_info(client, &tool, detail_level, output_mode).await
         }
-        Commands::Call { tool, args } => {
-            cmd_call_tool(client, &tool, args, output_mode).await
-        }
+        Commands::Call {
+            tool,
+            timeout,
+            no_validate,
+            args,
+        } => cmd_call_tool(client, &tool, args, timeout, !no_validate, output_mode).await,
+        Commands::Shell => cmd_shell(client, output_mode).await,
         Commands::Search {
             pattern,
             describe,
@@ -217,6 +467,33 @@ pub async fn execute_command(
             };
             cmd_search_tools(client, &pattern, detail_level, output_mode).await
         }
+        Commands::Export {
+            format,
+            mapping,
+            output,
+        } => cmd_export(client, format, mapping.as_deref(), output.as_deref()).await,
+        Commands::Docs { out, format } => cmd_docs(client, &out, format).await,
+        Commands::Snapshot => cmd_snapshot(client).await,
+        Commands::Diff { snapshot } => cmd_diff(client, &snapshot, output_mode).await,
+        Commands::Resources { command } => match command {
+            ResourcesCommand::List { server } => {
+                cmd_list_resources(client, &server, output_mode).await
+            }
+            ResourcesCommand::Templates { server } => {
+                cmd_list_resource_templates(client, &server, output_mode).await
+            }
+            ResourcesCommand::Read {
+                server,
+                uri,
+                output,
+            } => cmd_read_resource(client, &server, &uri, output.as_deref(), output_mode).await,
+        },
+        Commands::Prompts { command } => match command {
+            PromptsCommand::List { server } => cmd_list_prompts(client, &server, output_mode).await,
+            PromptsCommand::Get { prompt, args } => {
+                cmd_get_prompt(client, &prompt, args, output_mode).await
+            }
+        },
     }
 }
 
@@ -240,7 +517,7 @@ pub async fn run_command_auto_daemon(command: Option<Commands>, config: &Config)
     let output_mode = OutputMode::Human;
 
     // Get or spawn daemon client
-    let client = create_auto_daemon_client(config).await?;
+    let client = create_auto_daemon_client(config, None).await?;
 
     // Execute command
     execute_command(command, client, output_mode).await
@@ -320,6 +597,8 @@ mod tests {
         let _ = Commands::List {
             describe: false,
             verbose: false,
+            page_size: None,
+            cursor: None,
         };
         let _ = Commands::Info {
             name: "test".to_string(),
@@ -331,13 +610,55 @@ mod tests {
         };
         let _ = Commands::Call {
             tool: "test".to_string(),
+            timeout: None,
+            no_validate: false,
             args: vec![],
         };
+        let _ = Commands::Shell;
+        let _ = Commands::Serve {
+            stdio: true,
+            http: None,
+            rest: None,
+            token: None,
+        };
+        let _ = Commands::Completions {
+            shell: clap_complete::Shell::Bash,
+        };
+        let _ = Commands::Complete {
+            kind: CompletionKind::Tool,
+            word: "fs/".to_string(),
+        };
         let _ = Commands::Search {
             pattern: "test".to_string(),
             describe: false,
             verbose: false,
         };
+        let _ = Commands::Export {
+            format: ExportFormat::Openai,
+            mapping: None,
+            output: None,
+        };
+        let _ = Commands::Docs {
+            out: std::path::PathBuf::from("docs"),
+            format: DocsFormat::Markdown,
+        };
+        let _ = Commands::Snapshot;
+        let _ = Commands::Diff {
+            snapshot: std::path::PathBuf::from("tools.lock.json"),
+        };
+        let _ = Commands::Resources {
+            command: ResourcesCommand::Read {
+                server: "test".to_string(),
+                uri: "file:///test".to_string(),
+                output: None,
+            },
+        };
+        let _ = Commands::Prompts {
+            command: PromptsCommand::Get {
+                prompt: "test/prompt".to_string(),
+                args: vec![],
+            },
+        };
         let _ = Commands::Shutdown;
         let _ = Commands::Daemon {
             ttl: None,
@@ -0,0 +1,185 @@
+//! Shell completion commands (`mcp completions`, `mcp __complete`).
+//!
+//! `mcp completions <shell>` prints a script generated from the clap
+//! definition of [`Cli`](crate::cli::entry::Cli). For bash, zsh and fish the
+//! script also completes server and tool names by calling the hidden
+//! `mcp __complete` helper, which reads server names from the config and
+//! tool names from the daemon. Only the server being completed is asked for
+//! its tools, and the daemon keeps its connection for the next TAB.
+
+use crate::error::Result;
+use crate::ipc::ProtocolClient;
+use clap::ValueEnum;
+use clap_complete::Shell;
+use std::io::Write;
+
+/// What `mcp __complete` lists
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CompletionKind {
+    /// Server names
+    Server,
+    /// Tool identifiers (`server/` until a server is chosen, then `server/tool`)
+    Tool,
+}
+
+/// Bash: names after `call`, `tool` and `info`, clap's completion elsewhere
+const BASH_NAMES: &str = r#"
+_mcp_with_names() {
+    local cur="${COMP_WORDS[COMP_CWORD]}" prev="${COMP_WORDS[COMP_CWORD-1]}"
+    case "$prev" in
+        call|tool) COMPREPLY=($(mcp __complete tool "$cur" 2>/dev/null)) ;;
+        info) COMPREPLY=($(mcp __complete server "$cur" 2>/dev/null)) ;;
+        *) _mcp "$@"; return ;;
+    esac
+    if [[ ${#COMPREPLY[@]} -eq 1 && ${COMPREPLY[0]} == */ ]]; then
+        compopt -o nospace
+    fi
+}
+if [[ "${BASH_VERSINFO[0]}" -eq 4 && "${BASH_VERSINFO[1]}" -ge 4 || "${BASH_VERSINFO[0]}" -gt 4 ]]; then
+    complete -F _mcp_with_names -o nosort -o bashdefault -o default mcp
+else
+    complete -F _mcp_with_names -o bashdefault -o default mcp
+fi
+"#;
+
+/// Zsh: names after `call`, `tool` and `info`, clap's completion elsewhere
+const ZSH_NAMES: &str = r#"
+_mcp_with_names() {
+    local -a names
+    case "${words[CURRENT-1]}" in
+        call|tool)
+            names=(${(f)"$(mcp __complete tool "$PREFIX" 2>/dev/null)"})
+            compadd -S '' -- ${(M)names:#*/}
+            compadd -- ${names:#*/}
+            ;;
+        info)
+            names=(${(f)"$(mcp __complete server "$PREFIX" 2>/dev/null)"})
+            compadd -- $names
+            ;;
+        *) _mcp "$@" ;;
+    esac
+}
+compdef _mcp_with_names mcp
+"#;
+
+/// Fish: names after `call`, `tool` and `info`
+const FISH_NAMES: &str = r#"
+function __mcp_previous_is
+    contains -- (commandline -opc)[-1] $argv
+end
+complete -c mcp -f -n '__mcp_previous_is call tool' -a '(mcp __complete tool (commandline -ct) 2>/dev/null)'
+complete -c mcp -f -n '__mcp_previous_is info' -a '(mcp __complete server (commandline -ct) 2>/dev/null)'
+"#;
+
+/// Print the completion script for `shell`.
+///
+/// Elvish and PowerShell scripts complete commands and flags only.
+pub fn cmd_completions(shell: Shell, command: &mut clap::Command) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    write_completions(shell, command, &mut stdout).map_err(crate::error::McpError::io_error)
+}
+
+/// Write the completion script for `shell` to `out`.
+fn write_completions(
+    shell: Shell,
+    command: &mut clap::Command,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, command, name, out);
+    match shell {
+        Shell::Bash => out.write_all(BASH_NAMES.as_bytes()),
+        Shell::Zsh => out.write_all(ZSH_NAMES.as_bytes()),
+        Shell::Fish => out.write_all(FISH_NAMES.as_bytes()),
+        _ => Ok(()),
+    }
+}
+
+/// Print completion candidates for `word`, one per line.
+///
+/// Never fails: a server that can't list its tools simply has no
+/// candidates, so a TAB press doesn't print errors into the command line.
+pub async fn cmd_complete(
+    mut client: Box<dyn ProtocolClient>,
+    kind: CompletionKind,
+    word: &str,
+) -> Result<()> {
+    for candidate in complete_names(client.as_mut(), kind, word).await {
+        println!("{}", candidate);
+    }
+    Ok(())
+}
+
+/// Server names or tool identifiers starting with `word`.
+async fn complete_names(
+    client: &mut dyn ProtocolClient,
+    kind: CompletionKind,
+    word: &str,
+) -> Vec<String> {
+    let config = client.config();
+    let servers = config.servers.iter().map(|s| s.name.as_str());
+
+    let (server, prefix) = match (kind, word.split_once('/')) {
+        (CompletionKind::Server, _) => {
+            return servers
+                .filter(|name| name.starts_with(word))
+                .map(str::to_string)
+                .collect();
+        }
+        (CompletionKind::Tool, None) => {
+            return servers
+                .filter(|name| name.starts_with(word))
+                .map(|name| format!("{}/", name))
+                .collect();
+        }
+        (CompletionKind::Tool, Some(split)) => split,
+    };
+    if config.get_server(server).is_none() {
+        return Vec::new();
+    }
+
+    match client.list_tools(server).await {
+        Ok(tools) => tools
+            .into_iter()
+            .filter(|tool| tool.name.starts_with(prefix))
+            .map(|tool| format!("{}/{}", server, tool.name))
+            .collect(),
+        Err(e) => {
+            tracing::debug!("No tool completions for '{}': {}", server, e);
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::entry::Cli;
+    use clap::CommandFactory;
+
+    fn script(shell: Shell) -> String {
+        let mut out = Vec::new();
+        write_completions(shell, &mut Cli::command(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_scripts_complete_subcommands() {
+        for shell in [
+            Shell::Bash,
+            Shell::Zsh,
+            Shell::Fish,
+            Shell::Elvish,
+            Shell::PowerShell,
+        ] {
+            assert!(script(shell).contains("completions"), "{:?}", shell);
+        }
+    }
+
+    #[test]
+    fn test_scripts_complete_names_through_helper() {
+        assert!(script(Shell::Bash).contains("mcp __complete tool"));
+        assert!(script(Shell::Zsh).contains("mcp __complete tool"));
+        assert!(script(Shell::Fish).contains("mcp __complete tool"));
+    }
+}
@@ -4,9 +4,16 @@
 //! the MCP daemon process lifecycle.
 
 use crate::config::Config;
+use crate::config::loader::find_config_path;
+use crate::config_fingerprint::config_fingerprint;
+use crate::daemon::protocol::{
+    DaemonRequest, DaemonResponse, ProgressUpdate, progress_token, tool_call_params,
+};
 use crate::error::{McpError, Result};
-use crate::ipc::{ProtocolClient, create_ipc_client};
-use std::path::Path;
+use crate::ipc::{ProgressCallback, ProtocolClient, create_ipc_client};
+use crate::transport::{BoxedTransport, NotificationCallback, request_all_pages, send_initialize};
+use colored::Colorize;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -31,17 +38,24 @@ pub async fn create_direct_client(config: Arc<Config>) -> Result<Box<dyn Protoco
 ///
 /// # Arguments
 /// * `config` - Application configuration
+/// * `config_path` - File `config` was loaded from with `--config`, passed
+///   on to a spawned daemon; `None` lets it search the default locations
 ///
 /// # Returns
 /// * `Ok(Box<dyn ProtocolClient>)` - Connected client
 /// * `Err(McpError)` - Daemon connection or spawning error
-pub async fn create_auto_daemon_client(config: &Config) -> Result<Box<dyn ProtocolClient>> {
-    connect_or_spawn_daemon(config).await
+pub async fn create_auto_daemon_client(
+    config: &Config,
+    config_path: Option<&Path>,
+) -> Result<Box<dyn ProtocolClient>> {
+    connect_or_spawn_daemon(config, config_path).await
 }
 
 /// Run in require-daemon mode: connect to existing daemon only.
 ///
 /// Returns a connected ProtocolClient. Fails if daemon not running.
+/// A daemon with a different config is asked to reload it, and a warning is
+/// printed if that doesn't help, since this mode never restarts it.
 ///
 /// # Arguments
 /// * `config` - Application configuration
@@ -50,103 +64,252 @@ pub async fn create_auto_daemon_client(config: &Config) -> Result<Box<dyn Protoc
 /// * `Ok(Box<dyn ProtocolClient>)` - Connected client
 /// * `Err(McpError)` - Daemon not running error
 pub async fn create_require_daemon_client(config: &Config) -> Result<Box<dyn ProtocolClient>> {
-    connect_to_daemon(config).await
+    let mut client = connect_to_daemon(config).await?;
+    if !daemon_config_matches(client.as_mut(), config).await
+        && !reload_daemon_config(client.as_mut(), config).await
+    {
+        // stderr, so JSON output stays valid
+        eprintln!(
+            "{} The daemon was started with a different config; run 'mcp shutdown' and restart it to apply changes",
+            "Warning:".yellow().bold()
+        );
+    }
+    Ok(client)
 }
 
 /// Connect to an existing daemon or spawn one if needed (auto-daemon mode).
 ///
+/// A daemon started with a different config (its fingerprint doesn't match
+/// `config`'s) is first asked to reload its config file. If it still differs,
+/// it is asked to shut down and replaced. It stops accepting connections
+/// right away but finishes requests already in flight, so other clients'
+/// calls aren't lost. A daemon reading another config file is reported as an
+/// error instead, since CLIs using that file would replace it right back. A
+/// daemon speaking an older IPC protocol is replaced; a newer one is
+/// reported as an error.
+///
 /// Returns a connected ProtocolClient that can be used to execute commands.
 /// The caller is responsible for executing the command and handling the result.
 ///
 /// # Arguments
 /// * `config` - Application configuration
+/// * `config_path` - File `config` was loaded from with `--config`, if any
 ///
 /// # Returns
 /// * `Ok(Box<dyn ProtocolClient>)` - Connected client
 /// * `Err(McpError)` - Daemon connection or spawning error
-pub async fn connect_or_spawn_daemon(config: &Config) -> Result<Box<dyn ProtocolClient>> {
+pub async fn connect_or_spawn_daemon(
+    config: &Config,
+    config_path: Option<&Path>,
+) -> Result<Box<dyn ProtocolClient>> {
     tracing::debug!("connect_or_spawn_daemon called");
 
     // Check if daemon is running
     match try_connect_to_daemon(config).await {
-        Ok(client) => {
-            // Daemon is running, use it
-            tracing::info!("Using existing daemon");
-            Ok(client)
+        Ok(mut client) => {
+            if daemon_config_matches(client.as_mut(), config).await
+                || reload_daemon_config(client.as_mut(), config).await
+            {
+                // Daemon is running with the current config, use it
+                tracing::info!("Using existing daemon");
+                return Ok(client);
+            }
+            if let Some(daemon_path) = daemon_config_path(client.as_mut()).await
+                && let Some(path) = resolve_config_path(config_path).await?
+                && daemon_path != path
+            {
+                return Err(McpError::usage_error(format!(
+                    "The daemon is serving config '{}', not '{}'; run 'mcp shutdown' to replace it, or use --no-daemon",
+                    daemon_path.display(),
+                    path.display()
+                )));
+            }
+            tracing::info!("Daemon config is stale, replacing daemon...");
+            retire_daemon(client, config).await;
+        }
+        Err(McpError::IpcVersionMismatch { daemon, client }) if daemon < client => {
+            tracing::info!(
+                "Daemon speaks IPC protocol {} instead of {}, replacing daemon...",
+                daemon,
+                client
+            );
+            retire_daemon(create_ipc_client(config)?, config).await;
         }
+        // Replacing a newer daemon would break the CLIs using it
+        Err(e @ McpError::IpcVersionMismatch { .. }) => return Err(e),
         Err(_) => {
-            // Daemon not running, spawn it
             tracing::info!("Daemon not running, spawning...");
+        }
+    }
 
-            // Get TTL from config (includes env var override via config loader)
-            // Set minimum TTL of 5 seconds for auto-daemon mode to prevent race conditions
-            let mut ttl = config.daemon_ttl;
-            if ttl < 5 {
-                tracing::warn!(
-                    "Auto-daemon TTL too short ({}s), setting minimum of 5s to prevent race conditions",
-                    ttl
-                );
-                ttl = 5;
-            }
+    spawn_and_connect(config, config_path).await
+}
+
+/// Spawn a daemon and wait until it accepts connections.
+async fn spawn_and_connect(
+    config: &Config,
+    config_path: Option<&Path>,
+) -> Result<Box<dyn ProtocolClient>> {
+    // Get TTL from config (includes env var override via config loader)
+    // Set minimum TTL of 5 seconds for auto-daemon mode to prevent race conditions
+    let mut ttl = config.daemon_ttl;
+    if ttl < 5 {
+        tracing::warn!(
+            "Auto-daemon TTL too short ({}s), setting minimum of 5s to prevent race conditions",
+            ttl
+        );
+        ttl = 5;
+    }
+
+    // Spawn daemon as background task
+    tracing::debug!("Spawning daemon with TTL={}s...", ttl);
+
+    // Clone socket_path and config_path for the async block
+    let socket_path = config.socket_path.clone();
+    let config_path = config_path.map(Path::to_path_buf);
+    tokio::spawn(async move {
+        tracing::debug!("Inside tokio::spawn, about to spawn daemon...");
+        match spawn_background_daemon(ttl, &socket_path, config_path.as_deref()).await {
+            Ok(_) => tracing::debug!("spawn_background_daemon returned Ok"),
+            Err(e) => tracing::debug!("spawn_background_daemon failed: {}", e),
+        }
+    });
 
-            // Spawn daemon as background task
-            tracing::debug!("Spawning daemon with TTL={}s...", ttl);
+    // Wait for daemon to start with exponential backoff
+    let mut retries = 0;
+    let max_retries = 20; // More retries
+    let mut delay = Duration::from_millis(500); // Start with longer delay
 
-            // Clone socket_path for the async block
-            let socket_path = config.socket_path.clone();
-            tokio::spawn(async move {
-                tracing::debug!("Inside tokio::spawn, about to spawn daemon...");
-                match spawn_background_daemon(ttl, &socket_path).await {
-                    Ok(_) => tracing::debug!("spawn_background_daemon returned Ok"),
-                    Err(e) => tracing::debug!("spawn_background_daemon failed: {}", e),
+    loop {
+        tokio::time::sleep(delay).await;
+
+        match try_connect_to_daemon(config).await {
+            Ok(client) => {
+                tracing::info!("Connected to daemon after {} attempt(s)", retries + 1);
+                return Ok(client);
+            }
+            Err(e) => {
+                retries += 1;
+                if retries >= max_retries {
+                    return Err(McpError::IOError {
+                        source: std::io::Error::other(format!(
+                            "Failed to start daemon after {} attempts: {}",
+                            max_retries, e
+                        )),
+                    });
                 }
-            });
-
-            // Wait for daemon to start with exponential backoff
-            let mut retries = 0;
-            let max_retries = 20; // More retries
-            let mut delay = Duration::from_millis(500); // Start with longer delay
-
-            loop {
-                tokio::time::sleep(delay).await;
-
-                match try_connect_to_daemon(config).await {
-                    Ok(client) => {
-                        tracing::info!("Connected to daemon after {} attempt(s)", retries + 1);
-                        return Ok(client);
-                    }
-                    Err(e) => {
-                        retries += 1;
-                        if retries >= max_retries {
-                            return Err(McpError::IOError {
-                                source: std::io::Error::other(format!(
-                                    "Failed to start daemon after {} attempts: {}",
-                                    max_retries, e
-                                )),
-                            });
-                        }
-                        // Linear backoff: add 200ms each time, cap at 2 seconds
-                        delay += Duration::from_millis(200);
-                        if delay > Duration::from_secs(2) {
-                            delay = Duration::from_secs(2);
-                        }
-                        tracing::debug!(
-                            "Daemon not ready, retrying in {:?} (attempt {}/{})",
-                            delay,
-                            retries,
-                            max_retries
-                        );
-                    }
+                // Linear backoff: add 200ms each time, cap at 2 seconds
+                delay += Duration::from_millis(200);
+                if delay > Duration::from_secs(2) {
+                    delay = Duration::from_secs(2);
                 }
+                tracing::debug!(
+                    "Daemon not ready, retrying in {:?} (attempt {}/{})",
+                    delay,
+                    retries,
+                    max_retries
+                );
             }
         }
     }
 }
 
+/// Whether the daemon behind `client` runs with `config`.
+///
+/// A daemon that can't report its fingerprint counts as current, so a failed
+/// check never restarts it.
+async fn daemon_config_matches(client: &mut dyn ProtocolClient, config: &Config) -> bool {
+    match client
+        .send_request(&DaemonRequest::GetConfigFingerprint)
+        .await
+    {
+        Ok(DaemonResponse::ConfigFingerprint(fingerprint)) => {
+            fingerprint == config_fingerprint(config)
+        }
+        Ok(other) => {
+            tracing::debug!("Unexpected response to fingerprint request: {:?}", other);
+            true
+        }
+        Err(e) => {
+            tracing::debug!("Failed to get daemon config fingerprint: {}", e);
+            true
+        }
+    }
+}
+
+/// Ask the daemon to reread its config file; true if it then matches `config`.
+///
+/// Covers edits the daemon's file watcher hasn't picked up yet.
+async fn reload_daemon_config(client: &mut dyn ProtocolClient, config: &Config) -> bool {
+    match client.send_request(&DaemonRequest::ReloadConfig).await {
+        Ok(DaemonResponse::ConfigFingerprint(fingerprint)) => {
+            fingerprint == config_fingerprint(config)
+        }
+        Ok(other) => {
+            tracing::debug!("Daemon did not reload its config: {:?}", other);
+            false
+        }
+        Err(e) => {
+            tracing::debug!("Failed to ask daemon to reload its config: {}", e);
+            false
+        }
+    }
+}
+
+/// Config file the daemon behind `client` reads, if it reports one.
+async fn daemon_config_path(client: &mut dyn ProtocolClient) -> Option<PathBuf> {
+    match client.send_request(&DaemonRequest::GetConfigPath).await {
+        Ok(DaemonResponse::ConfigPath(path)) => path,
+        Ok(other) => {
+            tracing::debug!("Unexpected response to config path request: {:?}", other);
+            None
+        }
+        Err(e) => {
+            tracing::debug!("Failed to get daemon config path: {}", e);
+            None
+        }
+    }
+}
+
+/// Absolute path of the config file `config` was loaded from, resolved the
+/// way a daemon spawned with `config_path` resolves it.
+async fn resolve_config_path(config_path: Option<&Path>) -> Result<Option<PathBuf>> {
+    let path = match config_path {
+        Some(path) => Some(path.to_path_buf()),
+        None => find_config_path(None).await.map(PathBuf::from),
+    };
+    path.map(std::path::absolute)
+        .transpose()
+        .map_err(McpError::io_error)
+}
+
+/// Ask a stale daemon to shut down and wait until it stops accepting
+/// connections.
+///
+/// The daemon keeps serving requests already in flight after that, so this
+/// doesn't wait for them.
+async fn retire_daemon(mut client: Box<dyn ProtocolClient>, config: &Config) {
+    if let Err(e) = client.shutdown().await {
+        tracing::warn!("Failed to ask stale daemon to shut down: {}", e);
+    }
+
+    let start = std::time::Instant::now();
+    // A daemon on another IPC protocol still answers, with a version mismatch
+    while let Ok(_) | Err(McpError::IpcVersionMismatch { .. }) = try_connect_to_daemon(config).await
+    {
+        if start.elapsed() > Duration::from_secs(10) {
+            tracing::warn!("Timeout waiting for stale daemon to stop accepting connections");
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
 /// Connect to an existing daemon (require-daemon mode).
 ///
 /// Returns a connected ProtocolClient if daemon is running.
-/// Fails if daemon is not already running.
+/// Fails if daemon is not already running or speaks another IPC protocol
+/// version.
 ///
 /// # Arguments
 /// * `config` - Application configuration
@@ -160,6 +323,7 @@ pub async fn connect_to_daemon(config: &Config) -> Result<Box<dyn ProtocolClient
             tracing::info!("Using existing daemon");
             Ok(client)
         }
+        Err(e @ McpError::IpcVersionMismatch { .. }) => Err(e),
         Err(_) => Err(McpError::daemon_not_running(
             "Daemon is not running. Start it with 'mcp daemon' or use --auto-daemon",
         )),
@@ -177,7 +341,11 @@ async fn try_connect_to_daemon(config: &Config) -> Result<Box<dyn ProtocolClient
     }
 }
 
-async fn spawn_background_daemon(ttl: u64, socket_path: &Path) -> Result<()> {
+async fn spawn_background_daemon(
+    ttl: u64,
+    socket_path: &Path,
+    config_path: Option<&Path>,
+) -> Result<()> {
     // Spawn the daemon as a separate process using the binary itself
     // This is necessary because the daemon runs an IPC server that needs
     // to be independent of the client process
@@ -190,11 +358,17 @@ async fn spawn_background_daemon(ttl: u64, socket_path: &Path) -> Result<()> {
     // Build arguments for daemon subcommand - pass socket path explicitly
     // to ensure daemon uses the same IPC endpoint as the client expects
     let socket_path_str = socket_path.to_string_lossy().to_string();
-    let args = vec![
+    let mut args = vec![
         "daemon".to_string(),
         "--socket-path".to_string(),
         socket_path_str,
     ];
+    // Load the same config file, or the daemon's fingerprint won't match ours
+    if let Some(config_path) = config_path {
+        let config_path = std::path::absolute(config_path).map_err(McpError::io_error)?;
+        args.push("--config".to_string());
+        args.push(config_path.to_string_lossy().to_string());
+    }
 
     // Spawn the daemon process
     tracing::info!(
@@ -247,10 +421,7 @@ async fn spawn_background_daemon(ttl: u64, socket_path: &Path) -> Result<()> {
             .kill_on_drop(false)
             .spawn()
             .map_err(|e| McpError::IOError {
-                source: std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    format!("Failed to spawn daemon: {}", e),
-                ),
+                source: std::io::Error::other(format!("Failed to spawn daemon: {}", e)),
             })?;
 
         tracing::debug!("Daemon spawned with PID: {:?}", _child.id());
@@ -273,6 +444,109 @@ impl DirectProtocolClient {
     pub fn new(config: Arc<Config>) -> Self {
         Self { config }
     }
+
+    /// Open a fresh connection to a server and send the initialize request.
+    async fn connect(&self, server_name: &str) -> Result<BoxedTransport> {
+        let server_config = self.config.get_server(server_name).ok_or_else(|| {
+            let servers: Vec<String> = self.config.servers.iter().map(|s| s.name.clone()).collect();
+            McpError::ServerNotFound {
+                server: server_name.to_string(),
+                servers,
+            }
+        })?;
+
+        let mut transport = server_config.create_transport(server_name)?;
+
+        send_initialize(&mut transport, 0)
+            .await
+            .map_err(|e| McpError::IOError {
+                source: std::io::Error::other(e),
+            })?;
+
+        Ok(transport)
+    }
+
+    /// Open a fresh connection, initialize it and send a single MCP request.
+    ///
+    /// Server notifications received while waiting are passed to
+    /// `on_notification`. `timeout` replaces the transport's default request
+    /// timeout for this request. Returns the `result` member of the response, or an error built from the
+    /// JSON-RPC `error` member.
+    async fn send_mcp_request(
+        &self,
+        server_name: &str,
+        method: &str,
+        params: serde_json::Value,
+        timeout: Option<Duration>,
+        on_notification: NotificationCallback<'_>,
+    ) -> Result<serde_json::Value> {
+        let mut transport = self.connect(server_name).await?;
+        if let Some(timeout) = timeout {
+            transport.set_request_timeout(timeout);
+        }
+
+        let mcp_request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params
+        });
+
+        let response = transport
+            .send_with_notifications(mcp_request, on_notification)
+            .await;
+
+        // Let the server stop work on a request that timed out
+        if matches!(response, Err(McpError::Timeout { .. }))
+            && let Err(e) = transport.cancel_abandoned("Request timed out").await
+        {
+            tracing::debug!("Failed to cancel request to {}: {}", server_name, e);
+        }
+
+        // End the server-side session; failures here don't affect the result
+        if let Err(e) = transport.close().await {
+            tracing::debug!("Failed to close connection to {}: {}", server_name, e);
+        }
+
+        let response = response.map_err(|e| match e {
+            McpError::Timeout { .. } => e,
+            e => McpError::IOError {
+                source: std::io::Error::other(e),
+            },
+        })?;
+
+        if let Some(result) = response.get("result") {
+            Ok(result.clone())
+        } else if let Some(error) = response.get("error") {
+            let message = error
+                .get("message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("Unknown error");
+            Err(McpError::InvalidProtocol {
+                message: format!("{} failed: {}", method, message),
+            })
+        } else {
+            Err(McpError::InvalidProtocol {
+                message: "Invalid MCP response format".to_string(),
+            })
+        }
+    }
+
+    /// Open a fresh connection and send a paginated MCP list request,
+    /// following `nextCursor` on the same connection.
+    async fn send_paginated_request(
+        &self,
+        server_name: &str,
+        method: &str,
+        key: &str,
+    ) -> Result<serde_json::Value> {
+        let mut transport = self.connect(server_name).await?;
+        let result = request_all_pages(&mut transport, method, key, 1).await;
+        if let Err(e) = transport.close().await {
+            tracing::debug!("Failed to close connection to {}: {}", server_name, e);
+        }
+        result
+    }
 }
 
 #[async_trait::async_trait]
@@ -300,91 +574,27 @@ impl ProtocolClient for DirectProtocolClient {
         &mut self,
         server_name: &str,
     ) -> Result<Vec<crate::daemon::protocol::ToolInfo>> {
-        // Get server config and create transport directly
-        let server_config =
-            self.config
-                .get_server(server_name)
-                .ok_or_else(|| {
-                    let servers: Vec<String> = self.config.servers.iter().map(|s| s.name.clone()).collect();
-                    McpError::ServerNotFound {
-                        server: server_name.to_string(),
-                        servers,
-                    }
-                })?;
-
-        let mut transport = server_config.create_transport(server_name)?;
-
-        // MCP Protocol: Send initialize request first
-        let init_request = serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 0,
-            "method": "initialize",
-            "params": {
-                "protocolVersion": "2024-11-05",
-                "capabilities": {
-                    "roots": {},
-                    "sampling": {},
-                    "tools": {}
-                },
-                "clientInfo": {
-                    "name": "mcp-cli-rs",
-                    "version": env!("CARGO_PKG_VERSION")
-                }
-            }
-        });
-
-        // Send initialize and get response
-        transport
-            .send(init_request)
-            .await
-            .map_err(|e| McpError::IOError {
-                source: std::io::Error::other(e),
-            })?;
-
-        // Server automatically sends notifications/initialized - we don't need to send it
-        // Now send tools/list request
-        let mcp_request = serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "tools/list"
-        });
-
-        // Send request and get response
-        let response = transport
-            .send(mcp_request)
-            .await
-            .map_err(|e| McpError::IOError {
-                source: std::io::Error::other(e),
-            })?;
+        let result = self
+            .send_paginated_request(server_name, "tools/list", "tools")
+            .await?;
+        Ok(crate::daemon::protocol::parse_tool_list(&result))
+    }
 
-        // Parse response
-        if let Some(result) = response.get("result") {
-            let tools = if let Some(tools_array) = result.get("tools").and_then(|t| t.as_array()) {
-                tools_array
-                    .iter()
-                    .filter_map(|tool| {
-                        Some(crate::daemon::protocol::ToolInfo {
-                            name: tool.get("name")?.as_str()?.to_string(),
-                            description: tool
-                                .get("description")?
-                                .as_str()
-                                .map(|s| s.to_string())
-                                .unwrap_or_default(),
-                            input_schema: tool.get("inputSchema").cloned().unwrap_or_else(|| {
-                                serde_json::Value::Object(serde_json::Map::new())
-                            }),
-                        })
-                    })
-                    .collect()
-            } else {
-                Vec::new()
-            };
-            Ok(tools)
-        } else {
-            Err(McpError::InvalidProtocol {
-                message: "Invalid MCP response format".to_string(),
-            })
-        }
+    async fn list_tools_page(
+        &mut self,
+        server_name: &str,
+        cursor: Option<&str>,
+    ) -> Result<crate::daemon::protocol::ToolPage> {
+        let result = self
+            .send_mcp_request(
+                server_name,
+                "tools/list",
+                crate::daemon::protocol::list_page_params(cursor),
+                None,
+                &mut |_| {},
+            )
+            .await?;
+        Ok(crate::daemon::protocol::ToolPage::from_result(&result))
     }
 
     async fn execute_tool(
@@ -393,83 +603,99 @@ impl ProtocolClient for DirectProtocolClient {
         tool_name: &str,
         arguments: serde_json::Value,
     ) -> Result<serde_json::Value> {
-        // Get server config and create transport directly
-        let server_config =
-            self.config
-                .get_server(server_name)
-                .ok_or_else(|| {
-                    let servers: Vec<String> = self.config.servers.iter().map(|s| s.name.clone()).collect();
-                    McpError::ServerNotFound {
-                        server: server_name.to_string(),
-                        servers,
-                    }
-                })?;
-
-        let mut transport = server_config.create_transport(server_name)?;
+        self.execute_tool_with_progress(server_name, tool_name, arguments, None, &mut |_| {})
+            .await
+    }
 
-        // MCP Protocol: Send initialize request first
-        let init_request = serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 0,
-            "method": "initialize",
-            "params": {
-                "protocolVersion": "2024-11-05",
-                "capabilities": {
-                    "roots": {},
-                    "sampling": {},
-                    "tools": {}
-                },
-                "clientInfo": {
-                    "name": "mcp-cli-rs",
-                    "version": env!("CARGO_PKG_VERSION")
-                }
+    async fn execute_tool_with_progress(
+        &mut self,
+        server_name: &str,
+        tool_name: &str,
+        arguments: serde_json::Value,
+        timeout_secs: Option<u64>,
+        on_progress: ProgressCallback<'_>,
+    ) -> Result<serde_json::Value> {
+        let token = progress_token();
+        let mut on_notification = |notification: serde_json::Value| {
+            if let Some(update) = ProgressUpdate::from_notification(&notification, &token) {
+                on_progress(update);
             }
-        });
+        };
+        self.send_mcp_request(
+            server_name,
+            "tools/call",
+            tool_call_params(tool_name, arguments, &token),
+            Some(Duration::from_secs(timeout_secs.unwrap_or_else(|| {
+                self.config.server_timeout_secs(server_name)
+            }))),
+            &mut on_notification,
+        )
+        .await
+    }
 
-        // Send initialize and get response
-        transport
-            .send(init_request)
-            .await
-            .map_err(|e| McpError::IOError {
-                source: std::io::Error::other(e),
-            })?;
+    async fn list_resources(
+        &mut self,
+        server_name: &str,
+    ) -> Result<Vec<crate::daemon::protocol::ResourceInfo>> {
+        let result = self
+            .send_paginated_request(server_name, "resources/list", "resources")
+            .await?;
+        crate::daemon::protocol::parse_result_list(&result, "resources")
+    }
 
-        // Server automatically sends notifications/initialized - we don't need to send it
-        // Now send tools/call request
-        let mcp_request = serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "tools/call",
-            "params": {
-                "name": tool_name,
-                "arguments": arguments
-            }
-        });
+    async fn list_resource_templates(
+        &mut self,
+        server_name: &str,
+    ) -> Result<Vec<crate::daemon::protocol::ResourceTemplateInfo>> {
+        let result = self
+            .send_paginated_request(server_name, "resources/templates/list", "resourceTemplates")
+            .await?;
+        crate::daemon::protocol::parse_result_list(&result, "resourceTemplates")
+    }
 
-        // Send request and get response
-        let response = transport
-            .send(mcp_request)
-            .await
-            .map_err(|e| McpError::IOError {
-                source: std::io::Error::other(e),
-            })?;
+    async fn read_resource(
+        &mut self,
+        server_name: &str,
+        uri: &str,
+    ) -> Result<Vec<crate::daemon::protocol::ResourceContents>> {
+        let result = self
+            .send_mcp_request(
+                server_name,
+                "resources/read",
+                serde_json::json!({ "uri": uri }),
+                None,
+                &mut |_| {},
+            )
+            .await?;
+        crate::daemon::protocol::parse_result_list(&result, "contents")
+    }
 
-        // Parse response
-        if let Some(result) = response.get("result") {
-            Ok(result.clone())
-        } else if let Some(error) = response.get("error") {
-            let message = error
-                .get("message")
-                .and_then(|m| m.as_str())
-                .unwrap_or("Unknown error");
-            Err(McpError::InvalidProtocol {
-                message: format!("Tool execution failed: {}", message),
-            })
-        } else {
-            Err(McpError::InvalidProtocol {
-                message: "Invalid MCP response format".to_string(),
-            })
-        }
+    async fn list_prompts(
+        &mut self,
+        server_name: &str,
+    ) -> Result<Vec<crate::daemon::protocol::PromptInfo>> {
+        let result = self
+            .send_paginated_request(server_name, "prompts/list", "prompts")
+            .await?;
+        crate::daemon::protocol::parse_result_list(&result, "prompts")
+    }
+
+    async fn get_prompt(
+        &mut self,
+        server_name: &str,
+        prompt_name: &str,
+        arguments: std::collections::HashMap<String, String>,
+    ) -> Result<crate::daemon::protocol::PromptResult> {
+        let result = self
+            .send_mcp_request(
+                server_name,
+                "prompts/get",
+                serde_json::json!({ "name": prompt_name, "arguments": arguments }),
+                None,
+                &mut |_| {},
+            )
+            .await?;
+        crate::daemon::protocol::PromptResult::from_result(&result)
     }
 
     async fn shutdown(&mut self) -> Result<()> {
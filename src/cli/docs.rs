@@ -0,0 +1,585 @@
+//! Tool documentation generator (`mcp docs`).
+//!
+//! Writes one page per server, listing each allowed tool with its
+//! description, a parameter table and an example `mcp call`, plus an index
+//! page linking them. Pages contain no timestamps and list tools by name,
+//! so regenerating them only changes what changed upstream.
+
+use crate::cli::interactive::command_line;
+use crate::cli::list::query_list_servers;
+use crate::cli::models::{ServerModel, ToolModel};
+use crate::error::{McpError, Result};
+use crate::format::schema::{ParameterInfo, extract_nested_params, resolve_refs};
+use crate::format::{DetailLevel, extract_params_from_schema, format_param_list};
+use crate::ipc::ProtocolClient;
+use serde_json::{Map, Value, json};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Output format of the generated pages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum DocsFormat {
+    /// Markdown, rendered by GitHub and most code hosts
+    Markdown,
+    /// Standalone HTML pages
+    Html,
+}
+
+impl DocsFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            DocsFormat::Markdown => "md",
+            DocsFormat::Html => "html",
+        }
+    }
+}
+
+/// Generate documentation pages for every configured server.
+///
+/// # Arguments
+/// * `daemon` - Daemon IPC client
+/// * `out` - Directory to write the pages to, created if missing
+/// * `format` - Page format
+///
+/// # Errors
+/// Returns McpError::IOError if the directory or a page can't be written
+pub async fn cmd_docs(
+    daemon: Box<dyn ProtocolClient>,
+    out: &Path,
+    format: DocsFormat,
+) -> Result<()> {
+    let config = daemon.config();
+    let mut model = query_list_servers(daemon).await?;
+    // Keep config order so the index doesn't reshuffle between runs
+    model.servers.sort_by_key(|server| {
+        config
+            .servers
+            .iter()
+            .position(|s| s.name == server.name)
+            .unwrap_or(usize::MAX)
+    });
+
+    std::fs::create_dir_all(out).map_err(McpError::io_error)?;
+    let pages = render_pages(&model.servers, format);
+    for (file_name, content) in &pages {
+        std::fs::write(out.join(file_name), content).map_err(McpError::io_error)?;
+    }
+
+    for server in model.servers.iter().filter(|s| s.error.is_some()) {
+        crate::output::print_warning(&format!(
+            "Server '{}' is unavailable; it is listed in the index without a page",
+            server.name
+        ));
+    }
+    crate::output::print_success(&format!(
+        "Wrote {} page(s) documenting {} tool(s) to {}",
+        pages.len(),
+        model.total_tools,
+        out.display()
+    ));
+    Ok(())
+}
+
+/// Render the index page and one page per available server.
+///
+/// Returns `(file name, content)` pairs, the index first.
+pub fn render_pages(servers: &[ServerModel], format: DocsFormat) -> Vec<(String, String)> {
+    let names = page_names(servers, format);
+    let mut pages = vec![(
+        index_name(format),
+        match format {
+            DocsFormat::Markdown => markdown_index(servers, &names),
+            DocsFormat::Html => html_index(servers, &names),
+        },
+    )];
+    for (server, name) in servers.iter().zip(names) {
+        if server.error.is_some() {
+            continue;
+        }
+        let content = match format {
+            DocsFormat::Markdown => markdown_page(server),
+            DocsFormat::Html => html_page(server),
+        };
+        pages.push((name, content));
+    }
+    pages
+}
+
+/// Everything a page shows about one tool.
+struct ToolDoc<'a> {
+    tool: &'a ToolModel,
+    usage: String,
+    params: Vec<ParameterInfo>,
+    example: String,
+}
+
+impl<'a> ToolDoc<'a> {
+    fn new(server: &str, tool: &'a ToolModel) -> Self {
+        let schema = resolve_refs(&tool.input_schema);
+        let usage = format!(
+            "{}/{} {}",
+            server,
+            tool.name,
+            format_param_list(&extract_params_from_schema(&schema), DetailLevel::Summary)
+        );
+        let tool_id = format!("{}/{}", server, tool.name);
+        let arguments = example_value(&schema, "");
+        let example = if arguments.as_object().is_some_and(Map::is_empty) {
+            format!("mcp call {}", tool_id)
+        } else {
+            command_line(&tool_id, None, true, &arguments)
+        };
+        Self {
+            tool,
+            usage,
+            params: extract_nested_params(&tool.input_schema),
+            example,
+        }
+    }
+}
+
+/// Tools of a server, sorted by name.
+fn tool_docs(server: &ServerModel) -> Vec<ToolDoc<'_>> {
+    let mut tools: Vec<&ToolModel> = server.tools.iter().collect();
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+    tools
+        .into_iter()
+        .map(|tool| ToolDoc::new(&server.name, tool))
+        .collect()
+}
+
+/// Build example arguments filling in the required properties only.
+fn example_value(schema: &Value, name: &str) -> Value {
+    for key in ["default", "const"] {
+        if let Some(value) = schema.get(key) {
+            return value.clone();
+        }
+    }
+    for key in ["examples", "enum"] {
+        if let Some(value) = schema.get(key).and_then(|v| v.get(0)) {
+            return value.clone();
+        }
+    }
+    let param_type = match schema.get("type") {
+        Some(Value::Array(types)) => types
+            .iter()
+            .find_map(|t| t.as_str().filter(|t| *t != "null")),
+        Some(value) => value.as_str(),
+        None if schema.get("properties").is_some() => Some("object"),
+        None => None,
+    };
+    match param_type {
+        Some("object") => {
+            let required: Vec<&str> = schema
+                .get("required")
+                .and_then(Value::as_array)
+                .map(|names| names.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            let mut object = Map::new();
+            if let Some(Value::Object(properties)) = schema.get("properties") {
+                for (property, property_schema) in properties {
+                    if required.contains(&property.as_str()) {
+                        object.insert(property.clone(), example_value(property_schema, property));
+                    }
+                }
+            }
+            Value::Object(object)
+        }
+        Some("array") => json!([]),
+        Some("integer") | Some("number") => json!(0),
+        Some("boolean") => json!(true),
+        _ => json!(format!("<{}>", name)),
+    }
+}
+
+fn index_name(format: DocsFormat) -> String {
+    format!("index.{}", format.extension())
+}
+
+/// File names of the servers' pages, in server order.
+///
+/// Names are sanitized server names. One that matches the index or an
+/// earlier page, ignoring case for case-insensitive file systems, gets a
+/// numeric suffix (`-2`, `-3`, ...) so no page overwrites another. Servers
+/// without a page still claim their name, keeping the others stable.
+fn page_names(servers: &[ServerModel], format: DocsFormat) -> Vec<String> {
+    let mut used = HashSet::from([index_name(format)]);
+    servers
+        .iter()
+        .map(|server| {
+            let stem = page_stem(&server.name);
+            let mut name = format!("{}.{}", stem, format.extension());
+            let mut suffix = 2;
+            while !used.insert(name.to_lowercase()) {
+                name = format!("{}-{}.{}", stem, suffix, format.extension());
+                suffix += 1;
+            }
+            name
+        })
+        .collect()
+}
+
+/// Server name reduced to characters safe in a file name.
+fn page_stem(server: &str) -> String {
+    let stem: String = server
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    match stem.trim_start_matches('.') {
+        "" => "server".to_string(),
+        stem => stem.to_string(),
+    }
+}
+
+/// Anchor id of a tool's section.
+fn anchor(tool: &str) -> String {
+    let slug: String = tool
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("tool-{}", slug)
+}
+
+/// Type column text, including allowed values.
+fn type_text(param: &ParameterInfo) -> String {
+    if param.enum_values.is_empty() {
+        param.param_type.clone()
+    } else {
+        format!(
+            "{} (one of: {})",
+            param.param_type,
+            param.enum_values.join(", ")
+        )
+    }
+}
+
+// Markdown
+
+fn markdown_index(servers: &[ServerModel], page_names: &[String]) -> String {
+    let mut out = String::from("# MCP tools\n\n");
+    out.push_str("Generated by `mcp docs`.\n\n");
+    out.push_str("| Server | Tools | Description |\n|---|---|---|\n");
+    for (server, page_name) in servers.iter().zip(page_names) {
+        let name = match server.error {
+            None => format!("[{}]({})", md_cell(&server.name), page_name),
+            Some(_) => format!("{} (unavailable)", md_cell(&server.name)),
+        };
+        out.push_str(&format!(
+            "| {} | {} | {} |\n",
+            name,
+            server.tool_count,
+            md_cell(server.description.as_deref().unwrap_or(""))
+        ));
+    }
+    out
+}
+
+fn markdown_page(server: &ServerModel) -> String {
+    let tools = tool_docs(server);
+    let mut out = format!("# {}\n\n", server.name);
+    if let Some(description) = &server.description {
+        out.push_str(&format!("{}\n\n", description));
+    }
+    out.push_str("[All servers](index.md)\n\n");
+    if tools.is_empty() {
+        out.push_str("This server exposes no tools.\n");
+        return out;
+    }
+    for doc in &tools {
+        out.push_str(&format!(
+            "- [{}](#{})\n",
+            doc.tool.name,
+            anchor(&doc.tool.name)
+        ));
+    }
+
+    for doc in &tools {
+        out.push_str(&format!(
+            "\n<a id=\"{}\"></a>\n\n## {}\n\n",
+            anchor(&doc.tool.name),
+            doc.tool.name
+        ));
+        if let Some(description) = doc.tool.description.as_deref().filter(|d| !d.is_empty()) {
+            out.push_str(&format!("{}\n\n", description.trim_end()));
+        }
+        out.push_str(&format!("```\n{}\n```\n\n", doc.usage));
+        if doc.params.is_empty() {
+            out.push_str("No parameters.\n\n");
+        } else {
+            out.push_str("| Parameter | Type | Required | Description |\n|---|---|---|---|\n");
+            for param in &doc.params {
+                out.push_str(&format!(
+                    "| `{}` | {} | {} | {} |\n",
+                    param.name,
+                    md_cell(&type_text(param)),
+                    if param.required { "yes" } else { "no" },
+                    md_cell(param.description.as_deref().unwrap_or(""))
+                ));
+            }
+            out.push('\n');
+        }
+        out.push_str(&format!("Example:\n\n```sh\n{}\n```\n", doc.example));
+    }
+    out
+}
+
+/// Make text safe inside a Markdown table cell.
+fn md_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+// HTML
+
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem;line-height:1.5}\
+table{border-collapse:collapse}th,td{border:1px solid #ccc;padding:.3rem .6rem;text-align:left;vertical-align:top}\
+pre{background:#f5f5f5;padding:.6rem;overflow-x:auto}";
+
+fn html_document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        html_escape(title),
+        HTML_STYLE,
+        body
+    )
+}
+
+fn html_index(servers: &[ServerModel], page_names: &[String]) -> String {
+    let mut body = String::from("<h1>MCP tools</h1>\n<p>Generated by <code>mcp docs</code>.</p>\n");
+    body.push_str("<table>\n<tr><th>Server</th><th>Tools</th><th>Description</th></tr>\n");
+    for (server, page_name) in servers.iter().zip(page_names) {
+        let name = match server.error {
+            None => format!(
+                "<a href=\"{}\">{}</a>",
+                html_escape(page_name),
+                html_escape(&server.name)
+            ),
+            Some(_) => format!("{} (unavailable)", html_escape(&server.name)),
+        };
+        body.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            name,
+            server.tool_count,
+            html_escape(server.description.as_deref().unwrap_or(""))
+        ));
+    }
+    body.push_str("</table>\n");
+    html_document("MCP tools", &body)
+}
+
+fn html_page(server: &ServerModel) -> String {
+    let tools = tool_docs(server);
+    let mut body = format!("<h1>{}</h1>\n", html_escape(&server.name));
+    if let Some(description) = &server.description {
+        body.push_str(&format!("<p>{}</p>\n", html_escape(description)));
+    }
+    body.push_str("<p><a href=\"index.html\">All servers</a></p>\n");
+    if tools.is_empty() {
+        body.push_str("<p>This server exposes no tools.</p>\n");
+        return html_document(&server.name, &body);
+    }
+    body.push_str("<ul>\n");
+    for doc in &tools {
+        body.push_str(&format!(
+            "<li><a href=\"#{}\">{}</a></li>\n",
+            anchor(&doc.tool.name),
+            html_escape(&doc.tool.name)
+        ));
+    }
+    body.push_str("</ul>\n");
+
+    for doc in &tools {
+        body.push_str(&format!(
+            "<h2 id=\"{}\">{}</h2>\n",
+            anchor(&doc.tool.name),
+            html_escape(&doc.tool.name)
+        ));
+        if let Some(description) = doc.tool.description.as_deref().filter(|d| !d.is_empty()) {
+            body.push_str(&format!("<p>{}</p>\n", html_escape(description.trim_end())));
+        }
+        body.push_str(&format!(
+            "<pre><code>{}</code></pre>\n",
+            html_escape(&doc.usage)
+        ));
+        if doc.params.is_empty() {
+            body.push_str("<p>No parameters.</p>\n");
+        } else {
+            body.push_str(
+                "<table>\n<tr><th>Parameter</th><th>Type</th><th>Required</th><th>Description</th></tr>\n",
+            );
+            for param in &doc.params {
+                body.push_str(&format!(
+                    "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    html_escape(&param.name),
+                    html_escape(&type_text(param)),
+                    if param.required { "yes" } else { "no" },
+                    html_escape(param.description.as_deref().unwrap_or(""))
+                ));
+            }
+            body.push_str("</table>\n");
+        }
+        body.push_str(&format!(
+            "<p>Example:</p>\n<pre><code>{}</code></pre>\n",
+            html_escape(&doc.example)
+        ));
+    }
+    html_document(&server.name, &body)
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(tools: Vec<ToolModel>, error: Option<&str>) -> ServerModel {
+        ServerModel {
+            name: "fs".to_string(),
+            status: if error.is_some() {
+                "failed"
+            } else {
+                "connected"
+            }
+            .to_string(),
+            transport_type: None,
+            description: Some("Files | folders".to_string()),
+            tool_count: tools.len(),
+            tools,
+            error: error.map(str::to_string),
+            has_filtered_tools: false,
+        }
+    }
+
+    fn search_tool() -> ToolModel {
+        ToolModel {
+            name: "search".to_string(),
+            description: Some("Search files".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Text to find" },
+                    "filter": { "$ref": "#/$defs/Filter" },
+                    "mode": { "enum": ["fast", "full"] }
+                },
+                "required": ["query", "filter"],
+                "$defs": {
+                    "Filter": {
+                        "type": "object",
+                        "properties": { "depth": { "type": "integer" } },
+                        "required": ["depth"]
+                    }
+                }
+            }),
+        }
+    }
+
+    #[test]
+    fn test_example_value_fills_required_properties() {
+        let schema = resolve_refs(&search_tool().input_schema);
+        assert_eq!(
+            example_value(&schema, ""),
+            json!({ "query": "<query>", "filter": { "depth": 0 } })
+        );
+    }
+
+    #[test]
+    fn test_markdown_page() {
+        let pages = render_pages(&[server(vec![search_tool()], None)], DocsFormat::Markdown);
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].0, "index.md");
+        assert!(
+            pages[0]
+                .1
+                .contains("| [fs](fs.md) | 1 | Files \\| folders |")
+        );
+
+        let (name, page) = &pages[1];
+        assert_eq!(name, "fs.md");
+        assert!(page.contains("- [search](#tool-search)"));
+        assert!(page.contains("| `query` | string | yes | Text to find |"));
+        assert!(page.contains("| `filter.depth` | integer | yes |  |"));
+        assert!(page.contains("| `mode` | any (one of: fast, full) | no |  |"));
+        assert!(
+            page.contains(r#"mcp call fs/search -- '{"filter":{"depth":0},"query":"<query>"}'"#)
+        );
+    }
+
+    #[test]
+    fn test_unavailable_server_has_no_page() {
+        let pages = render_pages(
+            &[server(vec![], Some("Connection failed"))],
+            DocsFormat::Html,
+        );
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].0, "index.html");
+        assert!(pages[0].1.contains("fs (unavailable)"));
+    }
+
+    #[test]
+    fn test_html_page_escapes_text() {
+        let mut tool = search_tool();
+        tool.description = Some("Find <b>files</b>".to_string());
+        let pages = render_pages(&[server(vec![tool], None)], DocsFormat::Html);
+        assert_eq!(pages[1].0, "fs.html");
+        assert!(pages[1].1.contains("<p>Find &lt;b&gt;files&lt;/b&gt;</p>"));
+        assert!(pages[1].1.contains("<h2 id=\"tool-search\">search</h2>"));
+    }
+
+    fn named(name: &str) -> ServerModel {
+        ServerModel {
+            name: name.to_string(),
+            ..server(vec![], None)
+        }
+    }
+
+    #[test]
+    fn test_page_names_sanitize_server_names() {
+        let servers = [named("my server/v2"), named("..hidden"), named("..")];
+        assert_eq!(
+            page_names(&servers, DocsFormat::Markdown),
+            vec!["my-server-v2.md", "hidden.md", "server.md"]
+        );
+    }
+
+    #[test]
+    fn test_page_names_never_collide() {
+        let servers = [
+            named("my server"),
+            named("my/server"),
+            named("My-Server"),
+            named("index"),
+            named("my-server-2"),
+        ];
+        assert_eq!(
+            page_names(&servers, DocsFormat::Html),
+            vec![
+                "my-server.html",
+                "my-server-2.html",
+                "My-Server-3.html",
+                "index-2.html",
+                "my-server-2-2.html"
+            ]
+        );
+
+        let pages = render_pages(&servers, DocsFormat::Html);
+        let files: HashSet<&str> = pages.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(files.len(), 6);
+    }
+}
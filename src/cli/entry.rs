@@ -4,16 +4,18 @@
 //! the Cli struct definition, main function, and initialization.
 
 use crate::cli::command_router::{Commands, execute_command};
+use crate::cli::completions::cmd_completions;
 use crate::cli::config_setup::{setup_config, setup_config_for_daemon, setup_config_optional};
 use crate::cli::daemon_lifecycle::{
     create_auto_daemon_client, create_direct_client, create_require_daemon_client,
 };
+use crate::cli::serve::cmd_serve;
 use crate::config::Config;
 use crate::error::{McpError, Result};
 use crate::format::OutputMode;
 use crate::ipc::create_ipc_client;
 use crate::shutdown::{GracefulShutdown, run_with_graceful_shutdown};
-use clap::Parser;
+use clap::{CommandFactory, Parser};
 use std::path::PathBuf;
 use std::sync::Arc;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -62,10 +64,20 @@ Examples:
   mcp info filesystem read_file # Show tool schema
   mcp search "*file*"          # Search for tools by pattern
   mcp call filesystem read_file '{}'  # Call tool with args
+  mcp export --format openai   # Tools as OpenAI function declarations
+  mcp docs --out docs/         # Markdown pages documenting every tool
+  mcp snapshot > tools.lock.json  # Record every tool schema
+  mcp diff tools.lock.json     # Report changes since; exit 4 if breaking
+  mcp shell                    # Interactive shell
+  mcp serve --stdio            # Serve all servers as one MCP server
+  mcp serve --http 127.0.0.1:8808  # Same, over HTTP at /mcp
+  mcp serve --rest 127.0.0.1:8809  # Tools as REST endpoints with OpenAPI
+  mcp completions bash         # Print a bash completion script
 
 Environment Variables:
   MCP_NO_DAEMON=1     Disable connection caching (direct mode)
-  MCP_DAEMON_TTL=N    Set daemon idle timeout in seconds (default: 60)"#;
+  MCP_DAEMON_TTL=N    Set daemon idle timeout in seconds (default: 60)
+  MCP_GATEWAY_TOKEN   Bearer token required by `mcp serve --http/--rest`"#;
 
 /// Initialize tracing subscriber with appropriate output
 /// - Daemon mode: logs to file (~/.cache/mcp-cli/daemon.log)
@@ -133,15 +145,34 @@ async fn run(cli: Cli) -> Result<()> {
         return shutdown_daemon().await;
     }
 
+    // Completion scripts are generated from the CLI definition alone
+    if let Some(Commands::Completions { shell }) = &cli.command {
+        return cmd_completions(*shell, &mut Cli::command());
+    }
+
     // Load configuration using the loader
     let config = setup_config(cli.config.clone()).await?;
 
+    // Gateway mode owns stdout and its own connection pool
+    if let Some(Commands::Serve {
+        stdio,
+        http,
+        rest,
+        token,
+    }) = &cli.command
+    {
+        return cmd_serve(Arc::new(config), *stdio, *http, *rest, token.clone()).await;
+    }
+
     // Wrap config in Arc for shared ownership
     let daemon_config = Arc::new(config);
 
     // Initialize GracefulShutdown for clean shutdown on signals
+    // (the shell handles Ctrl+C itself, stopping only the running command)
     let shutdown = GracefulShutdown::new();
-    shutdown.spawn_signal_listener();
+    if !matches!(cli.command, Some(Commands::Shell)) {
+        shutdown.spawn_signal_listener();
+    }
 
     // Subscribe to shutdown notifications
     let shutdown_rx = shutdown.subscribe();
@@ -188,7 +219,7 @@ async fn run_auto_daemon_mode(cli: &Cli, config: &Config) -> Result<()> {
     let output_mode = OutputMode::from_flags(cli.json);
 
     // Get or spawn daemon client
-    let client = create_auto_daemon_client(config).await?;
+    let client = create_auto_daemon_client(config, cli.config.as_deref()).await?;
 
     // Execute the command (dispatch to command_router)
     execute_command(cli.command.clone(), client, output_mode).await
@@ -235,11 +266,22 @@ async fn run_standalone_daemon(
     cli_socket_path: Option<PathBuf>,
     cli_config_path: Option<PathBuf>,
 ) -> crate::error::Result<()> {
+    use crate::config::loader::find_config_path;
     use crate::daemon::run_daemon;
 
+    // Resolve the config file once, so reloads reread the same file and
+    // clients can tell which file that is
+    let config_path = match cli_config_path {
+        Some(path) => Some(path),
+        None => find_config_path(None).await.map(PathBuf::from),
+    }
+    .map(std::path::absolute)
+    .transpose()
+    .map_err(McpError::io_error)?;
+
     // Load configuration - allow daemon to start even without config file
     // Pass the CLI config path so daemon respects --config flag
-    let mut config = setup_config_for_daemon(cli_config_path).await?;
+    let mut config = setup_config_for_daemon(config_path.clone()).await?;
 
     // Determine TTL: CLI flag > env var > config > default (60s)
     let ttl = cli_ttl
@@ -274,7 +316,7 @@ async fn run_standalone_daemon(
 
     // Run daemon (this blocks until shutdown)
     tracing::info!("Daemon starting...");
-    match run_daemon(config, socket_path, lifecycle).await {
+    match run_daemon(config, config_path, socket_path, lifecycle).await {
         Ok(()) => {
             tracing::info!("Daemon exited normally");
             Ok(())
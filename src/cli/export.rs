@@ -0,0 +1,107 @@
+//! Tool catalog export command implementation (`mcp export`).
+
+use crate::client::ToolInfo;
+use crate::error::{McpError, Result};
+use crate::format::export::{ExportFormat, export_tools};
+use crate::ipc::ProtocolClient;
+use crate::parallel::{ParallelExecutor, list_tools_parallel};
+use colored::Colorize;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Export every allowed tool as function declarations for another framework.
+///
+/// The declarations are printed to stdout (or written to `output`); warnings
+/// about unreachable servers and removed schema keywords go to stderr.
+///
+/// # Arguments
+/// * `daemon` - Daemon IPC client
+/// * `format` - Target function-calling format
+/// * `mapping` - File to write the exported name → `server/tool` mapping to
+/// * `output` - File to write the declarations to instead of stdout
+///
+/// # Errors
+/// Returns McpError::IOError if an output file can't be written
+pub async fn cmd_export(
+    mut daemon: Box<dyn ProtocolClient>,
+    format: ExportFormat,
+    mapping: Option<&Path>,
+    output: Option<&Path>,
+) -> Result<()> {
+    let config = daemon.config();
+    let executor = ParallelExecutor::new(config.concurrency_limit);
+    let server_names = daemon.list_servers().await?;
+    let daemon = Arc::new(Mutex::new(daemon));
+
+    let (mut successes, failures) = list_tools_parallel(
+        server_names,
+        |server| {
+            let daemon = daemon.clone();
+            async move {
+                let tools = daemon.lock().await.list_tools(&server).await?;
+                Ok(tools
+                    .into_iter()
+                    .map(|tool| ToolInfo {
+                        name: tool.name,
+                        description: Some(tool.description),
+                        input_schema: tool.input_schema,
+                    })
+                    .collect())
+            }
+        },
+        &executor,
+        config.as_ref(),
+    )
+    .await?;
+
+    for server in &failures {
+        warn(&format!(
+            "Skipped server '{}': listing its tools failed",
+            server
+        ));
+    }
+    // Keep config order so repeated exports are identical
+    successes.sort_by_key(|(server, _)| {
+        config
+            .servers
+            .iter()
+            .position(|s| &s.name == server)
+            .unwrap_or(usize::MAX)
+    });
+    let tools: Vec<(String, ToolInfo)> = successes
+        .into_iter()
+        .flat_map(|(server, tools)| tools.into_iter().map(move |tool| (server.clone(), tool)))
+        .collect();
+
+    let export = export_tools(&tools, format);
+    for warning in &export.warnings {
+        warn(warning);
+    }
+
+    if let Some(path) = mapping {
+        write_json(path, &serde_json::json!(export.mapping))?;
+    } else if export.renamed > 0 {
+        warn(&format!(
+            "{} tool name(s) were sanitized or shortened; use --mapping FILE to translate them back",
+            export.renamed
+        ));
+    }
+
+    match output {
+        Some(path) => write_json(path, &export.tools),
+        None => {
+            crate::output::print_json(&export.tools);
+            Ok(())
+        }
+    }
+}
+
+/// Warnings go to stderr so stdout stays valid JSON.
+fn warn(message: &str) {
+    eprintln!("{} {}", "Warning:".yellow().bold(), message);
+}
+
+fn write_json(path: &Path, value: &serde_json::Value) -> Result<()> {
+    std::fs::write(path, format!("{:#}\n", value)).map_err(McpError::io_error)
+}
@@ -14,6 +14,7 @@ use crate::cli::models::*;
 use crate::format::{DetailLevel, OutputMode, extract_params_from_schema, format_param_list};
 use crate::output::print_json;
 use colored::Colorize;
+use std::io::IsTerminal;
 
 /// Format list servers output.
 ///
@@ -580,6 +581,63 @@ fn format_call_result_human(model: &CallResultModel) {
     }
 }
 
+/// Width of the human-mode progress bar in characters
+const PROGRESS_BAR_WIDTH: usize = 30;
+
+/// Report tool call progress on stderr, keeping stdout for the result.
+///
+/// Human mode redraws a single progress line when stderr is a terminal and
+/// prints one line per update otherwise; JSON mode writes one NDJSON event
+/// per update.
+pub fn format_progress(model: &ProgressModel, output_mode: OutputMode) {
+    match output_mode {
+        OutputMode::Human if std::io::stderr().is_terminal() => {
+            eprint!("\r\x1b[2K{}", progress_line(model));
+        }
+        OutputMode::Human => eprintln!("{}", progress_line(model)),
+        OutputMode::Json => match serde_json::to_string(model) {
+            Ok(json) => eprintln!("{}", json),
+            Err(e) => tracing::debug!("Failed to serialize progress: {}", e),
+        },
+    }
+}
+
+/// Erase the progress line drawn by `format_progress`, if any.
+///
+/// Call before printing the result so it doesn't share a line with the bar.
+pub fn clear_progress(output_mode: OutputMode) {
+    if output_mode == OutputMode::Human && std::io::stderr().is_terminal() {
+        eprint!("\r\x1b[2K");
+    }
+}
+
+/// Render a progress report as one line of text.
+///
+/// Shows a bar and percentage when the total is known, otherwise the raw
+/// progress value.
+fn progress_line(model: &ProgressModel) -> String {
+    let mut line = match model.total.filter(|total| *total > 0.0) {
+        Some(total) => {
+            let fraction = (model.progress / total).clamp(0.0, 1.0);
+            let filled = (fraction * PROGRESS_BAR_WIDTH as f64).round() as usize;
+            format!(
+                "[{}{}] {:>3.0}% ({}/{})",
+                "#".repeat(filled),
+                "-".repeat(PROGRESS_BAR_WIDTH - filled),
+                fraction * 100.0,
+                model.progress,
+                total
+            )
+        }
+        None => format!("Progress: {}", model.progress),
+    };
+    if let Some(ref message) = model.message {
+        line.push(' ');
+        line.push_str(message);
+    }
+    line
+}
+
 /// Format search results output.
 ///
 /// Displays search results with context-rich information.
@@ -804,10 +862,365 @@ fn format_search_results_human(model: &SearchResultModel, detail_level: DetailLe
     }
 }
 
+/// Format one page of tools from `mcp list --page-size`.
+pub fn format_tool_page(model: &ToolPageModel, output_mode: OutputMode) {
+    match output_mode {
+        OutputMode::Human => format_tool_page_human(model),
+        OutputMode::Json => print_json(model),
+    }
+}
+
+/// Format a page of tools for human-readable output.
+fn format_tool_page_human(model: &ToolPageModel) {
+    println!(
+        "{} {}",
+        "Tools".bold(),
+        format!("({})", model.tools.len()).dimmed()
+    );
+    println!("{}", "─".repeat(50).dimmed());
+
+    for paged in &model.tools {
+        let id = format!("{}/{}", paged.server, paged.tool.name);
+        match paged.tool.description {
+            Some(ref desc) if !desc.is_empty() => println!("  {}  {}", id.bold(), desc.dimmed()),
+            _ => println!("  {}", id.bold()),
+        }
+    }
+
+    if !model.failed_servers.is_empty() {
+        println!();
+        println!(
+            "{} Failed to list: {}",
+            "⚠".yellow(),
+            model.failed_servers.join(", ")
+        );
+    }
+
+    if let Some(ref cursor) = model.next_cursor {
+        println!();
+        println!("Next page: --cursor {}", cursor.cyan());
+    }
+}
+
+/// Format resources list output.
+///
+/// Displays the resources exposed by a single server.
+pub fn format_resource_list(model: &ResourceListModel, output_mode: OutputMode) {
+    match output_mode {
+        OutputMode::Human => format_resource_list_human(model),
+        OutputMode::Json => print_json(model),
+    }
+}
+
+/// Format resources list for human-readable output.
+fn format_resource_list_human(model: &ResourceListModel) {
+    if model.resources.is_empty() {
+        println!(
+            "No resources available on server '{}'",
+            model.server_name.bold()
+        );
+        return;
+    }
+
+    println!(
+        "{} {}",
+        format!("Resources on '{}'", model.server_name).bold(),
+        format!("({})", model.resources.len()).dimmed()
+    );
+    println!("{}", "─".repeat(50).dimmed());
+
+    for resource in &model.resources {
+        let mime = resource
+            .mime_type
+            .as_deref()
+            .map(|m| format!(" ({})", m))
+            .unwrap_or_default();
+        println!("  • {}{}", resource.name.bold(), mime.dimmed());
+        println!("    {}", resource.uri.cyan());
+        if let Some(ref desc) = resource.description {
+            println!("    {}", desc);
+        }
+    }
+
+    println!();
+    println!(
+        "{}",
+        format!(
+            "Use 'mcp resources read {} <uri>' to read a resource",
+            model.server_name
+        )
+        .dimmed()
+    );
+}
+
+/// Format resource templates output.
+///
+/// Displays the parameterized resource URIs exposed by a single server.
+pub fn format_resource_templates(model: &ResourceTemplateListModel, output_mode: OutputMode) {
+    match output_mode {
+        OutputMode::Human => format_resource_templates_human(model),
+        OutputMode::Json => print_json(model),
+    }
+}
+
+/// Format resource templates for human-readable output.
+fn format_resource_templates_human(model: &ResourceTemplateListModel) {
+    if model.templates.is_empty() {
+        println!(
+            "No resource templates available on server '{}'",
+            model.server_name.bold()
+        );
+        return;
+    }
+
+    println!(
+        "{} {}",
+        format!("Resource templates on '{}'", model.server_name).bold(),
+        format!("({})", model.templates.len()).dimmed()
+    );
+    println!("{}", "─".repeat(50).dimmed());
+
+    for template in &model.templates {
+        let mime = template
+            .mime_type
+            .as_deref()
+            .map(|m| format!(" ({})", m))
+            .unwrap_or_default();
+        println!("  • {}{}", template.name.bold(), mime.dimmed());
+        println!("    {}", template.uri_template.cyan());
+        if let Some(ref desc) = template.description {
+            println!("    {}", desc);
+        }
+    }
+}
+
+/// Format resource read output.
+///
+/// Text contents are printed as-is. Binary contents are never dumped as
+/// base64 in human mode; a summary is shown instead, or the destination path
+/// when the content was written out with `--output`.
+pub fn format_resource_contents(model: &ResourceReadModel, output_mode: OutputMode) {
+    match output_mode {
+        OutputMode::Human => format_resource_contents_human(model),
+        OutputMode::Json => print_json(model),
+    }
+}
+
+/// Format resource contents for human-readable output.
+fn format_resource_contents_human(model: &ResourceReadModel) {
+    if model.contents.is_empty() {
+        println!("Resource '{}' returned no content", model.uri);
+        return;
+    }
+
+    let show_headers = model.contents.len() > 1;
+
+    for content in &model.contents {
+        if show_headers {
+            let mime = content
+                .mime_type
+                .as_deref()
+                .map(|m| format!(" ({})", m))
+                .unwrap_or_default();
+            println!("{}{}", content.uri.bold(), mime.dimmed());
+            println!("{}", "─".repeat(50).dimmed());
+        }
+
+        if let Some(ref path) = content.saved_to {
+            let size = content
+                .size_bytes
+                .or_else(|| content.text.as_ref().map(|t| t.len()))
+                .unwrap_or_default();
+            println!("{} Saved {} bytes to {}", "✓".green(), size, path);
+        } else if let Some(ref text) = content.text {
+            println!("{}", text);
+        } else if content.blob.is_some() {
+            println!(
+                "{} Binary content ({} bytes, {}); use --output <FILE> to save it",
+                "⚠".yellow(),
+                content.size_bytes.unwrap_or_default(),
+                content
+                    .mime_type
+                    .as_deref()
+                    .unwrap_or("application/octet-stream")
+            );
+        }
+
+        if show_headers {
+            println!();
+        }
+    }
+}
+
+/// Format prompts list output.
+///
+/// Displays the prompt templates exposed by a single server with their arguments.
+pub fn format_prompt_list(model: &PromptListModel, output_mode: OutputMode) {
+    match output_mode {
+        OutputMode::Human => format_prompt_list_human(model),
+        OutputMode::Json => print_json(model),
+    }
+}
+
+/// Format prompts list for human-readable output.
+fn format_prompt_list_human(model: &PromptListModel) {
+    if model.prompts.is_empty() {
+        println!(
+            "No prompts available on server '{}'",
+            model.server_name.bold()
+        );
+        return;
+    }
+
+    println!(
+        "{} {}",
+        format!("Prompts on '{}'", model.server_name).bold(),
+        format!("({})", model.prompts.len()).dimmed()
+    );
+    println!("{}", "─".repeat(50).dimmed());
+
+    for prompt in &model.prompts {
+        let desc = prompt.description.as_deref().unwrap_or("No description");
+        println!("  • {}: {}", prompt.name.bold(), desc);
+
+        for arg in &prompt.arguments {
+            let req_str = if arg.required { "Required" } else { "Optional" };
+            match arg.description {
+                Some(ref arg_desc) => println!(
+                    "      {}  {}. {}",
+                    format!("--{}", arg.name).cyan(),
+                    req_str.dimmed(),
+                    arg_desc
+                ),
+                None => println!(
+                    "      {}  {}",
+                    format!("--{}", arg.name).cyan(),
+                    req_str.dimmed()
+                ),
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "{}",
+        format!(
+            "Use 'mcp prompts get {}/<prompt> -- --arg value' to render a prompt",
+            model.server_name
+        )
+        .dimmed()
+    );
+}
+
+/// Format prompts get output.
+///
+/// Prints each rendered message under its role.
+pub fn format_prompt_result(model: &PromptResultModel, output_mode: OutputMode) {
+    match output_mode {
+        OutputMode::Human => format_prompt_result_human(model),
+        OutputMode::Json => print_json(model),
+    }
+}
+
+/// Format a rendered prompt for human-readable output.
+fn format_prompt_result_human(model: &PromptResultModel) {
+    println!(
+        "{} {}/{}",
+        "Prompt:".bold(),
+        model.server_name,
+        model.prompt_name
+    );
+    if let Some(ref desc) = model.description {
+        println!("Description: {}", desc);
+    }
+    println!("{}", "─".repeat(50).dimmed());
+
+    for message in &model.messages {
+        println!();
+        let role = format!("[{}]", message.role);
+        if message.role == "assistant" {
+            println!("{}", role.green().bold());
+        } else {
+            println!("{}", role.cyan().bold());
+        }
+        for line in format_prompt_content(&message.content).lines() {
+            println!("  {}", line);
+        }
+    }
+}
+
+/// Render prompt message content as text.
+///
+/// Text is shown verbatim; images and embedded resources are summarized
+/// (embedded text resources are shown inline).
+fn format_prompt_content(content: &serde_json::Value) -> String {
+    match content.get("type").and_then(|t| t.as_str()) {
+        Some("text") => content
+            .get("text")
+            .and_then(|t| t.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        Some("image") | Some("audio") => format!(
+            "({} data: {} bytes, type: {})",
+            content["type"].as_str().unwrap_or_default(),
+            content
+                .get("data")
+                .and_then(|d| d.as_str())
+                .map(|d| d.len())
+                .unwrap_or_default(),
+            content
+                .get("mimeType")
+                .and_then(|m| m.as_str())
+                .unwrap_or("unknown")
+        ),
+        Some("resource") => {
+            let resource = &content["resource"];
+            let uri = resource.get("uri").and_then(|u| u.as_str()).unwrap_or("?");
+            match resource.get("text").and_then(|t| t.as_str()) {
+                Some(text) => format!("(resource: {})\n{}", uri, text),
+                None => format!("(resource: {})", uri),
+            }
+        }
+        _ => serde_json::to_string_pretty(content).unwrap_or_else(|_| content.to_string()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_progress_line_with_total() {
+        let model = ProgressModel {
+            server_name: "test".to_string(),
+            tool_name: "build".to_string(),
+            progress: 3.0,
+            total: Some(4.0),
+            message: Some("linking".to_string()),
+        };
+
+        let line = progress_line(&model);
+        let bar = format!("[{}{}]", "#".repeat(23), "-".repeat(7));
+        assert_eq!(line, format!("{}  75% (3/4) linking", bar));
+    }
+
+    #[test]
+    fn test_progress_line_without_total() {
+        let model = ProgressModel {
+            server_name: "test".to_string(),
+            tool_name: "build".to_string(),
+            progress: 12.0,
+            total: None,
+            message: None,
+        };
+
+        assert_eq!(progress_line(&model), "Progress: 12");
+        let json = serde_json::to_value(&model).unwrap();
+        assert_eq!(json["event"], "progress");
+        assert_eq!(json["progress"], 12.0);
+        assert!(json.get("total").is_none());
+    }
+
     #[test]
     fn test_output_mode_creation() {
         assert_eq!(OutputMode::from_flags(true), OutputMode::Json);
@@ -908,4 +1321,97 @@ mod tests {
         format_search_results(&model, DetailLevel::Summary, OutputMode::Json);
         format_search_results(&model, DetailLevel::Summary, OutputMode::Human);
     }
+
+    #[test]
+    fn test_resource_models_formatting() {
+        let list = ResourceListModel {
+            server_name: "srv".to_string(),
+            resources: vec![ResourceModel {
+                uri: "file:///readme.txt".to_string(),
+                name: "readme.txt".to_string(),
+                description: Some("Project readme".to_string()),
+                mime_type: Some("text/plain".to_string()),
+            }],
+        };
+        format_resource_list(&list, OutputMode::Json);
+        format_resource_list(&list, OutputMode::Human);
+
+        let templates = ResourceTemplateListModel {
+            server_name: "srv".to_string(),
+            templates: vec![ResourceTemplateModel {
+                uri_template: "file:///{path}".to_string(),
+                name: "file".to_string(),
+                description: None,
+                mime_type: None,
+            }],
+        };
+        format_resource_templates(&templates, OutputMode::Json);
+        format_resource_templates(&templates, OutputMode::Human);
+
+        let read = ResourceReadModel {
+            server_name: "srv".to_string(),
+            uri: "file:///logo.png".to_string(),
+            contents: vec![ResourceContentModel {
+                uri: "file:///logo.png".to_string(),
+                mime_type: Some("image/png".to_string()),
+                text: None,
+                blob: Some("iVBORw0KGgo=".to_string()),
+                size_bytes: Some(8),
+                saved_to: None,
+            }],
+        };
+        format_resource_contents(&read, OutputMode::Json);
+        format_resource_contents(&read, OutputMode::Human);
+    }
+
+    #[test]
+    fn test_prompt_models_formatting() {
+        let list = PromptListModel {
+            server_name: "srv".to_string(),
+            prompts: vec![PromptModel {
+                name: "review".to_string(),
+                description: Some("Review code".to_string()),
+                arguments: vec![PromptArgumentModel {
+                    name: "code".to_string(),
+                    description: None,
+                    required: true,
+                }],
+            }],
+        };
+        format_prompt_list(&list, OutputMode::Json);
+        format_prompt_list(&list, OutputMode::Human);
+
+        let result = PromptResultModel {
+            server_name: "srv".to_string(),
+            prompt_name: "review".to_string(),
+            description: None,
+            messages: vec![PromptMessageModel {
+                role: "user".to_string(),
+                content: serde_json::json!({"type": "text", "text": "Review this"}),
+            }],
+        };
+        format_prompt_result(&result, OutputMode::Json);
+        format_prompt_result(&result, OutputMode::Human);
+    }
+
+    #[test]
+    fn test_format_prompt_content() {
+        let text = serde_json::json!({"type": "text", "text": "hello"});
+        assert_eq!(format_prompt_content(&text), "hello");
+
+        let image = serde_json::json!({"type": "image", "data": "AAAA", "mimeType": "image/png"});
+        assert_eq!(
+            format_prompt_content(&image),
+            "(image data: 4 bytes, type: image/png)"
+        );
+
+        let resource = serde_json::json!({
+            "type": "resource",
+            "resource": {"uri": "file:///a.txt", "text": "body"}
+        });
+        assert_eq!(
+            format_prompt_content(&resource),
+            "(resource: file:///a.txt)\nbody"
+        );
+    }
 }
@@ -80,11 +80,17 @@ async fn query_server_info(
             }
             details
         }
-        ServerTransport::Http { url, headers } => {
+        ServerTransport::Http { url, headers, .. } | ServerTransport::Sse { url, headers } => {
             let mut details = serde_json::json!({
-                "type": "http",
+                "type": server.transport.type_name(),
                 "url": url,
             });
+            if let ServerTransport::Http {
+                sse_fallback: true, ..
+            } = &server.transport
+            {
+                details["sse_fallback"] = serde_json::Value::Bool(true);
+            }
             if !headers.is_empty() {
                 let headers_map: serde_json::Value = headers
                     .iter()
@@ -205,6 +211,22 @@ async fn query_tool_info(
     })
 }
 
+/// Verify a server exists in config before contacting it.
+///
+/// # Errors
+/// Returns McpError::ServerNotFound if server doesn't exist (ERR-02)
+pub(crate) fn ensure_server(daemon: &dyn ProtocolClient, server_name: &str) -> Result<()> {
+    let config = daemon.config();
+    if config.get_server(server_name).is_some() {
+        return Ok(());
+    }
+    print_error(&format!("Server '{}' not found", server_name));
+    Err(McpError::ServerNotFound {
+        server: server_name.to_string(),
+        servers: config.servers.iter().map(|s| s.name.clone()).collect(),
+    })
+}
+
 /// Parse a tool identifier from a string.
 ///
 /// Supports both "server/tool" and "server tool" formats (CLI-05).
@@ -0,0 +1,201 @@
+//! Interactive prompting for required tool arguments.
+//!
+//! When `mcp call` runs on a terminal without some of the tool's required
+//! arguments, each missing one is asked for in turn. Answers are converted
+//! and checked against the property's schema as they are entered, and the
+//! equivalent non-interactive command line is printed afterwards for reuse.
+
+use crate::cli::arguments::parse_property_value;
+use crate::error::{McpError, Result};
+use crate::format::schema::{ParameterInfo, extract_params_from_schema, validate_against_schema};
+use colored::Colorize;
+use serde_json::Value;
+use std::io::{BufRead, Write};
+
+/// Ask for every required property missing from `arguments`.
+///
+/// Invalid answers are explained and asked again. Returns whether any
+/// argument was prompted for.
+///
+/// # Errors
+/// Returns McpError::UsageError if input ends before every required
+/// argument was given
+/// Returns McpError::IOError if the prompt can't be read or written
+pub fn prompt_missing_arguments(
+    schema: &Value,
+    arguments: &mut Value,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<bool> {
+    let Some(given) = arguments.as_object_mut() else {
+        return Ok(false);
+    };
+    let missing: Vec<ParameterInfo> = extract_params_from_schema(schema)
+        .into_iter()
+        .filter(|param| param.required && !given.contains_key(&param.name))
+        .collect();
+
+    for param in &missing {
+        let property = &schema["properties"][&param.name];
+        let value = prompt_value(param, property, input, output)?;
+        given.insert(param.name.clone(), value);
+    }
+    Ok(!missing.is_empty())
+}
+
+/// Ask for one property until the answer fits its schema.
+fn prompt_value(
+    param: &ParameterInfo,
+    property: &Value,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<Value> {
+    writeln!(
+        output,
+        "{} ({})",
+        param.name.bold(),
+        param.param_type.dimmed()
+    )
+    .map_err(McpError::io_error)?;
+    if let Some(description) = &param.description {
+        writeln!(output, "  {}", description).map_err(McpError::io_error)?;
+    }
+    if !param.enum_values.is_empty() {
+        writeln!(output, "  one of: {}", param.enum_values.join(", "))
+            .map_err(McpError::io_error)?;
+    }
+
+    loop {
+        write!(output, "  > ").map_err(McpError::io_error)?;
+        output.flush().map_err(McpError::io_error)?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line).map_err(McpError::io_error)? == 0 {
+            writeln!(output).map_err(McpError::io_error)?;
+            return Err(McpError::usage_error(format!(
+                "Input ended before required argument '{}' was given",
+                param.name
+            )));
+        }
+        let text = line.trim_end_matches(['\r', '\n']);
+        if text.is_empty() {
+            writeln!(output, "  {}", "a value is required".red()).map_err(McpError::io_error)?;
+            continue;
+        }
+
+        let problem = match parse_property_value(&param.name, text, property) {
+            Ok(value) => match validate_against_schema(property, &value).first() {
+                None => return Ok(value),
+                Some(violation) => violation.message.clone(),
+            },
+            Err(McpError::UsageError { message }) => message,
+            Err(e) => e.to_string(),
+        };
+        writeln!(output, "  {}", problem.red()).map_err(McpError::io_error)?;
+    }
+}
+
+/// Format the `mcp call` command line that repeats a call non-interactively.
+pub fn command_line(
+    tool_id: &str,
+    timeout: Option<u64>,
+    validate: bool,
+    arguments: &Value,
+) -> String {
+    let mut command = format!("mcp call {}", tool_id);
+    if let Some(timeout) = timeout {
+        command.push_str(&format!(" --timeout {}", timeout));
+    }
+    if !validate {
+        command.push_str(" --no-validate");
+    }
+    command.push_str(" -- ");
+    command.push_str(&shell_quote(&arguments.to_string()));
+    command
+}
+
+/// Quote text as a single POSIX shell word.
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "count": { "type": "integer", "description": "How many", "minimum": 1 },
+                "color": { "type": "string", "enum": ["red", "green"] },
+                "note": { "type": "string" }
+            },
+            "required": ["count", "color"]
+        })
+    }
+
+    fn prompt(arguments: &mut Value, answers: &str) -> (Result<bool>, String) {
+        let mut output = Vec::new();
+        let result =
+            prompt_missing_arguments(&schema(), arguments, &mut answers.as_bytes(), &mut output);
+        (result, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn test_prompts_only_for_missing_required_arguments() {
+        let mut arguments = json!({ "color": "red" });
+        let (result, output) = prompt(&mut arguments, "3\n");
+
+        assert!(result.unwrap());
+        assert_eq!(arguments, json!({ "color": "red", "count": 3 }));
+        assert!(output.contains("count") && output.contains("integer"));
+        assert!(output.contains("How many"));
+        assert!(!output.contains("color"));
+    }
+
+    #[test]
+    fn test_asks_again_until_answer_is_valid() {
+        let mut arguments = json!({});
+        let (result, output) = prompt(&mut arguments, "\nblue\ngreen\nabc\n0\n2\n");
+
+        assert!(result.unwrap());
+        assert_eq!(arguments, json!({ "count": 2, "color": "green" }));
+        assert!(output.contains("one of: red, green"));
+        assert!(output.contains("a value is required"));
+        assert!(output.contains("--count expects an integer, got 'abc'"));
+        assert!(output.contains("must be at least 1"));
+        assert!(output.contains("must be one of"));
+    }
+
+    #[test]
+    fn test_end_of_input_is_an_error() {
+        let mut arguments = json!({});
+        let (result, _) = prompt(&mut arguments, "1\n");
+
+        assert!(matches!(result, Err(McpError::UsageError { .. })));
+    }
+
+    #[test]
+    fn test_nothing_missing_prompts_nothing() {
+        let mut arguments = json!({ "count": 1, "color": "red" });
+        let (result, output) = prompt(&mut arguments, "");
+
+        assert!(!result.unwrap());
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn test_command_line_repeats_the_call() {
+        let arguments = json!({ "note": "it's" });
+        assert_eq!(
+            command_line("srv/tool", Some(30), false, &arguments),
+            r#"mcp call srv/tool --timeout 30 --no-validate -- '{"note":"it'\''s"}'"#
+        );
+        assert_eq!(
+            command_line("srv/tool", None, true, &json!({})),
+            "mcp call srv/tool -- '{}'"
+        );
+    }
+}
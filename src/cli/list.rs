@@ -2,13 +2,17 @@
 
 use crate::cli::DetailLevel;
 use crate::cli::formatters;
-use crate::cli::models::{ListServersModel, ServerModel, ToolModel};
+use crate::cli::models::{ListServersModel, PagedToolModel, ServerModel, ToolModel, ToolPageModel};
 use crate::client::ToolInfo;
-use crate::error::Result;
+use crate::error::{McpError, Result};
 use crate::format::OutputMode;
 use crate::ipc::ProtocolClient;
 use crate::output::print_error;
-use crate::parallel::{ParallelExecutor, list_tools_parallel};
+use crate::parallel::{ParallelExecutor, is_tool_allowed, list_tools_parallel};
+use crate::transport::MAX_LIST_PAGES;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -38,11 +42,189 @@ pub async fn cmd_list_servers(
     Ok(())
 }
 
+/// Execute the list command with manual paging.
+///
+/// Returns up to `page_size` tools, following the servers in config order.
+/// Tools are read one server `tools/list` page at a time and buffered until
+/// the page is full, so large catalogs are never downloaded in full. The
+/// returned cursor is opaque to callers; it carries the server's own
+/// `nextCursor` plus how many tools of that server page were already
+/// returned. Pass it back to continue.
+///
+/// # Arguments
+/// * `daemon` - Daemon IPC client
+/// * `page_size` - Maximum number of tools on the page
+/// * `cursor` - Cursor returned by the previous page, or None for the first page
+/// * `output_mode` - Output format (human or JSON)
+///
+/// # Errors
+/// Returns McpError::UsageError if the page size is zero, or the cursor is
+/// invalid or names a server that is no longer configured
+pub async fn cmd_list_tools_page(
+    mut daemon: Box<dyn ProtocolClient>,
+    page_size: usize,
+    cursor: Option<&str>,
+    output_mode: OutputMode,
+) -> Result<()> {
+    if page_size == 0 {
+        return Err(McpError::usage_error(
+            "--page-size must be greater than zero",
+        ));
+    }
+
+    let servers = daemon.list_servers().await?;
+    let mut position = match cursor {
+        Some(cursor) => PageCursor::decode(cursor)?,
+        None => match servers.first() {
+            Some(first) => PageCursor::start_of(first),
+            None => {
+                formatters::format_tool_page(&ToolPageModel::default(), output_mode);
+                return Ok(());
+            }
+        },
+    };
+    if !servers.contains(&position.server) {
+        return Err(McpError::usage_error(format!(
+            "The cursor continues server '{}', which is not configured",
+            position.server
+        )));
+    }
+
+    let config = daemon.config();
+    let mut tools = Vec::new();
+    let mut failed_servers = Vec::new();
+    let mut requests = 0;
+    let next_cursor = loop {
+        requests += 1;
+        let (listed, server_cursor) = match daemon
+            .list_tools_page(&position.server, position.cursor.as_deref())
+            .await
+        {
+            Ok(page) => (page.tools, page.next_cursor),
+            Err(e) => {
+                tracing::warn!("Failed to list tools for {}: {}", position.server, e);
+                failed_servers.push(position.server.clone());
+                (Vec::new(), None)
+            }
+        };
+        let server_config = config.get_server(&position.server);
+        let listed: Vec<_> = listed
+            .into_iter()
+            .filter(|tool| server_config.is_none_or(|s| is_tool_allowed(&tool.name, s)))
+            .collect();
+
+        // Take what fits from this server page; stop mid-page if it is full
+        let wanted = page_size - tools.len();
+        let available = listed.len().saturating_sub(position.offset);
+        let taken = available.min(wanted);
+        tools.extend(
+            listed
+                .into_iter()
+                .skip(position.offset)
+                .take(taken)
+                .map(|tool| PagedToolModel {
+                    server: position.server.clone(),
+                    tool: ToolModel {
+                        name: tool.name,
+                        description: Some(tool.description),
+                        input_schema: tool.input_schema,
+                    },
+                }),
+        );
+        if taken < available {
+            position.offset += taken;
+            break Some(position);
+        }
+
+        match next_page(&servers, &position, server_cursor) {
+            None => break None,
+            Some(next) if tools.len() == page_size || requests >= MAX_LIST_PAGES => {
+                break Some(next);
+            }
+            Some(next) => position = next,
+        }
+    };
+
+    let page = ToolPageModel {
+        tools,
+        next_cursor: next_cursor.map(|next| next.encode()),
+        failed_servers,
+    };
+    formatters::format_tool_page(&page, output_mode);
+    Ok(())
+}
+
+/// Position in the paged tool listing
+///
+/// Names a server, that server's cursor for the page to fetch, and how many
+/// tools of that page the previous `mcp list` page already returned.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct PageCursor {
+    server: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cursor: Option<String>,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    offset: usize,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+impl PageCursor {
+    /// The first page of `server`
+    fn start_of(server: &str) -> Self {
+        Self {
+            server: server.to_string(),
+            cursor: None,
+            offset: 0,
+        }
+    }
+
+    fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).expect("PageCursor always serializes");
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    fn decode(cursor: &str) -> Result<Self> {
+        URL_SAFE_NO_PAD
+            .decode(cursor)
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .ok_or_else(|| McpError::usage_error(format!("Invalid cursor '{}'", cursor)))
+    }
+}
+
+/// The server page after `current`, given the server's cursor for its next page
+///
+/// A server that returns no cursor, or the cursor it was just sent, is done;
+/// the listing then moves on to the next server.
+fn next_page(
+    servers: &[String],
+    current: &PageCursor,
+    server_cursor: Option<String>,
+) -> Option<PageCursor> {
+    match server_cursor {
+        Some(next) if current.cursor.as_ref() != Some(&next) => Some(PageCursor {
+            server: current.server.clone(),
+            cursor: Some(next),
+            offset: 0,
+        }),
+        _ => servers
+            .iter()
+            .skip_while(|s| **s != current.server)
+            .nth(1)
+            .map(|s| PageCursor::start_of(s)),
+    }
+}
+
 /// Query daemon to build list servers model.
 ///
 /// This function handles all the data collection, building a model that
 /// can be formatted for either human or JSON output.
-async fn query_list_servers(mut daemon: Box<dyn ProtocolClient>) -> Result<ListServersModel> {
+pub(crate) async fn query_list_servers(
+    mut daemon: Box<dyn ProtocolClient>,
+) -> Result<ListServersModel> {
     let config = daemon.config();
 
     // Handle empty config - return empty model
@@ -178,6 +360,63 @@ async fn query_list_servers(mut daemon: Box<dyn ProtocolClient>) -> Result<ListS
 mod tests {
     use super::*;
 
+    fn servers() -> Vec<String> {
+        vec!["a".to_string(), "b".to_string()]
+    }
+
+    #[test]
+    fn test_next_page_follows_server_cursor() {
+        let next = next_page(
+            &servers(),
+            &PageCursor::start_of("a"),
+            Some("p2".to_string()),
+        );
+        assert_eq!(
+            next,
+            Some(PageCursor {
+                server: "a".to_string(),
+                cursor: Some("p2".to_string()),
+                offset: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_next_page_moves_to_next_server() {
+        let current = PageCursor {
+            server: "a".to_string(),
+            cursor: Some("p2".to_string()),
+            offset: 1,
+        };
+        assert_eq!(
+            next_page(&servers(), &current, None),
+            Some(PageCursor::start_of("b"))
+        );
+        // A server repeating the cursor it was sent is done as well
+        assert_eq!(
+            next_page(&servers(), &current, Some("p2".to_string())),
+            Some(PageCursor::start_of("b"))
+        );
+        assert_eq!(
+            next_page(&servers(), &PageCursor::start_of("b"), None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_page_cursor_roundtrip() {
+        let cursor = PageCursor {
+            server: "a".to_string(),
+            cursor: Some("opaque/server=cursor".to_string()),
+            offset: 2,
+        };
+        assert_eq!(PageCursor::decode(&cursor.encode()).unwrap(), cursor);
+        assert!(matches!(
+            PageCursor::decode("bogus"),
+            Err(McpError::UsageError { .. })
+        ));
+    }
+
     #[test]
     fn test_list_servers_model_building() {
         let model = ListServersModel {
@@ -11,6 +11,16 @@
 //! - [`daemon_lifecycle`] — Daemon start/stop/auto-spawn logic
 //! - [`config_setup`] — Configuration loading helpers
 //! - [`commands`] — Individual command implementations (list, call, info, search)
+//! - [`arguments`] — Tool argument parsing with schema-aware type coercion
+//! - [`interactive`] — Prompting for missing required tool arguments on a terminal
+//! - [`resources`] — Resource listing and reading (`mcp resources`)
+//! - [`prompts`] — Prompt listing and rendering (`mcp prompts`)
+//! - [`shell`] — Interactive shell over one connection (`mcp shell`)
+//! - [`serve`] — Gateway mode serving all servers as one (`mcp serve`)
+//! - [`completions`] — Shell completion scripts and candidates (`mcp completions`)
+//! - [`export`] — Tool catalog export to function-calling formats (`mcp export`)
+//! - [`docs`] — Markdown/HTML documentation of every tool (`mcp docs`)
+//! - [`snapshot`] — Tool schema snapshots and breaking-change diffs (`mcp snapshot`, `mcp diff`)
 //! - [`models`] — Shared data models for command output
 //! - [`formatters`] — Human/JSON output formatting for command results
 //! - [`filter`] — Tool filtering by name/description patterns
@@ -27,19 +37,29 @@
 //! ```
 
 // Module declarations - keep these public for tests and binary
+pub mod arguments;
 pub mod call;
 pub mod command_router;
 pub mod commands;
+pub mod completions;
 pub mod config_setup;
 pub mod daemon;
 pub mod daemon_lifecycle;
+pub mod docs;
 pub mod entry;
+pub mod export;
 pub mod filter;
 pub mod formatters;
 pub mod info;
+pub mod interactive;
 pub mod list;
 pub mod models;
+pub mod prompts;
+pub mod resources;
 pub mod search;
+pub mod serve;
+pub mod shell;
+pub mod snapshot;
 
 // DetailLevel is used internally for output formatting
 pub use crate::format::DetailLevel;
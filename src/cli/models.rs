@@ -66,6 +66,32 @@ pub struct ToolModel {
     pub input_schema: serde_json::Value,
 }
 
+/// Model for one page of tools from `mcp list --page-size`.
+///
+/// Tools from all servers are listed in server order; `next_cursor` is passed
+/// back via `--cursor` to fetch the following page.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToolPageModel {
+    /// Tools on this page
+    pub tools: Vec<PagedToolModel>,
+    /// Cursor for the next page, absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Servers that could not be listed while filling this page
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failed_servers: Vec<String>,
+}
+
+/// Model for a tool on a page, tagged with its server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PagedToolModel {
+    /// Server providing the tool
+    pub server: String,
+    /// Tool details
+    #[serde(flatten)]
+    pub tool: ToolModel,
+}
+
 /// Model for server info command output.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerInfoModel {
@@ -126,6 +152,7 @@ impl From<&ParameterModel> for crate::format::ParameterInfo {
             param_type: model.param_type.clone(),
             description: model.description.clone(),
             required: model.required,
+            enum_values: Vec::new(),
         }
     }
 }
@@ -153,6 +180,27 @@ pub struct CallResultModel {
     pub retries: u32,
 }
 
+/// Model for a progress report received while a tool call runs.
+///
+/// Serialized with `"event": "progress"` so `--json` consumers can tell
+/// progress lines on stderr apart from other events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename = "progress")]
+pub struct ProgressModel {
+    /// Server running the tool
+    pub server_name: String,
+    /// Tool being executed
+    pub tool_name: String,
+    /// Progress so far
+    pub progress: f64,
+    /// Total amount of work, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<f64>,
+    /// Description of the current step
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
 /// Model for search results output.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResultModel {
@@ -183,6 +231,144 @@ pub struct SearchMatchModel {
     pub input_schema: serde_json::Value,
 }
 
+/// Model for resources list command output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceListModel {
+    /// Server the resources were listed from
+    pub server_name: String,
+    /// Resources exposed by the server
+    pub resources: Vec<ResourceModel>,
+}
+
+/// Model for an individual resource.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceModel {
+    /// Resource URI
+    pub uri: String,
+    /// Human-readable resource name
+    pub name: String,
+    /// Resource description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// MIME type, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+/// Model for resource templates command output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceTemplateListModel {
+    /// Server the templates were listed from
+    pub server_name: String,
+    /// Resource templates exposed by the server
+    pub templates: Vec<ResourceTemplateModel>,
+}
+
+/// Model for an individual resource template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceTemplateModel {
+    /// RFC 6570 URI template
+    pub uri_template: String,
+    /// Human-readable template name
+    pub name: String,
+    /// Template description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// MIME type of matching resources, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+/// Model for resource read command output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceReadModel {
+    /// Server the resource was read from
+    pub server_name: String,
+    /// Requested resource URI
+    pub uri: String,
+    /// Content items returned by the server
+    pub contents: Vec<ResourceContentModel>,
+}
+
+/// Model for a single resource content item.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceContentModel {
+    /// URI of this content item
+    pub uri: String,
+    /// MIME type, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    /// Text content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Base64-encoded binary content (omitted once written to a file)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blob: Option<String>,
+    /// Size of the decoded binary content in bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<usize>,
+    /// Path the content was written to via --output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub saved_to: Option<String>,
+}
+
+/// Model for prompts list command output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptListModel {
+    /// Server the prompts were listed from
+    pub server_name: String,
+    /// Prompt templates exposed by the server
+    pub prompts: Vec<PromptModel>,
+}
+
+/// Model for an individual prompt template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptModel {
+    /// Prompt name
+    pub name: String,
+    /// Prompt description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Arguments accepted by the prompt
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<PromptArgumentModel>,
+}
+
+/// Model for a prompt argument.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptArgumentModel {
+    /// Argument name
+    pub name: String,
+    /// Argument description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether the argument is required
+    pub required: bool,
+}
+
+/// Model for prompts get command output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptResultModel {
+    /// Server the prompt was rendered on
+    pub server_name: String,
+    /// Prompt name
+    pub prompt_name: String,
+    /// Description returned with the rendered prompt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Rendered messages in conversation order
+    pub messages: Vec<PromptMessageModel>,
+}
+
+/// Model for a rendered prompt message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptMessageModel {
+    /// Message role ("user" or "assistant")
+    pub role: String,
+    /// Message content (text, image or embedded resource)
+    pub content: serde_json::Value,
+}
+
 /// Helper function for serde skip_serializing_if
 fn is_false(b: &bool) -> bool {
     !b
@@ -288,4 +474,20 @@ mod tests {
         assert!(json.contains("total_matches"));
         assert!(!json.contains("failed_servers")); // Should be skipped when empty
     }
+
+    #[test]
+    fn test_resource_content_model_saved_to_file() {
+        let model = ResourceContentModel {
+            uri: "file:///logo.png".to_string(),
+            mime_type: Some("image/png".to_string()),
+            text: None,
+            blob: None,
+            size_bytes: Some(68),
+            saved_to: Some("logo.png".to_string()),
+        };
+        let json = serde_json::to_string(&model).unwrap();
+        assert!(json.contains("saved_to"));
+        assert!(!json.contains("blob")); // Should be skipped once written
+        assert!(!json.contains("text"));
+    }
 }
@@ -0,0 +1,171 @@
+//! Prompt listing and rendering command implementation.
+
+use crate::cli::call::parse_arguments;
+use crate::cli::formatters;
+use crate::cli::info::{ensure_server, parse_tool_id};
+use crate::cli::models::{
+    PromptArgumentModel, PromptListModel, PromptMessageModel, PromptModel, PromptResultModel,
+};
+use crate::error::{McpError, Result};
+use crate::format::OutputMode;
+use crate::ipc::ProtocolClient;
+use crate::output::print_error;
+use std::collections::HashMap;
+
+/// Execute the prompts list command.
+///
+/// Lists the prompt templates exposed by a server via `prompts/list`.
+///
+/// # Arguments
+/// * `daemon` - Daemon IPC client
+/// * `server_name` - Name of the server to query
+/// * `output_mode` - Output format (human or JSON)
+///
+/// # Errors
+/// Returns McpError::ServerNotFound if server doesn't exist (ERR-02)
+pub async fn cmd_list_prompts(
+    mut daemon: Box<dyn ProtocolClient>,
+    server_name: &str,
+    output_mode: OutputMode,
+) -> Result<()> {
+    ensure_server(daemon.as_ref(), server_name)?;
+
+    let prompts = daemon.list_prompts(server_name).await.map_err(|e| {
+        print_error(&format!(
+            "Failed to list prompts on server '{}': {}",
+            server_name, e
+        ));
+        e
+    })?;
+
+    let model = PromptListModel {
+        server_name: server_name.to_string(),
+        prompts: prompts
+            .into_iter()
+            .map(|p| PromptModel {
+                name: p.name,
+                description: p.description,
+                arguments: p
+                    .arguments
+                    .into_iter()
+                    .map(|a| PromptArgumentModel {
+                        name: a.name,
+                        description: a.description,
+                        required: a.required,
+                    })
+                    .collect(),
+            })
+            .collect(),
+    };
+    formatters::format_prompt_list(&model, output_mode);
+    Ok(())
+}
+
+/// Execute the prompts get command.
+///
+/// Renders a prompt template via `prompts/get`. Arguments are accepted in the
+/// same forms as `mcp call` (JSON object or `--key value` flags); since MCP
+/// prompt arguments are strings, non-string values are passed as their JSON text.
+///
+/// # Arguments
+/// * `daemon` - Daemon IPC client
+/// * `prompt_id` - Prompt identifier in format "server/prompt" or "server prompt"
+/// * `args` - Raw command-line arguments
+/// * `output_mode` - Output format (human or JSON)
+///
+/// # Errors
+/// Returns McpError::ServerNotFound if server doesn't exist (ERR-02)
+/// Returns McpError::UsageError if arguments are malformed
+pub async fn cmd_get_prompt(
+    mut daemon: Box<dyn ProtocolClient>,
+    prompt_id: &str,
+    args: Vec<String>,
+    output_mode: OutputMode,
+) -> Result<()> {
+    let (server_name, prompt_name) = parse_tool_id(prompt_id)?;
+    ensure_server(daemon.as_ref(), &server_name)?;
+
+    let arguments = prompt_arguments(parse_arguments(args)?)?;
+
+    let prompt = daemon
+        .get_prompt(&server_name, &prompt_name, arguments)
+        .await
+        .map_err(|e| {
+            print_error(&format!(
+                "Failed to get prompt '{}' on server '{}': {}",
+                prompt_name, server_name, e
+            ));
+            e
+        })?;
+
+    let model = PromptResultModel {
+        server_name,
+        prompt_name,
+        description: prompt.description,
+        messages: prompt
+            .messages
+            .into_iter()
+            .map(|m| PromptMessageModel {
+                role: m.role,
+                content: m.content,
+            })
+            .collect(),
+    };
+    formatters::format_prompt_result(&model, output_mode);
+    Ok(())
+}
+
+/// Convert parsed call-style arguments into string prompt arguments.
+fn prompt_arguments(value: serde_json::Value) -> Result<HashMap<String, String>> {
+    let serde_json::Value::Object(map) = value else {
+        return Err(McpError::usage_error(
+            "Prompt arguments must be a JSON object or --key value pairs",
+        ));
+    };
+
+    Ok(map
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                serde_json::Value::String(s) => s,
+                other => other.to_string(),
+            };
+            (key, value)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_arguments_from_flags() {
+        let parsed = parse_arguments(vec![
+            "--name".to_string(),
+            "Ada".to_string(),
+            "--count".to_string(),
+            "3".to_string(),
+            "--formal".to_string(),
+        ])
+        .unwrap();
+
+        let args = prompt_arguments(parsed).unwrap();
+
+        assert_eq!(args.get("name").map(String::as_str), Some("Ada"));
+        assert_eq!(args.get("count").map(String::as_str), Some("3"));
+        assert_eq!(args.get("formal").map(String::as_str), Some("true"));
+    }
+
+    #[test]
+    fn test_prompt_arguments_empty() {
+        let args = prompt_arguments(parse_arguments(vec![]).unwrap()).unwrap();
+        assert!(args.is_empty());
+    }
+
+    #[test]
+    fn test_prompt_arguments_rejects_non_object() {
+        let result = prompt_arguments(serde_json::json!(["a", "b"]));
+        assert!(matches!(result, Err(McpError::UsageError { .. })));
+    }
+}
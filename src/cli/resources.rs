@@ -0,0 +1,267 @@
+//! Resource listing and reading command implementation.
+
+use crate::cli::formatters;
+use crate::cli::info::ensure_server;
+use crate::cli::models::{
+    ResourceContentModel, ResourceListModel, ResourceModel, ResourceReadModel,
+    ResourceTemplateListModel, ResourceTemplateModel,
+};
+use crate::daemon::protocol::ResourceContents;
+use crate::error::{McpError, Result};
+use crate::format::OutputMode;
+use crate::ipc::ProtocolClient;
+use crate::output::print_error;
+use base64::Engine;
+use std::path::Path;
+
+/// Execute the resources list command.
+///
+/// Lists the resources exposed by a server via `resources/list`.
+///
+/// # Arguments
+/// * `daemon` - Daemon IPC client
+/// * `server_name` - Name of the server to query
+/// * `output_mode` - Output format (human or JSON)
+///
+/// # Errors
+/// Returns McpError::ServerNotFound if server doesn't exist (ERR-02)
+pub async fn cmd_list_resources(
+    mut daemon: Box<dyn ProtocolClient>,
+    server_name: &str,
+    output_mode: OutputMode,
+) -> Result<()> {
+    ensure_server(daemon.as_ref(), server_name)?;
+
+    let resources = daemon.list_resources(server_name).await.map_err(|e| {
+        print_error(&format!(
+            "Failed to list resources on server '{}': {}",
+            server_name, e
+        ));
+        e
+    })?;
+
+    let model = ResourceListModel {
+        server_name: server_name.to_string(),
+        resources: resources
+            .into_iter()
+            .map(|r| ResourceModel {
+                uri: r.uri,
+                name: r.name,
+                description: r.description,
+                mime_type: r.mime_type,
+            })
+            .collect(),
+    };
+    formatters::format_resource_list(&model, output_mode);
+    Ok(())
+}
+
+/// Execute the resource templates command.
+///
+/// Lists the parameterized resource URIs exposed by a server via
+/// `resources/templates/list`.
+///
+/// # Arguments
+/// * `daemon` - Daemon IPC client
+/// * `server_name` - Name of the server to query
+/// * `output_mode` - Output format (human or JSON)
+///
+/// # Errors
+/// Returns McpError::ServerNotFound if server doesn't exist (ERR-02)
+pub async fn cmd_list_resource_templates(
+    mut daemon: Box<dyn ProtocolClient>,
+    server_name: &str,
+    output_mode: OutputMode,
+) -> Result<()> {
+    ensure_server(daemon.as_ref(), server_name)?;
+
+    let templates = daemon
+        .list_resource_templates(server_name)
+        .await
+        .map_err(|e| {
+            print_error(&format!(
+                "Failed to list resource templates on server '{}': {}",
+                server_name, e
+            ));
+            e
+        })?;
+
+    let model = ResourceTemplateListModel {
+        server_name: server_name.to_string(),
+        templates: templates
+            .into_iter()
+            .map(|t| ResourceTemplateModel {
+                uri_template: t.uri_template,
+                name: t.name,
+                description: t.description,
+                mime_type: t.mime_type,
+            })
+            .collect(),
+    };
+    formatters::format_resource_templates(&model, output_mode);
+    Ok(())
+}
+
+/// Execute the resource read command.
+///
+/// Reads a resource via `resources/read`. When `output` is given, the content
+/// is written to that file (binary blobs are base64-decoded first) and the
+/// output reports the destination instead of the data.
+///
+/// # Arguments
+/// * `daemon` - Daemon IPC client
+/// * `server_name` - Name of the server to query
+/// * `uri` - Resource URI to read
+/// * `output` - Optional file to write the content to
+/// * `output_mode` - Output format (human or JSON)
+///
+/// # Errors
+/// Returns McpError::ServerNotFound if server doesn't exist (ERR-02)
+/// Returns McpError::UsageError if `output` is given for a multi-part resource
+/// Returns McpError::IOError if the output file cannot be written
+pub async fn cmd_read_resource(
+    mut daemon: Box<dyn ProtocolClient>,
+    server_name: &str,
+    uri: &str,
+    output: Option<&Path>,
+    output_mode: OutputMode,
+) -> Result<()> {
+    ensure_server(daemon.as_ref(), server_name)?;
+
+    let contents = daemon.read_resource(server_name, uri).await.map_err(|e| {
+        print_error(&format!(
+            "Failed to read resource '{}' on server '{}': {}",
+            uri, server_name, e
+        ));
+        e
+    })?;
+
+    let mut models = contents
+        .into_iter()
+        .map(content_model)
+        .collect::<Result<Vec<_>>>()?;
+
+    if let Some(path) = output {
+        if models.len() != 1 {
+            return Err(McpError::usage_error(format!(
+                "Resource '{}' returned {} content items; --output requires exactly one",
+                uri,
+                models.len()
+            )));
+        }
+        save_content(&mut models[0], path)?;
+    }
+
+    let model = ResourceReadModel {
+        server_name: server_name.to_string(),
+        uri: uri.to_string(),
+        contents: models,
+    };
+    formatters::format_resource_contents(&model, output_mode);
+    Ok(())
+}
+
+/// Build a content model, computing the decoded size of binary blobs.
+fn content_model(content: ResourceContents) -> Result<ResourceContentModel> {
+    let size_bytes = match content.blob {
+        Some(ref blob) => Some(decode_blob(blob)?.len()),
+        None => None,
+    };
+    Ok(ResourceContentModel {
+        uri: content.uri,
+        mime_type: content.mime_type,
+        text: content.text,
+        blob: content.blob,
+        size_bytes,
+        saved_to: None,
+    })
+}
+
+/// Write a content item to `path` and drop its inline data from the model.
+fn save_content(content: &mut ResourceContentModel, path: &Path) -> Result<()> {
+    let bytes = if let Some(ref blob) = content.blob {
+        decode_blob(blob)?
+    } else {
+        content.text.clone().unwrap_or_default().into_bytes()
+    };
+
+    std::fs::write(path, &bytes).map_err(McpError::io_error)?;
+
+    content.size_bytes = Some(bytes.len());
+    content.text = None;
+    content.blob = None;
+    content.saved_to = Some(path.display().to_string());
+    Ok(())
+}
+
+/// Decode a base64 `blob` field from a resource content item.
+fn decode_blob(blob: &str) -> Result<Vec<u8>> {
+    base64::engine::general_purpose::STANDARD
+        .decode(blob)
+        .map_err(|e| McpError::InvalidProtocol {
+            message: format!("Invalid base64 blob in resource contents: {}", e),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blob_content() -> ResourceContents {
+        ResourceContents {
+            uri: "file:///logo.bin".to_string(),
+            mime_type: Some("application/octet-stream".to_string()),
+            text: None,
+            blob: Some("AAEC/w==".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_content_model_blob_size() {
+        let model = content_model(blob_content()).unwrap();
+        assert_eq!(model.size_bytes, Some(4));
+        assert!(model.blob.is_some());
+    }
+
+    #[test]
+    fn test_content_model_invalid_blob() {
+        let mut content = blob_content();
+        content.blob = Some("not base64!".to_string());
+        assert!(matches!(
+            content_model(content),
+            Err(McpError::InvalidProtocol { .. })
+        ));
+    }
+
+    #[test]
+    fn test_save_content_writes_decoded_blob() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("logo.bin");
+        let mut model = content_model(blob_content()).unwrap();
+
+        save_content(&mut model, &path).unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), vec![0x00, 0x01, 0x02, 0xff]);
+        assert!(model.blob.is_none());
+        assert_eq!(model.saved_to, Some(path.display().to_string()));
+    }
+
+    #[test]
+    fn test_save_content_writes_text() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("notes.txt");
+        let mut model = content_model(ResourceContents {
+            uri: "file:///notes.txt".to_string(),
+            mime_type: Some("text/plain".to_string()),
+            text: Some("hello".to_string()),
+            blob: None,
+        })
+        .unwrap();
+
+        save_content(&mut model, &path).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "hello");
+        assert_eq!(model.size_bytes, Some(5));
+        assert!(model.text.is_none());
+    }
+}
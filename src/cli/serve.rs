@@ -0,0 +1,54 @@
+//! Gateway command implementation (`mcp serve`).
+
+use crate::config::Config;
+use crate::error::{McpError, Result};
+use crate::gateway::http::serve_http;
+use crate::gateway::rest::serve_rest;
+use crate::gateway::stdio::serve_stdio;
+use crate::gateway::{Gateway, check_server_names};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+/// Environment variable holding the bearer token when `--token` is absent
+const TOKEN_ENV: &str = "MCP_GATEWAY_TOKEN";
+
+/// Serve every configured server as one MCP server.
+///
+/// # Arguments
+/// * `config` - Servers to front, with their tool filters
+/// * `stdio` - Speak MCP over stdin/stdout
+/// * `http` - Serve Streamable HTTP on this address instead
+/// * `rest` - Serve each tool as a REST endpoint on this address instead
+/// * `token` - Bearer token HTTP and REST clients must present
+///
+/// # Errors
+/// Returns McpError::UsageError if no listener was chosen or a server name
+/// can't be told apart from its tool names
+/// Returns McpError::IOError if the client connection fails
+pub async fn cmd_serve(
+    config: Arc<Config>,
+    stdio: bool,
+    http: Option<SocketAddr>,
+    rest: Option<SocketAddr>,
+    token: Option<String>,
+) -> Result<()> {
+    check_server_names(&config)?;
+    tracing::info!("Gateway serving {} server(s)", config.servers.len());
+    let gateway = Arc::new(Gateway::new(config));
+
+    if stdio {
+        return serve_stdio(gateway).await;
+    }
+    let token = token
+        .or_else(|| std::env::var(TOKEN_ENV).ok())
+        .filter(|token| !token.is_empty());
+    if let Some(addr) = http {
+        serve_http(gateway, addr, token).await
+    } else if let Some(addr) = rest {
+        serve_rest(gateway, addr, token).await
+    } else {
+        Err(McpError::usage_error(
+            "Choose a listener: --stdio, --http or --rest",
+        ))
+    }
+}
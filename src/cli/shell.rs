@@ -0,0 +1,562 @@
+//! Interactive shell command implementation (`mcp shell`).
+//!
+//! Reads commands with line editing and history and runs them through
+//! [`execute_command`] against one client, so a session pays for process
+//! startup and the daemon connection once instead of on every command.
+
+use crate::cli::command_router::{Commands, execute_command};
+use crate::config::Config;
+use crate::daemon::protocol::{
+    DaemonRequest, DaemonResponse, PromptInfo, PromptResult, ResourceContents, ResourceInfo,
+    ResourceTemplateInfo, ToolInfo, ToolPage,
+};
+use crate::error::{McpError, Result};
+use crate::format::OutputMode;
+use crate::ipc::{ProgressCallback, ProtocolClient};
+use async_trait::async_trait;
+use clap::Parser;
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::FileHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Words the shell completes in command position
+const SHELL_COMMANDS: &[&str] = &[
+    "call",
+    "exit",
+    "help",
+    "info",
+    "list",
+    "prompts",
+    "quit",
+    "resources",
+    "search",
+    "tool",
+    "use",
+];
+
+const SHELL_HELP: &str = r#"Commands:
+  list [-d|-v]                  List servers and tools
+  info <server>                 Show server details
+  tool <server/tool> [-d|-v]    Show tool details
+  call <server/tool> -- <args>  Execute a tool (JSON or --key value)
+  search <pattern>              Search tools by glob pattern
+  resources, prompts            Browse resources and prompts
+  use [server]                  Set (or clear) the default server
+  help                          Show this help
+  exit, quit                    Leave the shell
+
+With a default server, `call <tool>` and `tool <tool>` need no server prefix.
+Run `<command> --help` for a command's options."#;
+
+/// A command line typed into the shell, parsed with the CLI's own grammar
+#[derive(Parser)]
+#[command(no_binary_name = true, disable_version_flag = true)]
+struct ShellLine {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+/// Run the interactive shell until `exit`, `quit` or end of input.
+///
+/// # Arguments
+/// * `client` - Client every command in the session runs against
+/// * `output_mode` - Output format (human or JSON)
+///
+/// # Errors
+/// Returns McpError::IOError if the terminal can't be read
+pub async fn cmd_shell(client: Box<dyn ProtocolClient>, output_mode: OutputMode) -> Result<()> {
+    let config = client.config();
+    let client = SharedClient {
+        config: Arc::clone(&config),
+        client: Arc::new(Mutex::new(client)),
+    };
+
+    let helper = ShellHelper {
+        tools: load_tool_names(&client).await,
+        default_server: None,
+    };
+    let mut editor: Editor<ShellHelper, FileHistory> =
+        Editor::new().map_err(|e| McpError::io_error(std::io::Error::other(e)))?;
+    editor.set_helper(Some(helper));
+
+    let history_path = history_path();
+    if let Err(e) = editor.load_history(&history_path) {
+        tracing::debug!("No shell history loaded: {}", e);
+    }
+
+    loop {
+        let prompt = match editor.helper().and_then(|h| h.default_server.as_deref()) {
+            Some(server) => format!("mcp({})> ", server),
+            None => "mcp> ".to_string(),
+        };
+        let (returned, line) = tokio::task::spawn_blocking(move || {
+            let line = editor.readline(&prompt);
+            (editor, line)
+        })
+        .await
+        .map_err(|e| McpError::io_error(std::io::Error::other(e)))?;
+        editor = returned;
+
+        let line = match line {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(McpError::io_error(std::io::Error::other(e))),
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(line.as_str());
+
+        let mut words = match split_words(&line) {
+            Ok(words) => words,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                continue;
+            }
+        };
+        let helper = editor.helper_mut().expect("shell helper is set");
+
+        match words[0].as_str() {
+            "exit" | "quit" => break,
+            "help" => {
+                println!("{}", SHELL_HELP);
+                continue;
+            }
+            "use" => {
+                match words.get(1) {
+                    None => helper.default_server = None,
+                    Some(server) if config.get_server(server).is_some() => {
+                        helper.default_server = Some(server.clone());
+                    }
+                    Some(server) => eprintln!("error: Server '{}' not found", server),
+                }
+                continue;
+            }
+            // `info` on its own shows the default server
+            "info" if words.len() == 1 => words.extend(helper.default_server.clone()),
+            _ => {}
+        }
+
+        let command = match ShellLine::try_parse_from(&words) {
+            Ok(line) => line.command,
+            Err(e) => {
+                let _ = e.print();
+                continue;
+            }
+        };
+        let command = match command {
+            Commands::Daemon { .. }
+            | Commands::Shutdown
+            | Commands::Shell
+            | Commands::Serve { .. }
+            | Commands::Completions { .. }
+            | Commands::Complete { .. } => {
+                eprintln!("error: '{}' is not available in the shell", words[0]);
+                continue;
+            }
+            command => with_default_server(command, helper.default_server.as_deref()),
+        };
+
+        // Ctrl+C stops the running command, not the shell
+        tokio::select! {
+            result = Box::pin(execute_command(Some(command), Box::new(client.clone()), output_mode)) => {
+                if let Err(e) = result {
+                    eprintln!("error: {}", e);
+                }
+            }
+            _ = tokio::signal::ctrl_c() => eprintln!("Interrupted"),
+        }
+    }
+
+    if let Err(e) = editor.save_history(&history_path) {
+        tracing::debug!("Could not save shell history: {}", e);
+    }
+    Ok(())
+}
+
+/// Fetch every server's tool names for completion.
+///
+/// Servers whose tools can't be listed are completed by name only.
+async fn load_tool_names(client: &SharedClient) -> BTreeMap<String, Vec<String>> {
+    let mut client = client.client.lock().await;
+    let mut tools = BTreeMap::new();
+    for server in client.config().servers.iter().map(|s| s.name.clone()) {
+        let names = match client.list_tools(&server).await {
+            Ok(list) => list.into_iter().map(|tool| tool.name).collect(),
+            Err(e) => {
+                tracing::debug!("No tool completion for '{}': {}", server, e);
+                Vec::new()
+            }
+        };
+        tools.insert(server, names);
+    }
+    tools
+}
+
+/// Where shell history is kept between sessions.
+fn history_path() -> PathBuf {
+    let dir = dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("mcp-cli");
+    let _ = std::fs::create_dir_all(&dir);
+    dir.join("shell_history")
+}
+
+/// Prefix bare tool names in `call` and `tool` with the default server.
+fn with_default_server(command: Commands, default_server: Option<&str>) -> Commands {
+    let qualify = |tool: String| match default_server {
+        Some(server) if !tool.contains(['/', ' ']) => format!("{}/{}", server, tool),
+        _ => tool,
+    };
+    match command {
+        Commands::Call {
+            tool,
+            timeout,
+            no_validate,
+            args,
+        } => Commands::Call {
+            tool: qualify(tool),
+            timeout,
+            no_validate,
+            args,
+        },
+        Commands::Tool {
+            tool,
+            describe,
+            verbose,
+        } => Commands::Tool {
+            tool: qualify(tool),
+            describe,
+            verbose,
+        },
+        command => command,
+    }
+}
+
+/// Split a command line into words the way a POSIX shell would.
+///
+/// Single quotes keep their contents literally; double quotes and bare
+/// words allow backslash escapes.
+fn split_words(line: &str) -> std::result::Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => words.extend(word.take()),
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated ' quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => word.push(c),
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err("unterminated \" quote".to_string()),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated \" quote".to_string()),
+                    }
+                }
+            }
+            '\\' => word.get_or_insert_with(String::new).extend(chars.next()),
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+/// Tab completion for commands, server names and tool names
+struct ShellHelper {
+    /// Tool names by server, fetched when the shell starts
+    tools: BTreeMap<String, Vec<String>>,
+    /// Server set with `use`
+    default_server: Option<String>,
+}
+
+impl ShellHelper {
+    /// Completions for the last word of `line`, and where that word starts.
+    fn completions(&self, line: &str) -> (usize, Vec<String>) {
+        let start = line.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let word = &line[start..];
+        let previous: Vec<&str> = line[..start].split_whitespace().collect();
+
+        let candidates: Vec<String> = match previous.as_slice() {
+            [] => SHELL_COMMANDS.iter().map(|c| c.to_string()).collect(),
+            ["info" | "use"] => self.tools.keys().cloned().collect(),
+            ["call" | "tool"] => {
+                let qualified = self.tools.iter().flat_map(|(server, tools)| {
+                    tools.iter().map(move |tool| format!("{}/{}", server, tool))
+                });
+                let bare = self
+                    .default_server
+                    .as_ref()
+                    .and_then(|server| self.tools.get(server))
+                    .into_iter()
+                    .flatten()
+                    .cloned();
+                qualified.chain(bare).collect()
+            }
+            _ => Vec::new(),
+        };
+
+        let mut matches: Vec<String> = candidates
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(word))
+            .collect();
+        matches.sort();
+        matches.dedup();
+        (start, matches)
+    }
+}
+
+impl Completer for ShellHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(self.completions(&line[..pos]))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+/// Hands the session's client to each command without giving it away
+#[derive(Clone)]
+struct SharedClient {
+    config: Arc<Config>,
+    client: Arc<Mutex<Box<dyn ProtocolClient>>>,
+}
+
+#[async_trait]
+impl ProtocolClient for SharedClient {
+    fn config(&self) -> Arc<Config> {
+        Arc::clone(&self.config)
+    }
+
+    async fn send_request(&mut self, request: &DaemonRequest) -> Result<DaemonResponse> {
+        self.client.lock().await.send_request(request).await
+    }
+
+    async fn list_servers(&mut self) -> Result<Vec<String>> {
+        self.client.lock().await.list_servers().await
+    }
+
+    async fn list_tools(&mut self, server_name: &str) -> Result<Vec<ToolInfo>> {
+        self.client.lock().await.list_tools(server_name).await
+    }
+
+    async fn list_tools_page(
+        &mut self,
+        server_name: &str,
+        cursor: Option<&str>,
+    ) -> Result<ToolPage> {
+        self.client
+            .lock()
+            .await
+            .list_tools_page(server_name, cursor)
+            .await
+    }
+
+    async fn execute_tool(
+        &mut self,
+        server_name: &str,
+        tool_name: &str,
+        arguments: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        self.client
+            .lock()
+            .await
+            .execute_tool(server_name, tool_name, arguments)
+            .await
+    }
+
+    async fn execute_tool_with_progress(
+        &mut self,
+        server_name: &str,
+        tool_name: &str,
+        arguments: serde_json::Value,
+        timeout_secs: Option<u64>,
+        on_progress: ProgressCallback<'_>,
+    ) -> Result<serde_json::Value> {
+        self.client
+            .lock()
+            .await
+            .execute_tool_with_progress(
+                server_name,
+                tool_name,
+                arguments,
+                timeout_secs,
+                on_progress,
+            )
+            .await
+    }
+
+    async fn list_resources(&mut self, server_name: &str) -> Result<Vec<ResourceInfo>> {
+        self.client.lock().await.list_resources(server_name).await
+    }
+
+    async fn list_resource_templates(
+        &mut self,
+        server_name: &str,
+    ) -> Result<Vec<ResourceTemplateInfo>> {
+        self.client
+            .lock()
+            .await
+            .list_resource_templates(server_name)
+            .await
+    }
+
+    async fn read_resource(
+        &mut self,
+        server_name: &str,
+        uri: &str,
+    ) -> Result<Vec<ResourceContents>> {
+        self.client
+            .lock()
+            .await
+            .read_resource(server_name, uri)
+            .await
+    }
+
+    async fn list_prompts(&mut self, server_name: &str) -> Result<Vec<PromptInfo>> {
+        self.client.lock().await.list_prompts(server_name).await
+    }
+
+    async fn get_prompt(
+        &mut self,
+        server_name: &str,
+        prompt_name: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<PromptResult> {
+        self.client
+            .lock()
+            .await
+            .get_prompt(server_name, prompt_name, arguments)
+            .await
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        self.client.lock().await.shutdown().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn helper(default_server: Option<&str>) -> ShellHelper {
+        ShellHelper {
+            tools: BTreeMap::from([
+                (
+                    "fs".to_string(),
+                    vec!["read_file".to_string(), "write_file".to_string()],
+                ),
+                ("github".to_string(), vec!["search_repos".to_string()]),
+            ]),
+            default_server: default_server.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_split_words_handles_quotes_and_escapes() {
+        let words = split_words(r#"call fs/read_file -- '{"path": "/tmp/a b"}'"#).unwrap();
+        assert_eq!(
+            words,
+            vec!["call", "fs/read_file", "--", r#"{"path": "/tmp/a b"}"#]
+        );
+
+        let words = split_words(r#"search "a \"b\"" c\ d ''"#).unwrap();
+        assert_eq!(words, vec!["search", r#"a "b""#, "c d", ""]);
+
+        assert!(split_words("call 'open").is_err());
+    }
+
+    #[test]
+    fn test_completes_commands_and_servers() {
+        assert_eq!(
+            helper(None).completions("se"),
+            (0, vec!["search".to_string()])
+        );
+        assert_eq!(
+            helper(None).completions("use g"),
+            (4, vec!["github".to_string()])
+        );
+        assert_eq!(helper(None).completions("info ").1, vec!["fs", "github"]);
+    }
+
+    #[test]
+    fn test_completes_tool_ids() {
+        assert_eq!(
+            helper(None).completions("call fs/").1,
+            vec!["fs/read_file", "fs/write_file"]
+        );
+        assert_eq!(
+            helper(Some("fs")).completions("call wr").1,
+            vec!["write_file"]
+        );
+        assert!(
+            helper(None)
+                .completions("call fs/read_file --")
+                .1
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_default_server_qualifies_bare_tool_names() {
+        let command = ShellLine::try_parse_from(["call", "read_file", "--", "--path", "/tmp"])
+            .unwrap()
+            .command;
+        match with_default_server(command, Some("fs")) {
+            Commands::Call { tool, args, .. } => {
+                assert_eq!(tool, "fs/read_file");
+                assert_eq!(args, vec!["--path", "/tmp"]);
+            }
+            _ => panic!("expected a call"),
+        }
+
+        let command = ShellLine::try_parse_from(["tool", "github/search_repos"])
+            .unwrap()
+            .command;
+        match with_default_server(command, Some("fs")) {
+            Commands::Tool { tool, .. } => assert_eq!(tool, "github/search_repos"),
+            _ => panic!("expected a tool lookup"),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! Tool schema snapshot and diff commands (`mcp snapshot`, `mcp diff`).
+
+use crate::cli::list::query_list_servers;
+use crate::error::{McpError, Result};
+use crate::format::OutputMode;
+use crate::format::snapshot::{Change, SNAPSHOT_VERSION, Snapshot, ToolSnapshot, diff_snapshots};
+use crate::ipc::ProtocolClient;
+use colored::Colorize;
+use std::path::Path;
+
+/// Print a snapshot of every server's tools and schemas as JSON.
+///
+/// # Arguments
+/// * `daemon` - Daemon IPC client
+///
+/// # Errors
+/// Returns McpError::ConnectionError if a server's tools can't be listed,
+/// since the snapshot would be missing them
+pub async fn cmd_snapshot(daemon: Box<dyn ProtocolClient>) -> Result<()> {
+    let snapshot = take_snapshot(daemon).await?;
+    crate::output::print_json(&snapshot);
+    Ok(())
+}
+
+/// Compare the current tools against a snapshot file.
+///
+/// # Arguments
+/// * `daemon` - Daemon IPC client
+/// * `path` - Snapshot written earlier by `mcp snapshot`
+/// * `output_mode` - Output format (human or JSON)
+///
+/// # Errors
+/// Returns McpError::BreakingChanges if any change is breaking,
+/// McpError::UsageError if `path` isn't a snapshot, and
+/// McpError::ConnectionError if a server's tools can't be listed
+pub async fn cmd_diff(
+    daemon: Box<dyn ProtocolClient>,
+    path: &Path,
+    output_mode: OutputMode,
+) -> Result<()> {
+    let old = read_snapshot(path)?;
+    let new = take_snapshot(daemon).await?;
+    let changes = diff_snapshots(&old, &new);
+    let breaking = changes.iter().filter(|change| change.breaking).count();
+
+    match output_mode {
+        OutputMode::Human => print_changes_human(&changes, breaking),
+        OutputMode::Json => crate::output::print_json(&serde_json::json!({
+            "changes": changes,
+            "breaking": breaking,
+        })),
+    }
+
+    if breaking > 0 {
+        return Err(McpError::BreakingChanges { count: breaking });
+    }
+    Ok(())
+}
+
+/// Snapshot the tools every configured server currently exposes.
+async fn take_snapshot(daemon: Box<dyn ProtocolClient>) -> Result<Snapshot> {
+    let model = query_list_servers(daemon).await?;
+    let mut snapshot = Snapshot::default();
+    for server in model.servers {
+        // A missing server would look like a removed one
+        if server.error.is_some() {
+            return Err(McpError::connection_error(
+                &server.name,
+                std::io::Error::other("listing its tools failed"),
+            ));
+        }
+        snapshot.add_server(&server.name);
+        for tool in server.tools {
+            snapshot.add_tool(
+                &server.name,
+                &tool.name,
+                ToolSnapshot {
+                    description: tool.description.filter(|d| !d.is_empty()),
+                    input_schema: tool.input_schema,
+                },
+            );
+        }
+    }
+    Ok(snapshot)
+}
+
+fn read_snapshot(path: &Path) -> Result<Snapshot> {
+    let text = std::fs::read_to_string(path).map_err(McpError::io_error)?;
+    let snapshot: Snapshot = serde_json::from_str(&text).map_err(|e| {
+        McpError::usage_error(format!(
+            "'{}' is not a tool snapshot: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    if snapshot.version != SNAPSHOT_VERSION {
+        return Err(McpError::usage_error(format!(
+            "'{}' has snapshot version {}, expected {}; regenerate it with `mcp snapshot`",
+            path.display(),
+            snapshot.version,
+            SNAPSHOT_VERSION
+        )));
+    }
+    Ok(snapshot)
+}
+
+fn print_changes_human(changes: &[Change], breaking: usize) {
+    if changes.is_empty() {
+        println!("No changes");
+        return;
+    }
+
+    let mut current: Option<(&str, Option<&str>)> = None;
+    for change in changes {
+        let heading = (change.server.as_str(), change.tool.as_deref());
+        if current != Some(heading) {
+            match heading {
+                (server, Some(tool)) => println!("{}/{}", server, tool.bold()),
+                (server, None) => println!("{}", server.bold()),
+            }
+            current = Some(heading);
+        }
+        let label = if change.breaking {
+            "breaking".red().bold()
+        } else {
+            "ok      ".green()
+        };
+        if change.path.is_empty() {
+            println!("  {}  {}", label, change.message);
+        } else {
+            println!("  {}  {}: {}", label, change.path.cyan(), change.message);
+        }
+    }
+
+    println!();
+    println!(
+        "{} change(s), {} breaking",
+        changes.len(),
+        if breaking > 0 {
+            breaking.to_string().red().bold()
+        } else {
+            breaking.to_string().normal()
+        }
+    );
+}
@@ -1,25 +1,104 @@
 //! HTTP transport implementation for MCP server communication.
 //!
-//! This module implements HTTP-based transport for MCP servers, communicating
-//! via HTTP requests and responses. Uses reqwest client for HTTP communication.
+//! This module implements the MCP Streamable HTTP transport using reqwest:
+//!
+//! - Every client message is POSTed to the server URL with
+//!   `Accept: application/json, text/event-stream`. A request is answered
+//!   either with a single JSON body or with an SSE stream that may carry
+//!   notifications and server→client requests before the response.
+//! - An `Mcp-Session-Id` returned during initialization is sent on every
+//!   later request, along with the negotiated `MCP-Protocol-Version`.
+//! - After initialization a GET stream is opened for server-initiated
+//!   messages (servers answering 405 simply don't offer one).
+//! - Interrupted SSE streams are resumed with `Last-Event-ID`.
+//! - Closing the transport sends DELETE to end the server-side session.
+//!
+//! With [`HttpTransport::with_sse_fallback`], servers that reject the
+//! initialize POST with a 4xx status are contacted with the legacy HTTP+SSE
+//! transport instead (see [`crate::client::legacy_sse`]).
 
 use async_trait::async_trait;
+use serde_json::Value;
 use std::collections::HashMap;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
+use crate::client::legacy_sse::SseTransport;
+use crate::client::session::{
+    DEFAULT_REQUEST_TIMEOUT, IncomingMessage, NOTIFICATION_BUFFER, NotificationQueue,
+    SessionHandler, answer_request, observe_notifications,
+};
+use crate::client::sse::SseParser;
 use crate::error::{McpError, Result};
-use crate::transport::Transport;
-use http::header::{HeaderMap, HeaderName, HeaderValue};
+use crate::transport::{NotificationCallback, Transport, cancelled_notification};
+use http::StatusCode;
+use http::header::{ACCEPT, CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
 use reqwest::Client;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Header carrying the server-assigned session ID.
+const SESSION_ID_HEADER: &str = "mcp-session-id";
+
+/// Header carrying the negotiated protocol version.
+const PROTOCOL_VERSION_HEADER: &str = "mcp-protocol-version";
+
+/// Header used to resume an SSE stream after the given event.
+const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+/// How many times an interrupted response stream is resumed before giving up.
+const MAX_RESUME_ATTEMPTS: usize = 3;
+
+/// Delay before reopening the GET stream when the server gave no `retry`.
+const DEFAULT_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Lower bound on a server-provided `retry`, so the GET stream is not reopened
+/// in a tight loop.
+const MIN_RECONNECT_DELAY: Duration = Duration::from_millis(500);
+
+/// Upper bound on the backoff between failed GET streams.
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// A GET stream that delivers no message and closes sooner than this counts
+/// as a failure.
+const MIN_HEALTHY_STREAM: Duration = Duration::from_secs(10);
+
+/// Consecutive GET stream failures after which listening stops.
+const MAX_LISTEN_FAILURES: usize = 5;
 
 /// HTTP transport for remote server communication.
 ///
-/// This transport communicates with MCP servers via HTTP using POST requests
-/// with JSON bodies.
+/// This transport communicates with MCP servers using the Streamable HTTP
+/// transport: POST requests with JSON bodies, answered with JSON or SSE.
 pub struct HttpTransport {
+    /// State shared with the background GET stream listener.
+    inner: Arc<HttpInner>,
+
+    /// Server notifications not yet read via `receive_notification`.
+    notifications: mpsc::Receiver<Value>,
+
+    /// Task reading the GET stream, started after initialization.
+    listener: Option<JoinHandle<()>>,
+
+    /// Whether a 4xx reply to initialize switches to the legacy SSE transport.
+    sse_fallback: bool,
+
+    /// Legacy SSE transport in use after falling back.
+    legacy: Option<SseTransport>,
+
+    /// ID of the request being sent; left set if its caller gave up on it.
+    in_flight: Option<Value>,
+}
+
+/// Connection state shared between the transport and its listener task.
+struct HttpInner {
     client: Client,
     base_url: String,
     headers: HashMap<String, String>,
+    session_id: Mutex<Option<String>>,
+    protocol_version: Mutex<Option<String>>,
+    request_timeout: Mutex<Duration>,
+    handler: Arc<dyn SessionHandler>,
 }
 
 impl HttpTransport {
@@ -29,204 +108,602 @@ impl HttpTransport {
     /// * `url` - Base URL for the server
     /// * `headers` - HTTP headers to include in requests
     pub fn new(url: &str, headers: HashMap<String, String>) -> Self {
-        let client = Client::new();
+        let (queue, notifications) = NotificationQueue::new(NOTIFICATION_BUFFER);
         Self {
-            client,
-            base_url: url.to_string(),
-            headers,
+            inner: Arc::new(HttpInner {
+                client: Client::new(),
+                base_url: url.to_string(),
+                headers,
+                session_id: Mutex::new(None),
+                protocol_version: Mutex::new(None),
+                request_timeout: Mutex::new(DEFAULT_REQUEST_TIMEOUT),
+                handler: Arc::new(queue),
+            }),
+            notifications,
+            listener: None,
+            sse_fallback: false,
+            legacy: None,
+            in_flight: None,
+        }
+    }
+
+    /// Fall back to the legacy HTTP+SSE transport if the server rejects the
+    /// Streamable HTTP initialize request with a 4xx status, as servers that
+    /// only implement protocol 2024-11-05 do.
+    pub fn with_sse_fallback(mut self) -> Self {
+        self.sse_fallback = true;
+        self
+    }
+
+    /// Session ID assigned by the server, if any.
+    pub fn session_id(&self) -> Option<String> {
+        self.inner.session_id()
+    }
+
+    /// Forget the in-flight request once it is answered; one that timed out
+    /// may still be running on the server and is left to `cancel_abandoned`.
+    fn finish_in_flight(&mut self, response: &Result<Value>) {
+        if !matches!(response, Err(McpError::Timeout { .. })) {
+            self.in_flight = None;
+        }
+    }
+
+    /// Open the GET stream for server-initiated messages, once.
+    fn start_listener(&mut self) {
+        if self.listener.is_none() {
+            self.listener = Some(tokio::spawn(listen(Arc::clone(&self.inner))));
+        }
+    }
+
+    /// Stop the GET stream listener, if running.
+    fn stop_listener(&mut self) {
+        if let Some(listener) = self.listener.take() {
+            listener.abort();
         }
     }
 }
 
-#[async_trait]
-impl Transport for HttpTransport {
-    async fn receive_notification(&mut self) -> Result<serde_json::Value> {
-        Err(McpError::InvalidProtocol {
-            message: "HTTP transport does not support notifications".to_string(),
-        })
-    }
-
-    async fn send(&mut self, request: serde_json::Value) -> Result<serde_json::Value> {
-        // Create request URL (append base_url to request path if needed)
-        let url = self.base_url.clone();
-
-        // Serialize request to JSON
-        let body = request.to_string();
-
-        // Convert HashMap to HeaderMap
-        let mut headers = HeaderMap::new();
-        for (key, value) in &self.headers {
-            // Use simple string-based header names and values
-            // Note: This may not validate header names, but works for basic use
-            let key_str = key.clone();
-            let value_str = value.clone();
-            let _ = headers.insert(
-                match HeaderName::try_from(&key_str) {
-                    Ok(name) => name,
-                    Err(_) => HeaderName::from_static("x-invalid-header"),
-                },
-                match HeaderValue::from_str(&value_str) {
-                    Ok(value) => value,
-                    Err(_) => HeaderValue::from_static(""),
-                },
-            );
-        }
-
-        // Send POST request
+impl Drop for HttpTransport {
+    fn drop(&mut self) {
+        self.stop_listener();
+
+        // Best-effort session teardown when the transport wasn't closed explicitly
+        if self.inner.session_id().is_some()
+            && let Ok(runtime) = tokio::runtime::Handle::try_current()
+        {
+            let inner = Arc::clone(&self.inner);
+            runtime.spawn(async move {
+                if let Err(e) = inner.terminate_session().await {
+                    tracing::debug!("Failed to terminate HTTP session: {}", e);
+                }
+            });
+        }
+    }
+}
+
+impl HttpInner {
+    fn session_id(&self) -> Option<String> {
+        self.session_id
+            .lock()
+            .expect("Failed to acquire session id lock")
+            .clone()
+    }
+
+    fn set_session_id(&self, session_id: Option<String>) {
+        *self
+            .session_id
+            .lock()
+            .expect("Failed to acquire session id lock") = session_id;
+    }
+
+    fn set_protocol_version(&self, version: &str) {
+        *self
+            .protocol_version
+            .lock()
+            .expect("Failed to acquire protocol version lock") = Some(version.to_string());
+    }
+
+    fn request_timeout(&self) -> Duration {
+        *self
+            .request_timeout
+            .lock()
+            .expect("Failed to acquire request timeout lock")
+    }
+
+    /// Convert a reqwest error, reporting timeouts with the configured limit.
+    fn request_error(&self, e: reqwest::Error) -> McpError {
+        if e.is_timeout() {
+            return McpError::timeout(self.request_timeout());
+        }
+        McpError::ConnectionError {
+            server: "http".to_string(),
+            source: std::io::Error::other(format!("HTTP request failed: {}", e)),
+        }
+    }
+
+    /// Configured headers plus session and protocol version headers.
+    fn request_headers(&self) -> HeaderMap {
+        let mut headers = header_map(&self.headers);
+        if let Some(session_id) = self.session_id()
+            && let Ok(value) = HeaderValue::from_str(&session_id)
+        {
+            headers.insert(SESSION_ID_HEADER, value);
+        }
+        let version = self
+            .protocol_version
+            .lock()
+            .expect("Failed to acquire protocol version lock")
+            .clone();
+        if let Some(version) = version
+            && let Ok(value) = HeaderValue::from_str(&version)
+        {
+            headers.insert(PROTOCOL_VERSION_HEADER, value);
+        }
+        headers
+    }
+
+    /// POST one JSON-RPC message.
+    async fn post(&self, message: &Value) -> Result<reqwest::Response> {
         let response = self
             .client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .headers(headers)
-            .body(body)
-            .timeout(Duration::from_secs(30))
+            .post(&self.base_url)
+            .headers(self.request_headers())
+            .header(CONTENT_TYPE, "application/json")
+            .header(ACCEPT, "application/json, text/event-stream")
+            .body(message.to_string())
+            .timeout(self.request_timeout())
             .send()
             .await
-            .map_err(|e| {
-                // Check for HTTP errors
-                if let Some(status) = e.status()
-                    && (status.is_client_error() || status.is_server_error())
-                {
-                    return McpError::ConnectionError {
-                        server: "http".to_string(),
-                        source: std::io::Error::new(
-                            std::io::ErrorKind::InvalidInput,
-                            format!("HTTP {}", status),
-                        ),
+            .map_err(|e| self.request_error(e))?;
+
+        // Servers assign the session ID on the initialize response
+        if let Some(session_id) = response
+            .headers()
+            .get(SESSION_ID_HEADER)
+            .and_then(|v| v.to_str().ok())
+        {
+            self.set_session_id(Some(session_id.to_string()));
+        }
+
+        if response.status() == StatusCode::NOT_FOUND && self.session_id().is_some() {
+            // The server no longer knows our session; a new one needs initialize
+            self.set_session_id(None);
+            return Err(McpError::ConnectionError {
+                server: "http".to_string(),
+                source: std::io::Error::new(
+                    std::io::ErrorKind::NotConnected,
+                    "HTTP session expired; the server must be re-initialized",
+                ),
+            });
+        }
+
+        Ok(response)
+    }
+
+    /// Open an SSE stream with GET, optionally resuming after an event ID.
+    async fn get_stream(
+        &self,
+        last_event_id: Option<&str>,
+        timeout: Option<Duration>,
+    ) -> Result<reqwest::Response> {
+        let mut request = self
+            .client
+            .get(&self.base_url)
+            .headers(self.request_headers())
+            .header(ACCEPT, "text/event-stream");
+        if let Some(last_event_id) = last_event_id {
+            request = request.header(LAST_EVENT_ID_HEADER, last_event_id);
+        }
+        if let Some(timeout) = timeout {
+            request = request.timeout(timeout);
+        }
+        request.send().await.map_err(|e| self.request_error(e))
+    }
+
+    /// Send a request and return its response message.
+    async fn request(&self, request: &Value) -> Result<Value> {
+        let id = request.get("id").cloned().unwrap_or(Value::Null);
+        let response = self.post(request).await?;
+        self.read_response(response, &id).await
+    }
+
+    /// Read the response to the request with the given `id` from a POST reply.
+    async fn read_response(&self, response: reqwest::Response, id: &Value) -> Result<Value> {
+        let status = response.status();
+
+        if is_event_stream(&response) {
+            return self.read_stream_response(response, id).await;
+        }
+        if status == StatusCode::ACCEPTED {
+            return Err(McpError::InvalidProtocol {
+                message: "Server accepted the request without returning a response".to_string(),
+            });
+        }
+
+        let body = response.bytes().await.map_err(|e| self.request_error(e))?;
+        match serde_json::from_slice::<Value>(&body) {
+            Ok(message) => Ok(message),
+            // Error statuses without a JSON-RPC body are connection failures
+            Err(_) if !status.is_success() => Err(http_status_error(status)),
+            Err(e) => Err(McpError::InvalidProtocol {
+                message: format!("Failed to parse response: {}", e),
+            }),
+        }
+    }
+
+    /// Read an SSE response stream until the response to `id` arrives.
+    ///
+    /// Other messages on the stream are dispatched to the handler. If the
+    /// stream ends early it is resumed with `Last-Event-ID`.
+    async fn read_stream_response(
+        &self,
+        mut response: reqwest::Response,
+        id: &Value,
+    ) -> Result<Value> {
+        let mut last_event_id: Option<String> = None;
+        let mut resumes = 0;
+
+        loop {
+            let mut parser = SseParser::new();
+            while let Some(chunk) = response.chunk().await.map_err(|e| self.request_error(e))? {
+                for event in parser.feed(&chunk) {
+                    if event.id.is_some() {
+                        last_event_id = event.id.clone();
+                    }
+                    if !event.is_message() || event.data.is_empty() {
+                        continue;
+                    }
+                    let Ok(message) = serde_json::from_str::<Value>(&event.data) else {
+                        tracing::debug!("Ignoring non-JSON SSE event: {}", event.data);
+                        continue;
                     };
+                    match IncomingMessage::classify(message) {
+                        IncomingMessage::Response {
+                            id: ref response_id,
+                            message,
+                        } if response_id == id => return Ok(message),
+                        other => self.dispatch(other).await,
+                    }
                 }
-                McpError::InvalidProtocol {
-                    message: format!("HTTP request failed: {}", e),
+            }
+
+            let Some(ref resume_from) = last_event_id else {
+                return Err(McpError::InvalidProtocol {
+                    message: "SSE stream closed before the response was received".to_string(),
+                });
+            };
+            resumes += 1;
+            if resumes > MAX_RESUME_ATTEMPTS {
+                return Err(McpError::InvalidProtocol {
+                    message: format!(
+                        "SSE stream closed before the response was received ({} resume attempts)",
+                        MAX_RESUME_ATTEMPTS
+                    ),
+                });
+            }
+
+            tracing::debug!("Resuming SSE stream after event {}", resume_from);
+            response = self
+                .get_stream(Some(resume_from), Some(self.request_timeout()))
+                .await?;
+            if !response.status().is_success() {
+                return Err(http_status_error(response.status()));
+            }
+        }
+    }
+
+    /// Handle a server-initiated message.
+    async fn dispatch(self: &HttpInner, message: IncomingMessage) {
+        match message {
+            IncomingMessage::Notification(notification) => {
+                self.handler.on_notification(notification).await
+            }
+            IncomingMessage::Request { id, method, params } => {
+                let response = answer_request(self.handler.as_ref(), id, &method, params).await;
+                if let Err(e) = self.post(&response).await {
+                    tracing::warn!("Failed to answer server request '{}': {}", method, e);
                 }
-            })?;
-
-        // Parse response JSON
-        let response_json: serde_json::Value =
-            response
-                .json()
-                .await
-                .map_err(|e| McpError::InvalidProtocol {
-                    message: format!("Failed to parse response: {}", e),
-                })?;
-
-        Ok(response_json)
-    }
-
-    async fn send_notification(&mut self, notification: serde_json::Value) -> Result<()> {
-        // Convert HashMap to HeaderMap
-        let mut headers = HeaderMap::new();
-        for (key, value) in &self.headers {
-            headers.insert(
-                key.parse::<HeaderName>()
-                    .expect("Invalid header name - should be validated on construction"),
-                value
-                    .parse::<HeaderValue>()
-                    .expect("Invalid header value - should be validated on construction"),
-            );
-        }
-
-        // Send notification without expecting response
-        let body = serde_json::to_string(&notification).map_err(|e| McpError::InvalidProtocol {
-            message: format!("Failed to serialize notification: {}", e),
-        })?;
-
-        self.client
-            .post(&self.base_url)
-            .header("Content-Type", "application/json")
-            .headers(headers)
-            .body(body)
-            .timeout(Duration::from_secs(30))
+            }
+            IncomingMessage::Response { id, .. } => {
+                tracing::warn!("Dropping response with unknown id: {}", id)
+            }
+            IncomingMessage::Invalid(message) => {
+                tracing::warn!("Ignoring malformed JSON-RPC message: {}", message)
+            }
+        }
+    }
+
+    /// Send DELETE to end the server-side session.
+    ///
+    /// Servers that don't allow clients to end sessions answer 405, which is
+    /// not an error.
+    async fn terminate_session(&self) -> Result<()> {
+        if self.session_id().is_none() {
+            return Ok(());
+        }
+
+        let response = self
+            .client
+            .delete(&self.base_url)
+            .headers(self.request_headers())
+            .timeout(self.request_timeout())
             .send()
             .await
-            .map_err(|e| {
-                // Check for HTTP errors
-                if let Some(status) = e.status()
-                    && (status.is_client_error() || status.is_server_error())
-                {
-                    return McpError::ConnectionError {
-                        server: "http".to_string(),
-                        source: std::io::Error::new(
-                            std::io::ErrorKind::InvalidInput,
-                            format!("HTTP {}: {}", status, e),
-                        ),
-                    };
+            .map_err(|e| self.request_error(e))?;
+        self.set_session_id(None);
+
+        let status = response.status();
+        if status.is_success() || status == StatusCode::METHOD_NOT_ALLOWED {
+            Ok(())
+        } else {
+            Err(http_status_error(status))
+        }
+    }
+}
+
+/// Read server-initiated messages from the GET stream until the transport
+/// is dropped, reconnecting (with `Last-Event-ID`) when the stream ends.
+///
+/// Streams that are rejected, or close quickly without delivering a message,
+/// count as failures; reconnects back off exponentially until
+/// [`MAX_LISTEN_FAILURES`] consecutive failures stop the listener.
+async fn listen(inner: Arc<HttpInner>) {
+    let mut last_event_id: Option<String> = None;
+    let mut reconnect_delay = DEFAULT_RECONNECT_DELAY;
+    let mut failures = 0;
+
+    loop {
+        match inner.get_stream(last_event_id.as_deref(), None).await {
+            Ok(response) if response.status() == StatusCode::METHOD_NOT_ALLOWED => {
+                tracing::debug!("Server does not offer a GET stream");
+                return;
+            }
+            Ok(mut response) if response.status().is_success() && is_event_stream(&response) => {
+                let opened = Instant::now();
+                let mut delivered = false;
+                let mut parser = SseParser::new();
+                while let Ok(Some(chunk)) = response.chunk().await {
+                    for event in parser.feed(&chunk) {
+                        if event.id.is_some() {
+                            last_event_id = event.id.clone();
+                        }
+                        if let Some(retry) = event.retry {
+                            reconnect_delay = Duration::from_millis(retry).max(MIN_RECONNECT_DELAY);
+                        }
+                        if !event.is_message() || event.data.is_empty() {
+                            continue;
+                        }
+                        delivered = true;
+                        match serde_json::from_str::<Value>(&event.data) {
+                            Ok(message) => inner.dispatch(IncomingMessage::classify(message)).await,
+                            Err(_) => {
+                                tracing::debug!("Ignoring non-JSON SSE event: {}", event.data)
+                            }
+                        }
+                    }
                 }
-                McpError::ConnectionError {
-                    server: "http".to_string(),
-                    source: std::io::Error::other(format!("HTTP error: {}", e)),
+                if delivered || opened.elapsed() >= MIN_HEALTHY_STREAM {
+                    failures = 0;
+                } else {
+                    failures += 1;
+                    tracing::debug!("GET stream closed without delivering a message");
                 }
-            })?;
+            }
+            Ok(response) => {
+                failures += 1;
+                tracing::debug!("GET stream rejected: HTTP {}", response.status());
+            }
+            Err(e) => {
+                failures += 1;
+                tracing::debug!("GET stream failed: {}", e);
+            }
+        }
+
+        if failures >= MAX_LISTEN_FAILURES {
+            tracing::debug!("Giving up on GET stream after {} failures", failures);
+            return;
+        }
+        tokio::time::sleep(listen_backoff(reconnect_delay, failures)).await;
+    }
+}
+
+/// Delay before reopening the GET stream: `base` after a healthy stream,
+/// doubling with each consecutive failure up to [`MAX_RECONNECT_DELAY`].
+///
+/// A server `retry` above the cap is still honoured.
+fn listen_backoff(base: Duration, failures: usize) -> Duration {
+    let Some(doublings) = failures.checked_sub(1) else {
+        return base;
+    };
+    let factor = 2u32.saturating_pow(doublings as u32);
+    base.saturating_mul(factor)
+        .min(MAX_RECONNECT_DELAY)
+        .max(base)
+}
+
+/// Convert configured headers to a header map, skipping invalid entries.
+pub(crate) fn header_map(headers: &HashMap<String, String>) -> HeaderMap {
+    let mut map = HeaderMap::new();
+    for (key, value) in headers {
+        match (
+            HeaderName::try_from(key.as_str()),
+            HeaderValue::from_str(value),
+        ) {
+            (Ok(name), Ok(value)) => {
+                map.insert(name, value);
+            }
+            _ => tracing::warn!("Skipping invalid HTTP header '{}'", key),
+        }
+    }
+    map
+}
+
+fn is_event_stream(response: &reqwest::Response) -> bool {
+    response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("text/event-stream"))
+}
+
+fn http_status_error(status: StatusCode) -> McpError {
+    McpError::ConnectionError {
+        server: "http".to_string(),
+        source: std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("HTTP {}", status)),
+    }
+}
+
+#[async_trait]
+impl Transport for HttpTransport {
+    async fn receive_notification(&mut self) -> Result<Value> {
+        if let Some(legacy) = self.legacy.as_mut() {
+            return legacy.receive_notification().await;
+        }
+        self.start_listener();
+        match tokio::time::timeout(Duration::from_secs(10), self.notifications.recv()).await {
+            Ok(Some(notification)) => Ok(notification),
+            Ok(None) => Err(McpError::InvalidProtocol {
+                message: "Server connection closed".to_string(),
+            }),
+            Err(_) => Err(McpError::Timeout { timeout: 10 }),
+        }
+    }
+
+    async fn send(&mut self, request: Value) -> Result<Value> {
+        if let Some(legacy) = self.legacy.as_mut() {
+            return legacy.send(request).await;
+        }
+
+        let is_initialize = request.get("method").and_then(|m| m.as_str()) == Some("initialize");
+        let response = if is_initialize && self.sse_fallback {
+            let reply = self.inner.post(&request).await?;
+            if reply.status().is_client_error() {
+                tracing::info!(
+                    "Server rejected Streamable HTTP initialize (HTTP {}), falling back to SSE",
+                    reply.status()
+                );
+                let mut legacy =
+                    SseTransport::new(&self.inner.base_url, self.inner.headers.clone());
+                legacy.set_request_timeout(self.inner.request_timeout());
+                let response = legacy.send(request).await?;
+                self.legacy = Some(legacy);
+                return Ok(response);
+            }
+            let id = request.get("id").cloned().unwrap_or(Value::Null);
+            self.inner.read_response(reply, &id).await?
+        } else {
+            self.in_flight = request.get("id").cloned();
+            let response = self.inner.request(&request).await;
+            self.finish_in_flight(&response);
+            response?
+        };
+
+        if is_initialize && let Some(result) = response.get("result") {
+            if let Some(version) = result.get("protocolVersion").and_then(|v| v.as_str()) {
+                self.inner.set_protocol_version(version);
+            }
+            self.start_listener();
+        }
+
+        Ok(response)
+    }
+
+    async fn send_with_notifications(
+        &mut self,
+        request: Value,
+        on_notification: NotificationCallback<'_>,
+    ) -> Result<Value> {
+        if let Some(legacy) = self.legacy.as_mut() {
+            return legacy
+                .send_with_notifications(request, on_notification)
+                .await;
+        }
+        // Initialization has side effects handled by `send`
+        if request.get("method").and_then(|m| m.as_str()) == Some("initialize") {
+            return self.send(request).await;
+        }
+        self.in_flight = request.get("id").cloned();
+        let response = self.inner.request(&request);
+        let response =
+            observe_notifications(response, &mut self.notifications, on_notification).await;
+        self.finish_in_flight(&response);
+        response
+    }
 
-        Ok(())
+    async fn send_notification(&mut self, notification: Value) -> Result<()> {
+        if let Some(legacy) = self.legacy.as_mut() {
+            return legacy.send_notification(notification).await;
+        }
+        let response = self.inner.post(&notification).await?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(http_status_error(status))
+        }
     }
 
     async fn ping(&self) -> Result<()> {
-        // Create a minimal ping request
+        if let Some(legacy) = self.legacy.as_ref() {
+            return legacy.ping().await;
+        }
         let request = serde_json::json!({
             "jsonrpc": "2.0",
             "method": "ping",
             "id": "ping"
         });
+        let response = self.inner.request(&request).await?;
+        match response.get("error") {
+            Some(error) => Err(McpError::InvalidProtocol {
+                message: format!("Ping failed: {}", error),
+            }),
+            None => Ok(()),
+        }
+    }
 
-        // Convert HashMap to HeaderMap
-        let mut headers = HeaderMap::new();
-        for (key, value) in &self.headers {
-            headers.insert(
-                key.parse::<HeaderName>()
-                    .expect("Invalid header name - should be validated on construction"),
-                value
-                    .parse::<HeaderValue>()
-                    .expect("Invalid header value - should be validated on construction"),
-            );
-        }
-
-        // Send request and expect HTTP 200 OK
-        let body = serde_json::to_string(&request).map_err(|e| McpError::InvalidProtocol {
-            message: format!("Failed to serialize request: {}", e),
-        })?;
-        let response = self
-            .client
-            .post(&self.base_url)
-            .header("Content-Type", "application/json")
-            .headers(headers)
-            .body(body)
-            .timeout(Duration::from_secs(30))
-            .send()
-            .await
-            .map_err(|e| {
-                // Check for HTTP errors
-                if let Some(status) = e.status()
-                    && (status.is_client_error() || status.is_server_error())
-                {
-                    return McpError::ConnectionError {
-                        server: "http".to_string(),
-                        source: std::io::Error::new(
-                            std::io::ErrorKind::InvalidInput,
-                            format!("HTTP {}", status),
-                        ),
-                    };
-                }
-                McpError::InvalidProtocol {
-                    message: format!("HTTP request failed: {}", e),
-                }
-            })?;
+    fn transport_type(&self) -> &str {
+        match &self.legacy {
+            Some(legacy) => legacy.transport_type(),
+            None => "http",
+        }
+    }
 
-        // Check if response is HTTP 200 OK
-        if !response.status().is_success() {
-            return Err(McpError::ConnectionError {
-                server: "http".to_string(),
-                source: std::io::Error::new(
-                    std::io::ErrorKind::InvalidInput,
-                    format!("HTTP {}", response.status()),
-                ),
-            });
+    fn is_closed(&self) -> bool {
+        self.legacy
+            .as_ref()
+            .is_some_and(|legacy| legacy.is_closed())
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        if let Some(legacy) = self.legacy.as_mut() {
+            return legacy.close().await;
         }
+        self.stop_listener();
+        self.inner.terminate_session().await
+    }
 
-        Ok(())
+    async fn cancel_abandoned(&mut self, reason: &str) -> Result<()> {
+        if let Some(legacy) = self.legacy.as_mut() {
+            return legacy.cancel_abandoned(reason).await;
+        }
+        match self.in_flight.take() {
+            Some(id) => {
+                self.send_notification(cancelled_notification(id, reason))
+                    .await
+            }
+            None => Ok(()),
+        }
     }
 
-    fn transport_type(&self) -> &str {
-        "http"
+    fn set_request_timeout(&mut self, timeout: Duration) {
+        if let Some(legacy) = self.legacy.as_mut() {
+            legacy.set_request_timeout(timeout);
+        }
+        *self
+            .inner
+            .request_timeout
+            .lock()
+            .expect("Failed to acquire request timeout lock") = timeout;
     }
 }
 
@@ -238,6 +715,38 @@ mod tests {
     fn test_http_transport_creation() {
         let headers = HashMap::new();
         let transport = HttpTransport::new("http://example.com/api", headers);
-        assert_eq!(transport.base_url, "http://example.com/api");
+        assert_eq!(transport.inner.base_url, "http://example.com/api");
+        assert!(transport.session_id().is_none());
+    }
+
+    #[test]
+    fn test_request_headers_include_session() {
+        let headers = HashMap::from([
+            ("Authorization".to_string(), "Bearer token".to_string()),
+            ("bad header".to_string(), "x".to_string()),
+        ]);
+        let transport = HttpTransport::new("http://example.com/api", headers);
+        transport.inner.set_session_id(Some("abc".to_string()));
+        transport.inner.set_protocol_version("2025-06-18");
+
+        let headers = transport.inner.request_headers();
+
+        assert_eq!(headers.get("authorization").unwrap(), "Bearer token");
+        assert_eq!(headers.get(SESSION_ID_HEADER).unwrap(), "abc");
+        assert_eq!(headers.get(PROTOCOL_VERSION_HEADER).unwrap(), "2025-06-18");
+        assert_eq!(headers.len(), 3);
+    }
+
+    #[test]
+    fn test_listen_backoff_doubles_up_to_cap() {
+        let base = Duration::from_secs(1);
+        assert_eq!(listen_backoff(base, 0), base);
+        assert_eq!(listen_backoff(base, 1), base);
+        assert_eq!(listen_backoff(base, 3), Duration::from_secs(4));
+        assert_eq!(listen_backoff(base, 40), MAX_RECONNECT_DELAY);
+
+        // A server asking for a longer delay than the cap gets it
+        let long = Duration::from_secs(120);
+        assert_eq!(listen_backoff(long, 2), long);
     }
 }
@@ -0,0 +1,349 @@
+//! Legacy HTTP+SSE transport for MCP servers built against protocol 2024-11-05.
+//!
+//! The client opens a long-lived GET stream at the server URL. The server's
+//! first event is `endpoint`, whose data is the URL that client messages are
+//! POSTed to; every server message (responses included) then arrives as a
+//! `message` event on the stream.
+//!
+//! The stream and the POST endpoint are bridged to a [`JsonRpcSession`] via
+//! in-memory pipes, so id correlation, timeouts and server→client requests
+//! work exactly as they do for stdio.
+
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::client::http::header_map;
+use crate::client::session::{
+    JsonRpcSession, NOTIFICATION_BUFFER, NotificationQueue, observe_notifications,
+};
+use crate::client::sse::{SseEvent, SseParser};
+use crate::error::{McpError, Result};
+use crate::transport::{NotificationCallback, Transport};
+use http::header::{ACCEPT, CONTENT_TYPE};
+use reqwest::{Client, Url};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, DuplexStream};
+use tokio::sync::{OnceCell, mpsc};
+use tokio::task::JoinHandle;
+
+/// Time allowed for the server to announce its POST endpoint.
+const ENDPOINT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Buffer size of the pipes between the HTTP tasks and the session.
+const PIPE_CAPACITY: usize = 64 * 1024;
+
+/// Legacy SSE transport for remote server communication.
+///
+/// The stream is opened lazily on first use, so the transport can be created
+/// outside an async context like the other transports.
+pub struct SseTransport {
+    client: Client,
+    url: String,
+    headers: HashMap<String, String>,
+
+    /// Receives server notifications from the session.
+    queue: Arc<NotificationQueue>,
+
+    /// Server notifications not yet read via `receive_notification`.
+    notifications: mpsc::Receiver<Value>,
+
+    /// Open stream and session, once connected.
+    connection: OnceCell<SseConnection>,
+
+    /// Response timeout applied to the session, if changed from its default.
+    request_timeout: Option<Duration>,
+}
+
+/// An open SSE stream bridged to a JSON-RPC session.
+struct SseConnection {
+    session: JsonRpcSession,
+
+    /// Tasks pumping the GET stream into the session and session output to POSTs.
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl Drop for SseConnection {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+impl SseTransport {
+    /// Create a new SseTransport from server configuration.
+    ///
+    /// # Arguments
+    /// * `url` - URL of the server's SSE stream
+    /// * `headers` - HTTP headers to include in requests
+    pub fn new(url: &str, headers: HashMap<String, String>) -> Self {
+        let (queue, notifications) = NotificationQueue::new(NOTIFICATION_BUFFER);
+        Self {
+            client: Client::new(),
+            url: url.to_string(),
+            headers,
+            queue: Arc::new(queue),
+            notifications,
+            connection: OnceCell::new(),
+            request_timeout: None,
+        }
+    }
+
+    /// The session over the open stream, connecting first if necessary.
+    async fn session(&self) -> Result<&JsonRpcSession> {
+        let connection = self.connection.get_or_try_init(|| self.connect()).await?;
+        Ok(&connection.session)
+    }
+
+    /// Open the stream, wait for the endpoint event and start the session.
+    async fn connect(&self) -> Result<SseConnection> {
+        let mut response = self
+            .client
+            .get(&self.url)
+            .headers(header_map(&self.headers))
+            .header(ACCEPT, "text/event-stream")
+            .send()
+            .await
+            .map_err(connection_error)?;
+        if !response.status().is_success() {
+            return Err(connection_error(format!("HTTP {}", response.status())));
+        }
+
+        // Messages that arrive in the same chunk as the endpoint are kept
+        let mut parser = SseParser::new();
+        let mut early = Vec::new();
+        let endpoint = tokio::time::timeout(ENDPOINT_TIMEOUT, async {
+            let mut endpoint = None;
+            while endpoint.is_none() {
+                let Some(chunk) = response.chunk().await.map_err(connection_error)? else {
+                    return Err(connection_error(
+                        "SSE stream closed before the endpoint event",
+                    ));
+                };
+                for event in parser.feed(&chunk) {
+                    if endpoint.is_none() && event.event.as_deref() == Some("endpoint") {
+                        endpoint = Some(event.data);
+                    } else {
+                        early.push(event);
+                    }
+                }
+            }
+            Ok(endpoint.unwrap_or_default())
+        })
+        .await
+        .map_err(|_| McpError::timeout(ENDPOINT_TIMEOUT))??;
+
+        let endpoint = Url::parse(&self.url)
+            .and_then(|base| base.join(endpoint.trim()))
+            .map_err(|e| McpError::InvalidProtocol {
+                message: format!("Invalid SSE endpoint '{}': {}", endpoint, e),
+            })?;
+        tracing::debug!("SSE server announced endpoint {}", endpoint);
+
+        let (mut stream_writer, session_reader) = tokio::io::duplex(PIPE_CAPACITY);
+        let (session_writer, post_reader) = tokio::io::duplex(PIPE_CAPACITY);
+
+        let pump = tokio::spawn(async move {
+            for event in early {
+                if forward_event(&mut stream_writer, event).await.is_err() {
+                    return;
+                }
+            }
+            while let Ok(Some(chunk)) = response.chunk().await {
+                for event in parser.feed(&chunk) {
+                    if forward_event(&mut stream_writer, event).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            tracing::debug!("SSE stream closed");
+        });
+        let poster = tokio::spawn(post_messages(
+            self.client.clone(),
+            endpoint,
+            self.headers.clone(),
+            post_reader,
+        ));
+
+        let session = JsonRpcSession::new(
+            BufReader::new(session_reader),
+            session_writer,
+            self.queue.clone(),
+        );
+        if let Some(timeout) = self.request_timeout {
+            session.set_request_timeout(timeout);
+        }
+        Ok(SseConnection {
+            session,
+            tasks: vec![pump, poster],
+        })
+    }
+}
+
+/// Write a `message` event to the session as one line of JSON.
+async fn forward_event(writer: &mut DuplexStream, event: SseEvent) -> std::io::Result<()> {
+    if !event.is_message() || event.data.is_empty() {
+        return Ok(());
+    }
+    // Events may carry pretty-printed JSON; the session expects one per line
+    let line = match serde_json::from_str::<Value>(&event.data) {
+        Ok(message) => message.to_string(),
+        Err(_) => {
+            tracing::debug!("Ignoring non-JSON SSE event: {}", event.data);
+            return Ok(());
+        }
+    };
+    writer.write_all(line.as_bytes()).await?;
+    writer.write_all(b"\n").await
+}
+
+/// POST each line written by the session to the endpoint.
+///
+/// A rejected POST is logged; the affected request then fails with the
+/// session's request timeout, as no response will arrive on the stream.
+async fn post_messages(
+    client: Client,
+    endpoint: Url,
+    headers: HashMap<String, String>,
+    reader: DuplexStream,
+) {
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let result = client
+            .post(endpoint.clone())
+            .headers(header_map(&headers))
+            .header(CONTENT_TYPE, "application/json")
+            .body(line)
+            .send()
+            .await;
+        match result {
+            Ok(response) if response.status().is_success() => {}
+            Ok(response) => {
+                tracing::warn!("SSE endpoint rejected message: HTTP {}", response.status())
+            }
+            Err(e) => tracing::warn!("Failed to POST to SSE endpoint: {}", e),
+        }
+    }
+}
+
+fn connection_error(e: impl std::fmt::Display) -> McpError {
+    McpError::ConnectionError {
+        server: "sse".to_string(),
+        source: std::io::Error::other(format!("SSE connection failed: {}", e)),
+    }
+}
+
+#[async_trait]
+impl Transport for SseTransport {
+    async fn send(&mut self, request: Value) -> Result<Value> {
+        self.session().await?.send_request(request).await
+    }
+
+    async fn send_notification(&mut self, notification: Value) -> Result<()> {
+        self.session().await?.notify(&notification).await
+    }
+
+    async fn send_with_notifications(
+        &mut self,
+        request: Value,
+        on_notification: NotificationCallback<'_>,
+    ) -> Result<Value> {
+        self.session().await?;
+        let Some(connection) = self.connection.get() else {
+            return Err(connection_error("not connected"));
+        };
+        let response = connection.session.send_request(request);
+        observe_notifications(response, &mut self.notifications, on_notification).await
+    }
+
+    async fn receive_notification(&mut self) -> Result<Value> {
+        self.session().await?;
+        match tokio::time::timeout(Duration::from_secs(10), self.notifications.recv()).await {
+            Ok(Some(notification)) => Ok(notification),
+            Ok(None) => Err(McpError::InvalidProtocol {
+                message: "Server connection closed".to_string(),
+            }),
+            Err(_) => Err(McpError::Timeout { timeout: 10 }),
+        }
+    }
+
+    async fn ping(&self) -> Result<()> {
+        let response = self
+            .session()
+            .await?
+            .request("ping", serde_json::json!({}))
+            .await?;
+        match response.get("error") {
+            Some(error) => Err(McpError::InvalidProtocol {
+                message: format!("Ping failed: {}", error),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn transport_type(&self) -> &str {
+        "sse"
+    }
+
+    fn is_closed(&self) -> bool {
+        self.connection
+            .get()
+            .is_some_and(|connection| connection.session.is_closed())
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        // Closing the stream ends the session on the server
+        self.connection.take();
+        Ok(())
+    }
+
+    async fn cancel_abandoned(&mut self, _reason: &str) -> Result<()> {
+        // The session cancels each request as it is abandoned
+        match self.connection.get() {
+            Some(connection) => connection.session.finish_cancellations().await,
+            None => Ok(()),
+        }
+    }
+
+    fn set_request_timeout(&mut self, timeout: Duration) {
+        self.request_timeout = Some(timeout);
+        if let Some(connection) = self.connection.get() {
+            connection.session.set_request_timeout(timeout);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_forward_event_compacts_json() {
+        let (mut writer, reader) = tokio::io::duplex(1024);
+        let event = SseEvent {
+            data: "{\n  \"jsonrpc\": \"2.0\",\n  \"id\": 1\n}".to_string(),
+            ..SseEvent::default()
+        };
+        forward_event(&mut writer, event).await.unwrap();
+        forward_event(
+            &mut writer,
+            SseEvent {
+                event: Some("ping".to_string()),
+                data: "{}".to_string(),
+                ..SseEvent::default()
+            },
+        )
+        .await
+        .unwrap();
+        drop(writer);
+
+        let mut lines = BufReader::new(reader).lines();
+        assert_eq!(
+            lines.next_line().await.unwrap().as_deref(),
+            Some(r#"{"id":1,"jsonrpc":"2.0"}"#)
+        );
+        assert_eq!(lines.next_line().await.unwrap(), None);
+    }
+}
@@ -4,12 +4,20 @@
 //! including tool listing, execution, and protocol handling.
 
 pub mod http;
+pub mod legacy_sse;
+pub mod session;
+pub mod sse;
 pub mod stdio;
 
 use crate::config::Config;
+use crate::daemon::protocol::{
+    PromptInfo, PromptResult, ResourceContents, ResourceInfo, ResourceTemplateInfo,
+    parse_result_list,
+};
 use crate::error::{McpError, Result};
-use crate::transport::{Transport, TransportFactory};
+use crate::transport::{Transport, TransportFactory, request_all_pages, send_initialize};
 use serde_json::Value;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 /// Information about a tool available on a MCP server.
 ///
@@ -36,6 +44,9 @@ pub struct McpClient {
 
     /// Server identifier for error messages.
     server_name: String,
+
+    /// Whether the initialize handshake has completed on this transport.
+    initialized: bool,
 }
 
 impl McpClient {
@@ -48,6 +59,7 @@ impl McpClient {
         Self {
             transport,
             server_name,
+            initialized: false,
         }
     }
 
@@ -55,24 +67,13 @@ impl McpClient {
     ///
     /// Sends initialize request with client capabilities and receives server capabilities.
     /// The server automatically sends notifications/initialized - we don't need to wait for it.
+    /// Does nothing once the handshake has completed, since servers reject a second one.
     pub async fn initialize(&mut self) -> Result<()> {
-        let init_request = Self::json_rpc_request(
-            "initialize",
-            serde_json::json!({
-                "protocolVersion": "2024-11-05",
-                "capabilities": {
-                    "roots": {},
-                    "sampling": {},
-                    "tools": {}
-                },
-                "clientInfo": {
-                    "name": "mcp-cli-rs",
-                    "version": env!("CARGO_PKG_VERSION")
-                }
-            }),
-        );
+        if self.initialized {
+            return Ok(());
+        }
 
-        let response = self.transport.send(init_request).await?;
+        let response = send_initialize(&mut self.transport, Self::generate_request_id()).await?;
 
         let _result = response["result"]
             .as_object()
@@ -80,12 +81,14 @@ impl McpClient {
                 message: "Expected result in initialize response".to_string(),
             })?;
 
+        self.initialized = true;
         Ok(())
     }
 
     /// List available tools from the server.
     ///
     /// This implements DISC-01: discovery of available tools.
+    /// Follows `nextCursor` so paginated catalogs are returned in full.
     /// Returns a vector of ToolInfo structs representing available tools.
     ///
     /// # Errors
@@ -94,15 +97,13 @@ impl McpClient {
     pub async fn list_tools(&mut self) -> Result<Vec<ToolInfo>> {
         self.initialize().await?;
 
-        let request = Self::json_rpc_request("tools/list", serde_json::json!({}));
-        let response = self.transport.send(request).await?;
-
-        // Parse response
-        let result = response["result"]
-            .as_object()
-            .ok_or_else(|| McpError::InvalidProtocol {
-                message: "Expected result object in response".to_string(),
-            })?;
+        let result = request_all_pages(
+            &mut self.transport,
+            "tools/list",
+            "tools",
+            Self::generate_request_id(),
+        )
+        .await?;
 
         let tools_array = result["tools"]
             .as_array()
@@ -116,7 +117,7 @@ impl McpClient {
             .filter_map(|tool| {
                 let name = tool["name"].as_str()?.to_string();
                 let description = tool["description"].as_str().map(|s| s.to_string());
-                let input_schema = tool["input_schema"].clone();
+                let input_schema = tool["inputSchema"].clone();
 
                 Some(ToolInfo {
                     name,
@@ -174,6 +175,111 @@ impl McpClient {
         Ok(serde_json::Value::Object(result.clone()))
     }
 
+    /// List resources exposed by the server, following pagination cursors.
+    ///
+    /// # Errors
+    /// Returns McpError::InvalidProtocol if the server rejects the request or
+    /// the response is malformed
+    pub async fn list_resources(&mut self) -> Result<Vec<ResourceInfo>> {
+        let result = self.request_all("resources/list", "resources").await?;
+        parse_result_list(&result, "resources")
+    }
+
+    /// List resource templates (parameterized URIs) exposed by the server,
+    /// following pagination cursors.
+    ///
+    /// # Errors
+    /// Returns McpError::InvalidProtocol if the server rejects the request or
+    /// the response is malformed
+    pub async fn list_resource_templates(&mut self) -> Result<Vec<ResourceTemplateInfo>> {
+        let result = self
+            .request_all("resources/templates/list", "resourceTemplates")
+            .await?;
+        parse_result_list(&result, "resourceTemplates")
+    }
+
+    /// Read a resource by URI.
+    ///
+    /// # Arguments
+    /// * `uri` - Resource URI as advertised by `resources/list`
+    ///
+    /// # Errors
+    /// Returns McpError::InvalidProtocol if the server rejects the request or
+    /// the response is malformed
+    pub async fn read_resource(&mut self, uri: &str) -> Result<Vec<ResourceContents>> {
+        let result = self
+            .request_result("resources/read", serde_json::json!({ "uri": uri }))
+            .await?;
+        parse_result_list(&result, "contents")
+    }
+
+    /// List prompt templates exposed by the server, following pagination cursors.
+    ///
+    /// # Errors
+    /// Returns McpError::InvalidProtocol if the server rejects the request or
+    /// the response is malformed
+    pub async fn list_prompts(&mut self) -> Result<Vec<PromptInfo>> {
+        let result = self.request_all("prompts/list", "prompts").await?;
+        parse_result_list(&result, "prompts")
+    }
+
+    /// Render a prompt template with the given arguments.
+    ///
+    /// # Arguments
+    /// * `prompt_name` - Name of the prompt as returned by `prompts/list`
+    /// * `arguments` - Prompt arguments (MCP prompt arguments are always strings)
+    ///
+    /// # Errors
+    /// Returns McpError::InvalidProtocol if the server rejects the request or
+    /// the response is malformed
+    pub async fn get_prompt(
+        &mut self,
+        prompt_name: &str,
+        arguments: &std::collections::HashMap<String, String>,
+    ) -> Result<PromptResult> {
+        let result = self
+            .request_result(
+                "prompts/get",
+                serde_json::json!({ "name": prompt_name, "arguments": arguments }),
+            )
+            .await?;
+        PromptResult::from_result(&result)
+    }
+
+    /// Send a paginated list request and return the merged result.
+    async fn request_all(&mut self, method: &str, key: &str) -> Result<Value> {
+        request_all_pages(
+            &mut self.transport,
+            method,
+            key,
+            Self::generate_request_id(),
+        )
+        .await
+    }
+
+    /// Send a request and return the `result` member of the response.
+    async fn request_result(&mut self, method: &str, params: Value) -> Result<Value> {
+        let request = Self::json_rpc_request(method, params);
+        let response = self.transport.send(request).await?;
+
+        if let Some(error) = response.get("error") {
+            let error_msg = error
+                .get("message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("Unknown error");
+            return Err(McpError::InvalidProtocol {
+                message: format!("{} failed: {}", method, error_msg),
+            });
+        }
+
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| McpError::InvalidProtocol {
+                message: "Expected result object in response".to_string(),
+            })
+    }
+
     /// Create a JSON-RPC 2.0 request.
     ///
     /// This helper method builds standardized JSON-RPC requests for MCP protocol.
@@ -195,12 +301,11 @@ impl McpClient {
 
     /// Generate a unique request ID.
     ///
-    /// Uses a simple timestamp-based ID for uniqueness.
+    /// IDs come from a process-wide counter, so they are unique and
+    /// monotonically increasing even for requests issued in the same instant.
     fn generate_request_id() -> u64 {
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs()
+        static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+        NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed)
     }
 }
 
@@ -248,4 +353,11 @@ mod tests {
         let request = McpClient::json_rpc_request("tools/list", serde_json::json!({}));
         println!("JSON-RPC request: {}", request);
     }
+
+    #[test]
+    fn test_request_ids_are_unique_and_increasing() {
+        let first = McpClient::generate_request_id();
+        let second = McpClient::generate_request_id();
+        assert!(second > first);
+    }
 }
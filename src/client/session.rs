@@ -0,0 +1,792 @@
+//! JSON-RPC session layer for newline-delimited stream transports.
+//!
+//! A [`JsonRpcSession`] owns both halves of a JSON-RPC byte stream (e.g. a
+//! server process's stdout and stdin). Outgoing requests get monotonically
+//! increasing IDs and are registered in a pending-request map; a background
+//! reader task routes every incoming line:
+//!
+//! - responses complete the pending request with the matching ID
+//! - notifications go to [`SessionHandler::on_notification`]
+//! - server→client requests go to [`SessionHandler::on_request`] and the
+//!   handler's answer is written back to the server
+//! - lines that are not JSON (stray log output) are skipped
+//!
+//! Because responses are matched by ID rather than by arrival order, any
+//! number of requests can be in flight on one connection. A request whose
+//! caller stops waiting (or that times out) is cancelled on the server with
+//! `notifications/cancelled` as soon as it is abandoned.
+
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+use crate::error::{McpError, Result};
+use crate::transport::{NotificationCallback, cancelled_notification};
+
+/// Default time to wait for the response to a request.
+///
+/// Tool calls replace it with the configured `timeout_secs` while they run.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Maximum number of unread server notifications a transport buffers.
+pub const NOTIFICATION_BUFFER: usize = 64;
+
+/// Requests awaiting a response, keyed by session-assigned ID.
+type PendingMap = Arc<Mutex<HashMap<u64, oneshot::Sender<Value>>>>;
+
+/// Write half of the stream, shared by callers and the reader task.
+type SharedWriter = Arc<tokio::sync::Mutex<Box<dyn AsyncWrite + Send + Unpin>>>;
+
+/// JSON-RPC error returned by a [`SessionHandler`] for a server→client request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestError {
+    /// JSON-RPC error code
+    pub code: i64,
+    /// Human-readable error message
+    pub message: String,
+}
+
+impl RequestError {
+    /// Error for a server→client request method the client does not implement.
+    pub fn method_not_found(method: &str) -> Self {
+        Self {
+            code: -32601,
+            message: format!("Method not found: {}", method),
+        }
+    }
+}
+
+/// Receives the messages a server sends on its own initiative.
+///
+/// Both methods have defaults: notifications are logged, `ping` is answered
+/// and every other request is rejected with "Method not found".
+#[async_trait]
+pub trait SessionHandler: Send + Sync {
+    /// Handle a server notification (a message with a method and no ID).
+    ///
+    /// Called on the reader task, so implementations should return quickly.
+    async fn on_notification(&self, notification: Value) {
+        tracing::debug!("Server notification: {}", notification);
+    }
+
+    /// Handle a server→client request and return its result.
+    async fn on_request(
+        &self,
+        method: &str,
+        _params: Value,
+    ) -> std::result::Result<Value, RequestError> {
+        match method {
+            "ping" => Ok(serde_json::json!({})),
+            _ => Err(RequestError::method_not_found(method)),
+        }
+    }
+}
+
+/// Handler that uses the default behavior for every message.
+pub struct DefaultSessionHandler;
+
+impl SessionHandler for DefaultSessionHandler {}
+
+/// A JSON-RPC connection with request/response correlation.
+///
+/// Dropping the session stops the reader task; requests still in flight fail
+/// with a connection error.
+pub struct JsonRpcSession {
+    writer: SharedWriter,
+    pending: PendingMap,
+    /// Cancellations of abandoned requests that may still be being written.
+    cancelling: Mutex<Vec<JoinHandle<Result<()>>>>,
+    next_id: AtomicU64,
+    request_timeout: Mutex<Duration>,
+    reader: JoinHandle<()>,
+}
+
+impl JsonRpcSession {
+    /// Start a session over the given stream halves.
+    ///
+    /// Spawns the reader task, so this must be called within a Tokio runtime.
+    ///
+    /// # Arguments
+    /// * `reader` - Stream of newline-delimited JSON-RPC messages from the server
+    /// * `writer` - Stream the client's messages are written to
+    /// * `handler` - Receives server notifications and requests
+    pub fn new<R, W>(reader: R, writer: W, handler: Arc<dyn SessionHandler>) -> Self
+    where
+        R: AsyncBufRead + Send + Unpin + 'static,
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        let writer: SharedWriter = Arc::new(tokio::sync::Mutex::new(Box::new(writer)));
+        let pending: PendingMap = Arc::new(Mutex::new(HashMap::new()));
+        let reader = tokio::spawn(read_loop(
+            reader,
+            Arc::clone(&pending),
+            Arc::clone(&writer),
+            handler,
+        ));
+
+        Self {
+            writer,
+            pending,
+            cancelling: Mutex::new(Vec::new()),
+            next_id: AtomicU64::new(1),
+            request_timeout: Mutex::new(DEFAULT_REQUEST_TIMEOUT),
+            reader,
+        }
+    }
+
+    /// Set how long to wait for each response (default 30 seconds).
+    pub fn with_request_timeout(self, timeout: Duration) -> Self {
+        self.set_request_timeout(timeout);
+        self
+    }
+
+    /// Change how long to wait for responses to requests sent from now on.
+    pub fn set_request_timeout(&self, timeout: Duration) {
+        *self
+            .request_timeout
+            .lock()
+            .expect("Failed to acquire request timeout lock") = timeout;
+    }
+
+    /// Send a request message and wait for its response message.
+    ///
+    /// The request's `id` is replaced by a session-assigned ID for
+    /// correlation; the response is returned with the caller's original `id`
+    /// restored, so callers may keep using fixed IDs.
+    ///
+    /// If the returned future is dropped or times out before the response
+    /// arrives, the server is sent `notifications/cancelled` for the request
+    /// (see [`Self::finish_cancellations`]).
+    ///
+    /// # Errors
+    /// Returns McpError::Timeout if no response arrives in time
+    /// Returns McpError::ConnectionError if the connection closes first
+    pub async fn send_request(&self, request: Value) -> Result<Value> {
+        let Value::Object(mut request) = request else {
+            return Err(McpError::InvalidProtocol {
+                message: "JSON-RPC request must be an object".to_string(),
+            });
+        };
+
+        let original_id = request.get("id").cloned().unwrap_or(Value::Null);
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        request.insert("id".to_string(), Value::from(id));
+
+        let (tx, rx) = oneshot::channel();
+        self.pending_map().insert(id, tx);
+        let guard = AbandonOnDrop {
+            session: self,
+            id: Some(id),
+        };
+
+        let result = self.await_response(Value::Object(request), rx).await;
+        // A timed-out request may still be running on the server
+        if matches!(result, Err(McpError::Timeout { .. })) {
+            guard.abandon("Request timed out");
+        } else {
+            guard.finish();
+        }
+
+        let mut response = result?;
+        response["id"] = original_id;
+        Ok(response)
+    }
+
+    /// Write a request and wait for the response delivered to `rx`.
+    async fn await_response(&self, request: Value, rx: oneshot::Receiver<Value>) -> Result<Value> {
+        let timeout = *self
+            .request_timeout
+            .lock()
+            .expect("Failed to acquire request timeout lock");
+        write_line(&self.writer, &request).await?;
+
+        match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(_)) => Err(connection_closed()),
+            Err(_) => Err(McpError::timeout(timeout)),
+        }
+    }
+
+    /// Send a request by method name and wait for its response message.
+    pub async fn request(&self, method: &str, params: Value) -> Result<Value> {
+        self.send_request(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": null,
+            "method": method,
+            "params": params
+        }))
+        .await
+    }
+
+    /// Send a notification; no response is expected.
+    pub async fn notify(&self, notification: &Value) -> Result<()> {
+        write_line(&self.writer, notification).await
+    }
+
+    /// Wait until the cancellations of abandoned requests have been written.
+    ///
+    /// The server may still answer a cancelled request; such late responses
+    /// are discarded by the reader task.
+    ///
+    /// # Errors
+    /// Returns McpError::ConnectionError if a cancellation could not be written
+    pub async fn finish_cancellations(&self) -> Result<()> {
+        let cancelling = std::mem::take(&mut *self.cancelling_list());
+        for cancel in cancelling {
+            cancel
+                .await
+                .map_err(|e| McpError::connection_error("stdio", std::io::Error::other(e)))??;
+        }
+        Ok(())
+    }
+
+    /// Send `notifications/cancelled` for an abandoned request in the background.
+    ///
+    /// Runs from `Drop`, so the write is spawned rather than awaited. A
+    /// request dropped outside a Tokio runtime is not cancelled.
+    fn cancel_request(&self, id: u64, reason: &str) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            tracing::debug!("No runtime to cancel abandoned request {}", id);
+            return;
+        };
+        tracing::debug!("Cancelling abandoned request {}", id);
+        let writer = Arc::clone(&self.writer);
+        let notification = cancelled_notification(Value::from(id), reason);
+        let cancel = runtime.spawn(async move { write_line(&writer, &notification).await });
+
+        let mut cancelling = self.cancelling_list();
+        // Written cancellations need no more attention; a failed write also
+        // shows up as a closed connection on the next request
+        cancelling.retain(|cancel| !cancel.is_finished());
+        cancelling.push(cancel);
+    }
+
+    fn cancelling_list(&self) -> std::sync::MutexGuard<'_, Vec<JoinHandle<Result<()>>>> {
+        self.cancelling
+            .lock()
+            .expect("Failed to acquire cancellation lock")
+    }
+
+    /// Number of requests currently awaiting a response.
+    pub fn pending_count(&self) -> usize {
+        self.pending_map().len()
+    }
+
+    /// Whether the reader task has stopped (the server closed the stream).
+    pub fn is_closed(&self) -> bool {
+        self.reader.is_finished()
+    }
+
+    fn pending_map(&self) -> std::sync::MutexGuard<'_, HashMap<u64, oneshot::Sender<Value>>> {
+        self.pending
+            .lock()
+            .expect("Failed to acquire pending request lock")
+    }
+}
+
+impl Drop for JsonRpcSession {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+/// Removes a request from the pending map when its caller is done with it,
+/// cancelling it on the server if the caller gave up before it finished.
+struct AbandonOnDrop<'a> {
+    session: &'a JsonRpcSession,
+    id: Option<u64>,
+}
+
+impl AbandonOnDrop<'_> {
+    /// The request completed (successfully or not); nothing to cancel.
+    fn finish(mut self) {
+        if let Some(id) = self.id.take() {
+            self.session.pending_map().remove(&id);
+        }
+    }
+
+    /// The request is still running on the server but nobody will wait for it.
+    fn abandon(mut self, reason: &str) {
+        if let Some(id) = self.id.take() {
+            self.session.pending_map().remove(&id);
+            self.session.cancel_request(id, reason);
+        }
+    }
+}
+
+impl Drop for AbandonOnDrop<'_> {
+    fn drop(&mut self) {
+        if let Some(id) = self.id.take() {
+            self.session.pending_map().remove(&id);
+            self.session.cancel_request(id, "Request cancelled");
+        }
+    }
+}
+
+fn connection_closed() -> McpError {
+    McpError::connection_error(
+        "stdio",
+        std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            "server closed the connection before responding",
+        ),
+    )
+}
+
+/// Write one message as a line of JSON and flush it.
+async fn write_line(writer: &SharedWriter, message: &Value) -> Result<()> {
+    let mut line = message.to_string();
+    line.push('\n');
+
+    let mut writer = writer.lock().await;
+    writer
+        .write_all(line.as_bytes())
+        .await
+        .map_err(|e| McpError::connection_error("stdio", e))?;
+    writer
+        .flush()
+        .await
+        .map_err(|e| McpError::connection_error("stdio", e))
+}
+
+/// Read messages until the stream closes, routing each one.
+async fn read_loop<R>(
+    reader: R,
+    pending: PendingMap,
+    writer: SharedWriter,
+    handler: Arc<dyn SessionHandler>,
+) where
+    R: AsyncBufRead + Send + Unpin,
+{
+    let mut lines = reader.lines();
+    loop {
+        let line = match lines.next_line().await {
+            Ok(Some(line)) => line,
+            Ok(None) => break,
+            Err(e) => {
+                tracing::warn!("Failed to read from server: {}", e);
+                break;
+            }
+        };
+
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<Value>(line) {
+            Ok(message) => route_message(message, &pending, &writer, &handler).await,
+            Err(_) => tracing::debug!("Ignoring non-JSON output from server: {}", line),
+        }
+    }
+
+    // Dropping the senders fails every request still waiting for a response
+    pending
+        .lock()
+        .expect("Failed to acquire pending request lock")
+        .clear();
+}
+
+/// Kinds of message a server can send, as seen by the client.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IncomingMessage {
+    /// Server→client request (method and ID)
+    Request {
+        id: Value,
+        method: String,
+        params: Value,
+    },
+    /// Notification (method, no ID)
+    Notification(Value),
+    /// Response to one of the client's requests (ID, no method)
+    Response { id: Value, message: Value },
+    /// Neither a method nor an ID
+    Invalid(Value),
+}
+
+impl IncomingMessage {
+    /// Classify a JSON-RPC message received from a server.
+    pub fn classify(message: Value) -> Self {
+        let method = message
+            .get("method")
+            .and_then(|m| m.as_str())
+            .map(str::to_string);
+        let id = message.get("id").filter(|id| !id.is_null()).cloned();
+
+        match (method, id) {
+            (Some(method), Some(id)) => IncomingMessage::Request {
+                id,
+                params: message.get("params").cloned().unwrap_or(Value::Null),
+                method,
+            },
+            (Some(_), None) => IncomingMessage::Notification(message),
+            (None, Some(id)) => IncomingMessage::Response { id, message },
+            (None, None) => IncomingMessage::Invalid(message),
+        }
+    }
+}
+
+/// Run a server→client request through `handler` and build the response message.
+pub async fn answer_request(
+    handler: &dyn SessionHandler,
+    id: Value,
+    method: &str,
+    params: Value,
+) -> Value {
+    match handler.on_request(method, params).await {
+        Ok(result) => serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "result": result
+        }),
+        Err(error) => serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": error.code, "message": error.message }
+        }),
+    }
+}
+
+/// Session handler that buffers notifications for a transport's
+/// `receive_notification`, dropping them when the buffer is full.
+pub struct NotificationQueue {
+    sender: tokio::sync::mpsc::Sender<Value>,
+}
+
+impl NotificationQueue {
+    /// Create a queue holding up to `capacity` unread notifications.
+    pub fn new(capacity: usize) -> (Self, tokio::sync::mpsc::Receiver<Value>) {
+        let (sender, receiver) = tokio::sync::mpsc::channel(capacity);
+        (Self { sender }, receiver)
+    }
+}
+
+#[async_trait]
+impl SessionHandler for NotificationQueue {
+    async fn on_notification(&self, notification: Value) {
+        if self.sender.try_send(notification).is_err() {
+            tracing::debug!("Notification buffer full; dropping server notification");
+        }
+    }
+}
+
+/// Wait for `response`, passing notifications from `notifications` to
+/// `on_notification` as they arrive.
+///
+/// Notifications already queued when the response arrives are delivered too,
+/// since servers send progress for a request before its response.
+pub async fn observe_notifications<F>(
+    response: F,
+    notifications: &mut tokio::sync::mpsc::Receiver<Value>,
+    on_notification: NotificationCallback<'_>,
+) -> Result<Value>
+where
+    F: std::future::Future<Output = Result<Value>>,
+{
+    tokio::pin!(response);
+    let result = loop {
+        tokio::select! {
+            result = &mut response => break result,
+            Some(notification) = notifications.recv() => on_notification(notification),
+        }
+    };
+    while let Ok(notification) = notifications.try_recv() {
+        on_notification(notification);
+    }
+    result
+}
+
+/// Deliver one incoming message to its destination.
+async fn route_message(
+    message: Value,
+    pending: &PendingMap,
+    writer: &SharedWriter,
+    handler: &Arc<dyn SessionHandler>,
+) {
+    match IncomingMessage::classify(message) {
+        IncomingMessage::Request { id, method, params } => {
+            // Answer off the reader task so a slow handler cannot hold up
+            // responses to our own requests
+            let handler = Arc::clone(handler);
+            let writer = Arc::clone(writer);
+            tokio::spawn(async move {
+                let response = answer_request(handler.as_ref(), id, &method, params).await;
+                if let Err(e) = write_line(&writer, &response).await {
+                    tracing::warn!("Failed to answer server request '{}': {}", method, e);
+                }
+            });
+        }
+        IncomingMessage::Notification(notification) => handler.on_notification(notification).await,
+        IncomingMessage::Response { id, message } => {
+            let sender = id.as_u64().and_then(|id| {
+                pending
+                    .lock()
+                    .expect("Failed to acquire pending request lock")
+                    .remove(&id)
+            });
+            match sender {
+                // The requester may have timed out; nothing to do then
+                Some(sender) => {
+                    let _ = sender.send(message);
+                }
+                None => tracing::warn!("Dropping response with unknown id: {}", id),
+            }
+        }
+        IncomingMessage::Invalid(message) => {
+            tracing::warn!("Ignoring malformed JSON-RPC message: {}", message)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{BufReader, DuplexStream, ReadHalf, WriteHalf};
+
+    /// Server side of an in-memory session: read client lines, write server lines
+    struct FakeServer {
+        lines: tokio::io::Lines<BufReader<ReadHalf<DuplexStream>>>,
+        writer: WriteHalf<DuplexStream>,
+    }
+
+    impl FakeServer {
+        async fn recv(&mut self) -> Value {
+            let line = self.lines.next_line().await.unwrap().unwrap();
+            serde_json::from_str(&line).unwrap()
+        }
+
+        async fn send_line(&mut self, line: &str) {
+            self.writer.write_all(line.as_bytes()).await.unwrap();
+            self.writer.write_all(b"\n").await.unwrap();
+        }
+
+        async fn send(&mut self, message: Value) {
+            self.send_line(&message.to_string()).await;
+        }
+    }
+
+    fn session_pair(handler: Arc<dyn SessionHandler>) -> (JsonRpcSession, FakeServer) {
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        let (client_read, client_write) = tokio::io::split(client);
+        let (server_read, server_write) = tokio::io::split(server);
+
+        let session = JsonRpcSession::new(BufReader::new(client_read), client_write, handler);
+        let server = FakeServer {
+            lines: BufReader::new(server_read).lines(),
+            writer: server_write,
+        };
+        (session, server)
+    }
+
+    /// Handler that records notifications
+    #[derive(Default)]
+    struct RecordingHandler {
+        notifications: Mutex<Vec<Value>>,
+    }
+
+    #[async_trait]
+    impl SessionHandler for RecordingHandler {
+        async fn on_notification(&self, notification: Value) {
+            self.notifications.lock().unwrap().push(notification);
+        }
+    }
+
+    #[test]
+    fn test_classify_messages() {
+        assert!(matches!(
+            IncomingMessage::classify(serde_json::json!({"id": 1, "method": "ping"})),
+            IncomingMessage::Request { .. }
+        ));
+        assert!(matches!(
+            IncomingMessage::classify(serde_json::json!({"id": null, "method": "notifications/x"})),
+            IncomingMessage::Notification(_)
+        ));
+        assert!(matches!(
+            IncomingMessage::classify(serde_json::json!({"id": 1, "result": {}})),
+            IncomingMessage::Response { .. }
+        ));
+        assert!(matches!(
+            IncomingMessage::classify(serde_json::json!({"result": {}})),
+            IncomingMessage::Invalid(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_ids_are_monotonic_and_restored() {
+        let (session, mut server) = session_pair(Arc::new(DefaultSessionHandler));
+
+        let client = tokio::spawn(async move {
+            let first = session
+                .send_request(serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "a"}))
+                .await
+                .unwrap();
+            let second = session
+                .send_request(serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "b"}))
+                .await
+                .unwrap();
+            (first, second)
+        });
+
+        for _ in 0..2 {
+            let request = server.recv().await;
+            server
+                .send(serde_json::json!({"jsonrpc": "2.0", "id": request["id"], "result": request["id"]}))
+                .await;
+        }
+
+        let (first, second) = client.await.unwrap();
+        // Session ids on the wire are distinct and increasing
+        assert_eq!(first["result"], 1);
+        assert_eq!(second["result"], 2);
+        // Callers see the id they sent
+        assert_eq!(first["id"], 1);
+        assert_eq!(second["id"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_out_of_order_responses_are_correlated() {
+        let (session, mut server) = session_pair(Arc::new(DefaultSessionHandler));
+        let session = Arc::new(session);
+
+        let a = tokio::spawn({
+            let session = Arc::clone(&session);
+            async move { session.request("a", serde_json::json!({})).await.unwrap() }
+        });
+        let b = tokio::spawn({
+            let session = Arc::clone(&session);
+            async move { session.request("b", serde_json::json!({})).await.unwrap() }
+        });
+
+        let first = server.recv().await;
+        let second = server.recv().await;
+        assert_eq!(session.pending_count(), 2);
+
+        // Answer in reverse order, echoing the method name
+        for request in [second, first] {
+            server
+                .send(serde_json::json!({"jsonrpc": "2.0", "id": request["id"], "result": request["method"]}))
+                .await;
+        }
+
+        assert_eq!(a.await.unwrap()["result"], "a");
+        assert_eq!(b.await.unwrap()["result"], "b");
+        assert_eq!(session.pending_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_notifications_and_log_lines_are_not_responses() {
+        let handler = Arc::new(RecordingHandler::default());
+        let (session, mut server) = session_pair(handler.clone());
+
+        let client =
+            tokio::spawn(async move { session.request("tools/list", serde_json::json!({})).await });
+
+        let request = server.recv().await;
+        server.send_line("server starting up...").await;
+        server
+            .send(serde_json::json!({"jsonrpc": "2.0", "method": "notifications/message", "params": {"data": "hi"}}))
+            .await;
+        server
+            .send(
+                serde_json::json!({"jsonrpc": "2.0", "id": request["id"], "result": {"tools": []}}),
+            )
+            .await;
+
+        let response = client.await.unwrap().unwrap();
+        assert_eq!(response["result"], serde_json::json!({"tools": []}));
+
+        let notifications = handler.notifications.lock().unwrap();
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0]["method"], "notifications/message");
+    }
+
+    #[tokio::test]
+    async fn test_server_requests_are_answered() {
+        let (_session, mut server) = session_pair(Arc::new(DefaultSessionHandler));
+
+        server
+            .send(serde_json::json!({"jsonrpc": "2.0", "id": "srv-1", "method": "ping"}))
+            .await;
+        let pong = server.recv().await;
+        assert_eq!(pong["id"], "srv-1");
+        assert_eq!(pong["result"], serde_json::json!({}));
+
+        server
+            .send(
+                serde_json::json!({"jsonrpc": "2.0", "id": 7, "method": "sampling/createMessage"}),
+            )
+            .await;
+        let rejected = server.recv().await;
+        assert_eq!(rejected["id"], 7);
+        assert_eq!(rejected["error"]["code"], -32601);
+    }
+
+    #[tokio::test]
+    async fn test_closed_connection_fails_pending_requests() {
+        let (session, mut server) = session_pair(Arc::new(DefaultSessionHandler));
+
+        let client =
+            tokio::spawn(async move { session.request("slow", serde_json::json!({})).await });
+        let _ = server.recv().await;
+        drop(server);
+
+        let result = client.await.unwrap();
+        assert!(matches!(result, Err(McpError::ConnectionError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_request_is_cancelled() {
+        let (session, mut server) = session_pair(Arc::new(DefaultSessionHandler));
+
+        // Give up on the request once the server has seen it
+        let mut slow = Box::pin(session.request("slow", serde_json::json!({})));
+        let request = tokio::select! {
+            _ = &mut slow => panic!("no response was sent"),
+            request = server.recv() => request,
+        };
+        drop(slow);
+        assert_eq!(session.pending_count(), 0);
+
+        // The cancellation goes out without anyone asking for it
+        let cancelled = server.recv().await;
+        assert_eq!(cancelled["method"], "notifications/cancelled");
+        assert_eq!(cancelled["params"]["requestId"], request["id"]);
+        assert_eq!(cancelled["params"]["reason"], "Request cancelled");
+
+        // Nothing left to cancel, and the session still works
+        session.finish_cancellations().await.unwrap();
+        assert!(session.cancelling_list().is_empty());
+        let client =
+            tokio::spawn(async move { session.request("next", serde_json::json!({})).await });
+        let request = server.recv().await;
+        assert_eq!(request["method"], "next");
+        server
+            .send(serde_json::json!({"jsonrpc": "2.0", "id": request["id"], "result": {}}))
+            .await;
+        assert!(client.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        let (session, mut server) = session_pair(Arc::new(DefaultSessionHandler));
+        let session = session.with_request_timeout(Duration::from_millis(50));
+
+        let result = session.request("never", serde_json::json!({})).await;
+        let request = server.recv().await;
+
+        // A sub-second limit is reported as 1s, not 0s
+        assert!(matches!(result, Err(McpError::Timeout { timeout: 1 })));
+        assert_eq!(session.pending_count(), 0);
+
+        // The timed-out request is still running on the server
+        session.finish_cancellations().await.unwrap();
+        let cancelled = server.recv().await;
+        assert_eq!(cancelled["params"]["requestId"], request["id"]);
+        assert_eq!(cancelled["params"]["reason"], "Request timed out");
+    }
+}
@@ -0,0 +1,165 @@
+//! Incremental parser for Server-Sent Events (`text/event-stream`) bodies.
+//!
+//! Bytes are fed in as they arrive from the network; complete events are
+//! returned once their terminating blank line has been seen. Implements the
+//! field rules of the WHATWG event-stream format: `data` lines are joined with
+//! newlines, `event`, `id` and `retry` set the corresponding properties, and
+//! lines starting with `:` are comments. Lines may end in `\n` or `\r\n`.
+
+/// One dispatched Server-Sent Event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    /// Event type from the `event` field (None means the default "message")
+    pub event: Option<String>,
+    /// Event payload; multiple `data` lines are joined with `\n`
+    pub data: String,
+    /// Event ID from the `id` field, if this event set one
+    pub id: Option<String>,
+    /// Reconnection delay in milliseconds from the `retry` field
+    pub retry: Option<u64>,
+}
+
+impl SseEvent {
+    /// Whether this is a default "message" event.
+    pub fn is_message(&self) -> bool {
+        matches!(self.event.as_deref(), None | Some("message"))
+    }
+}
+
+/// Incremental event-stream parser.
+#[derive(Debug, Default)]
+pub struct SseParser {
+    /// Bytes of the current, not yet terminated line
+    line: Vec<u8>,
+    /// Event being assembled
+    current: SseEvent,
+    /// Whether any `data` field was seen for the current event
+    has_data: bool,
+}
+
+impl SseParser {
+    /// Create a parser with no buffered input.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed a chunk of the stream and return the events it completes.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        let mut events = Vec::new();
+        for &byte in chunk {
+            if byte != b'\n' {
+                self.line.push(byte);
+                continue;
+            }
+
+            let mut line = std::mem::take(&mut self.line);
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            if let Some(event) = self.process_line(&String::from_utf8_lossy(&line)) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    /// Apply one line; a blank line dispatches the pending event.
+    fn process_line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+
+        match field {
+            "data" => {
+                if self.has_data {
+                    self.current.data.push('\n');
+                }
+                self.current.data.push_str(value);
+                self.has_data = true;
+            }
+            "event" => self.current.event = Some(value.to_string()),
+            "id" if !value.contains('\0') => self.current.id = Some(value.to_string()),
+            "retry" => {
+                if let Ok(retry) = value.parse() {
+                    self.current.retry = Some(retry);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Finish the current event. Events without data are only dispatched when
+    /// they carry an ID or retry value, so callers can track resumption state.
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = std::mem::take(&mut self.current);
+        let has_data = std::mem::take(&mut self.has_data);
+        if has_data || event.id.is_some() || event.retry.is_some() {
+            Some(event)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_single_event() {
+        let mut parser = SseParser::new();
+        let events = parser.feed(b"id: 7\nevent: message\ndata: {\"a\":1}\n\n");
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].id.as_deref(), Some("7"));
+        assert_eq!(events[0].data, "{\"a\":1}");
+        assert!(events[0].is_message());
+    }
+
+    #[test]
+    fn test_event_split_across_chunks() {
+        let mut parser = SseParser::new();
+        assert!(parser.feed(b"data: hel").is_empty());
+        assert!(parser.feed(b"lo\r\n").is_empty());
+
+        let events = parser.feed(b"\r\n");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].data, "hello");
+    }
+
+    #[test]
+    fn test_multiline_data_and_comments() {
+        let mut parser = SseParser::new();
+        let events = parser.feed(b": keep-alive\ndata: one\ndata: two\n\n");
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].data, "one\ntwo");
+    }
+
+    #[test]
+    fn test_named_event_and_retry() {
+        let mut parser = SseParser::new();
+        let events = parser.feed(b"retry: 1500\n\nevent: endpoint\ndata: /messages?session=1\n\n");
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].retry, Some(1500));
+        assert!(events[0].data.is_empty());
+        assert_eq!(events[1].event.as_deref(), Some("endpoint"));
+        assert!(!events[1].is_message());
+    }
+
+    #[test]
+    fn test_blank_lines_without_fields_are_ignored() {
+        let mut parser = SseParser::new();
+        assert!(parser.feed(b"\n\n: comment\n\n").is_empty());
+    }
+}
@@ -6,28 +6,36 @@
 
 use async_trait::async_trait;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::client::http::HttpTransport;
+use crate::client::legacy_sse::SseTransport;
+use crate::client::session::{
+    JsonRpcSession, NOTIFICATION_BUFFER, NotificationQueue, observe_notifications,
+};
 use crate::config::ServerTransport;
 use crate::error::{McpError, Result};
-use crate::transport::{Transport, TransportFactory};
-use tokio::io::{AsyncBufReadExt, BufReader};
+use crate::transport::{NotificationCallback, Transport, TransportFactory};
+use tokio::io::BufReader;
 use tokio::process::Command;
+use tokio::sync::mpsc;
 
 /// Stdio transport for local process communication.
 ///
 /// This transport spawns a server process and communicates with it via
-/// stdin/stdout using JSON-RPC over newline-delimited JSON.
+/// stdin/stdout using JSON-RPC over newline-delimited JSON. Messages are
+/// demultiplexed by a [`JsonRpcSession`], so server notifications and log
+/// lines are never mistaken for responses.
 pub struct StdioTransport {
     /// Child process handle.
     _child: tokio::process::Child,
 
-    /// Process stdin handle for sending requests.
-    stdin: tokio::process::ChildStdin,
+    /// JSON-RPC session over the process's stdin/stdout.
+    session: Arc<JsonRpcSession>,
 
-    /// Process stdout handle for reading responses.
-    stdout: BufReader<tokio::process::ChildStdout>,
+    /// Server notifications not yet read via `receive_notification`.
+    notifications: mpsc::Receiver<serde_json::Value>,
 }
 
 impl StdioTransport {
@@ -42,7 +50,7 @@ impl StdioTransport {
     /// # Important
     /// - Uses `kill_on_drop(true)` to prevent Windows zombie processes (PITFALLS.md - CONN-04)
     /// - Uses `BufReader` for line-by-line reading (newline-delimited JSON)
-    /// - Uses `writeln!` for sending messages (XP-03)
+    /// - Must be called within a Tokio runtime (starts the session reader task)
     pub fn new(
         command: &str,
         args: &[String],
@@ -86,136 +94,80 @@ impl StdioTransport {
                 message: "Failed to get stdout handle".to_string(),
             })?;
 
-        // Return StdioTransport
+        let (queue, notifications) = NotificationQueue::new(NOTIFICATION_BUFFER);
+        let session = JsonRpcSession::new(BufReader::new(stdout), stdin, Arc::new(queue));
+
         Ok(StdioTransport {
             _child: child,
-            stdin,
-            stdout: BufReader::new(stdout),
+            session: Arc::new(session),
+            notifications,
         })
     }
+
+    /// Shared handle to the underlying JSON-RPC session.
+    ///
+    /// Requests sent through the session may run concurrently with each other
+    /// and with requests sent through this transport.
+    pub fn session(&self) -> Arc<JsonRpcSession> {
+        Arc::clone(&self.session)
+    }
 }
 
 #[async_trait]
 impl Transport for StdioTransport {
     async fn receive_notification(&mut self) -> Result<serde_json::Value> {
-        use std::time::Duration;
-        use tokio::time::timeout;
-
-        let mut line = String::new();
-        let notification = timeout(Duration::from_secs(10), async move {
-            let _ = self
-                .stdout
-                .read_line(&mut line)
-                .await
-                .map_err(|e| McpError::connection_error("stdio", e))?;
-            if line.trim().is_empty() {
-                return Err(McpError::InvalidProtocol {
-                    message: "Empty notification line".to_string(),
-                });
-            }
-            let notification: serde_json::Value =
-                serde_json::from_str(&line).map_err(|e| McpError::InvalidProtocol {
-                    message: format!("Invalid JSON notification: {}", e),
-                })?;
-            Ok(notification)
-        })
-        .await;
-
-        match notification {
-            Ok(Ok(n)) => Ok(n),
-            Ok(Err(e)) => Err(e),
+        match tokio::time::timeout(Duration::from_secs(10), self.notifications.recv()).await {
+            Ok(Some(notification)) => Ok(notification),
+            Ok(None) => Err(McpError::InvalidProtocol {
+                message: "Server connection closed".to_string(),
+            }),
             Err(_) => Err(McpError::Timeout { timeout: 10 }),
         }
     }
 
     async fn send(&mut self, request: serde_json::Value) -> Result<serde_json::Value> {
-        // Send request using write! + newline (newline-delimited JSON)
-        let request_str = request.to_string();
-        use tokio::io::AsyncWriteExt;
-        self.stdin
-            .write_all(request_str.as_bytes())
-            .await
-            .map_err(|e| McpError::connection_error("stdio", e))?;
-        self.stdin
-            .write_all(b"\n")
-            .await
-            .map_err(|e| McpError::connection_error("stdio", e))?;
-
-        // Flush stdin to ensure message is sent
-        self.stdin
-            .flush()
-            .await
-            .map_err(|e| McpError::connection_error("stdio", e))?;
-
-        // Read response (newline-delimited JSON)
-        let response_str = tokio::time::timeout(Duration::from_secs(30), async move {
-            let mut line = String::new();
-            let _ = self
-                .stdout
-                .read_line(&mut line)
-                .await
-                .map_err(|e| McpError::connection_error("stdio", e))?;
-            if line.trim().is_empty() {
-                return Err(McpError::InvalidProtocol {
-                    message: "Empty response line".to_string(),
-                });
-            }
-            Ok(line)
-        })
-        .await
-        .map_err(|_| McpError::Timeout { timeout: 30 })?;
-
-        // Parse response JSON
-        let response_str = response_str?;
-        let response: serde_json::Value =
-            serde_json::from_str(&response_str).map_err(|e| McpError::InvalidProtocol {
-                message: format!("Invalid JSON response: {}", e),
-            })?;
-
-        Ok(response)
+        self.session.send_request(request).await
     }
 
     async fn send_notification(&mut self, notification: serde_json::Value) -> Result<()> {
-        // Send notification using write! + newline (newline-delimited JSON)
-        let notification_str = notification.to_string();
-        use tokio::io::AsyncWriteExt;
-        self.stdin
-            .write_all(notification_str.as_bytes())
-            .await
-            .map_err(|e| McpError::connection_error("stdio", e))?;
-        self.stdin
-            .write_all(b"\n")
-            .await
-            .map_err(|e| McpError::connection_error("stdio", e))?;
-
-        // Flush stdin to ensure message is sent
-        self.stdin
-            .flush()
-            .await
-            .map_err(|e| McpError::connection_error("stdio", e))?;
-
-        Ok(())
+        self.session.notify(&notification).await
+    }
+
+    async fn send_with_notifications(
+        &mut self,
+        request: serde_json::Value,
+        on_notification: NotificationCallback<'_>,
+    ) -> Result<serde_json::Value> {
+        let response = self.session.send_request(request);
+        observe_notifications(response, &mut self.notifications, on_notification).await
     }
 
     async fn ping(&self) -> Result<()> {
-        // Create a minimal ping request
-        let _request = serde_json::json!({
-            "jsonrpc": "2.0",
-            "method": "ping",
-            "id": "ping"
-        });
-
-        // We need mutable access for send, so we can't ping without mutation
-        // This is a limitation for immutable ping - we'll skip ping for now
-        // In Phase 2, we'll need a mut wrapper or separate ping method
-        Err(McpError::InvalidProtocol {
-            message: "Ping not yet implemented for stdio transport".to_string(),
-        })
+        let response = self.session.request("ping", serde_json::json!({})).await?;
+        match response.get("error") {
+            Some(error) => Err(McpError::InvalidProtocol {
+                message: format!("Ping failed: {}", error),
+            }),
+            None => Ok(()),
+        }
     }
 
     fn transport_type(&self) -> &str {
         "stdio"
     }
+
+    fn is_closed(&self) -> bool {
+        self.session.is_closed()
+    }
+
+    async fn cancel_abandoned(&mut self, _reason: &str) -> Result<()> {
+        // The session cancels each request as it is abandoned
+        self.session.finish_cancellations().await
+    }
+
+    fn set_request_timeout(&mut self, timeout: Duration) {
+        self.session.set_request_timeout(timeout);
+    }
 }
 
 impl TransportFactory for ServerTransport {
@@ -231,8 +183,20 @@ impl TransportFactory for ServerTransport {
                     .expect("Failed to create stdio transport");
                 Box::new(transport)
             }
-            ServerTransport::Http { url, headers } => {
+            ServerTransport::Http {
+                url,
+                headers,
+                sse_fallback,
+            } => {
                 let transport = HttpTransport::new(url, headers.clone());
+                if *sse_fallback {
+                    Box::new(transport.with_sse_fallback())
+                } else {
+                    Box::new(transport)
+                }
+            }
+            ServerTransport::Sse { url, headers } => {
+                let transport = SseTransport::new(url, headers.clone());
                 Box::new(transport)
             }
         }
@@ -240,7 +204,7 @@ impl TransportFactory for ServerTransport {
 
     fn supports_filtering(&self) -> bool {
         match self {
-            ServerTransport::Http { .. } => true,
+            ServerTransport::Http { .. } | ServerTransport::Sse { .. } => true,
             ServerTransport::Stdio { .. } => false,
         }
     }
@@ -11,8 +11,9 @@ use crate::McpError;
 
 /// Transport protocol for MCP server connections.
 ///
-/// Supports both local stdio execution and remote HTTP connections.
-#[derive(Debug, Clone, Deserialize, Serialize)]
+/// Supports local stdio execution, remote Streamable HTTP connections and the
+/// legacy HTTP+SSE transport used by servers built against protocol 2024-11-05.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(tag = "type")]
 pub enum ServerTransport {
     /// Server runs locally via stdio communication.
@@ -50,6 +51,26 @@ pub enum ServerTransport {
         /// HTTP headers to include in requests.
         #[serde(default)]
         headers: HashMap<String, String>,
+
+        /// Fall back to the legacy SSE transport when the server rejects
+        /// the Streamable HTTP initialize request with a 4xx status.
+        #[serde(default)]
+        sse_fallback: bool,
+    },
+
+    /// Server accessed via the legacy HTTP+SSE transport (protocol 2024-11-05).
+    ///
+    /// The client opens an SSE stream at the URL; the server announces the
+    /// endpoint that client messages are POSTed to.
+    #[serde(rename = "sse")]
+    Sse {
+        /// SSE stream URL (required for SSE transport).
+        #[serde(default)]
+        url: String,
+
+        /// HTTP headers to include in requests.
+        #[serde(default)]
+        headers: HashMap<String, String>,
     },
 }
 
@@ -59,6 +80,7 @@ impl ServerTransport {
         match self {
             ServerTransport::Stdio { .. } => "stdio",
             ServerTransport::Http { .. } => "http",
+            ServerTransport::Sse { .. } => "sse",
         }
     }
 
@@ -66,7 +88,7 @@ impl ServerTransport {
     pub fn command(&self) -> &str {
         match self {
             ServerTransport::Stdio { command, .. } => command,
-            ServerTransport::Http { .. } => "",
+            ServerTransport::Http { .. } | ServerTransport::Sse { .. } => "",
         }
     }
 
@@ -74,7 +96,7 @@ impl ServerTransport {
     pub fn args(&self) -> &[String] {
         match self {
             ServerTransport::Stdio { args, .. } => args,
-            ServerTransport::Http { .. } => &[],
+            ServerTransport::Http { .. } | ServerTransport::Sse { .. } => &[],
         }
     }
 
@@ -82,7 +104,7 @@ impl ServerTransport {
     pub fn env(&self) -> &HashMap<String, String> {
         match self {
             ServerTransport::Stdio { env, .. } => env,
-            ServerTransport::Http { .. } => {
+            ServerTransport::Http { .. } | ServerTransport::Sse { .. } => {
                 static EMPTY: LazyLock<HashMap<String, String>> = LazyLock::new(HashMap::new);
                 &EMPTY
             }
@@ -93,26 +115,26 @@ impl ServerTransport {
     pub fn cwd(&self) -> Option<&String> {
         match self {
             ServerTransport::Stdio { cwd, .. } => cwd.as_ref(),
-            ServerTransport::Http { .. } => None,
+            ServerTransport::Http { .. } | ServerTransport::Sse { .. } => None,
         }
     }
 
-    /// Extract the URL for HTTP transport.
+    /// Extract the URL for HTTP and SSE transports.
     pub fn url(&self) -> &str {
         match self {
             ServerTransport::Stdio { .. } => "",
-            ServerTransport::Http { url, .. } => url,
+            ServerTransport::Http { url, .. } | ServerTransport::Sse { url, .. } => url,
         }
     }
 
-    /// Extract headers for HTTP transport.
+    /// Extract headers for HTTP and SSE transports.
     pub fn headers(&self) -> &HashMap<String, String> {
         match self {
             ServerTransport::Stdio { .. } => {
                 static EMPTY: LazyLock<HashMap<String, String>> = LazyLock::new(HashMap::new);
                 &EMPTY
             }
-            ServerTransport::Http { headers, .. } => headers,
+            ServerTransport::Http { headers, .. } | ServerTransport::Sse { headers, .. } => headers,
         }
     }
 }
@@ -120,7 +142,7 @@ impl ServerTransport {
 /// Configuration for a single MCP server.
 ///
 /// Represents a configured MCP server with optional tool filtering capabilities.
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct ServerConfig {
     /// Unique server identifier.
     pub name: String,
@@ -146,6 +168,11 @@ pub struct ServerConfig {
     /// Supports wildcard patterns (*, ?) for flexible matching.
     #[serde(default)]
     pub disabled_tools: Option<Vec<String>>,
+
+    /// Optional request timeout in seconds for this server.
+    /// Overrides the global `timeout_secs`; `mcp call --timeout` overrides both.
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
 }
 
 impl ServerConfig {
@@ -175,8 +202,20 @@ impl ServerConfig {
                     crate::client::stdio::StdioTransport::new(command, args, env, cwd.as_deref())?;
                 Ok(Box::new(transport))
             }
-            ServerTransport::Http { url, headers } => Ok(Box::new(
-                crate::client::http::HttpTransport::new(url, headers.clone()),
+            ServerTransport::Http {
+                url,
+                headers,
+                sse_fallback,
+            } => {
+                let transport = crate::client::http::HttpTransport::new(url, headers.clone());
+                if *sse_fallback {
+                    Ok(Box::new(transport.with_sse_fallback()))
+                } else {
+                    Ok(Box::new(transport))
+                }
+            }
+            ServerTransport::Sse { url, headers } => Ok(Box::new(
+                crate::client::legacy_sse::SseTransport::new(url, headers.clone()),
             )),
         }
     }
@@ -226,6 +265,14 @@ pub struct Config {
     #[serde(default = "default_daemon_ttl")]
     pub daemon_ttl: u64,
 
+    /// Maximum number of daemon connections open to one server at a time.
+    ///
+    /// Concurrent requests to a server each use their own connection, up to
+    /// this limit; further requests wait for one to be returned, in the order
+    /// they arrived. Default value of 4.
+    #[serde(default = "default_max_connections_per_server")]
+    pub max_connections_per_server: usize,
+
     /// Socket path for IPC communication.
     ///
     /// Platform-specific path for daemon IPC.
@@ -242,6 +289,7 @@ impl Default for Config {
             retry_delay_ms: default_retry_delay_ms(),
             timeout_secs: default_timeout_secs(),
             daemon_ttl: default_daemon_ttl(),
+            max_connections_per_server: default_max_connections_per_server(),
             socket_path: crate::ipc::get_socket_path(),
         }
     }
@@ -280,6 +328,15 @@ impl Config {
         self.servers_by_name().get(name).map(|v| &**v)
     }
 
+    /// Request timeout for a server in seconds.
+    ///
+    /// The server's own `timeout_secs` if set, otherwise the global one.
+    pub fn server_timeout_secs(&self, name: &str) -> u64 {
+        self.get_server(name)
+            .and_then(|server| server.timeout_secs)
+            .unwrap_or(self.timeout_secs)
+    }
+
     /// Checks if the configuration has any servers defined.
     ///
     /// This is used to display CONFIG-05 warnings when no servers are configured.
@@ -311,6 +368,10 @@ fn default_daemon_ttl() -> u64 {
     60
 }
 
+fn default_max_connections_per_server() -> usize {
+    4
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -328,6 +389,7 @@ mod tests {
             description: None,
             allowed_tools: None,
             disabled_tools: None,
+            timeout_secs: None,
         };
         assert_eq!(server.name, "test-server");
     }
@@ -351,6 +413,30 @@ mod tests {
         assert_eq!(config.timeout_secs, 1800);
     }
 
+    #[test]
+    fn test_server_timeout_overrides_global() {
+        let server = |name: &str, timeout_secs| ServerConfig {
+            name: name.to_string(),
+            transport: ServerTransport::Http {
+                url: "http://localhost".to_string(),
+                headers: HashMap::new(),
+                sse_fallback: false,
+            },
+            description: None,
+            allowed_tools: None,
+            disabled_tools: None,
+            timeout_secs,
+        };
+        let config = Config {
+            servers: vec![server("slow", Some(7200)), server("default", None)],
+            timeout_secs: 60,
+            ..Default::default()
+        };
+        assert_eq!(config.server_timeout_secs("slow"), 7200);
+        assert_eq!(config.server_timeout_secs("default"), 60);
+        assert_eq!(config.server_timeout_secs("missing"), 60);
+    }
+
     #[test]
     fn test_daemon_ttl_default() {
         let config = Config::default();
@@ -366,6 +452,12 @@ mod tests {
         assert_eq!(config.daemon_ttl, 120);
     }
 
+    #[test]
+    fn test_max_connections_per_server_default() {
+        let config = Config::default();
+        assert_eq!(config.max_connections_per_server, 4);
+    }
+
     #[test]
     fn test_servers_by_name() {
         let config = Config {
@@ -381,6 +473,7 @@ mod tests {
                     description: None,
                     allowed_tools: None,
                     disabled_tools: None,
+                    timeout_secs: None,
                 },
                 ServerConfig {
                     name: "server2".to_string(),
@@ -393,6 +486,7 @@ mod tests {
                     description: None,
                     allowed_tools: None,
                     disabled_tools: None,
+                    timeout_secs: None,
                 },
             ],
             ..Default::default()
@@ -417,6 +511,7 @@ mod tests {
                 description: None,
                 allowed_tools: None,
                 disabled_tools: None,
+                timeout_secs: None,
             }],
             ..Default::default()
         };
@@ -13,7 +13,7 @@ use tracing::debug;
 ///
 /// Checks that required fields are present and valid according to CONFIG-04:
 /// - Stdio transport: command field must not be empty
-/// - HTTP transport: url field must not be empty and must be a valid URL
+/// - HTTP and SSE transports: url field must not be empty and must be a valid URL
 ///
 /// # Arguments
 /// * `server` - Server configuration to validate
@@ -36,7 +36,7 @@ pub fn validate_server_config(server: &ServerConfig, config_path: &str) -> Resul
                 server.name, command
             );
         }
-        ServerTransport::Http { url, .. } => {
+        ServerTransport::Http { url, .. } | ServerTransport::Sse { url, .. } => {
             if url.is_empty() {
                 return Err(McpError::MissingRequiredField {
                     server: server.name.clone(),
@@ -59,8 +59,10 @@ pub fn validate_server_config(server: &ServerConfig, config_path: &str) -> Resul
             }
 
             debug!(
-                "Server '{}' HTTP config validated (url: {})",
-                server.name, url
+                "Server '{}' {} config validated (url: {})",
+                server.name,
+                server.transport.type_name(),
+                url
             );
         }
     }
@@ -89,6 +91,8 @@ pub fn validate_server_config(server: &ServerConfig, config_path: &str) -> Resul
 
 /// Validates all server configurations in the config.
 ///
+/// Also checks that `max_connections_per_server` is at least 1.
+///
 /// # Arguments
 /// * `config` - Configuration to validate
 /// * `config_path` - Path to config file (for error reporting)
@@ -97,6 +101,15 @@ pub fn validate_server_config(server: &ServerConfig, config_path: &str) -> Resul
 /// * `Ok(())` if all validations pass
 /// * `Err(McpError)` if any validation fails
 pub fn validate_config(config: &Config, config_path: &str) -> Result<(), McpError> {
+    if config.max_connections_per_server == 0 {
+        return Err(McpError::ConfigParseError {
+            path: Path::new(config_path).to_path_buf(),
+            source: Box::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "max_connections_per_server must be at least 1",
+            )),
+        });
+    }
     for server in &config.servers {
         validate_server_config(server, config_path)?
     }
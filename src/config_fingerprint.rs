@@ -26,9 +26,11 @@ use crate::config::Config;
 pub fn config_fingerprint(config: &Config) -> String {
     use sha2::{Digest, Sha256};
 
-    // Serialize config to JSON
-    let json =
-        serde_json::to_string(config).expect("Failed to serialize config for fingerprinting");
+    // Serialize config to JSON; going through a Value sorts the keys of maps
+    // such as a server's `env`, whose iteration order differs between processes
+    let json = serde_json::to_value(config)
+        .expect("Failed to serialize config for fingerprinting")
+        .to_string();
     let mut hasher = Sha256::new();
     hasher.update(json.as_bytes());
     let result = hasher.finalize();
@@ -130,6 +132,36 @@ mod tests {
         assert_ne!(fp, fp2, "Changing config values should change hash");
     }
 
+    #[test]
+    fn test_config_fingerprint_ignores_map_order() {
+        let server = || crate::config::ServerConfig {
+            name: "server".to_string(),
+            transport: crate::config::ServerTransport::Stdio {
+                command: "cmd".to_string(),
+                args: vec![],
+                env: (0..32)
+                    .map(|i| (format!("VAR_{}", i), i.to_string()))
+                    .collect(),
+                cwd: None,
+            },
+            description: None,
+            allowed_tools: None,
+            disabled_tools: None,
+            timeout_secs: None,
+        };
+        // Each HashMap gets its own hasher keys, so their orders differ
+        let config1 = Config {
+            servers: vec![server()],
+            ..Config::default()
+        };
+        let config2 = Config {
+            servers: vec![server()],
+            ..Config::default()
+        };
+
+        assert_eq!(config_fingerprint(&config1), config_fingerprint(&config2));
+    }
+
     #[test]
     fn test_config_hash_detection_logic() {
         let mut config1 = Config::default();
@@ -182,20 +214,20 @@ mod tests {
 
         // Create daemon state similar to how daemon/mod.rs creates it
         let lifecycle = DaemonLifecycle::new(60);
-        let connection_pool = Arc::new(crate::daemon::pool::ConnectionPool::new(Arc::new(
-            config1.clone(),
-        )));
+        let config = crate::daemon::reload::SharedConfig::new(config1.clone());
+        let connection_pool = Arc::new(crate::daemon::pool::ConnectionPool::new(config.clone()));
 
         let state = crate::daemon::DaemonState {
-            config: Arc::new(config1.clone()),
-            config_fingerprint: fp1.clone(),
+            config,
+            config_path: None,
             lifecycle: Arc::new(Mutex::new(lifecycle)),
             connection_pool,
         };
 
         // If config hash matches, no change
         assert_eq!(
-            state.config_fingerprint, fp1,
+            state.config.fingerprint(),
+            fp1,
             "State should store initial fingerprint"
         );
 
@@ -205,7 +237,7 @@ mod tests {
         assert!(fp2 != fp1, "New config should have different hash");
 
         // Integration: config hash should detect change
-        assert!(config_hash_changed(&config1, &state.config));
-        assert!(state.config_fingerprint != fp2);
+        assert!(config_hash_changed(&config1, &state.config.get()));
+        assert!(state.config.fingerprint() != fp2);
     }
 }
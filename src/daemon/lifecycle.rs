@@ -1,4 +1,6 @@
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
 use tokio::sync::{Mutex, RwLock};
@@ -11,6 +13,26 @@ pub struct DaemonLifecycle {
     /// Time after which daemon should shutdown
     pub idle_timeout: Duration,
     pub shutting_down: Arc<RwLock<bool>>,
+    /// Deadlines of the requests still running, by request number
+    running: Arc<std::sync::Mutex<HashMap<u64, Instant>>>,
+    /// Number given to the next request
+    next_request: Arc<AtomicU64>,
+}
+
+/// A request counted as running by [`DaemonLifecycle::begin_request`] until
+/// dropped
+pub struct RunningRequest {
+    running: Arc<std::sync::Mutex<HashMap<u64, Instant>>>,
+    number: u64,
+}
+
+impl Drop for RunningRequest {
+    fn drop(&mut self) {
+        self.running
+            .lock()
+            .expect("Failed to acquire running requests lock")
+            .remove(&self.number);
+    }
 }
 
 impl DaemonLifecycle {
@@ -24,9 +46,37 @@ impl DaemonLifecycle {
             last_activity,
             idle_timeout,
             shutting_down: Arc::new(RwLock::new(false)),
+            running: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            next_request: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// Count a request as running until the returned guard is dropped
+    ///
+    /// The daemon doesn't go idle while a request runs within `timeout`, and
+    /// a daemon shutting down waits for it until then.
+    pub fn begin_request(&self, timeout: Duration) -> RunningRequest {
+        let number = self.next_request.fetch_add(1, Ordering::Relaxed);
+        self.running
+            .lock()
+            .expect("Failed to acquire running requests lock")
+            .insert(number, Instant::now() + timeout);
+        RunningRequest {
+            running: Arc::clone(&self.running),
+            number,
+        }
+    }
+
+    /// Latest deadline of the requests still running, if any
+    pub fn latest_deadline(&self) -> Option<Instant> {
+        self.running
+            .lock()
+            .expect("Failed to acquire running requests lock")
+            .values()
+            .max()
+            .copied()
+    }
+
     /// Update the last activity timestamp to now
     /// Call this whenever a request is received
     pub async fn update_activity(&self) {
@@ -36,10 +86,18 @@ impl DaemonLifecycle {
 
     /// Check if the daemon should shutdown due to idle timeout
     /// Call this periodically (e.g., every 1 second) in a separate task
+    ///
+    /// A daemon with requests running within their timeout isn't idle.
     pub async fn should_shutdown(&self) -> bool {
         if *self.shutting_down.read().await {
             return true;
         }
+        if self
+            .latest_deadline()
+            .is_some_and(|deadline| deadline > Instant::now())
+        {
+            return false;
+        }
         let last_activity = self.last_activity.lock().await;
         let elapsed = last_activity.elapsed();
         elapsed > self.idle_timeout
@@ -8,6 +8,7 @@
 //!
 //! - [`lifecycle`] — Idle timeout monitoring and shutdown signaling
 //! - [`pool`] — Connection pool managing persistent MCP server connections
+//! - [`reload`] — Hot reloading of the config when its file changes
 //! - [`protocol`] — JSON-based request/response protocol over IPC
 //! - [`orphan`] — Cleanup of stale daemon processes and socket files
 //!
@@ -20,9 +21,12 @@
 //! ```
 //!
 //! The daemon auto-spawns when a CLI command needs it (unless `--no-daemon`)
-//! and self-terminates after an idle timeout (default 60s).
+//! and self-terminates after an idle timeout (default 60s). It watches its
+//! config file and reloads it in place, keeping connections to servers whose
+//! settings didn't change.
 
 use anyhow::Result;
+use std::collections::HashMap;
 use std::io::ErrorKind;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -32,12 +36,14 @@ use tokio::sync::Mutex;
 use crate::config::Config;
 use crate::daemon::lifecycle::DaemonLifecycle;
 use crate::daemon::pool::ConnectionPool;
+use crate::daemon::reload::{SharedConfig, reload_config, watch_config};
 use crate::ipc::{IpcServer, create_ipc_server};
 
 pub mod lifecycle;
 pub mod orphan;
 pub mod pool;
 pub mod protocol;
+pub mod reload;
 
 /// Configuration fingerprint hash
 pub type ConfigFingerprint = String;
@@ -45,10 +51,12 @@ pub type ConfigFingerprint = String;
 /// Daemon state managed in run_daemon()
 #[derive(Clone)]
 pub struct DaemonState {
-    /// Loaded configuration
-    pub config: Arc<Config>,
-    /// Config file content fingerprint for validation
-    pub config_fingerprint: ConfigFingerprint,
+    /// Current configuration, shared with the connection pool and swapped
+    /// on reload
+    pub config: SharedConfig,
+    /// Absolute path of the config file reread on reload; None if the daemon
+    /// started without one
+    pub config_path: Option<PathBuf>,
     /// Lifecycle manager for idle timeout
     pub lifecycle: Arc<Mutex<DaemonLifecycle>>,
     /// Connection pool for persistent MCP server connections
@@ -72,11 +80,15 @@ impl DaemonState {
 /// This function:
 /// 1. Creates an IPC server (Unix socket on Unix, named pipe on Windows)
 /// 2. Calculates config fingerprint
-/// 3. Spawns idle timeout monitor
+/// 3. Spawns idle timeout monitor, and a watcher reloading `config_path`
+///    when it changes
 /// 4. Main loop accepts connections and handles requests
-/// 5. Removes socket file on exit
+/// 5. On shutdown, removes the socket file, then waits for requests still
+///    in flight, until their timeout, so a replacement daemon can start
+///    without losing them
 pub async fn run_daemon(
     config: Config,
+    config_path: Option<PathBuf>,
     socket_path: PathBuf,
     lifecycle: DaemonLifecycle,
 ) -> Result<()> {
@@ -87,19 +99,19 @@ pub async fn run_daemon(
     tracing::info!("IPC server started on: {:?}", socket_path);
 
     // Calculate config fingerprint
-    let config_fingerprint = config_fingerprint(&config);
-    tracing::info!("Config fingerprint: {}", config_fingerprint);
+    let config = SharedConfig::new(config);
+    tracing::info!("Config fingerprint: {}", config.fingerprint());
 
     // Get current process PID
     let pid = std::process::id();
     tracing::info!("Daemon PID: {}", pid);
 
     // Initialize connection pool
-    let connection_pool = Arc::new(ConnectionPool::new(Arc::new(config.clone())));
+    let connection_pool = Arc::new(ConnectionPool::new(config.clone()));
 
     let state = DaemonState {
-        config: Arc::new(config),
-        config_fingerprint: config_fingerprint.clone(),
+        config,
+        config_path,
         lifecycle: Arc::new(Mutex::new(lifecycle)),
         connection_pool,
     };
@@ -110,8 +122,23 @@ pub async fn run_daemon(
         crate::daemon::lifecycle::run_idle_timer(state2.lifecycle).await;
     });
 
+    // Spawn config file watcher
+    let watcher_task = state
+        .config_path
+        .clone()
+        .map(|path| tokio::spawn(watch_config(path, (*state.connection_pool).clone())));
+
     tracing::info!("Daemon main loop starting");
 
+    // Handlers of accepted connections, awaited on shutdown
+    let mut clients = tokio::task::JoinSet::new();
+    // Tells idle connections to close once the daemon shuts down
+    let (stop_clients, clients_stopped) = tokio::sync::watch::channel(false);
+
+    // Checked on a fixed schedule, so a steady stream of connections can't
+    // keep the daemon from noticing it should shut down
+    let mut shutdown_check = tokio::time::interval(Duration::from_secs(1));
+
     // Main loop: accept connections or wait for shutdown signal
     loop {
         tokio::select! {
@@ -122,8 +149,9 @@ pub async fn run_daemon(
                         tracing::debug!("Accepted connection from: {}", client_addr);
                         let state_clone = state.clone();
                         let stream_clone = stream;
-                        tokio::spawn(async move {
-                            handle_client(stream_clone, state_clone).await;
+                        let stopped = clients_stopped.clone();
+                        clients.spawn(async move {
+                            handle_client(stream_clone, state_clone, stopped).await;
                         });
                     }
                     Err(e) => {
@@ -138,69 +166,325 @@ pub async fn run_daemon(
             }
 
             // Wait for shutdown timeout
-            _ = tokio::time::sleep(Duration::from_secs(1)) => {
+            _ = shutdown_check.tick() => {
                 // Check if we should shutdown
                 if state.lifecycle.lock().await.should_shutdown().await {
                     break;
                 }
             }
         }
+
+        // Forget handlers that already finished
+        while clients.try_join_next().is_some() {}
     }
 
+    // Free the socket path before draining, so a replacement daemon can bind
+    // it without this one removing its socket file afterwards. The file goes
+    // first: once connecting fails, the path is free.
     tracing::info!("Daemon shutting down, removing resource files");
+    if let Some(watcher_task) = watcher_task {
+        watcher_task.abort();
+    }
     let socket_path_clone = socket_path.clone();
     cleanup_socket(socket_path_clone).await?;
+    drop(ipc_server);
+
+    let _ = stop_clients.send(true);
+    let lifecycle = state.lifecycle.lock().await.clone();
+    drain_clients(clients, &lifecycle).await;
 
     tracing::info!("Daemon shutdown complete");
     Ok(())
 }
 
-/// Handle client requests
-async fn handle_client(stream: impl crate::ipc::IpcStream, state: DaemonState) {
-    use tokio::io::BufReader;
+/// Time given to requests past their deadline, so a call that timed out
+/// can still report it
+const DRAIN_GRACE: Duration = Duration::from_secs(1);
+
+/// Wait for in-flight requests to finish, aborting those still running
+/// past the latest of their deadlines
+async fn drain_clients(mut clients: tokio::task::JoinSet<()>, lifecycle: &DaemonLifecycle) {
+    if clients.is_empty() {
+        return;
+    }
+    tracing::info!("Waiting for {} client(s) to finish", clients.len());
+    loop {
+        // Open connections may still start requests, so look again each time
+        let deadline = lifecycle
+            .latest_deadline()
+            .unwrap_or_else(std::time::Instant::now)
+            + DRAIN_GRACE;
+        match tokio::time::timeout_at(deadline.into(), clients.join_next()).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return,
+            Err(_) => {
+                tracing::warn!(
+                    "Aborting {} client(s) with requests past their deadline",
+                    clients.len()
+                );
+                clients.shutdown().await;
+                return;
+            }
+        }
+    }
+}
+
+/// Serve one client connection
+///
+/// The connection opens with a handshake in each direction. After that the
+/// client may send any number of requests, each handled on its own task and
+/// answered with its ID as soon as it completes, so responses may arrive in
+/// any order. Tool calls stream progress frames before the result and are
+/// cancelled by a cancellation frame with their ID or when the client
+/// disconnects. A request reusing the ID of one still in flight is rejected.
+/// Once `stopped` turns true the connection is closed as soon as no request
+/// is in flight.
+async fn handle_client(
+    stream: impl crate::ipc::IpcStream + 'static,
+    state: DaemonState,
+    mut stopped: tokio::sync::watch::Receiver<bool>,
+) {
+    use crate::daemon::protocol::{DaemonRequest, FrameRequest, RequestFrame, ResponseFrame};
+    use tokio::io::{AsyncBufReadExt, BufReader};
     tracing::debug!("Daemon: New client connected");
 
     // Update activity timestamp
     state.update_activity().await;
 
-    // Wrap stream for buffered reading
     let (reader, mut writer) = tokio::io::split(stream);
-    let mut buf_reader = BufReader::new(reader);
-
-    // Read request from stream
-    tracing::debug!("Daemon: Reading request...");
-    let request = match crate::daemon::protocol::receive_request(&mut buf_reader).await {
-        Ok(req) => {
-            tracing::debug!("Daemon: Got request: {:?}", req);
-            req
+    // `next_line` is cancel safe, unlike `read_line`
+    let mut lines = BufReader::new(reader).lines();
+
+    if !accept_handshake(&mut lines, &mut writer).await {
+        return;
+    }
+
+    // Responses from all request tasks go through one writer
+    let (responses, mut outgoing) = tokio::sync::mpsc::unbounded_channel::<ResponseFrame>();
+    let sender = tokio::spawn(async move {
+        while let Some(frame) = outgoing.recv().await {
+            if let Err(e) = crate::daemon::protocol::send_frame(&mut writer, &frame).await {
+                tracing::debug!("Daemon: Error sending response: {}", e);
+                return;
+            }
+        }
+    });
+
+    let mut requests = tokio::task::JoinSet::new();
+    // Requests in flight by ID, and whether they are tool calls; sending or
+    // dropping a tool call's sender cancels it
+    let mut cancels: HashMap<u64, (tokio::sync::oneshot::Sender<()>, bool)> = HashMap::new();
+
+    loop {
+        let line = tokio::select! {
+            line = lines.next_line() => line,
+            Some(_) = requests.join_next(), if !requests.is_empty() => continue,
+            _ = stopped.wait_for(|stopped| *stopped), if requests.is_empty() => {
+                tracing::debug!("Daemon: Closing idle client connection");
+                break;
+            }
+        };
+        let line = match line {
+            Ok(Some(line)) => line,
+            Ok(None) => break,
+            Err(e) => {
+                tracing::debug!("Daemon: Error reading request: {}", e);
+                break;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
         }
+
+        let frame: RequestFrame = match serde_json::from_str(&line) {
+            Ok(frame) => frame,
+            Err(e) => {
+                // Answer a frame whose ID is readable so its caller isn't left waiting
+                tracing::debug!("Daemon: Invalid request frame: {}", e);
+                let id = serde_json::from_str::<serde_json::Value>(&line)
+                    .ok()
+                    .and_then(|frame| frame.get("id")?.as_u64());
+                if let Some(id) = id {
+                    let _ = responses.send(ResponseFrame {
+                        id,
+                        response: crate::daemon::protocol::DaemonResponse::Error {
+                            code: 1,
+                            message: format!("Invalid request: {}", e),
+                        },
+                    });
+                }
+                continue;
+            }
+        };
+        tracing::debug!("Daemon: Got request {}: {:?}", frame.id, frame.request);
+
+        cancels.retain(|_, (cancel, _)| !cancel.is_closed());
+        let request = match frame.request {
+            FrameRequest::Serve(request) => request,
+            FrameRequest::Cancel => {
+                match cancels.remove(&frame.id) {
+                    Some((cancel, true)) => {
+                        tracing::debug!("Daemon: Client cancelled tool call {}", frame.id);
+                        let _ = cancel.send(());
+                    }
+                    Some(request) => {
+                        cancels.insert(frame.id, request);
+                        tracing::debug!("Daemon: Request {} is not a tool call", frame.id);
+                    }
+                    None => tracing::debug!("Daemon: No tool call {} to cancel", frame.id),
+                }
+                continue;
+            }
+        };
+
+        // Another request with the ID would take over its cancellation and
+        // response
+        if cancels.contains_key(&frame.id) {
+            tracing::debug!("Daemon: Request ID {} is already in use", frame.id);
+            let _ = responses.send(ResponseFrame {
+                id: frame.id,
+                response: crate::daemon::protocol::DaemonResponse::Error {
+                    code: 1,
+                    message: format!("Request ID {} is already in use", frame.id),
+                },
+            });
+            continue;
+        }
+        let (cancel, cancelled) = tokio::sync::oneshot::channel();
+        let is_tool_call = matches!(request, DaemonRequest::ExecuteTool { .. });
+        cancels.insert(frame.id, (cancel, is_tool_call));
+        requests.spawn(serve_request(
+            state.clone(),
+            frame.id,
+            request,
+            async {
+                let _ = cancelled.await;
+            },
+            responses.clone(),
+        ));
+    }
+
+    // Nobody is left to receive the results of tool calls still running
+    drop(cancels);
+    while requests.join_next().await.is_some() {}
+    drop(responses);
+    let _ = sender.await;
+
+    // Update activity timestamp
+    state.update_activity().await;
+}
+
+/// Read the client's handshake and answer it with the daemon's
+///
+/// Returns false if the connection should be closed: the client asked for
+/// another protocol version, or sent a bare request because it predates the
+/// handshake, in which case it is told so in the response format it reads.
+async fn accept_handshake<R, W>(lines: &mut tokio::io::Lines<R>, writer: &mut W) -> bool
+where
+    R: tokio::io::AsyncBufRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin,
+{
+    use crate::daemon::protocol::{DaemonResponse, Handshake, IPC_PROTOCOL_VERSION, send_frame};
+
+    let line = match lines.next_line().await {
+        Ok(Some(line)) => line,
+        Ok(None) => return false,
         Err(e) => {
-            tracing::debug!("Daemon: Error reading request: {}", e);
-            return;
+            tracing::debug!("Daemon: Error reading handshake: {}", e);
+            return false;
         }
     };
 
-    // Handle request
-    tracing::debug!("Daemon: Handling request...");
-    let response = handle_request(request, &state).await;
-    tracing::debug!("Daemon: Generated response: {:?}", response);
+    let Ok(handshake) = serde_json::from_str::<Handshake>(&line) else {
+        tracing::info!("Rejecting client that predates the IPC handshake");
+        let response = DaemonResponse::Error {
+            code: 1,
+            message: format!(
+                "This mcp is older than the running daemon, which requires IPC protocol {}; upgrade mcp, or stop the daemon with the newer version's 'mcp shutdown'",
+                IPC_PROTOCOL_VERSION
+            ),
+        };
+        let _ = send_frame(writer, &response).await;
+        return false;
+    };
 
-    // Send response
-    tracing::debug!("Daemon: Sending response...");
-    if let Err(e) = crate::daemon::protocol::send_response(&mut writer, &response).await {
-        tracing::debug!("Daemon: Error sending response: {}", e);
-        return;
+    if let Err(e) = send_frame(writer, &Handshake::current()).await {
+        tracing::debug!("Daemon: Error sending handshake: {}", e);
+        return false;
     }
-    tracing::debug!("Daemon: Response sent");
+    if handshake.protocol_version != IPC_PROTOCOL_VERSION {
+        tracing::info!(
+            "Rejecting client speaking IPC protocol {} (daemon speaks {})",
+            handshake.protocol_version,
+            IPC_PROTOCOL_VERSION
+        );
+        return false;
+    }
+    true
+}
+
+/// Handle request `id` from a client connection, sending its progress and
+/// final response through `responses`
+async fn serve_request(
+    state: DaemonState,
+    id: u64,
+    request: crate::daemon::protocol::DaemonRequest,
+    cancelled: impl std::future::Future<Output = ()> + Send,
+    responses: tokio::sync::mpsc::UnboundedSender<crate::daemon::protocol::ResponseFrame>,
+) {
+    use crate::daemon::protocol::{DaemonResponse, ResponseFrame};
+
+    state.update_activity().await;
+    let _running = state
+        .lifecycle
+        .lock()
+        .await
+        .begin_request(request_timeout(&request, &state.config.get()));
+
+    let response = handle_request(
+        request,
+        &state,
+        &mut |update| {
+            let _ = responses.send(ResponseFrame {
+                id,
+                response: DaemonResponse::Progress(update),
+            });
+        },
+        cancelled,
+    )
+    .await;
+    tracing::debug!("Daemon: Response to request {}: {:?}", id, response);
+
+    let _ = responses.send(ResponseFrame { id, response });
 
     // Update activity timestamp
     state.update_activity().await;
 }
 
+/// Longest `request` can run: a tool call's own or its server's timeout,
+/// or the default request timeout for anything else
+fn request_timeout(request: &crate::daemon::protocol::DaemonRequest, config: &Config) -> Duration {
+    match request {
+        crate::daemon::protocol::DaemonRequest::ExecuteTool {
+            server_name,
+            timeout_secs,
+            ..
+        } => Duration::from_secs(
+            timeout_secs.unwrap_or_else(|| config.server_timeout_secs(server_name)),
+        ),
+        _ => crate::client::session::DEFAULT_REQUEST_TIMEOUT,
+    }
+}
+
 /// Handle daemon request and return response
+///
+/// Tool calls pass their progress to `send_progress` and are cancelled once
+/// `cancelled` completes; other requests ignore both.
 async fn handle_request(
     request: crate::daemon::protocol::DaemonRequest,
     state: &DaemonState,
+    send_progress: &mut (dyn FnMut(crate::daemon::protocol::ProgressUpdate) + Send),
+    cancelled: impl std::future::Future<Output = ()> + Send,
 ) -> crate::daemon::protocol::DaemonResponse {
     match request {
         crate::daemon::protocol::DaemonRequest::Ping => {
@@ -208,9 +492,34 @@ async fn handle_request(
         }
 
         crate::daemon::protocol::DaemonRequest::GetConfigFingerprint => {
-            crate::daemon::protocol::DaemonResponse::ConfigFingerprint(
-                state.config_fingerprint.clone(),
-            )
+            crate::daemon::protocol::DaemonResponse::ConfigFingerprint(state.config.fingerprint())
+        }
+
+        crate::daemon::protocol::DaemonRequest::GetConfigPath => {
+            crate::daemon::protocol::DaemonResponse::ConfigPath(state.config_path.clone())
+        }
+
+        crate::daemon::protocol::DaemonRequest::ReloadConfig => {
+            let Some(path) = &state.config_path else {
+                return crate::daemon::protocol::DaemonResponse::Error {
+                    code: 1,
+                    message: "Daemon was started without a config file".to_string(),
+                };
+            };
+            tracing::info!("ReloadConfig: path={}", path.display());
+
+            match reload_config(path, &state.connection_pool).await {
+                Ok(_) => crate::daemon::protocol::DaemonResponse::ConfigFingerprint(
+                    state.config.fingerprint(),
+                ),
+                Err(e) => {
+                    tracing::error!("Reload config failed: {}", e);
+                    crate::daemon::protocol::DaemonResponse::Error {
+                        code: 3,
+                        message: format!("Reload config failed: {}", e),
+                    }
+                }
+            }
         }
 
         crate::daemon::protocol::DaemonRequest::Shutdown => {
@@ -224,32 +533,48 @@ async fn handle_request(
             server_name,
             tool_name,
             arguments,
+            timeout_secs,
         } => {
-            tracing::info!("ExecuteTool: server={}, tool={}", server_name, tool_name);
+            execute_tool_streaming(
+                state,
+                send_progress,
+                &server_name,
+                &tool_name,
+                arguments,
+                timeout_secs.map(Duration::from_secs),
+                cancelled,
+            )
+            .await
+        }
 
-            // Execute tool using connection pool's execute method
-            match state
-                .connection_pool
-                .execute(&server_name, &tool_name, arguments)
-                .await
-            {
-                Ok(result) => crate::daemon::protocol::DaemonResponse::ToolResult(result),
+        crate::daemon::protocol::DaemonRequest::ListTools { server_name } => {
+            tracing::info!("ListTools: server={}", server_name);
+
+            // Get list of tools using connection pool's list_tools method
+            match state.connection_pool.list_tools(&server_name).await {
+                Ok(tools) => crate::daemon::protocol::DaemonResponse::ToolList(tools),
                 Err(e) => {
-                    tracing::error!("Tool execution failed: {}", e);
+                    tracing::error!("List tools failed: {}", e);
                     crate::daemon::protocol::DaemonResponse::Error {
                         code: 3,
-                        message: format!("Tool execution failed: {}", e),
+                        message: format!("List tools failed: {}", e),
                     }
                 }
             }
         }
 
-        crate::daemon::protocol::DaemonRequest::ListTools { server_name } => {
-            tracing::info!("ListTools: server={}", server_name);
+        crate::daemon::protocol::DaemonRequest::ListToolsPage {
+            server_name,
+            cursor,
+        } => {
+            tracing::info!("ListToolsPage: server={}", server_name);
 
-            // Get list of tools using connection pool's list_tools method
-            match state.connection_pool.list_tools(&server_name).await {
-                Ok(tools) => crate::daemon::protocol::DaemonResponse::ToolList(tools),
+            match state
+                .connection_pool
+                .list_tools_page(&server_name, cursor.as_deref())
+                .await
+            {
+                Ok(page) => crate::daemon::protocol::DaemonResponse::ToolPage(page),
                 Err(e) => {
                     tracing::error!("List tools failed: {}", e);
                     crate::daemon::protocol::DaemonResponse::Error {
@@ -266,6 +591,7 @@ async fn handle_request(
             // Get list of configured server names from config
             let servers: Vec<String> = state
                 .config
+                .get()
                 .servers
                 .iter()
                 .map(|s| s.name.clone())
@@ -273,22 +599,168 @@ async fn handle_request(
 
             crate::daemon::protocol::DaemonResponse::ServerList(servers)
         }
+
+        crate::daemon::protocol::DaemonRequest::ListResources { server_name } => {
+            tracing::info!("ListResources: server={}", server_name);
+
+            match state.connection_pool.list_resources(&server_name).await {
+                Ok(resources) => crate::daemon::protocol::DaemonResponse::ResourceList(resources),
+                Err(e) => {
+                    tracing::error!("List resources failed: {}", e);
+                    crate::daemon::protocol::DaemonResponse::Error {
+                        code: 3,
+                        message: format!("List resources failed: {}", e),
+                    }
+                }
+            }
+        }
+
+        crate::daemon::protocol::DaemonRequest::ListResourceTemplates { server_name } => {
+            tracing::info!("ListResourceTemplates: server={}", server_name);
+
+            match state
+                .connection_pool
+                .list_resource_templates(&server_name)
+                .await
+            {
+                Ok(templates) => {
+                    crate::daemon::protocol::DaemonResponse::ResourceTemplateList(templates)
+                }
+                Err(e) => {
+                    tracing::error!("List resource templates failed: {}", e);
+                    crate::daemon::protocol::DaemonResponse::Error {
+                        code: 3,
+                        message: format!("List resource templates failed: {}", e),
+                    }
+                }
+            }
+        }
+
+        crate::daemon::protocol::DaemonRequest::ReadResource { server_name, uri } => {
+            tracing::info!("ReadResource: server={}, uri={}", server_name, uri);
+
+            match state
+                .connection_pool
+                .read_resource(&server_name, &uri)
+                .await
+            {
+                Ok(contents) => crate::daemon::protocol::DaemonResponse::ResourceContents(contents),
+                Err(e) => {
+                    tracing::error!("Read resource failed: {}", e);
+                    crate::daemon::protocol::DaemonResponse::Error {
+                        code: 3,
+                        message: format!("Read resource failed: {}", e),
+                    }
+                }
+            }
+        }
+
+        crate::daemon::protocol::DaemonRequest::ListPrompts { server_name } => {
+            tracing::info!("ListPrompts: server={}", server_name);
+
+            match state.connection_pool.list_prompts(&server_name).await {
+                Ok(prompts) => crate::daemon::protocol::DaemonResponse::PromptList(prompts),
+                Err(e) => {
+                    tracing::error!("List prompts failed: {}", e);
+                    crate::daemon::protocol::DaemonResponse::Error {
+                        code: 3,
+                        message: format!("List prompts failed: {}", e),
+                    }
+                }
+            }
+        }
+
+        crate::daemon::protocol::DaemonRequest::GetPrompt {
+            server_name,
+            prompt_name,
+            arguments,
+        } => {
+            tracing::info!("GetPrompt: server={}, prompt={}", server_name, prompt_name);
+
+            match state
+                .connection_pool
+                .get_prompt(&server_name, &prompt_name, &arguments)
+                .await
+            {
+                Ok(prompt) => crate::daemon::protocol::DaemonResponse::PromptResult(prompt),
+                Err(e) => {
+                    tracing::error!("Get prompt failed: {}", e);
+                    crate::daemon::protocol::DaemonResponse::Error {
+                        code: 3,
+                        message: format!("Get prompt failed: {}", e),
+                    }
+                }
+            }
+        }
     }
 }
 
-/// Calculate config file fingerprint using SHA256
-fn config_fingerprint(config: &Config) -> String {
-    use sha2::{Digest, Sha256};
+/// Execute a tool, passing each progress notification to `send_progress`
+/// before returning the final response
+///
+/// The call is cancelled on the MCP server if `cancelled` resolves first.
+async fn execute_tool_streaming(
+    state: &DaemonState,
+    send_progress: &mut (dyn FnMut(crate::daemon::protocol::ProgressUpdate) + Send),
+    server_name: &str,
+    tool_name: &str,
+    arguments: serde_json::Value,
+    timeout: Option<Duration>,
+    cancelled: impl std::future::Future<Output = ()> + Send,
+) -> crate::daemon::protocol::DaemonResponse {
+    tracing::info!("ExecuteTool: server={}, tool={}", server_name, tool_name);
+
+    let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut on_progress = move |update| {
+        let _ = progress_tx.send(update);
+    };
+    let execution = state.connection_pool.execute_cancellable(
+        server_name,
+        tool_name,
+        arguments,
+        timeout,
+        &mut on_progress,
+        cancelled,
+    );
+    tokio::pin!(execution);
+
+    let result = loop {
+        tokio::select! {
+            result = &mut execution => break result,
+            Some(update) = progress_rx.recv() => {
+                send_progress(update);
+                state.update_activity().await;
+            }
+        }
+    };
+    while let Ok(update) = progress_rx.try_recv() {
+        send_progress(update);
+    }
 
-    // Serialize config to JSON
-    let json =
-        serde_json::to_string(config).expect("Failed to serialize config for fingerprinting");
-    let mut hasher = Sha256::new();
-    hasher.update(json.as_bytes());
-    let result = hasher.finalize();
+    tool_response(result)
+}
 
-    // Convert to hex string
-    hex::encode(result)
+/// Convert a tool execution result into the daemon response
+fn tool_response(
+    result: crate::error::Result<serde_json::Value>,
+) -> crate::daemon::protocol::DaemonResponse {
+    match result {
+        Ok(result) => crate::daemon::protocol::DaemonResponse::ToolResult(result),
+        Err(e @ crate::error::McpError::Timeout { .. }) => {
+            tracing::error!("Tool execution failed: {}", e);
+            crate::daemon::protocol::DaemonResponse::Error {
+                code: crate::daemon::protocol::TIMEOUT_ERROR_CODE,
+                message: e.to_string(),
+            }
+        }
+        Err(e) => {
+            tracing::error!("Tool execution failed: {}", e);
+            crate::daemon::protocol::DaemonResponse::Error {
+                code: 3,
+                message: format!("Tool execution failed: {}", e),
+            }
+        }
+    }
 }
 
 /// Clean up socket file on daemon exit
@@ -319,7 +791,7 @@ mod tests {
     #[test]
     fn test_config_fingerprint() {
         let config = Config::default();
-        let fp = config_fingerprint(&config);
+        let fp = crate::config_fingerprint::config_fingerprint(&config);
         assert!(!fp.is_empty());
     }
 
@@ -327,16 +799,21 @@ mod tests {
     async fn test_handle_request_ping() {
         let lifecycle = DaemonLifecycle::new(30);
         let config = Config::default();
+        let config = SharedConfig::new(config);
         let state = DaemonState {
-            config: Arc::new(config),
-            config_fingerprint: String::new(),
+            config: config.clone(),
+            config_path: None,
             lifecycle: Arc::new(Mutex::new(lifecycle)),
-            connection_pool: Arc::new(crate::daemon::pool::ConnectionPool::new(Arc::new(
-                Config::default(),
-            ))),
+            connection_pool: Arc::new(crate::daemon::pool::ConnectionPool::new(config)),
         };
 
-        let response = handle_request(DaemonRequest::Ping, &state).await;
+        let response = handle_request(
+            DaemonRequest::Ping,
+            &state,
+            &mut |_| {},
+            std::future::pending(),
+        )
+        .await;
         assert!(matches!(response, DaemonResponse::Pong));
     }
 
@@ -344,16 +821,21 @@ mod tests {
     async fn test_handle_request_shutdown() {
         let lifecycle = DaemonLifecycle::new(30);
         let config = Config::default();
+        let config = SharedConfig::new(config);
         let state = DaemonState {
-            config: Arc::new(config),
-            config_fingerprint: String::new(),
+            config: config.clone(),
+            config_path: None,
             lifecycle: Arc::new(Mutex::new(lifecycle)),
-            connection_pool: Arc::new(crate::daemon::pool::ConnectionPool::new(Arc::new(
-                Config::default(),
-            ))),
+            connection_pool: Arc::new(crate::daemon::pool::ConnectionPool::new(config)),
         };
 
-        let response = handle_request(DaemonRequest::Shutdown, &state).await;
+        let response = handle_request(
+            DaemonRequest::Shutdown,
+            &state,
+            &mut |_| {},
+            std::future::pending(),
+        )
+        .await;
         assert!(matches!(response, DaemonResponse::ShutdownAck));
     }
 }
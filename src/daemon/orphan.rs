@@ -39,7 +39,7 @@ pub fn get_fingerprint_file_path(socket_path: &Path) -> PathBuf {
 /// Platform-specific implementation using native APIs.
 #[cfg(unix)]
 pub fn is_daemon_running(pid: u32) -> bool {
-    use nix::sys::signal::{Signal, kill};
+    use nix::sys::signal::kill;
     use nix::unistd::Pid;
 
     // Send signal 0 to check if process exists
@@ -233,8 +233,9 @@ async fn try_connect_via_ipc(daemon_config: &Config, socket_path: &Path) -> Resu
     )
     .await
     {
-        Ok(Ok(_)) => {
-            // Successfully connected and received response
+        Ok(Ok(_)) | Ok(Err(crate::error::McpError::IpcVersionMismatch { .. })) => {
+            // Successfully connected and received response, if only to say
+            // the daemon speaks another protocol version
             Ok(())
         }
         Ok(Err(_)) | Err(_) => {
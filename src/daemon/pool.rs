@@ -2,17 +2,58 @@
 //!
 //! This module provides a thread-safe connection pool that caches transport connections
 //! for MCP servers, ensuring connections are reused across multiple requests.
+//! Its config can be reloaded while it runs; see [`ConnectionPool::reload`].
+//!
+//! Each server gets up to `max_connections_per_server` connections, so
+//! concurrent requests to one server run side by side. Requests beyond the
+//! limit wait for a connection to be returned and are served first come,
+//! first served.
 
 use async_trait::async_trait;
+use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 
+use crate::client::session::DEFAULT_REQUEST_TIMEOUT;
 use crate::config::Config;
-use crate::daemon::protocol::ToolInfo;
+use crate::daemon::protocol::{
+    ProgressUpdate, PromptInfo, PromptResult, ResourceContents, ResourceInfo, ResourceTemplateInfo,
+    ToolInfo, ToolPage, list_page_params, parse_result_list, parse_tool_list, progress_token,
+    tool_call_params,
+};
+use crate::daemon::reload::{ConfigChanges, SharedConfig, server_changed};
 use crate::error::McpError;
 use crate::error::Result;
-use crate::transport::{BoxedTransport, Transport};
+use crate::transport::{
+    BoxedTransport, NotificationCallback, Transport, request_all_pages, send_initialize,
+};
+
+/// What a server reported in its `initialize` response
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InitializeResult {
+    /// Protocol version the server chose
+    pub protocol_version: Option<String>,
+    /// Capabilities the server offers, e.g. `tools` or `resources`
+    pub capabilities: serde_json::Value,
+    /// Name and version of the server implementation
+    pub server_info: serde_json::Value,
+}
+
+impl InitializeResult {
+    /// Parse the `result` of an `initialize` response
+    pub fn from_result(result: &serde_json::Value) -> Self {
+        Self {
+            protocol_version: result
+                .get("protocolVersion")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            capabilities: result.get("capabilities").cloned().unwrap_or_default(),
+            server_info: result.get("serverInfo").cloned().unwrap_or_default(),
+        }
+    }
+}
 
 /// Represents a pooled MCP server connection with metadata for tracking.
 pub struct PooledConnection {
@@ -21,6 +62,12 @@ pub struct PooledConnection {
     pub created_at: Instant,
     pub last_used: Instant,
     pub health_check_failures: u32,
+    /// Config the connection was made with
+    pub config: Arc<Config>,
+    /// Handshake result; None until the connection has been initialized
+    pub initialized: Option<InitializeResult>,
+    /// Counts the connection against its server's limit while it is in use
+    permit: Option<OwnedSemaphorePermit>,
 }
 
 impl PooledConnection {
@@ -31,97 +78,304 @@ impl PooledConnection {
     fn touch(&mut self) {
         self.last_used = Instant::now();
     }
+
+    /// Keep the connection from being reused
+    fn mark_broken(&mut self) {
+        self.health_check_failures = MAX_HEALTH_FAILURES;
+    }
+
+    /// Whether the connection still fits `config`, adopting it if so
+    fn refresh(&mut self, config: &Arc<Config>) -> bool {
+        if Arc::ptr_eq(&self.config, config) {
+            return true;
+        }
+        if server_changed(&self.config, config, &self.server_name) {
+            return false;
+        }
+        self.config = Arc::clone(config);
+        true
+    }
 }
 
 const MAX_HEALTH_FAILURES: u32 = 3;
 
+/// Whether a connection error shows the request never reached the server
+///
+/// Only then is it safe to send the request again on a new connection:
+/// a write to a dead process fails, and an HTTP server that forgot the
+/// session rejects the request before running it.
+fn request_not_delivered(source: &std::io::Error) -> bool {
+    matches!(
+        source.kind(),
+        std::io::ErrorKind::BrokenPipe | std::io::ErrorKind::NotConnected
+    )
+}
+
+/// Error for a tool call cancelled by the client
+fn tool_call_cancelled() -> McpError {
+    McpError::io_error(std::io::Error::new(
+        std::io::ErrorKind::Interrupted,
+        "Tool call cancelled",
+    ))
+}
+
+/// Connections to one server
+struct ServerConnections {
+    /// Connections not in use, most recently returned last
+    idle: Vec<PooledConnection>,
+    /// One permit per connection the server may have; queues waiters fairly
+    permits: Arc<Semaphore>,
+    /// Number of connections the server may have; `permits` is sized for it
+    limit: usize,
+    /// Permits still to be removed from `permits` once they are returned,
+    /// after the limit was lowered while connections were in use
+    owed: usize,
+}
+
+impl ServerConnections {
+    fn new(limit: usize) -> Self {
+        Self {
+            idle: Vec::new(),
+            permits: Arc::new(Semaphore::new(limit)),
+            limit,
+            owed: 0,
+        }
+    }
+
+    /// Resize `permits` for a new limit
+    ///
+    /// Permits in use can't be taken back, so a lowered limit removes what
+    /// it can now and owes the rest until those permits are returned.
+    fn set_limit(&mut self, limit: usize) {
+        if limit > self.limit {
+            let added = limit - self.limit;
+            let repaid = added.min(self.owed);
+            self.owed -= repaid;
+            self.permits.add_permits(added - repaid);
+        } else {
+            let removed = self.limit - limit;
+            self.owed += removed - self.permits.forget_permits(removed);
+        }
+        self.limit = limit;
+    }
+
+    /// Keep a returned permit from the semaphore if the limit still owes one
+    fn repay(&mut self, permit: OwnedSemaphorePermit) -> Option<OwnedSemaphorePermit> {
+        if self.owed == 0 {
+            return Some(permit);
+        }
+        self.owed -= 1;
+        permit.forget();
+        None
+    }
+}
+
 /// Connection pool that caches transport connections by server name.
 #[derive(Clone)]
 pub struct ConnectionPool {
-    connections: Arc<Mutex<HashMap<String, PooledConnection>>>,
-    config: Arc<Config>,
+    connections: Arc<Mutex<HashMap<String, ServerConnections>>>,
+    config: SharedConfig,
 }
 
 impl ConnectionPool {
-    pub fn new(config: Arc<Config>) -> Self {
+    /// Create a pool; pass a [`SharedConfig`] to reload it from elsewhere
+    pub fn new(config: impl Into<SharedConfig>) -> Self {
         ConnectionPool {
             connections: Arc::new(Mutex::new(HashMap::new())),
-            config,
+            config: config.into(),
         }
     }
 
-    /// Take a connection from the pool for use
-    pub async fn take(&self, server_name: &str) -> Result<Option<PooledConnection>> {
-        tracing::debug!("take() called for server: {}", server_name);
+    /// The config in effect now
+    pub fn config(&self) -> Arc<Config> {
+        self.config.get()
+    }
+
+    /// Swap in a new config, closing pooled connections to servers that
+    /// were removed or changed
+    ///
+    /// Connections in use keep running with the old config and are closed
+    /// when returned. Connections to unchanged servers stay open.
+    pub fn reload(&self, config: Config) -> ConfigChanges {
+        let old = self.config.replace(config);
+        let new = self.config.get();
+        let changes = ConfigChanges::between(&old, &new);
+
         let mut connections = self
             .connections
             .lock()
             .expect("Failed to acquire connection pool lock");
-        tracing::debug!("Got lock, checking for existing connection");
-
-        if let Some(mut conn) = connections.remove(server_name) {
-            tracing::debug!("Found existing connection for: {}", server_name);
-            if conn.is_healthy() {
-                conn.touch();
-                tracing::debug!("Connection is healthy, returning it");
-                return Ok(Some(conn));
-            }
-            tracing::debug!("Connection is unhealthy, discarding");
+        connections.retain(|name, server| {
+            server.idle.retain_mut(|conn| conn.refresh(&new));
+            new.get_server(name).is_some()
+        });
+        changes
+    }
+
+    /// Take a connection from the pool for use
+    ///
+    /// Reuses an idle connection or opens a new one. When the server already
+    /// has `max_connections_per_server` connections in use, waits until one
+    /// is returned; waiting requests are served in the order they arrived.
+    pub async fn take(&self, server_name: &str) -> Result<Option<PooledConnection>> {
+        tracing::debug!("take() called for server: {}", server_name);
+        let config = self.config.get();
+        if config.get_server(server_name).is_none() {
+            return Err(self.server_not_found(server_name));
         }
 
-        tracing::debug!(
-            "No existing connection, creating new one for: {}",
-            server_name
-        );
-        drop(connections); // Release lock before creating transport
+        let permit = self
+            .acquire(server_name, config.max_connections_per_server)
+            .await;
+
+        if let Some(mut conn) = self.take_idle(server_name) {
+            tracing::debug!("Reusing idle connection to: {}", server_name);
+            conn.touch();
+            conn.permit = Some(permit);
+            return Ok(Some(conn));
+        }
 
-        let transport = self.create_transport(server_name)?;
+        tracing::debug!("No idle connection, creating new one for: {}", server_name);
+        let transport = Self::create_transport(&config, server_name)?;
         Ok(Some(PooledConnection {
             transport,
             server_name: server_name.to_string(),
             created_at: Instant::now(),
             last_used: Instant::now(),
             health_check_failures: 0,
+            config,
+            initialized: None,
+            permit: Some(permit),
         }))
     }
 
+    /// Wait for a place among the connections to `server_name`
+    ///
+    /// A permit released by a connection that was dropped rather than put
+    /// back may be owed to a lowered limit; such permits are removed and
+    /// the wait goes on.
+    async fn acquire(&self, server_name: &str, limit: usize) -> OwnedSemaphorePermit {
+        loop {
+            let permits = self.permits(server_name, limit);
+            if permits.available_permits() == 0 {
+                tracing::debug!("All connections to '{}' are busy, waiting", server_name);
+            }
+            let permit = permits
+                .acquire_owned()
+                .await
+                .expect("Connection semaphore is never closed");
+
+            let mut connections = self
+                .connections
+                .lock()
+                .expect("Failed to acquire connection pool lock");
+            match connections.get_mut(server_name) {
+                Some(server) => {
+                    if let Some(permit) = server.repay(permit) {
+                        return permit;
+                    }
+                }
+                None => return permit,
+            }
+        }
+    }
+
+    /// The semaphore limiting connections to `server_name` to `limit`
+    ///
+    /// A changed limit resizes the semaphore, so connections in use keep
+    /// counting against it.
+    fn permits(&self, server_name: &str, limit: usize) -> Arc<Semaphore> {
+        let mut connections = self
+            .connections
+            .lock()
+            .expect("Failed to acquire connection pool lock");
+        let server = connections
+            .entry(server_name.to_string())
+            .or_insert_with(|| ServerConnections::new(limit));
+        if server.limit != limit {
+            tracing::debug!(
+                "Connection limit for '{}' changed from {} to {}",
+                server_name,
+                server.limit,
+                limit
+            );
+            server.set_limit(limit);
+        }
+        Arc::clone(&server.permits)
+    }
+
+    /// Pop the most recently returned usable idle connection to `server_name`
+    ///
+    /// Unhealthy or closed connections found on the way are discarded.
+    fn take_idle(&self, server_name: &str) -> Option<PooledConnection> {
+        let mut connections = self
+            .connections
+            .lock()
+            .expect("Failed to acquire connection pool lock");
+        let server = connections.get_mut(server_name)?;
+        while let Some(conn) = server.idle.pop() {
+            if conn.is_healthy() && !conn.transport.is_closed() {
+                return Some(conn);
+            }
+            tracing::debug!(
+                "Discarding unhealthy or closed connection to: {}",
+                server_name
+            );
+        }
+        None
+    }
+
     /// Return a connection to the pool
-    pub fn put_back(&self, conn: PooledConnection) {
+    ///
+    /// A connection whose server changed in a reload since it was taken is
+    /// closed instead, as is one beyond a lowered connection limit. Either
+    /// way its place goes to the next waiting request, unless the lowered
+    /// limit removes it.
+    pub fn put_back(&self, mut conn: PooledConnection) {
         let server_name = conn.server_name.clone();
+        let permit = conn.permit.take();
         let mut connections = self
             .connections
             .lock()
             .expect("Failed to acquire connection pool lock");
-        connections.insert(server_name, conn);
+        if !conn.refresh(&self.config.get()) {
+            tracing::debug!("Closing connection to reconfigured server: {}", server_name);
+            return;
+        }
+        let Some(server) = connections.get_mut(&server_name) else {
+            tracing::debug!("Closing connection to removed server: {}", server_name);
+            return;
+        };
+        // A permit owed to a lowered limit goes away rather than waking a waiter
+        let permit = permit.and_then(|permit| server.repay(permit));
+        if server.idle.len() >= server.limit {
+            tracing::debug!("Closing connection beyond the limit for: {}", server_name);
+            return;
+        }
+        server.idle.push(conn);
+        // Release the permit only now, so a woken waiter finds the connection
+        drop(connections);
+        drop(permit);
         tracing::debug!("Returned connection to pool");
     }
 
-    /// Initialize MCP protocol on a connection (shared between execute and list_tools)
-    async fn initialize_mcp_connection(transport: &mut BoxedTransport) -> Result<()> {
-        let init_request = serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 0,
-            "method": "initialize",
-            "params": {
-                "protocolVersion": "2024-11-05",
-                "capabilities": {
-                    "roots": {},
-                    "sampling": {},
-                    "tools": {}
-                },
-                "clientInfo": {
-                    "name": "mcp-cli-rs",
-                    "version": env!("CARGO_PKG_VERSION")
-                }
-            }
-        });
-
-        transport
-            .send(init_request)
-            .await
-            .map_err(|e| McpError::InvalidProtocol {
-                message: format!("Initialize request failed: {}", e),
-            })?;
+    /// Send `initialize` and `notifications/initialized` on a new connection
+    async fn initialize_mcp_connection(transport: &mut BoxedTransport) -> Result<InitializeResult> {
+        let response =
+            send_initialize(transport, 0)
+                .await
+                .map_err(|e| McpError::InvalidProtocol {
+                    message: format!("Initialize request failed: {}", e),
+                })?;
+        let Some(result) = response.get("result") else {
+            let msg = response
+                .pointer("/error/message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("Invalid response");
+            return Err(McpError::InvalidProtocol {
+                message: format!("Initialize failed: {}", msg),
+            });
+        };
 
         let initialized_notification = serde_json::json!({
             "jsonrpc": "2.0",
@@ -135,7 +389,55 @@ impl ConnectionPool {
                 message: format!("Failed to send initialized notification: {}", e),
             })?;
 
-        Ok(())
+        Ok(InitializeResult::from_result(result))
+    }
+
+    /// Take a connection to `server_name`, initializing it if it is new
+    ///
+    /// Each connection is initialized once, before its first request. Also
+    /// returns whether the connection was reused from the pool.
+    async fn take_initialized(&self, server_name: &str) -> Result<(PooledConnection, bool)> {
+        let mut conn = match self.take(server_name).await? {
+            Some(c) => c,
+            None => return Err(self.server_not_found(server_name)),
+        };
+        let reused = conn.initialized.is_some();
+        if !reused {
+            tracing::debug!("Initializing new connection to: {}", server_name);
+            conn.initialized = Some(Self::initialize_mcp_connection(&mut conn.transport).await?);
+        }
+        Ok((conn, reused))
+    }
+
+    /// Return a connection to the pool after a request ended with `result`
+    ///
+    /// Connections that lost their server or were marked broken are closed
+    /// instead. Returns true if the request should be sent again on a new
+    /// connection: `may_retry` is set and the connection died before the
+    /// request reached the server.
+    fn release<T>(&self, conn: PooledConnection, may_retry: bool, result: &Result<T>) -> bool {
+        match result {
+            Err(McpError::ConnectionError { source, .. }) => {
+                let retry = may_retry && request_not_delivered(source);
+                if retry {
+                    tracing::info!(
+                        "Connection to '{}' was lost, reconnecting",
+                        conn.server_name
+                    );
+                } else {
+                    tracing::debug!("Discarding connection to: {}", conn.server_name);
+                }
+                retry
+            }
+            _ if conn.is_healthy() => {
+                self.put_back(conn);
+                false
+            }
+            _ => {
+                tracing::debug!("Discarding broken connection to: {}", conn.server_name);
+                false
+            }
+        }
     }
 
     /// Execute a tool using cached or new connection
@@ -144,37 +446,142 @@ impl ConnectionPool {
         server_name: &str,
         tool_name: &str,
         arguments: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        self.execute_with_progress(server_name, tool_name, arguments, &mut |_| {})
+            .await
+    }
+
+    /// Execute a tool, reporting the server's progress notifications
+    ///
+    /// The request carries a progress token; each `notifications/progress`
+    /// for it is passed to `on_progress` while the call runs.
+    pub async fn execute_with_progress(
+        &self,
+        server_name: &str,
+        tool_name: &str,
+        arguments: serde_json::Value,
+        on_progress: &mut (dyn FnMut(ProgressUpdate) + Send),
+    ) -> Result<serde_json::Value> {
+        self.execute_cancellable(
+            server_name,
+            tool_name,
+            arguments,
+            None,
+            on_progress,
+            std::future::pending(),
+        )
+        .await
+    }
+
+    /// Execute a tool, reporting progress, until `cancelled` resolves
+    ///
+    /// The call fails with `McpError::Timeout` after `timeout`, or the
+    /// server's configured timeout if `None`. The time spent waiting for a
+    /// connection counts against it, and cancellation ends that wait too. On
+    /// timeout or cancellation of a sent call the server is sent
+    /// `notifications/cancelled` for it and the connection is returned to
+    /// the pool for reuse.
+    pub async fn execute_cancellable(
+        &self,
+        server_name: &str,
+        tool_name: &str,
+        arguments: serde_json::Value,
+        timeout: Option<Duration>,
+        on_progress: &mut (dyn FnMut(ProgressUpdate) + Send),
+        cancelled: impl std::future::Future<Output = ()> + Send,
     ) -> Result<serde_json::Value> {
         tracing::debug!(
             "execute() called for server: {}, tool: {}",
             server_name,
             tool_name
         );
-        let mut conn = match self.take(server_name).await? {
-            Some(c) => c,
-            None => {
-                let servers: Vec<String> = self.config.servers.iter().map(|s| s.name.clone()).collect();
-                return Err(McpError::ServerNotFound {
-                    server: server_name.to_string(),
-                    servers,
-                });
-            }
-        };
-        tracing::debug!("Got connection, initializing MCP connection");
+        tokio::pin!(cancelled);
 
-        Self::initialize_mcp_connection(&mut conn.transport).await?;
-
-        tracing::debug!("MCP connection initialized, sending tools/call request");
+        let timeout = timeout.unwrap_or_else(|| {
+            Duration::from_secs(self.config.get().server_timeout_secs(server_name))
+        });
+        let deadline = tokio::time::Instant::now() + timeout;
+        let token = progress_token();
+        let params = tool_call_params(tool_name, arguments, &token);
+        let mut may_retry = true;
+        loop {
+            // Waiting behind max_connections_per_server is bounded the same way
+            let taken = tokio::select! {
+                taken = self.take_initialized(server_name) => taken,
+                _ = tokio::time::sleep_until(deadline) => {
+                    tracing::info!("Timed out waiting for a connection to '{}'", server_name);
+                    return Err(McpError::timeout(timeout));
+                }
+                _ = cancelled.as_mut() => {
+                    tracing::info!("Cancelled while waiting for a connection to '{}'", server_name);
+                    return Err(tool_call_cancelled());
+                }
+            };
+            let (mut conn, reused) = taken?;
+            let result = Self::send_tool_call(
+                &mut conn,
+                tool_name,
+                params.clone(),
+                &token,
+                deadline,
+                on_progress,
+                cancelled.as_mut(),
+            )
+            .await;
+            if self.release(conn, reused && may_retry, &result) {
+                may_retry = false;
+                continue;
+            }
+            // Report the whole timeout, not what was left of it for the call
+            return result.map_err(|e| match e {
+                McpError::Timeout { .. } => McpError::timeout(timeout),
+                e => e,
+            });
+        }
+    }
 
+    /// Send a `tools/call` request on `conn` and wait for its result
+    ///
+    /// See [`Self::execute_cancellable`]; the call must finish by `deadline`.
+    /// A connection whose call couldn't be cancelled on the server is marked
+    /// broken.
+    async fn send_tool_call(
+        conn: &mut PooledConnection,
+        tool_name: &str,
+        params: serde_json::Value,
+        token: &serde_json::Value,
+        deadline: tokio::time::Instant,
+        on_progress: &mut (dyn FnMut(ProgressUpdate) + Send),
+        cancelled: std::pin::Pin<&mut (impl std::future::Future<Output = ()> + Send)>,
+    ) -> Result<serde_json::Value> {
+        let server_name = conn.server_name.clone();
+        tracing::debug!("Sending tools/call request");
         let mcp_request = serde_json::json!({
             "jsonrpc": "2.0",
             "id": 1,
             "method": "tools/call",
-            "params": { "name": tool_name, "arguments": arguments }
+            "params": params
         });
 
-        let result = match conn.transport.send(mcp_request).await {
-            Ok(response) => {
+        let mut on_notification = |notification: serde_json::Value| {
+            if let Some(update) = ProgressUpdate::from_notification(&notification, token) {
+                on_progress(update);
+            }
+        };
+        // Only tool calls get the long per-server timeout; other requests on
+        // this connection keep the transport's default
+        conn.transport
+            .set_request_timeout(deadline.saturating_duration_since(tokio::time::Instant::now()));
+        let outcome = tokio::select! {
+            response = conn.transport.send_with_notifications(mcp_request, &mut on_notification) => {
+                Some(response)
+            }
+            _ = cancelled => None,
+        };
+        conn.transport.set_request_timeout(DEFAULT_REQUEST_TIMEOUT);
+
+        match outcome {
+            Some(Ok(response)) => {
                 if let Some(result) = response.get("result") {
                     Ok(result.clone())
                 } else if let Some(error) = response.get("error") {
@@ -191,74 +598,112 @@ impl ConnectionPool {
                     })
                 }
             }
-            Err(e) => Err(McpError::InvalidProtocol {
+            Some(Err(e @ McpError::Timeout { .. })) => {
+                tracing::info!(
+                    "Tool call timed out: server={}, tool={}",
+                    server_name,
+                    tool_name
+                );
+                if let Err(cancel_error) =
+                    conn.transport.cancel_abandoned("Request timed out").await
+                {
+                    tracing::warn!(
+                        "Failed to cancel tool call on '{}': {}",
+                        server_name,
+                        cancel_error
+                    );
+                    conn.mark_broken();
+                }
+                Err(e)
+            }
+            Some(Err(e @ McpError::ConnectionError { .. })) => Err(e),
+            Some(Err(e)) => Err(McpError::InvalidProtocol {
                 message: format!("Transport error: {}", e),
             }),
-        };
-
-        self.put_back(conn);
-        result
+            None => {
+                tracing::info!(
+                    "Cancelling tool call: server={}, tool={}",
+                    server_name,
+                    tool_name
+                );
+                if let Err(e) = conn.transport.cancel_abandoned("Cancelled by client").await {
+                    // The connection is broken; let the next request open a new one
+                    tracing::warn!("Failed to cancel tool call on '{}': {}", server_name, e);
+                    conn.mark_broken();
+                }
+                Err(tool_call_cancelled())
+            }
+        }
     }
 
-    /// List tools using cached or new connection
+    /// List tools using cached or new connection, following pagination cursors
     pub async fn list_tools(&self, server_name: &str) -> Result<Vec<ToolInfo>> {
         tracing::debug!("list_tools() called for server: {}", server_name);
-        let mut conn = match self.take(server_name).await? {
-            Some(c) => c,
-            None => {
-                let servers: Vec<String> = self.config.servers.iter().map(|s| s.name.clone()).collect();
-                return Err(McpError::ServerNotFound {
-                    server: server_name.to_string(),
-                    servers,
-                });
-            }
-        };
-        tracing::debug!("Got connection, initializing MCP connection");
+        let result = self.request_all(server_name, "tools/list", "tools").await?;
+        Ok(parse_tool_list(&result))
+    }
 
-        Self::initialize_mcp_connection(&mut conn.transport).await?;
+    /// List a single page of tools, continuing from the server's `cursor`
+    pub async fn list_tools_page(
+        &self,
+        server_name: &str,
+        cursor: Option<&str>,
+    ) -> Result<ToolPage> {
+        tracing::debug!("list_tools_page() called for server: {}", server_name);
+        let result = self
+            .request(server_name, "tools/list", list_page_params(cursor))
+            .await?;
+        Ok(ToolPage::from_result(&result))
+    }
 
-        tracing::debug!("MCP connection initialized, sending tools/list request");
+    /// Send an MCP request using cached or new connection and return its `result`
+    async fn request(
+        &self,
+        server_name: &str,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        tracing::debug!(
+            "request() called for server: {}, method: {}",
+            server_name,
+            method
+        );
+        let mut may_retry = true;
+        loop {
+            let (mut conn, reused) = self.take_initialized(server_name).await?;
+            let result = Self::send_request(&mut conn, method, params.clone()).await;
+            if self.release(conn, reused && may_retry, &result) {
+                may_retry = false;
+                continue;
+            }
+            return result;
+        }
+    }
 
+    /// Send an MCP request on `conn` and return its `result`
+    async fn send_request(
+        conn: &mut PooledConnection,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value> {
         let mcp_request = serde_json::json!({
             "jsonrpc": "2.0",
             "id": 1,
-            "method": "tools/list"
+            "method": method,
+            "params": params
         });
 
-        let result = match conn.transport.send(mcp_request).await {
+        match conn.transport.send(mcp_request).await {
             Ok(response) => {
-                tracing::debug!("Got response: {:?}", response);
                 if let Some(result) = response.get("result") {
-                    let tools: Vec<ToolInfo> = if let Some(tools_array) =
-                        result.get("tools").and_then(|t| t.as_array())
-                    {
-                        tools_array
-                            .iter()
-                            .filter_map(|tool| {
-                                let name = tool.get("name")?.as_str()?.to_string();
-                                let description = tool.get("description")?.as_str()?.to_string();
-                                let input_schema =
-                                    tool.get("inputSchema").cloned().unwrap_or_else(|| {
-                                        serde_json::Value::Object(serde_json::Map::new())
-                                    });
-                                Some(ToolInfo {
-                                    name,
-                                    description,
-                                    input_schema,
-                                })
-                            })
-                            .collect()
-                    } else {
-                        Vec::new()
-                    };
-                    Ok(tools)
+                    Ok(result.clone())
                 } else if let Some(error) = response.get("error") {
                     let msg = error
                         .get("message")
                         .and_then(|m| m.as_str())
                         .unwrap_or("Unknown");
                     Err(McpError::InvalidProtocol {
-                        message: format!("List error: {}", msg),
+                        message: format!("{} error: {}", method, msg),
                     })
                 } else {
                     Err(McpError::InvalidProtocol {
@@ -266,24 +711,122 @@ impl ConnectionPool {
                     })
                 }
             }
+            Err(e @ McpError::ConnectionError { .. }) => Err(e),
             Err(e) => Err(McpError::InvalidProtocol {
                 message: format!("Transport error: {}", e),
             }),
-        };
+        }
+    }
+
+    /// Send a paginated MCP list request and return the merged result
+    ///
+    /// See [`request_all_pages`] for how cursors are followed.
+    async fn request_all(
+        &self,
+        server_name: &str,
+        method: &str,
+        key: &str,
+    ) -> Result<serde_json::Value> {
+        tracing::debug!(
+            "request_all() called for server: {}, method: {}",
+            server_name,
+            method
+        );
+        let mut may_retry = true;
+        loop {
+            let (mut conn, reused) = self.take_initialized(server_name).await?;
+            let result = request_all_pages(&mut conn.transport, method, key, 1).await;
+            if self.release(conn, reused && may_retry, &result) {
+                may_retry = false;
+                continue;
+            }
+            return result;
+        }
+    }
 
-        self.put_back(conn);
-        result
+    /// List resources using cached or new connection, following pagination cursors
+    pub async fn list_resources(&self, server_name: &str) -> Result<Vec<ResourceInfo>> {
+        let result = self
+            .request_all(server_name, "resources/list", "resources")
+            .await?;
+        parse_result_list(&result, "resources")
     }
 
-    fn create_transport(&self, server_name: &str) -> Result<BoxedTransport> {
-        tracing::debug!("Creating transport for server: {}", server_name);
-        let server_config = self
+    /// List resource templates using cached or new connection, following pagination cursors
+    pub async fn list_resource_templates(
+        &self,
+        server_name: &str,
+    ) -> Result<Vec<ResourceTemplateInfo>> {
+        let result = self
+            .request_all(server_name, "resources/templates/list", "resourceTemplates")
+            .await?;
+        parse_result_list(&result, "resourceTemplates")
+    }
+
+    /// Read a resource using cached or new connection
+    pub async fn read_resource(
+        &self,
+        server_name: &str,
+        uri: &str,
+    ) -> Result<Vec<ResourceContents>> {
+        let result = self
+            .request(
+                server_name,
+                "resources/read",
+                serde_json::json!({ "uri": uri }),
+            )
+            .await?;
+        parse_result_list(&result, "contents")
+    }
+
+    /// List prompts using cached or new connection, following pagination cursors
+    pub async fn list_prompts(&self, server_name: &str) -> Result<Vec<PromptInfo>> {
+        let result = self
+            .request_all(server_name, "prompts/list", "prompts")
+            .await?;
+        parse_result_list(&result, "prompts")
+    }
+
+    /// Render a prompt using cached or new connection
+    pub async fn get_prompt(
+        &self,
+        server_name: &str,
+        prompt_name: &str,
+        arguments: &HashMap<String, String>,
+    ) -> Result<PromptResult> {
+        let result = self
+            .request(
+                server_name,
+                "prompts/get",
+                serde_json::json!({ "name": prompt_name, "arguments": arguments }),
+            )
+            .await?;
+        PromptResult::from_result(&result)
+    }
+
+    /// Error for a server missing from the current config
+    fn server_not_found(&self, server_name: &str) -> McpError {
+        let servers: Vec<String> = self
             .config
+            .get()
+            .servers
+            .iter()
+            .map(|s| s.name.clone())
+            .collect();
+        McpError::ServerNotFound {
+            server: server_name.to_string(),
+            servers,
+        }
+    }
+
+    fn create_transport(config: &Config, server_name: &str) -> Result<BoxedTransport> {
+        tracing::debug!("Creating transport for server: {}", server_name);
+        let server_config = config
             .servers
             .iter()
             .find(|s| s.name.as_str() == server_name)
             .ok_or_else(|| {
-                let servers: Vec<String> = self.config.servers.iter().map(|s| s.name.clone()).collect();
+                let servers: Vec<String> = config.servers.iter().map(|s| s.name.clone()).collect();
                 McpError::ServerNotFound {
                     server: server_name.to_string(),
                     servers,
@@ -302,19 +845,25 @@ impl ConnectionPool {
         })
     }
 
+    /// Close all idle connections
     pub fn clear(&self) {
         let mut connections = self
             .connections
             .lock()
             .expect("Failed to acquire connection pool lock");
-        connections.clear();
+        for server in connections.values_mut() {
+            server.idle.clear();
+        }
     }
 
+    /// Number of idle connections, across all servers
     pub fn count(&self) -> usize {
         self.connections
             .lock()
             .expect("Failed to acquire connection pool lock")
-            .len()
+            .values()
+            .map(|server| server.idle.len())
+            .sum()
     }
 }
 
@@ -330,13 +879,9 @@ pub trait ConnectionPoolInterface: Send + Sync {
 impl ConnectionPoolInterface for ConnectionPool {
     async fn get(&self, server_name: &str) -> Result<Box<dyn Transport + Send + Sync>> {
         if let Some(conn) = self.take(server_name).await? {
-            Ok(conn.transport)
+            Ok(Box::new(HeldConnection(conn)))
         } else {
-            let servers: Vec<String> = self.config.servers.iter().map(|s| s.name.clone()).collect();
-            Err(McpError::ServerNotFound {
-                server: server_name.to_string(),
-                servers,
-            })
+            Err(self.server_not_found(server_name))
         }
     }
 
@@ -345,7 +890,9 @@ impl ConnectionPoolInterface for ConnectionPool {
             .connections
             .lock()
             .expect("Failed to acquire connection pool lock");
-        connections.remove(server_name);
+        if let Some(server) = connections.get_mut(server_name) {
+            server.idle.clear();
+        }
     }
 
     fn clear(&self) {
@@ -357,6 +904,62 @@ impl ConnectionPoolInterface for ConnectionPool {
     }
 }
 
+/// A connection handed out by [`ConnectionPoolInterface::get`]
+///
+/// Holds the connection's permit until the transport is dropped, so the
+/// connection keeps counting against its server's limit while in use.
+struct HeldConnection(PooledConnection);
+
+#[async_trait::async_trait]
+impl Transport for HeldConnection {
+    async fn send(&mut self, request: Value) -> Result<Value> {
+        self.0.transport.send(request).await
+    }
+
+    async fn send_notification(&mut self, notification: Value) -> Result<()> {
+        self.0.transport.send_notification(notification).await
+    }
+
+    async fn receive_notification(&mut self) -> Result<Value> {
+        self.0.transport.receive_notification().await
+    }
+
+    async fn ping(&self) -> Result<()> {
+        self.0.transport.ping().await
+    }
+
+    fn transport_type(&self) -> &str {
+        self.0.transport.transport_type()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.0.transport.is_closed()
+    }
+
+    async fn send_with_notifications(
+        &mut self,
+        request: Value,
+        on_notification: NotificationCallback<'_>,
+    ) -> Result<Value> {
+        self.0
+            .transport
+            .send_with_notifications(request, on_notification)
+            .await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.0.transport.close().await
+    }
+
+    async fn cancel_abandoned(&mut self, reason: &str) -> Result<()> {
+        self.0.transport.cancel_abandoned(reason).await
+    }
+
+    fn set_request_timeout(&mut self, timeout: Duration) {
+        self.0.transport.set_request_timeout(timeout);
+    }
+}
+
 pub struct DummyConnectionPool {
     count: usize,
 }
@@ -422,4 +1025,86 @@ mod tests {
         let pool = ConnectionPool::new(config);
         assert_eq!(pool.count(), 0);
     }
+
+    /// Take every available permit of `server`
+    fn take_all(server: &ServerConnections) -> Vec<OwnedSemaphorePermit> {
+        std::iter::from_fn(|| Arc::clone(&server.permits).try_acquire_owned().ok()).collect()
+    }
+
+    #[test]
+    fn test_lowered_limit_is_repaid_by_permits_in_use() {
+        let mut server = ServerConnections::new(2);
+        let mut in_use = take_all(&server);
+        assert_eq!(in_use.len(), 2);
+
+        // Nothing is free to remove, so both permits in use stay counted
+        server.set_limit(1);
+        assert_eq!(server.owed, 1);
+        assert_eq!(server.permits.available_permits(), 0);
+
+        // The first one returned pays the debt instead of being released
+        assert!(server.repay(in_use.pop().unwrap()).is_none());
+        assert_eq!(server.owed, 0);
+        assert_eq!(server.permits.available_permits(), 0);
+
+        let permit = server.repay(in_use.pop().unwrap());
+        assert!(permit.is_some());
+        drop(permit);
+        assert_eq!(server.permits.available_permits(), 1);
+    }
+
+    #[test]
+    fn test_lowered_limit_removes_free_permits_first() {
+        let mut server = ServerConnections::new(3);
+        let _in_use = Arc::clone(&server.permits).try_acquire_owned().unwrap();
+
+        server.set_limit(1);
+        assert_eq!(server.owed, 0);
+        assert_eq!(server.permits.available_permits(), 0);
+    }
+
+    #[test]
+    fn test_raised_limit_cancels_debt_before_adding_permits() {
+        let mut server = ServerConnections::new(2);
+        let mut in_use = take_all(&server);
+        server.set_limit(1);
+        assert_eq!(server.owed, 1);
+
+        // One of the two new places covers the debt, the other is free now
+        server.set_limit(3);
+        assert_eq!(server.owed, 0);
+        assert_eq!(server.permits.available_permits(), 1);
+
+        // Returned permits are released again
+        let permit = server.repay(in_use.pop().unwrap());
+        assert!(permit.is_some());
+        drop(permit);
+        drop(in_use);
+        assert_eq!(server.permits.available_permits(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_waiters_are_woken_in_arrival_order() {
+        let pool = ConnectionPool::new(Config::default());
+        let held = pool.acquire("mock", 1).await;
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut waiters = Vec::new();
+        for i in 0..3 {
+            let pool = pool.clone();
+            let order = Arc::clone(&order);
+            waiters.push(tokio::spawn(async move {
+                let _permit = pool.acquire("mock", 1).await;
+                order.lock().unwrap().push(i);
+            }));
+            // Let the waiter queue up before the next one arrives
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        drop(held);
+        for waiter in waiters {
+            waiter.await.unwrap();
+        }
+        assert_eq!(*order.lock().unwrap(), vec![0, 1, 2]);
+    }
 }
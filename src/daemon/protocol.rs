@@ -1,5 +1,7 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
 use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
 
 /// Request types sent from CLI to daemon
@@ -10,40 +12,181 @@ pub enum DaemonRequest {
     Ping,
     /// Get config file fingerprint for cache validation
     GetConfigFingerprint,
+    /// Reread the config file now; answered with the new fingerprint
+    ReloadConfig,
+    /// Get the absolute path of the config file the daemon reads
+    GetConfigPath,
     /// Execute a tool on a specific server
     ExecuteTool {
         server_name: String,
         tool_name: String,
         arguments: serde_json::Value,
+        /// Seconds to wait for the result; the server's configured timeout if absent
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_secs: Option<u64>,
     },
     /// List available tools on a specific server
     ListTools { server_name: String },
+    /// List a single page of a server's tools
+    ///
+    /// `cursor` is the server's `nextCursor` from the previous page, passed
+    /// back unchanged; the first page is requested without one.
+    ListToolsPage {
+        server_name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cursor: Option<String>,
+    },
     /// List all configured servers
     ListServers,
+    /// List resources exposed by a specific server
+    ListResources { server_name: String },
+    /// List resource templates exposed by a specific server
+    ListResourceTemplates { server_name: String },
+    /// Read the contents of a resource by URI
+    ReadResource { server_name: String, uri: String },
+    /// List prompt templates exposed by a specific server
+    ListPrompts { server_name: String },
+    /// Render a prompt template with the given arguments
+    GetPrompt {
+        server_name: String,
+        prompt_name: String,
+        arguments: HashMap<String, String>,
+    },
     /// Request daemon shutdown
     Shutdown,
 }
 
 /// Response types sent from daemon to CLI
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+///
+/// `Progress` frames may precede the final response to `ExecuteTool`; every
+/// other variant ends the request.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum DaemonResponse {
     /// Acknowledge ping
     Pong,
+    /// Intermediate progress report for a running tool call
+    Progress(ProgressUpdate),
     /// Return config file fingerprint
     ConfigFingerprint(String),
+    /// Return config file path; `None` if the daemon runs without one
+    ConfigPath(Option<PathBuf>),
     /// Tool execution result
     ToolResult(serde_json::Value),
     /// List of available tools
     ToolList(Vec<ToolInfo>),
+    /// One page of a server's tools
+    ToolPage(ToolPage),
     /// List of configured servers
     ServerList(Vec<String>),
+    /// List of available resources
+    ResourceList(Vec<ResourceInfo>),
+    /// List of available resource templates
+    ResourceTemplateList(Vec<ResourceTemplateInfo>),
+    /// Contents returned by a resource read
+    ResourceContents(Vec<ResourceContents>),
+    /// List of available prompts
+    PromptList(Vec<PromptInfo>),
+    /// Rendered prompt messages
+    PromptResult(PromptResult),
     /// Acknowledge shutdown request
     ShutdownAck,
     /// Error response
     Error { code: u32, message: String },
 }
 
+/// `Error` code for a tool call that exceeded its timeout.
+pub const TIMEOUT_ERROR_CODE: u32 = 4;
+
+/// Version of the IPC protocol spoken between CLI and daemon
+///
+/// Version 1 had no handshake and carried a single bare request per
+/// connection; CLIs and daemons from before the handshake speak it.
+pub const IPC_PROTOCOL_VERSION: u32 = 2;
+
+/// First frame sent in each direction on a new IPC connection
+///
+/// The client announces its protocol version and the daemon answers with
+/// its own; the daemon closes the connection after answering if they differ.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Handshake {
+    pub protocol_version: u32,
+}
+
+impl Handshake {
+    /// Handshake announcing [`IPC_PROTOCOL_VERSION`]
+    pub fn current() -> Self {
+        Self {
+            protocol_version: IPC_PROTOCOL_VERSION,
+        }
+    }
+}
+
+/// A request sent after the handshake
+///
+/// `id` is chosen by the client and unique among the requests in flight on
+/// the connection; every response to the request carries it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RequestFrame {
+    pub id: u64,
+    pub request: FrameRequest,
+}
+
+/// What a [`RequestFrame`] asks of the daemon
+///
+/// On the wire a cancellation is the string `"cancel_request"` and any other
+/// request is the [`DaemonRequest`] itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrameRequest {
+    /// Cancel the tool call whose frame carries the same ID
+    ///
+    /// Sent on the connection that carries the `ExecuteTool` when the CLI is
+    /// interrupted; the daemon cancels the call on the MCP server and sends
+    /// no response of its own.
+    Cancel,
+    /// A request the daemon serves and answers
+    Serve(DaemonRequest),
+}
+
+const CANCEL_REQUEST: &str = "cancel_request";
+
+impl From<DaemonRequest> for FrameRequest {
+    fn from(request: DaemonRequest) -> Self {
+        FrameRequest::Serve(request)
+    }
+}
+
+impl Serialize for FrameRequest {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            FrameRequest::Cancel => serializer.serialize_str(CANCEL_REQUEST),
+            FrameRequest::Serve(request) => request.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for FrameRequest {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        if value == CANCEL_REQUEST {
+            return Ok(FrameRequest::Cancel);
+        }
+        DaemonRequest::deserialize(value)
+            .map(FrameRequest::Serve)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// A response to the request with the same `id`
+///
+/// Requests on one connection are answered as they complete, so responses
+/// may arrive in any order.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResponseFrame {
+    pub id: u64,
+    pub response: DaemonResponse,
+}
+
 /// Tool information returned by daemon
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ToolInfo {
@@ -66,6 +209,238 @@ impl ToolInfo {
     }
 }
 
+/// Progress reported by a server via `notifications/progress`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProgressUpdate {
+    /// Progress so far; increases with each notification
+    pub progress: f64,
+    /// Total amount of work, if the server knows it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<f64>,
+    /// Human-readable description of the current step
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl ProgressUpdate {
+    /// Parse a `notifications/progress` notification carrying `token`
+    ///
+    /// Returns None for other notifications and for progress reports
+    /// belonging to a different request.
+    pub fn from_notification(
+        notification: &serde_json::Value,
+        token: &serde_json::Value,
+    ) -> Option<Self> {
+        if notification.get("method")?.as_str()? != "notifications/progress" {
+            return None;
+        }
+        let params = notification.get("params")?;
+        if params.get("progressToken")? != token {
+            return None;
+        }
+        Some(Self {
+            progress: params.get("progress")?.as_f64()?,
+            total: params.get("total").and_then(|t| t.as_f64()),
+            message: params
+                .get("message")
+                .and_then(|m| m.as_str())
+                .map(str::to_string),
+        })
+    }
+}
+
+/// Generate a progress token that is unique within this process
+///
+/// Attached to requests as `_meta.progressToken` so the server's progress
+/// notifications can be matched to the request.
+pub fn progress_token() -> serde_json::Value {
+    static COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+    let n = COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    serde_json::Value::String(format!("mcp-cli-{}-{}", std::process::id(), n))
+}
+
+/// Build `tools/call` params, requesting progress notifications for `token`
+pub fn tool_call_params(
+    tool_name: &str,
+    arguments: serde_json::Value,
+    token: &serde_json::Value,
+) -> serde_json::Value {
+    serde_json::json!({
+        "name": tool_name,
+        "arguments": arguments,
+        "_meta": { "progressToken": token }
+    })
+}
+
+/// Resource information returned by daemon
+///
+/// Field names follow the MCP wire format (`mimeType`), so entries from a
+/// `resources/list` result deserialize directly.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceInfo {
+    pub uri: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+/// Resource template information returned by daemon
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceTemplateInfo {
+    pub uri_template: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+/// A single content item from a `resources/read` result
+///
+/// Exactly one of `text` or `blob` (base64-encoded) is set by the server.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceContents {
+    pub uri: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob: Option<String>,
+}
+
+/// Prompt template information returned by daemon
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PromptInfo {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<PromptArgument>,
+}
+
+/// Argument accepted by a prompt template
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PromptArgument {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Result of a `prompts/get` request
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PromptResult {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub messages: Vec<PromptMessage>,
+}
+
+/// A single rendered prompt message
+///
+/// `content` is kept as raw JSON since it may be text, image or an embedded
+/// resource.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PromptMessage {
+    pub role: String,
+    pub content: serde_json::Value,
+}
+
+impl PromptResult {
+    /// Parse a `prompts/get` result object
+    pub fn from_result(result: &serde_json::Value) -> crate::error::Result<Self> {
+        serde_json::from_value(result.clone()).map_err(|e| {
+            crate::error::McpError::InvalidProtocol {
+                message: format!("Invalid prompts/get result: {}", e),
+            }
+        })
+    }
+}
+
+/// Deserialize the array stored under `key` in an MCP result object
+///
+/// Used for list results such as `{"resources": [...]}`. A missing key yields
+/// an empty list; malformed entries are reported as a protocol error.
+pub fn parse_result_list<T: serde::de::DeserializeOwned>(
+    result: &serde_json::Value,
+    key: &str,
+) -> crate::error::Result<Vec<T>> {
+    match result.get(key) {
+        Some(items) => serde_json::from_value(items.clone()).map_err(|e| {
+            crate::error::McpError::InvalidProtocol {
+                message: format!("Invalid '{}' in MCP result: {}", key, e),
+            }
+        }),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Convert the `tools` array of a `tools/list` result into [`ToolInfo`]s
+///
+/// Entries without a name are skipped; a missing description becomes empty
+/// and a missing `inputSchema` becomes an empty object.
+pub fn parse_tool_list(result: &serde_json::Value) -> Vec<ToolInfo> {
+    result
+        .get("tools")
+        .and_then(|t| t.as_array())
+        .map(|tools| {
+            tools
+                .iter()
+                .filter_map(|tool| {
+                    Some(ToolInfo {
+                        name: tool.get("name")?.as_str()?.to_string(),
+                        description: tool
+                            .get("description")
+                            .and_then(|d| d.as_str())
+                            .unwrap_or_default()
+                            .to_string(),
+                        input_schema: tool
+                            .get("inputSchema")
+                            .cloned()
+                            .unwrap_or_else(|| serde_json::Value::Object(serde_json::Map::new())),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// One page of a `tools/list` result
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ToolPage {
+    pub tools: Vec<ToolInfo>,
+    /// The server's cursor for the following page; absent on the last page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+impl ToolPage {
+    /// Parse a single `tools/list` result, keeping its `nextCursor`
+    pub fn from_result(result: &serde_json::Value) -> Self {
+        Self {
+            tools: parse_tool_list(result),
+            next_cursor: result
+                .get("nextCursor")
+                .and_then(|c| c.as_str())
+                .map(str::to_string),
+        }
+    }
+}
+
+/// `params` of an MCP list request for the page at `cursor`
+pub fn list_page_params(cursor: Option<&str>) -> serde_json::Value {
+    match cursor {
+        Some(cursor) => serde_json::json!({ "cursor": cursor }),
+        None => serde_json::json!({}),
+    }
+}
+
 /// Server information for JSON output
 #[derive(Debug, Clone, Serialize)]
 pub struct ServerInfo {
@@ -179,74 +554,55 @@ pub struct ToolResult {
     pub metadata: ExecutionMetadata,
 }
 
-/// Send a NDJSON-encoded request to daemon
-pub async fn send_request<W>(writer: &mut W, request: &DaemonRequest) -> Result<()>
+/// Send a frame as one NDJSON line
+pub async fn send_frame<W, T>(writer: &mut W, frame: &T) -> Result<()>
 where
     W: AsyncWrite + Unpin,
+    T: Serialize,
 {
-    let json = serde_json::to_string(request).context("Failed to serialize request")?;
+    let mut json = serde_json::to_string(frame).context("Failed to serialize frame")?;
+    json.push('\n');
     writer
         .write_all(json.as_bytes())
         .await
-        .context("Failed to write request")?;
-    writer
-        .write_all(b"\n")
-        .await
-        .context("Failed to write newline")?;
-    writer.flush().await.context("Failed to flush request")?;
+        .context("Failed to write frame")?;
+    writer.flush().await.context("Failed to flush frame")?;
     Ok(())
 }
 
-/// Receive a NDJSON-encoded request from daemon
-pub async fn receive_request<R>(reader: &mut R) -> Result<DaemonRequest>
+/// Receive the next non-empty NDJSON line; None once the stream has ended
+pub async fn receive_line<R>(reader: &mut R) -> Result<Option<String>>
 where
     R: AsyncBufRead + Unpin,
 {
-    let mut line = String::new();
-    reader
-        .read_line(&mut line)
-        .await
-        .context("Failed to read request")?;
-    let line = line.trim();
-    if line.is_empty() {
-        return Err(anyhow::anyhow!("Received empty NDJSON line"));
+    loop {
+        let mut line = String::new();
+        let read = reader
+            .read_line(&mut line)
+            .await
+            .context("Failed to read frame")?;
+        if read == 0 {
+            return Ok(None);
+        }
+        let line = line.trim();
+        if !line.is_empty() {
+            return Ok(Some(line.to_string()));
+        }
     }
-    serde_json::from_str(line).context("Failed to deserialize request")
-}
-
-/// Send a NDJSON-encoded response to daemon
-pub async fn send_response<W>(writer: &mut W, response: &DaemonResponse) -> Result<()>
-where
-    W: AsyncWrite + Unpin,
-{
-    let json = serde_json::to_string(response).context("Failed to serialize response")?;
-    writer
-        .write_all(json.as_bytes())
-        .await
-        .context("Failed to write response")?;
-    writer
-        .write_all(b"\n")
-        .await
-        .context("Failed to write newline")?;
-    writer.flush().await.context("Failed to flush response")?;
-    Ok(())
 }
 
-/// Receive a NDJSON-encoded response from daemon
-pub async fn receive_response<R>(reader: &mut R) -> Result<DaemonResponse>
+/// Receive and deserialize the next NDJSON frame; None once the stream has ended
+pub async fn receive_frame<R, T>(reader: &mut R) -> Result<Option<T>>
 where
     R: AsyncBufRead + Unpin,
+    T: serde::de::DeserializeOwned,
 {
-    let mut line = String::new();
-    reader
-        .read_line(&mut line)
-        .await
-        .context("Failed to read response")?;
-    let line = line.trim();
-    if line.is_empty() {
-        return Err(anyhow::anyhow!("Received empty NDJSON line"));
+    match receive_line(reader).await? {
+        Some(line) => serde_json::from_str(&line)
+            .map(Some)
+            .context("Failed to deserialize frame"),
+        None => Ok(None),
     }
-    serde_json::from_str(line).context("Failed to deserialize response")
 }
 
 #[cfg(test)]
@@ -272,4 +628,203 @@ mod tests {
         let info = ToolInfo::new("test_tool", "Test description", serde_json::json!({}));
         assert_eq!(info.name, "test_tool");
     }
+
+    #[test]
+    fn test_parse_resource_list() {
+        let result = serde_json::json!({
+            "resources": [
+                {"uri": "file:///a.txt", "name": "a.txt", "mimeType": "text/plain"},
+                {"uri": "file:///b.bin", "name": "b.bin"}
+            ]
+        });
+        let resources: Vec<ResourceInfo> = parse_result_list(&result, "resources").unwrap();
+        assert_eq!(resources.len(), 2);
+        assert_eq!(resources[0].mime_type.as_deref(), Some("text/plain"));
+        assert!(resources[1].description.is_none());
+    }
+
+    #[test]
+    fn test_parse_result_list_missing_key() {
+        let result = serde_json::json!({});
+        let templates: Vec<ResourceTemplateInfo> =
+            parse_result_list(&result, "resourceTemplates").unwrap();
+        assert!(templates.is_empty());
+    }
+
+    #[test]
+    fn test_parse_result_list_malformed() {
+        let result = serde_json::json!({"contents": [{"text": "missing uri"}]});
+        let parsed: crate::error::Result<Vec<ResourceContents>> =
+            parse_result_list(&result, "contents");
+        assert!(parsed.is_err());
+    }
+
+    #[test]
+    fn test_parse_tool_list_defaults() {
+        let result = serde_json::json!({
+            "tools": [
+                {"name": "echo", "description": "Echo input", "inputSchema": {"type": "object"}},
+                {"name": "bare"},
+                {"description": "no name"}
+            ]
+        });
+
+        let tools = parse_tool_list(&result);
+
+        assert_eq!(tools.len(), 2);
+        assert_eq!(tools[0].input_schema["type"], "object");
+        assert_eq!(tools[1].description, "");
+        assert_eq!(tools[1].input_schema, serde_json::json!({}));
+    }
+
+    #[test]
+    fn test_tool_page_keeps_next_cursor() {
+        let page = ToolPage::from_result(&serde_json::json!({
+            "tools": [{"name": "echo"}],
+            "nextCursor": "opaque-2"
+        }));
+        assert_eq!(page.tools.len(), 1);
+        assert_eq!(page.next_cursor.as_deref(), Some("opaque-2"));
+
+        let last = ToolPage::from_result(&serde_json::json!({"tools": []}));
+        assert!(last.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_parse_prompt_list() {
+        let result = serde_json::json!({
+            "prompts": [{
+                "name": "review",
+                "description": "Review code",
+                "arguments": [{"name": "code", "required": true}, {"name": "style"}]
+            }]
+        });
+        let prompts: Vec<PromptInfo> = parse_result_list(&result, "prompts").unwrap();
+        assert_eq!(prompts.len(), 1);
+        assert!(prompts[0].arguments[0].required);
+        assert!(!prompts[0].arguments[1].required);
+    }
+
+    #[test]
+    fn test_prompt_result_from_result() {
+        let result = serde_json::json!({
+            "messages": [{"role": "user", "content": {"type": "text", "text": "hi"}}]
+        });
+        let prompt = PromptResult::from_result(&result).unwrap();
+        assert!(prompt.description.is_none());
+        assert_eq!(prompt.messages[0].role, "user");
+
+        assert!(PromptResult::from_result(&serde_json::json!({})).is_err());
+    }
+
+    #[test]
+    fn test_progress_update_from_notification() {
+        let token = serde_json::json!("tok-1");
+        let notification = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/progress",
+            "params": {"progressToken": "tok-1", "progress": 3, "total": 10, "message": "step 3"}
+        });
+
+        let update = ProgressUpdate::from_notification(&notification, &token).unwrap();
+        assert_eq!(update.progress, 3.0);
+        assert_eq!(update.total, Some(10.0));
+        assert_eq!(update.message.as_deref(), Some("step 3"));
+
+        let other = serde_json::json!("tok-2");
+        assert!(ProgressUpdate::from_notification(&notification, &other).is_none());
+        let log = serde_json::json!({"jsonrpc": "2.0", "method": "notifications/message"});
+        assert!(ProgressUpdate::from_notification(&log, &token).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_frames_roundtrip_over_ndjson() {
+        let mut bytes = Vec::new();
+        send_frame(&mut bytes, &Handshake::current()).await.unwrap();
+        let frame = ResponseFrame {
+            id: 7,
+            response: DaemonResponse::Progress(ProgressUpdate {
+                progress: 1.0,
+                total: None,
+                message: None,
+            }),
+        };
+        send_frame(&mut bytes, &frame).await.unwrap();
+        bytes.extend_from_slice(b"\n");
+
+        let mut reader = tokio::io::BufReader::new(bytes.as_slice());
+        let handshake: Option<Handshake> = receive_frame(&mut reader).await.unwrap();
+        assert_eq!(handshake, Some(Handshake::current()));
+        let received: Option<ResponseFrame> = receive_frame(&mut reader).await.unwrap();
+        assert_eq!(received, Some(frame));
+        // Blank lines are skipped and the end of the stream is not an error
+        let end: Option<ResponseFrame> = receive_frame(&mut reader).await.unwrap();
+        assert!(end.is_none());
+    }
+
+    #[test]
+    fn test_frame_wire_format() {
+        let json = serde_json::to_string(&Handshake::current()).unwrap();
+        assert_eq!(
+            json,
+            format!("{{\"protocol_version\":{}}}", IPC_PROTOCOL_VERSION)
+        );
+
+        let frame = RequestFrame {
+            id: 3,
+            request: DaemonRequest::Ping.into(),
+        };
+        let json = serde_json::to_string(&frame).unwrap();
+        assert_eq!(json, r#"{"id":3,"request":"ping"}"#);
+        let parsed: RequestFrame = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, frame);
+
+        // A bare version 1 request is not mistaken for a handshake
+        assert!(serde_json::from_str::<Handshake>("\"ping\"").is_err());
+    }
+
+    #[test]
+    fn test_cancel_request_serialization() {
+        let json = serde_json::to_string(&FrameRequest::Cancel).unwrap();
+        assert_eq!(json, "\"cancel_request\"");
+        let parsed: FrameRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, FrameRequest::Cancel);
+        // Only a frame's request can be a cancellation
+        assert!(serde_json::from_str::<DaemonRequest>(&json).is_err());
+    }
+
+    #[test]
+    fn test_execute_tool_timeout_is_optional() {
+        let parsed: DaemonRequest = serde_json::from_str(
+            r#"{"execute_tool":{"server_name":"s","tool_name":"t","arguments":{}}}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            parsed,
+            DaemonRequest::ExecuteTool {
+                timeout_secs: None,
+                ..
+            }
+        ));
+
+        let req = DaemonRequest::ExecuteTool {
+            server_name: "s".to_string(),
+            tool_name: "t".to_string(),
+            arguments: serde_json::json!({}),
+            timeout_secs: Some(5),
+        };
+        let json = serde_json::to_string(&req).unwrap();
+        assert_eq!(serde_json::from_str::<DaemonRequest>(&json).unwrap(), req);
+    }
+
+    #[test]
+    fn test_read_resource_request_roundtrip() {
+        let req = DaemonRequest::ReadResource {
+            server_name: "fs".to_string(),
+            uri: "file:///a.txt".to_string(),
+        };
+        let json = serde_json::to_string(&req).unwrap();
+        let parsed: DaemonRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, req);
+    }
 }
@@ -0,0 +1,267 @@
+//! Hot reloading of the daemon's configuration.
+//!
+//! The daemon keeps its [`Config`] in a [`SharedConfig`] that the connection
+//! pool reads on every request. Reloading swaps in the new config and closes
+//! pooled connections only for servers whose settings changed, so unchanged
+//! servers keep their warm connections.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+use crate::config::Config;
+use crate::config::loader::load_config;
+use crate::config_fingerprint::config_fingerprint;
+use crate::daemon::ConfigFingerprint;
+use crate::daemon::pool::ConnectionPool;
+use crate::error::Result;
+
+/// How often the daemon checks its config file for changes
+pub const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Configuration shared between the daemon and its connection pool
+///
+/// Clones share the same config, so a reload through one is seen by all.
+#[derive(Clone)]
+pub struct SharedConfig {
+    current: Arc<RwLock<Arc<Config>>>,
+}
+
+impl SharedConfig {
+    pub fn new(config: Config) -> Self {
+        Arc::new(config).into()
+    }
+
+    /// The config in effect now; later reloads don't affect the returned value
+    pub fn get(&self) -> Arc<Config> {
+        Arc::clone(&self.current.read().expect("Failed to acquire config lock"))
+    }
+
+    /// Fingerprint of the config in effect now
+    pub fn fingerprint(&self) -> ConfigFingerprint {
+        config_fingerprint(&self.get())
+    }
+
+    /// Swap in `config`, returning the one it replaces
+    pub(crate) fn replace(&self, config: Config) -> Arc<Config> {
+        let mut current = self.current.write().expect("Failed to acquire config lock");
+        std::mem::replace(&mut *current, Arc::new(config))
+    }
+}
+
+impl From<Arc<Config>> for SharedConfig {
+    fn from(config: Arc<Config>) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(config)),
+        }
+    }
+}
+
+impl From<Config> for SharedConfig {
+    fn from(config: Config) -> Self {
+        Self::new(config)
+    }
+}
+
+/// Servers added, removed or changed between two configs
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ConfigChanges {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+}
+
+impl ConfigChanges {
+    /// Compare the servers of `old` and `new`, in config order
+    pub fn between(old: &Config, new: &Config) -> Self {
+        let mut changes = Self::default();
+        for server in &old.servers {
+            if new.get_server(&server.name).is_none() {
+                changes.removed.push(server.name.clone());
+            } else if server_changed(old, new, &server.name) {
+                changes.changed.push(server.name.clone());
+            }
+        }
+        for server in &new.servers {
+            if old.get_server(&server.name).is_none() {
+                changes.added.push(server.name.clone());
+            }
+        }
+        changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl fmt::Display for ConfigChanges {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "no server changes");
+        }
+        let parts: Vec<String> = [
+            ("added", &self.added),
+            ("removed", &self.removed),
+            ("changed", &self.changed),
+        ]
+        .into_iter()
+        .filter(|(_, names)| !names.is_empty())
+        .map(|(label, names)| format!("{} {} ({})", names.len(), label, names.join(", ")))
+        .collect();
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// Whether connections made for `name` under `old` are wrong for `new`
+///
+/// A server missing from `new` counts as changed. Besides the server's own
+/// settings this compares its effective timeout, which can come from the
+/// global `timeout_secs`.
+pub fn server_changed(old: &Config, new: &Config, name: &str) -> bool {
+    old.get_server(name) != new.get_server(name)
+        || new.get_server(name).is_none()
+        || old.server_timeout_secs(name) != new.server_timeout_secs(name)
+}
+
+/// Reread the config file and swap it into the pool
+///
+/// The daemon's socket path is kept, since the running daemon can't move.
+///
+/// # Errors
+/// Returns the config loading error; the current config stays in effect.
+pub async fn reload_config(path: &Path, pool: &ConnectionPool) -> Result<ConfigChanges> {
+    let mut config = load_config(path).await?;
+    config.socket_path = pool.config().socket_path.clone();
+
+    let changes = pool.reload(config);
+    tracing::info!("Reloaded config from {}: {}", path.display(), changes);
+    Ok(changes)
+}
+
+/// Reload the config whenever the file at `path` changes
+///
+/// Polls the file's modification time and size every [`WATCH_INTERVAL`].
+/// A file that fails to load or disappears leaves the current config in
+/// effect until the next change.
+pub async fn watch_config(path: PathBuf, pool: ConnectionPool) {
+    tracing::info!("Watching config file: {}", path.display());
+    let mut last = file_stamp(&path);
+    loop {
+        tokio::time::sleep(WATCH_INTERVAL).await;
+
+        let stamp = file_stamp(&path);
+        if stamp == last {
+            continue;
+        }
+        last = stamp;
+
+        if stamp.is_none() {
+            tracing::warn!(
+                "Config file {} is gone, keeping current config",
+                path.display()
+            );
+            continue;
+        }
+        if let Err(e) = reload_config(&path, &pool).await {
+            tracing::warn!("Keeping current config, reload failed: {}", e);
+        }
+    }
+}
+
+/// Modification time and size of a file, None if it can't be read
+fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ServerConfig, ServerTransport};
+    use std::collections::HashMap;
+
+    fn server(name: &str, command: &str) -> ServerConfig {
+        ServerConfig {
+            name: name.to_string(),
+            transport: ServerTransport::Stdio {
+                command: command.to_string(),
+                args: vec![],
+                env: HashMap::new(),
+                cwd: None,
+            },
+            description: None,
+            allowed_tools: None,
+            disabled_tools: None,
+            timeout_secs: None,
+        }
+    }
+
+    fn config(servers: Vec<ServerConfig>) -> Config {
+        Config {
+            servers,
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn test_changes_between_configs() {
+        let old = config(vec![
+            server("kept", "a"),
+            server("edited", "b"),
+            server("gone", "c"),
+        ]);
+        let new = config(vec![
+            server("new", "d"),
+            server("edited", "e"),
+            server("kept", "a"),
+        ]);
+
+        let changes = ConfigChanges::between(&old, &new);
+
+        assert_eq!(changes.added, vec!["new"]);
+        assert_eq!(changes.removed, vec!["gone"]);
+        assert_eq!(changes.changed, vec!["edited"]);
+        assert_eq!(
+            changes.to_string(),
+            "1 added (new), 1 removed (gone), 1 changed (edited)"
+        );
+    }
+
+    #[test]
+    fn test_global_timeout_changes_servers_without_their_own() {
+        let mut timed = server("timed", "a");
+        timed.timeout_secs = Some(5);
+        let old = config(vec![timed.clone(), server("untimed", "b")]);
+        let mut new = old.clone();
+        new.timeout_secs = old.timeout_secs + 1;
+
+        let changes = ConfigChanges::between(&old, &new);
+
+        assert_eq!(changes.changed, vec!["untimed"]);
+    }
+
+    #[test]
+    fn test_identical_configs_have_no_changes() {
+        let old = config(vec![server("kept", "a")]);
+
+        let changes = ConfigChanges::between(&old, &old.clone());
+
+        assert!(changes.is_empty());
+        assert_eq!(changes.to_string(), "no server changes");
+    }
+
+    #[test]
+    fn test_shared_config_clones_see_replacement() {
+        let shared = SharedConfig::new(config(vec![server("old", "a")]));
+        let clone = shared.clone();
+        let before = shared.fingerprint();
+
+        let replaced = clone.replace(config(vec![server("new", "b")]));
+
+        assert_eq!(replaced.servers[0].name, "old");
+        assert_eq!(shared.get().servers[0].name, "new");
+        assert_ne!(shared.fingerprint(), before);
+    }
+}
@@ -5,7 +5,8 @@
 //! Each variant includes structured context for actionable error messages.
 //!
 //! The [`exit_code`] function maps errors to process exit codes (1=client,
-//! 2=server, 3=network/IO) for scripting compatibility.
+//! 2=server, 3=network/IO, 4=breaking tool changes) for scripting
+//! compatibility.
 //!
 //! # Usage
 //!
@@ -72,6 +73,16 @@ pub enum McpError {
     #[error("Tool '{}' not found in server '{}'", tool, server)]
     ToolNotFound { tool: String, server: String },
 
+    #[error(
+        "Invalid arguments for tool '{}':\n  {}\n(use --no-validate to send them anyway)",
+        tool,
+        violations.join("\n  ")
+    )]
+    InvalidArguments {
+        tool: String,
+        violations: Vec<String>,
+    },
+
     // Daemon errors (DAEMON-04)
     #[error("Daemon not running: {}", message)]
     DaemonNotRunning { message: String },
@@ -111,6 +122,9 @@ pub enum McpError {
     #[error("IPC error: {}", message)]
     IpcError { message: String },
 
+    #[error("{}", ipc_version_mismatch(*daemon, *client))]
+    IpcVersionMismatch { daemon: u32, client: u32 },
+
     #[cfg(unix)]
     #[error("Socket bind failed at '{}': {}", path, source)]
     SocketBindError {
@@ -145,6 +159,10 @@ pub enum McpError {
 
     #[error("Max retry attempts ({}) exceeded", attempts)]
     MaxRetriesExceeded { attempts: u32 },
+
+    // Snapshot diff errors
+    #[error("{} breaking tool change(s) found", count)]
+    BreakingChanges { count: usize },
 }
 
 /// Exit codes for CLI process (ERR-03).
@@ -153,11 +171,13 @@ pub enum McpError {
 /// - `1` — Client/usage errors (bad input, missing resources)
 /// - `2` — Server/protocol errors
 /// - `3` — Network or IO errors
+/// - `4` — Breaking tool changes found by `mcp diff`
 #[cfg(unix)]
 pub fn exit_code(error: &McpError) -> i32 {
     match error {
         McpError::ServerNotFound { .. }
         | McpError::ToolNotFound { .. }
+        | McpError::InvalidArguments { .. }
         | McpError::ConfigReadError { .. }
         | McpError::ConfigParseError { .. }
         | McpError::MissingRequiredField { .. }
@@ -172,8 +192,11 @@ pub fn exit_code(error: &McpError) -> i32 {
 
         McpError::ConnectionError { .. } | McpError::Timeout { .. } | McpError::IOError { .. } => 3, // Network or IO error
 
+        McpError::BreakingChanges { .. } => 4, // Breaking tool changes
+
         // IPC errors also return client error code
         McpError::IpcError { .. }
+        | McpError::IpcVersionMismatch { .. }
         | McpError::SocketBindError { .. }
         | McpError::ConnectionRefused { .. }
         | McpError::StaleSocket { .. } => 1,
@@ -188,6 +211,7 @@ pub fn exit_code(error: &McpError) -> i32 {
     match error {
         McpError::ServerNotFound { .. }
         | McpError::ToolNotFound { .. }
+        | McpError::InvalidArguments { .. }
         | McpError::ConfigReadError { .. }
         | McpError::ConfigParseError { .. }
         | McpError::MissingRequiredField { .. }
@@ -201,14 +225,33 @@ pub fn exit_code(error: &McpError) -> i32 {
 
         McpError::ConnectionError { .. } | McpError::Timeout { .. } | McpError::IOError { .. } => 3, // Network or IO error
 
+        McpError::BreakingChanges { .. } => 4, // Breaking tool changes
+
         // IPC errors also return client error code
         McpError::IpcError { .. }
+        | McpError::IpcVersionMismatch { .. }
         | McpError::PipeCreationError { .. }
         | McpError::PipeBusy { .. }
         | McpError::DaemonNotRunning { .. } => 1,
     }
 }
 
+/// Message for [`McpError::IpcVersionMismatch`], naming the side that is
+/// out of date.
+fn ipc_version_mismatch(daemon: u32, client: u32) -> String {
+    if daemon < client {
+        format!(
+            "The running daemon speaks IPC protocol {} but this mcp requires {}; stop it with 'mcp shutdown' and retry",
+            daemon, client
+        )
+    } else {
+        format!(
+            "The running daemon speaks IPC protocol {} but this mcp only supports {}; upgrade mcp or stop the daemon with 'mcp shutdown' from the newer version",
+            daemon, client
+        )
+    }
+}
+
 /// Context helper for missing fields (CONFIG-04).
 impl McpError {
     /// Create a [`MissingRequiredField`](McpError::MissingRequiredField) error.
@@ -313,6 +356,14 @@ impl McpError {
         Self::PipeBusy { path: path.into() }
     }
 
+    /// Create a [`Timeout`](McpError::Timeout) error for a limit of `timeout`.
+    ///
+    /// Rounds up to whole seconds, so sub-second limits are not reported as 0s.
+    pub fn timeout(timeout: std::time::Duration) -> Self {
+        let secs = timeout.as_secs() + u64::from(timeout.subsec_nanos() > 0);
+        Self::Timeout { timeout: secs }
+    }
+
     /// Create an [`OperationCancelled`](McpError::OperationCancelled) error.
     pub fn operation_cancelled(timeout: u64) -> Self {
         Self::OperationCancelled { timeout }
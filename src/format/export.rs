@@ -0,0 +1,486 @@
+//! Tool catalog export to LLM function-calling formats.
+//!
+//! Turns `(server, tool)` pairs into the tool declarations other agent
+//! frameworks expect. Each format's output is the value of its request's
+//! `tools` field:
+//!
+//! - `openai` — `[{"type": "function", "function": {name, description, parameters}}]`
+//! - `anthropic` — `[{name, description, input_schema}]`
+//! - `gemini` — `[{"functionDeclarations": [{name, description, parameters}]}]`
+//! - `jsonschema` — one JSON Schema document per tool, titled with its name
+//!
+//! Tools are named `server__tool`. Characters a vendor doesn't allow become
+//! `_`, and names over its length limit (or clashing after sanitizing) are
+//! shortened with a hash suffix; [`Export::mapping`] translates every name
+//! back to `server/tool`. Schema keywords a vendor rejects are removed and
+//! reported in [`Export::warnings`].
+
+use crate::client::ToolInfo;
+use serde_json::{Map, Value, json};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+
+/// Target format of `mcp export`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// OpenAI Chat Completions `tools`
+    Openai,
+    /// Anthropic Messages `tools`
+    Anthropic,
+    /// Gemini `tools` with function declarations
+    Gemini,
+    /// Plain JSON Schema, one document per tool
+    Jsonschema,
+}
+
+/// Keywords Gemini's OpenAPI-subset schemas accept
+const GEMINI_KEYWORDS: &[&str] = &[
+    "type",
+    "format",
+    "title",
+    "description",
+    "nullable",
+    "enum",
+    "maxItems",
+    "minItems",
+    "properties",
+    "required",
+    "minProperties",
+    "maxProperties",
+    "minLength",
+    "maxLength",
+    "pattern",
+    "example",
+    "anyOf",
+    "propertyOrdering",
+    "default",
+    "items",
+    "minimum",
+    "maximum",
+];
+
+/// Composition keywords OpenAI and Anthropic reject at the top level
+const TOP_LEVEL_COMPOSITION: &[&str] = &["oneOf", "anyOf", "allOf", "not", "enum"];
+
+/// Exported catalog with the data needed to map names back
+#[derive(Debug, Clone)]
+pub struct Export {
+    /// Tool declarations in the target format
+    pub tools: Value,
+    /// Exported name → `server/tool`
+    pub mapping: BTreeMap<String, String>,
+    /// Names that differ from `server__tool`
+    pub renamed: usize,
+    /// Schema keywords removed, one message per tool
+    pub warnings: Vec<String>,
+}
+
+/// Export tools in `format`.
+pub fn export_tools(tools: &[(String, ToolInfo)], format: ExportFormat) -> Export {
+    let mut used = HashSet::new();
+    let mut mapping = BTreeMap::new();
+    let mut renamed = 0;
+    let mut warnings = Vec::new();
+    let mut declarations = Vec::new();
+
+    for (server, tool) in tools {
+        let full_name = format!("{}__{}", server, tool.name);
+        let id = format!("{}/{}", server, tool.name);
+        let name = export_name(&full_name, &id, format, &used);
+        if name != full_name {
+            renamed += 1;
+        }
+        used.insert(name.clone());
+        mapping.insert(name.clone(), id);
+
+        let mut removed = Vec::new();
+        let schema = convert_schema(&tool.input_schema, format, &mut removed);
+        if !removed.is_empty() {
+            warnings.push(format!(
+                "{}: removed unsupported schema keywords: {}",
+                name,
+                removed.join(", ")
+            ));
+        }
+        let description = tool.description.clone().unwrap_or_default();
+        declarations.push(declaration(format, &name, &description, schema));
+    }
+
+    let tools = match format {
+        ExportFormat::Gemini => json!([{ "functionDeclarations": declarations }]),
+        _ => Value::Array(declarations),
+    };
+    Export {
+        tools,
+        mapping,
+        renamed,
+        warnings,
+    }
+}
+
+/// Naming rules of a vendor
+struct NameRules {
+    max_len: usize,
+    allowed: fn(char) -> bool,
+    /// The first character must be a letter or underscore
+    letter_first: bool,
+}
+
+fn name_rules(format: ExportFormat) -> Option<NameRules> {
+    match format {
+        ExportFormat::Openai | ExportFormat::Anthropic => Some(NameRules {
+            max_len: 64,
+            allowed: |c| c.is_ascii_alphanumeric() || c == '_' || c == '-',
+            letter_first: false,
+        }),
+        ExportFormat::Gemini => Some(NameRules {
+            max_len: 64,
+            allowed: |c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ':'),
+            letter_first: true,
+        }),
+        ExportFormat::Jsonschema => None,
+    }
+}
+
+/// Pick a name for `full_name` that `format` accepts and isn't in `used`.
+///
+/// `id` is the tool's unambiguous `server/tool` name.
+fn export_name(full_name: &str, id: &str, format: ExportFormat, used: &HashSet<String>) -> String {
+    let Some(rules) = name_rules(format) else {
+        return unique_name(full_name.to_string(), usize::MAX, id, used);
+    };
+
+    let mut name: String = full_name
+        .chars()
+        .map(|c| if (rules.allowed)(c) { c } else { '_' })
+        .collect();
+    if rules.letter_first && !name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        name.insert(0, '_');
+    }
+    unique_name(name, rules.max_len, id, used)
+}
+
+/// Shorten `name` to `max_len` with a hash of `id`, as needed to fit and to
+/// stay distinct from `used`.
+///
+/// Should the hashed name be taken too, `id` is hashed again with a counter
+/// until it isn't.
+fn unique_name(name: String, max_len: usize, id: &str, used: &HashSet<String>) -> String {
+    if name.chars().count() <= max_len && !used.contains(&name) {
+        return name;
+    }
+    let keep = max_len.saturating_sub(9).min(name.chars().count());
+    let prefix: String = name.chars().take(keep).collect();
+    let mut attempt = 0;
+    loop {
+        let input = match attempt {
+            0 => id.to_string(),
+            n => format!("{}#{}", id, n),
+        };
+        let hash = hex::encode(Sha256::digest(input.as_bytes()));
+        let candidate = format!("{}_{}", prefix, &hash[..8]);
+        if !used.contains(&candidate) {
+            return candidate;
+        }
+        attempt += 1;
+    }
+}
+
+fn declaration(format: ExportFormat, name: &str, description: &str, schema: Value) -> Value {
+    match format {
+        ExportFormat::Openai => json!({
+            "type": "function",
+            "function": { "name": name, "description": description, "parameters": schema }
+        }),
+        ExportFormat::Anthropic => json!({
+            "name": name,
+            "description": description,
+            "input_schema": schema
+        }),
+        ExportFormat::Gemini => {
+            let mut declaration = json!({ "name": name, "description": description });
+            // Gemini rejects an object schema without properties
+            let has_properties = schema
+                .get("properties")
+                .and_then(Value::as_object)
+                .is_some_and(|properties| !properties.is_empty());
+            if has_properties {
+                declaration["parameters"] = schema;
+            }
+            declaration
+        }
+        ExportFormat::Jsonschema => {
+            // Keywords the tool's schema already sets are left alone
+            let mut document = match schema {
+                Value::Object(schema) => schema,
+                _ => Map::new(),
+            };
+            document
+                .entry("$schema")
+                .or_insert_with(|| json!("https://json-schema.org/draft/2020-12/schema"));
+            document.entry("title").or_insert_with(|| json!(name));
+            if !description.is_empty() {
+                document
+                    .entry("description")
+                    .or_insert_with(|| json!(description));
+            }
+            Value::Object(document)
+        }
+    }
+}
+
+/// Rewrite an `inputSchema` for `format`, recording removed keywords as
+/// `keyword (/json/pointer)` in `removed`.
+fn convert_schema(schema: &Value, format: ExportFormat, removed: &mut Vec<String>) -> Value {
+    let mut schema = match schema {
+        Value::Object(_) => schema.clone(),
+        _ => json!({}),
+    };
+    match format {
+        ExportFormat::Openai | ExportFormat::Anthropic => {
+            let object = schema.as_object_mut().expect("schema is an object");
+            for keyword in TOP_LEVEL_COMPOSITION {
+                if object.remove(*keyword).is_some() {
+                    removed.push(format!("{} (/)", keyword));
+                }
+            }
+            object.insert("type".to_string(), json!("object"));
+            object.entry("properties").or_insert_with(|| json!({}));
+            schema
+        }
+        ExportFormat::Gemini => {
+            gemini_schema(&mut schema, "", removed);
+            schema["type"] = json!("object");
+            schema
+        }
+        ExportFormat::Jsonschema => schema,
+    }
+}
+
+/// Reduce a schema to Gemini's keywords, recursing into subschemas.
+fn gemini_schema(schema: &mut Value, path: &str, removed: &mut Vec<String>) {
+    let Some(object) = schema.as_object_mut() else {
+        return;
+    };
+
+    // ["string", "null"] becomes "string" with nullable
+    if let Some(Value::Array(types)) = object.get("type") {
+        let non_null: Vec<Value> = types.iter().filter(|t| *t != "null").cloned().collect();
+        let nullable = non_null.len() < types.len();
+        if non_null.len() > 1 {
+            removed.push(format!("type union ({}/type)", path));
+        }
+        match non_null.into_iter().next() {
+            Some(first) => object.insert("type".to_string(), first),
+            None => object.remove("type"),
+        };
+        if nullable {
+            object.insert("nullable".to_string(), json!(true));
+        }
+    }
+    // A constant is an enum of one
+    if let Some(constant) = object.remove("const") {
+        object.entry("enum").or_insert_with(|| json!([constant]));
+    }
+
+    let unsupported: Vec<String> = object
+        .keys()
+        .filter(|key| !GEMINI_KEYWORDS.contains(&key.as_str()))
+        .cloned()
+        .collect();
+    for key in unsupported {
+        object.remove(&key);
+        let at = if path.is_empty() { "/" } else { path };
+        removed.push(format!("{} ({})", key, at));
+    }
+
+    if let Some(Value::Object(properties)) = object.get_mut("properties") {
+        for (name, property) in properties.iter_mut() {
+            gemini_schema(property, &format!("{}/properties/{}", path, name), removed);
+        }
+    }
+    if let Some(items) = object.get_mut("items") {
+        gemini_schema(items, &format!("{}/items", path), removed);
+    }
+    if let Some(Value::Array(variants)) = object.get_mut("anyOf") {
+        for (index, variant) in variants.iter_mut().enumerate() {
+            gemini_schema(variant, &format!("{}/anyOf/{}", path, index), removed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(name: &str, input_schema: Value) -> ToolInfo {
+        ToolInfo {
+            name: name.to_string(),
+            description: Some(format!("The {} tool", name)),
+            input_schema,
+        }
+    }
+
+    fn schema() -> Value {
+        json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object",
+            "properties": {
+                "path": { "type": "string" },
+                "encoding": { "type": ["string", "null"], "default": "utf-8" },
+                "options": {
+                    "type": "object",
+                    "properties": { "mode": { "const": "fast" } },
+                    "additionalProperties": false
+                }
+            },
+            "required": ["path"],
+            "additionalProperties": false
+        })
+    }
+
+    #[test]
+    fn test_openai_and_anthropic_shapes() {
+        let tools = vec![("fs".to_string(), tool("read_file", schema()))];
+
+        let export = export_tools(&tools, ExportFormat::Openai);
+        let function = &export.tools[0]["function"];
+        assert_eq!(export.tools[0]["type"], "function");
+        assert_eq!(function["name"], "fs__read_file");
+        assert_eq!(function["description"], "The read_file tool");
+        assert_eq!(function["parameters"]["required"], json!(["path"]));
+        assert!(export.warnings.is_empty());
+
+        let export = export_tools(&tools, ExportFormat::Anthropic);
+        assert_eq!(export.tools[0]["name"], "fs__read_file");
+        assert_eq!(export.tools[0]["input_schema"]["type"], "object");
+        assert_eq!(export.mapping["fs__read_file"], "fs/read_file");
+        assert_eq!(export.renamed, 0);
+    }
+
+    #[test]
+    fn test_gemini_strips_unsupported_keywords() {
+        let tools = vec![("fs".to_string(), tool("read_file", schema()))];
+        let export = export_tools(&tools, ExportFormat::Gemini);
+
+        let declaration = &export.tools[0]["functionDeclarations"][0];
+        let parameters = &declaration["parameters"];
+        assert!(parameters.get("$schema").is_none());
+        assert!(parameters.get("additionalProperties").is_none());
+        assert_eq!(parameters["properties"]["encoding"]["type"], "string");
+        assert_eq!(parameters["properties"]["encoding"]["nullable"], true);
+        assert_eq!(
+            parameters["properties"]["options"]["properties"]["mode"]["enum"],
+            json!(["fast"])
+        );
+
+        assert_eq!(export.warnings.len(), 1);
+        let warning = &export.warnings[0];
+        assert!(warning.contains("$schema (/)"), "{}", warning);
+        assert!(
+            warning.contains("additionalProperties (/properties/options)"),
+            "{}",
+            warning
+        );
+    }
+
+    #[test]
+    fn test_gemini_omits_empty_parameters() {
+        let tools = vec![("t".to_string(), tool("now", json!({ "type": "object" })))];
+        let export = export_tools(&tools, ExportFormat::Gemini);
+        assert!(
+            export.tools[0]["functionDeclarations"][0]
+                .get("parameters")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_names_are_sanitized_and_shortened() {
+        let long = "a".repeat(80);
+        let tools = vec![
+            ("my server".to_string(), tool("read.file", json!({}))),
+            ("my_server".to_string(), tool("read_file", json!({}))),
+            ("fs".to_string(), tool(&long, json!({}))),
+        ];
+        let export = export_tools(&tools, ExportFormat::Openai);
+        let names: Vec<&str> = export
+            .tools
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tool| tool["function"]["name"].as_str().unwrap())
+            .collect();
+
+        assert_eq!(names[0], "my_server__read_file");
+        // The second tool sanitizes to the same name, so it gets a hash suffix
+        assert!(
+            names[1].starts_with("my_server__read_file_"),
+            "{}",
+            names[1]
+        );
+        assert_ne!(names[0], names[1]);
+        assert_eq!(names[2].len(), 64);
+        assert_eq!(export.renamed, 3);
+
+        assert_eq!(export.mapping[names[0]], "my server/read.file");
+        assert_eq!(export.mapping[names[1]], "my_server/read_file");
+        assert_eq!(export.mapping[names[2]], format!("fs/{}", long));
+    }
+
+    #[test]
+    fn test_names_split_differently_stay_distinct() {
+        // Both are `a__b__c` once joined with the separator
+        let tools = vec![
+            ("a__b".to_string(), tool("c", json!({}))),
+            ("a".to_string(), tool("b__c", json!({}))),
+            ("a_b".to_string(), tool("_c", json!({}))),
+        ];
+        let export = export_tools(&tools, ExportFormat::Jsonschema);
+
+        assert_eq!(export.mapping.len(), 3);
+        assert_eq!(export.mapping["a__b__c"], "a__b/c");
+        assert!(export.mapping.values().any(|id| id == "a/b__c"));
+        assert!(export.mapping.values().any(|id| id == "a_b/_c"));
+    }
+
+    #[test]
+    fn test_unique_name_rehashes_taken_names() {
+        let first = unique_name(
+            "x".to_string(),
+            64,
+            "s/t",
+            &HashSet::from(["x".to_string()]),
+        );
+        let used = HashSet::from(["x".to_string(), first.clone()]);
+        let second = unique_name("x".to_string(), 64, "s/t", &used);
+
+        assert!(second.starts_with("x_"), "{}", second);
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_gemini_names_start_with_letter() {
+        let tools = vec![("1password".to_string(), tool("get", json!({})))];
+        let export = export_tools(&tools, ExportFormat::Gemini);
+        assert_eq!(
+            export.tools[0]["functionDeclarations"][0]["name"],
+            "_1password__get"
+        );
+    }
+
+    #[test]
+    fn test_jsonschema_documents() {
+        let tools = vec![("fs".to_string(), tool("read_file", schema()))];
+        let export = export_tools(&tools, ExportFormat::Jsonschema);
+        let document = &export.tools[0];
+        assert_eq!(document["title"], "fs__read_file");
+        assert_eq!(document["description"], "The read_file tool");
+        // The tool's own $schema is kept
+        assert_eq!(
+            document["$schema"],
+            "http://json-schema.org/draft-07/schema#"
+        );
+        assert_eq!(document["additionalProperties"], false);
+    }
+}
@@ -5,8 +5,10 @@
 //!
 //! # Module Structure
 //!
+//! - [`export`] — Tool catalog export to LLM function-calling formats
 //! - [`params`] — Parameter formatting with [`DetailLevel`] control
 //! - [`schema`] — JSON Schema extraction into [`ParameterInfo`] structs
+//! - [`snapshot`] — Tool schema snapshots and breaking-change detection
 //! - [`OutputMode`] — Human vs JSON output mode selection
 //!
 //! # Integration
@@ -27,8 +29,10 @@
 //! let level = DetailLevel::Summary;
 //! ```
 
+pub mod export;
 pub mod params;
 pub mod schema;
+pub mod snapshot;
 
 // Re-export commonly used items
 pub use params::{format_param_help, format_param_list, DetailLevel};
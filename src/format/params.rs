@@ -35,6 +35,7 @@ pub enum DetailLevel {
 ///         param_type: "string".to_string(),
 ///         description: Some("Search query".to_string()),
 ///         required: true,
+///         enum_values: vec![],
 ///     },
 /// ];
 ///
@@ -67,6 +68,7 @@ pub fn format_param_list(params: &[ParameterInfo], level: DetailLevel) -> String
 ///     param_type: "number".to_string(),
 ///     description: Some("Maximum items".to_string()),
 ///     required: false,
+///     enum_values: vec![],
 /// };
 ///
 /// let output = format_param_help(&param, DetailLevel::Summary);
@@ -203,6 +205,7 @@ mod tests {
             param_type: param_type.to_string(),
             description: description.map(|s| s.to_string()),
             required,
+            enum_values: vec![],
         }
     }
 
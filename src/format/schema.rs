@@ -1,7 +1,8 @@
 //! JSON Schema parsing for parameter extraction.
 //!
 //! This module provides utilities to parse JSON Schema and extract
-//! parameter information for display purposes.
+//! parameter information for display purposes, and to check tool arguments
+//! against a tool's `inputSchema` before they are sent.
 
 use serde_json::Value;
 
@@ -16,6 +17,8 @@ pub struct ParameterInfo {
     pub description: Option<String>,
     /// Whether the parameter is required
     pub required: bool,
+    /// Allowed values from the schema's `enum`, empty if unrestricted
+    pub enum_values: Vec<String>,
 }
 
 /// Extract parameter information from a JSON Schema.
@@ -79,11 +82,27 @@ pub fn extract_params_from_schema(schema: &Value) -> Vec<ParameterInfo> {
 
         let required = required_fields.contains(&name.as_str());
 
+        let enum_values = prop_schema
+            .get("enum")
+            .and_then(|e| e.as_array())
+            .map(|values| {
+                values
+                    .iter()
+                    .map(|v| {
+                        v.as_str()
+                            .map(str::to_string)
+                            .unwrap_or_else(|| v.to_string())
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
         params.push(ParameterInfo {
             name: name.clone(),
             param_type,
             description,
             required,
+            enum_values,
         });
     }
 
@@ -97,6 +116,375 @@ pub fn extract_params_from_schema(schema: &Value) -> Vec<ParameterInfo> {
     params
 }
 
+/// Inline local `$ref`s (`#/$defs/...`, `#/definitions/...`) of a JSON Schema.
+///
+/// Keys next to a `$ref` override the referenced schema's. References that
+/// can't be resolved, point outside the document or recurse into
+/// themselves are left in place.
+///
+/// # Examples
+/// ```
+/// use serde_json::json;
+/// use mcp_cli_rs::format::schema::resolve_refs;
+///
+/// let schema = json!({
+///     "properties": { "point": { "$ref": "#/$defs/Point" } },
+///     "$defs": { "Point": { "type": "object" } }
+/// });
+///
+/// let resolved = resolve_refs(&schema);
+/// assert_eq!(resolved["properties"]["point"]["type"], "object");
+/// ```
+pub fn resolve_refs(schema: &Value) -> Value {
+    resolve_at(schema, schema, &mut Vec::new())
+}
+
+fn resolve_at<'a>(root: &'a Value, value: &'a Value, active: &mut Vec<&'a str>) -> Value {
+    match value {
+        Value::Object(map) => {
+            if let Some(reference) = map.get("$ref").and_then(Value::as_str)
+                && !active.contains(&reference)
+                && let Some(target) = reference
+                    .strip_prefix('#')
+                    .and_then(|pointer| root.pointer(pointer))
+            {
+                active.push(reference);
+                let mut resolved = resolve_at(root, target, active);
+                active.pop();
+                if let Value::Object(resolved_map) = &mut resolved {
+                    for (key, sibling) in map.iter().filter(|(key, _)| *key != "$ref") {
+                        resolved_map.insert(key.clone(), resolve_at(root, sibling, active));
+                    }
+                }
+                return resolved;
+            }
+            Value::Object(
+                map.iter()
+                    .map(|(key, child)| (key.clone(), resolve_at(root, child, active)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| resolve_at(root, item, active))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Extract parameters including the properties of nested objects.
+///
+/// Works like [`extract_params_from_schema`] after [`resolve_refs`], then
+/// lists each object parameter's own properties right after it, named
+/// `parent.child`, or `parent[].child` for objects inside arrays.
+///
+/// # Examples
+/// ```
+/// use serde_json::json;
+/// use mcp_cli_rs::format::schema::extract_nested_params;
+///
+/// let schema = json!({
+///     "properties": {
+///         "options": {
+///             "type": "object",
+///             "properties": { "mode": { "type": "string" } }
+///         }
+///     }
+/// });
+///
+/// let params = extract_nested_params(&schema);
+/// assert_eq!(params[0].name, "options");
+/// assert_eq!(params[1].name, "options.mode");
+/// ```
+pub fn extract_nested_params(schema: &Value) -> Vec<ParameterInfo> {
+    let mut params = Vec::new();
+    collect_nested(&resolve_refs(schema), "", &mut params);
+    params
+}
+
+fn collect_nested(schema: &Value, prefix: &str, params: &mut Vec<ParameterInfo>) {
+    for mut param in extract_params_from_schema(schema) {
+        let property = &schema["properties"][&param.name];
+        let name = format!("{}{}", prefix, param.name);
+        param.name = name.clone();
+        params.push(param);
+        if property.get("properties").is_some() {
+            collect_nested(property, &format!("{}.", name), params);
+        } else if let Some(items) = property.get("items")
+            && items.get("properties").is_some()
+        {
+            collect_nested(items, &format!("{}[].", name), params);
+        }
+    }
+}
+
+/// A place where a value does not satisfy its JSON Schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaViolation {
+    /// JSON pointer to the offending value ("" for the value itself)
+    pub path: String,
+    /// What is wrong with the value
+    pub message: String,
+}
+
+impl std::fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "(root): {}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// Check a value against a JSON Schema, returning every violation found.
+///
+/// Supports the keywords tool schemas use in practice: `type`, `enum`,
+/// `const`, numeric bounds, string and array lengths, `required`,
+/// `properties`, `additionalProperties`, `items`, `allOf`, `anyOf` and
+/// `oneOf` (treated like `anyOf`). Local `$ref`s (`#/...`) are followed
+/// as the value is walked, so recursive definitions work; remote ones are
+/// ignored. Other keywords, such as `pattern` and `format`, are not checked
+/// and never produce violations.
+///
+/// # Examples
+/// ```
+/// use serde_json::json;
+/// use mcp_cli_rs::format::schema::validate_against_schema;
+///
+/// let schema = json!({
+///     "type": "object",
+///     "properties": { "limit": { "type": "integer", "minimum": 1 } },
+///     "required": ["query"]
+/// });
+///
+/// let violations = validate_against_schema(&schema, &json!({ "limit": 0 }));
+/// assert_eq!(violations.len(), 2);
+/// assert_eq!(violations[0].path, "/query");
+/// assert_eq!(violations[1].path, "/limit");
+/// ```
+pub fn validate_against_schema(schema: &Value, value: &Value) -> Vec<SchemaViolation> {
+    let mut violations = Vec::new();
+    validate_at(schema, schema, value, "", &[], &mut violations);
+    violations
+}
+
+/// Check `value` at `path` against `schema`, a part of `root`.
+///
+/// `followed` lists the `$ref`s already followed for this same value, so a
+/// reference cycle that never descends into the value stops.
+fn validate_at<'a>(
+    root: &'a Value,
+    schema: &'a Value,
+    value: &Value,
+    path: &str,
+    followed: &[&'a str],
+    out: &mut Vec<SchemaViolation>,
+) {
+    let schema = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => {
+            violation(out, path, "no value is allowed here");
+            return;
+        }
+        Value::Object(schema) => schema,
+        _ => return,
+    };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str)
+        && !followed.contains(&reference)
+        && let Some(target) = reference
+            .strip_prefix('#')
+            .and_then(|pointer| root.pointer(pointer))
+    {
+        let mut followed = followed.to_vec();
+        followed.push(reference);
+        validate_at(root, target, value, path, &followed, out);
+    }
+
+    // A value of the wrong type makes the remaining keywords meaningless
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(|t| t.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|t| has_type(value, t)) {
+            violation(
+                out,
+                path,
+                format!("expected {}, got {}", types.join(" or "), type_name(value)),
+            );
+            return;
+        }
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum")
+        && !allowed.contains(value)
+    {
+        let choices: Vec<String> = allowed.iter().map(Value::to_string).collect();
+        violation(out, path, format!("must be one of: {}", choices.join(", ")));
+    }
+    if let Some(expected) = schema.get("const")
+        && expected != value
+    {
+        violation(out, path, format!("must be {}", expected));
+    }
+
+    if let Some(number) = value.as_f64() {
+        let bound = |key: &str| schema.get(key).and_then(Value::as_f64);
+        if let Some(min) = bound("minimum")
+            && number < min
+        {
+            violation(out, path, format!("must be at least {}", min));
+        }
+        if let Some(max) = bound("maximum")
+            && number > max
+        {
+            violation(out, path, format!("must be at most {}", max));
+        }
+        if let Some(min) = bound("exclusiveMinimum")
+            && number <= min
+        {
+            violation(out, path, format!("must be greater than {}", min));
+        }
+        if let Some(max) = bound("exclusiveMaximum")
+            && number >= max
+        {
+            violation(out, path, format!("must be less than {}", max));
+        }
+    }
+
+    if let Some(text) = value.as_str() {
+        let length = text.chars().count() as u64;
+        if let Some(min) = schema.get("minLength").and_then(Value::as_u64)
+            && length < min
+        {
+            violation(
+                out,
+                path,
+                format!("must be at least {} characters long", min),
+            );
+        }
+        if let Some(max) = schema.get("maxLength").and_then(Value::as_u64)
+            && length > max
+        {
+            violation(
+                out,
+                path,
+                format!("must be at most {} characters long", max),
+            );
+        }
+    }
+
+    if let Some(items) = value.as_array() {
+        let count = items.len() as u64;
+        if let Some(min) = schema.get("minItems").and_then(Value::as_u64)
+            && count < min
+        {
+            violation(out, path, format!("must have at least {} items", min));
+        }
+        if let Some(max) = schema.get("maxItems").and_then(Value::as_u64)
+            && count > max
+        {
+            violation(out, path, format!("must have at most {} items", max));
+        }
+        if let Some(item_schema) = schema.get("items").filter(|s| !s.is_array()) {
+            for (index, item) in items.iter().enumerate() {
+                let item_path = format!("{}/{}", path, index);
+                validate_at(root, item_schema, item, &item_path, &[], out);
+            }
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(name) {
+                    violation(out, &child_path(path, name), "required property is missing");
+                }
+            }
+        }
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (name, property) in object {
+            let property_path = child_path(path, name);
+            match properties.and_then(|p| p.get(name)) {
+                Some(property_schema) => {
+                    validate_at(root, property_schema, property, &property_path, &[], out)
+                }
+                None => match schema.get("additionalProperties") {
+                    Some(Value::Bool(false)) => violation(out, &property_path, "unknown property"),
+                    Some(additional) => {
+                        validate_at(root, additional, property, &property_path, &[], out)
+                    }
+                    None => {}
+                },
+            }
+        }
+    }
+
+    if let Some(Value::Array(schemas)) = schema.get("allOf") {
+        for sub_schema in schemas {
+            validate_at(root, sub_schema, value, path, followed, out);
+        }
+    }
+    for key in ["anyOf", "oneOf"] {
+        if let Some(Value::Array(schemas)) = schema.get(key)
+            && !schemas.iter().any(|sub_schema| {
+                let mut sub_violations = Vec::new();
+                validate_at(root, sub_schema, value, path, followed, &mut sub_violations);
+                sub_violations.is_empty()
+            })
+        {
+            violation(out, path, "does not match any of the allowed schemas");
+        }
+    }
+}
+
+fn violation(out: &mut Vec<SchemaViolation>, path: &str, message: impl Into<String>) {
+    out.push(SchemaViolation {
+        path: path.to_string(),
+        message: message.into(),
+    });
+}
+
+/// Whether a value has the given JSON Schema type.
+fn has_type(value: &Value, schema_type: &str) -> bool {
+    match schema_type {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        // Unknown type names are not enforced
+        _ => true,
+    }
+}
+
+/// JSON Schema type name of a value.
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// JSON pointer to a property of the value at `path` (RFC 6901 escaping).
+fn child_path(path: &str, name: &str) -> String {
+    format!("{}/{}", path, name.replace('~', "~0").replace('/', "~1"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -210,4 +598,259 @@ mod tests {
         assert_eq!(params[1].name, "a_param");
         assert_eq!(params[2].name, "z_param");
     }
+
+    #[test]
+    fn test_enum_values() {
+        let schema = json!({
+            "properties": {
+                "color": { "type": "string", "enum": ["red", "green"] },
+                "level": { "type": "integer", "enum": [1, 2] },
+                "name": { "type": "string" }
+            }
+        });
+
+        let params = extract_params_from_schema(&schema);
+        assert_eq!(params[0].enum_values, vec!["red", "green"]);
+        assert_eq!(params[1].enum_values, vec!["1", "2"]);
+        assert!(params[2].enum_values.is_empty());
+    }
+    #[test]
+    fn test_validate_accepts_matching_value() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "query": { "type": "string", "minLength": 1 },
+                "limit": { "type": "integer", "minimum": 1, "maximum": 100 },
+                "mode": { "enum": ["fast", "full"] }
+            },
+            "required": ["query"]
+        });
+        let value = json!({ "query": "rust", "limit": 10, "mode": "fast" });
+        assert!(validate_against_schema(&schema, &value).is_empty());
+    }
+
+    #[test]
+    fn test_validate_reports_every_violation_with_path() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "limit": { "type": "integer", "maximum": 100 },
+                "mode": { "enum": ["fast", "full"] },
+                "name": { "type": "string" }
+            },
+            "required": ["query"]
+        });
+        let value = json!({ "limit": 500, "mode": "slow", "name": 3 });
+
+        let violations: Vec<String> = validate_against_schema(&schema, &value)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            violations,
+            vec![
+                "/query: required property is missing",
+                "/limit: must be at most 100",
+                "/mode: must be one of: \"fast\", \"full\"",
+                "/name: expected string, got integer",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_nested_objects_and_arrays() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "filter": {
+                    "type": "object",
+                    "properties": { "owner": { "type": "string" } },
+                    "required": ["owner"],
+                    "additionalProperties": false
+                },
+                "tags": { "type": "array", "items": { "type": "string" }, "maxItems": 2 }
+            }
+        });
+        let value = json!({
+            "filter": { "owner/team": "x" },
+            "tags": ["a", 1, "c"]
+        });
+
+        let violations = validate_against_schema(&schema, &value);
+        let paths: Vec<&str> = violations.iter().map(|v| v.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["/filter/owner", "/filter/owner~1team", "/tags", "/tags/1"]
+        );
+    }
+
+    #[test]
+    fn test_validate_integer_and_number_types() {
+        let schema = json!({ "type": "integer" });
+        assert!(validate_against_schema(&schema, &json!(3)).is_empty());
+        assert!(validate_against_schema(&schema, &json!(3.0)).is_empty());
+        assert_eq!(validate_against_schema(&schema, &json!(3.5)).len(), 1);
+
+        let schema = json!({ "type": ["number", "null"] });
+        assert!(validate_against_schema(&schema, &json!(3.5)).is_empty());
+        assert!(validate_against_schema(&schema, &json!(null)).is_empty());
+        assert_eq!(
+            validate_against_schema(&schema, &json!("3"))[0].message,
+            "expected number or null, got string"
+        );
+    }
+
+    #[test]
+    fn test_validate_any_of() {
+        let schema = json!({ "anyOf": [{ "type": "string" }, { "type": "integer" }] });
+        assert!(validate_against_schema(&schema, &json!("a")).is_empty());
+        assert_eq!(validate_against_schema(&schema, &json!(true)).len(), 1);
+    }
+
+    #[test]
+    fn test_validate_empty_schema_accepts_anything() {
+        assert!(validate_against_schema(&json!({}), &json!({ "x": [1, 2] })).is_empty());
+    }
+
+    #[test]
+    fn test_validate_follows_refs_into_defs() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "owner": { "$ref": "#/$defs/Person" },
+                "tree": { "$ref": "#/$defs/Node" }
+            },
+            "$defs": {
+                "Person": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "address": { "$ref": "#/$defs/Address" }
+                    },
+                    "required": ["name"]
+                },
+                "Address": {
+                    "type": "object",
+                    "properties": { "zip": { "type": "string", "maxLength": 5 } },
+                    "additionalProperties": false
+                },
+                "Node": {
+                    "type": "object",
+                    "properties": { "children": { "type": "array", "items": { "$ref": "#/$defs/Node" } } }
+                }
+            }
+        });
+        let value = json!({
+            "owner": { "address": { "zip": "123456", "city": "x" } },
+            "tree": { "children": [{ "children": [{ "children": 3 }] }] }
+        });
+
+        let violations = validate_against_schema(&schema, &value);
+        let paths: Vec<&str> = violations.iter().map(|v| v.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "/owner/name",
+                "/owner/address/city",
+                "/owner/address/zip",
+                "/tree/children/0/children/0/children"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_stops_on_ref_cycles() {
+        let schema = json!({
+            "$ref": "#/$defs/A",
+            "$defs": { "A": { "$ref": "#/$defs/B" }, "B": { "anyOf": [{ "$ref": "#/$defs/A" }] } }
+        });
+        assert!(validate_against_schema(&schema, &json!(1)).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_refs_with_siblings() {
+        let schema = json!({
+            "properties": {
+                "target": { "$ref": "#/$defs/Path", "description": "Target file" }
+            },
+            "$defs": { "Path": { "type": "string", "description": "A path" } }
+        });
+        let resolved = resolve_refs(&schema);
+        assert_eq!(
+            resolved["properties"]["target"],
+            json!({ "type": "string", "description": "Target file" })
+        );
+    }
+
+    #[test]
+    fn test_resolve_refs_leaves_cycles_and_unknown_refs() {
+        let schema = json!({
+            "properties": {
+                "tree": { "$ref": "#/$defs/Node" },
+                "remote": { "$ref": "https://example.com/schema.json" }
+            },
+            "$defs": {
+                "Node": {
+                    "type": "object",
+                    "properties": { "children": { "type": "array", "items": { "$ref": "#/$defs/Node" } } }
+                }
+            }
+        });
+        let resolved = resolve_refs(&schema);
+        let tree = &resolved["properties"]["tree"];
+        assert_eq!(tree["type"], "object");
+        assert_eq!(
+            tree["properties"]["children"]["items"],
+            json!({ "$ref": "#/$defs/Node" })
+        );
+        assert_eq!(
+            resolved["properties"]["remote"],
+            json!({ "$ref": "https://example.com/schema.json" })
+        );
+    }
+
+    #[test]
+    fn test_extract_nested_params() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "query": { "type": "string" },
+                "filter": { "$ref": "#/$defs/Filter" },
+                "sort": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": { "field": { "type": "string" } },
+                        "required": ["field"]
+                    }
+                }
+            },
+            "required": ["query"],
+            "$defs": {
+                "Filter": {
+                    "type": "object",
+                    "properties": {
+                        "tag": { "type": "string" },
+                        "after": { "type": "string" }
+                    },
+                    "required": ["tag"]
+                }
+            }
+        });
+        let names: Vec<(String, bool)> = extract_nested_params(&schema)
+            .into_iter()
+            .map(|p| (p.name, p.required))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("query".to_string(), true),
+                ("filter".to_string(), false),
+                ("filter.tag".to_string(), true),
+                ("filter.after".to_string(), false),
+                ("sort".to_string(), false),
+                ("sort[].field".to_string(), true),
+            ]
+        );
+    }
 }
@@ -0,0 +1,558 @@
+//! Tool schema snapshots and the changes between two of them.
+//!
+//! A [`Snapshot`] records every server's tools with their descriptions and
+//! `inputSchema`s (`mcp snapshot`). [`diff_snapshots`] compares two of them
+//! and classifies each [`Change`] as breaking or not (`mcp diff`): a call
+//! that was valid against the old schema may be rejected by the new one
+//! after a breaking change.
+//!
+//! Breaking changes are removed servers, tools and properties, new required
+//! properties, optional properties becoming required, narrowed types, removed
+//! enum values, tighter bounds or patterns, and closing
+//! `additionalProperties`. The opposite changes are non-breaking, as are
+//! description changes and any other schema edit.
+
+use crate::format::schema::resolve_refs;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Snapshot file format version written by this build
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Tools of every server at one point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// File format version
+    pub version: u32,
+    /// Tools by name, by server name
+    pub servers: BTreeMap<String, BTreeMap<String, ToolSnapshot>>,
+}
+
+/// One tool in a [`Snapshot`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolSnapshot {
+    /// Tool description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// JSON Schema of the tool's arguments
+    #[serde(rename = "inputSchema")]
+    pub input_schema: Value,
+}
+
+impl Default for Snapshot {
+    fn default() -> Self {
+        Self {
+            version: SNAPSHOT_VERSION,
+            servers: BTreeMap::new(),
+        }
+    }
+}
+
+impl Snapshot {
+    /// Record a server, with no tools yet.
+    pub fn add_server(&mut self, server: &str) {
+        self.servers.entry(server.to_string()).or_default();
+    }
+
+    /// Record a tool of `server`.
+    pub fn add_tool(&mut self, server: &str, name: &str, tool: ToolSnapshot) {
+        self.servers
+            .entry(server.to_string())
+            .or_default()
+            .insert(name.to_string(), tool);
+    }
+}
+
+/// One difference between two snapshots.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Change {
+    /// Server the change is in
+    pub server: String,
+    /// Tool the change is in, if it's not about the whole server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    /// Argument the change is about, e.g. `filter.tag` or `items[]`; empty
+    /// for the tool or its arguments object itself
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub path: String,
+    /// Whether calls valid before may now be rejected
+    pub breaking: bool,
+    /// What changed
+    pub message: String,
+}
+
+/// Compare two snapshots, in server, tool and property order.
+///
+/// # Examples
+/// ```
+/// use serde_json::json;
+/// use mcp_cli_rs::format::snapshot::{Snapshot, ToolSnapshot, diff_snapshots};
+///
+/// let tool = |required: Vec<&str>| ToolSnapshot {
+///     description: None,
+///     input_schema: json!({
+///         "type": "object",
+///         "properties": { "path": { "type": "string" }, "mode": { "type": "string" } },
+///         "required": required
+///     }),
+/// };
+/// let mut old = Snapshot::default();
+/// old.add_tool("fs", "read", tool(vec!["path"]));
+/// let mut new = Snapshot::default();
+/// new.add_tool("fs", "read", tool(vec!["path", "mode"]));
+///
+/// let changes = diff_snapshots(&old, &new);
+/// assert_eq!(changes.len(), 1);
+/// assert_eq!(changes[0].path, "mode");
+/// assert!(changes[0].breaking);
+/// ```
+pub fn diff_snapshots(old: &Snapshot, new: &Snapshot) -> Vec<Change> {
+    let mut changes = Vec::new();
+    let server_names: BTreeSet<&String> = old.servers.keys().chain(new.servers.keys()).collect();
+    for server in server_names {
+        let change = |breaking: bool, message: &str| Change {
+            server: server.clone(),
+            tool: None,
+            path: String::new(),
+            breaking,
+            message: message.to_string(),
+        };
+        let (old_tools, new_tools) = match (old.servers.get(server), new.servers.get(server)) {
+            (Some(old_tools), Some(new_tools)) => (old_tools, new_tools),
+            (Some(_), None) => {
+                changes.push(change(true, "server removed"));
+                continue;
+            }
+            (None, Some(_)) => {
+                changes.push(change(false, "server added"));
+                continue;
+            }
+            (None, None) => continue,
+        };
+
+        let tool_names: BTreeSet<&String> = old_tools.keys().chain(new_tools.keys()).collect();
+        for tool in tool_names {
+            let mut tool_changes = Vec::new();
+            match (old_tools.get(tool), new_tools.get(tool)) {
+                (Some(_), None) => tool_changes.push((String::new(), true, "tool removed".into())),
+                (None, Some(_)) => tool_changes.push((String::new(), false, "tool added".into())),
+                (Some(old_tool), Some(new_tool)) => {
+                    diff_tool(old_tool, new_tool, &mut tool_changes)
+                }
+                (None, None) => {}
+            }
+            changes.extend(
+                tool_changes
+                    .into_iter()
+                    .map(|(path, breaking, message)| Change {
+                        server: server.clone(),
+                        tool: Some(tool.clone()),
+                        path,
+                        breaking,
+                        message,
+                    }),
+            );
+        }
+    }
+    changes
+}
+
+/// `(path, breaking, message)` of a change within one tool
+type SchemaChange = (String, bool, String);
+
+fn diff_tool(old: &ToolSnapshot, new: &ToolSnapshot, changes: &mut Vec<SchemaChange>) {
+    if old.description != new.description {
+        changes.push((String::new(), false, "description changed".into()));
+    }
+    if old.input_schema == new.input_schema {
+        return;
+    }
+    let before = changes.len();
+    diff_schema(
+        &resolve_refs(&old.input_schema),
+        &resolve_refs(&new.input_schema),
+        "",
+        changes,
+    );
+    // Report edits none of the checks below understand rather than hiding them
+    if changes.len() == before {
+        changes.push((String::new(), false, "inputSchema changed".into()));
+    }
+}
+
+/// Bounds that reject more values as they grow
+const LOWER_BOUNDS: &[&str] = &[
+    "minimum",
+    "exclusiveMinimum",
+    "minLength",
+    "minItems",
+    "minProperties",
+];
+
+/// Bounds that reject more values as they shrink
+const UPPER_BOUNDS: &[&str] = &[
+    "maximum",
+    "exclusiveMaximum",
+    "maxLength",
+    "maxItems",
+    "maxProperties",
+];
+
+fn diff_schema(old: &Value, new: &Value, path: &str, changes: &mut Vec<SchemaChange>) {
+    let mut push =
+        |breaking: bool, message: String| changes.push((path.to_string(), breaking, message));
+
+    match (type_set(old), type_set(new)) {
+        (None, Some(new_types)) => push(true, format!("type restricted to {}", join(&new_types))),
+        (Some(_), None) => push(false, "type restriction removed".into()),
+        (Some(old_types), Some(new_types)) if old_types != new_types => {
+            // `number` still accepts every `integer`
+            let accepted = |t: &String| {
+                new_types.contains(t) || (t == "integer" && new_types.contains("number"))
+            };
+            let narrowed = !old_types.iter().all(accepted);
+            push(
+                narrowed,
+                format!(
+                    "type changed from {} to {}",
+                    join(&old_types),
+                    join(&new_types)
+                ),
+            );
+        }
+        _ => {}
+    }
+
+    match (value_set(old, "enum"), value_set(new, "enum")) {
+        (None, Some(_)) => push(true, "values restricted to an enum".into()),
+        (Some(_), None) => push(false, "enum restriction removed".into()),
+        (Some(old_values), Some(new_values)) => {
+            let removed: BTreeSet<_> = old_values.difference(&new_values).cloned().collect();
+            let added: BTreeSet<_> = new_values.difference(&old_values).cloned().collect();
+            if !removed.is_empty() {
+                push(true, format!("enum value(s) removed: {}", join(&removed)));
+            }
+            if !added.is_empty() {
+                push(false, format!("enum value(s) added: {}", join(&added)));
+            }
+        }
+        (None, None) => {}
+    }
+    if old.get("const") != new.get("const") {
+        push(new.get("const").is_some(), "const changed".into());
+    }
+
+    for (keys, tighter) in [(LOWER_BOUNDS, true), (UPPER_BOUNDS, false)] {
+        for key in keys.iter().copied() {
+            let (old_bound, new_bound) = (
+                old.get(key).and_then(Value::as_f64),
+                new.get(key).and_then(Value::as_f64),
+            );
+            let breaking = match (old_bound, new_bound) {
+                (None, Some(_)) => true,
+                (Some(_), None) => false,
+                (Some(a), Some(b)) if a != b => (b > a) == tighter,
+                _ => continue,
+            };
+            let message = match new_bound {
+                Some(bound) => format!("{} set to {}", key, bound),
+                None => format!("{} removed", key),
+            };
+            push(breaking, message);
+        }
+    }
+    if old.get("pattern") != new.get("pattern") {
+        let message = match new.get("pattern").and_then(Value::as_str) {
+            Some(pattern) => format!("pattern set to {}", pattern),
+            None => "pattern removed".into(),
+        };
+        push(new.get("pattern").is_some(), message);
+    }
+    let closed = |schema: &Value| schema.get("additionalProperties") == Some(&Value::Bool(false));
+    if closed(old) != closed(new) {
+        let message = if closed(new) {
+            "additional properties no longer accepted"
+        } else {
+            "additional properties now accepted"
+        };
+        push(closed(new), message.into());
+    }
+
+    diff_properties(old, new, path, changes);
+
+    if let (Some(old_items), Some(new_items)) = (old.get("items"), new.get("items")) {
+        diff_schema(old_items, new_items, &format!("{}[]", path), changes);
+    }
+}
+
+fn diff_properties(old: &Value, new: &Value, path: &str, changes: &mut Vec<SchemaChange>) {
+    let empty = serde_json::Map::new();
+    let old_properties = old
+        .get("properties")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    let new_properties = new
+        .get("properties")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    let old_required = value_set(old, "required").unwrap_or_default();
+    let new_required = value_set(new, "required").unwrap_or_default();
+
+    let names: BTreeSet<&String> = old_properties.keys().chain(new_properties.keys()).collect();
+    for name in names {
+        let property_path = if path.is_empty() {
+            name.clone()
+        } else {
+            format!("{}.{}", path, name)
+        };
+        let was_required = old_required.contains(name.as_str());
+        let is_required = new_required.contains(name.as_str());
+        let mut push = |breaking: bool, message: &str| {
+            changes.push((property_path.clone(), breaking, message.to_string()))
+        };
+        match (old_properties.get(name), new_properties.get(name)) {
+            (Some(_), None) => push(true, "property removed"),
+            (None, Some(_)) if is_required => push(true, "required property added"),
+            (None, Some(_)) => push(false, "optional property added"),
+            (Some(old_property), Some(new_property)) => {
+                if !was_required && is_required {
+                    push(true, "property became required");
+                } else if was_required && !is_required {
+                    push(false, "property became optional");
+                }
+                diff_schema(old_property, new_property, &property_path, changes);
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// Types a schema accepts, `None` when it doesn't restrict them.
+fn type_set(schema: &Value) -> Option<BTreeSet<String>> {
+    match schema.get("type")? {
+        Value::String(name) => Some(BTreeSet::from([name.clone()])),
+        Value::Array(names) => Some(
+            names
+                .iter()
+                .filter_map(|name| name.as_str().map(str::to_string))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// Members of an array keyword, as JSON text for non-strings.
+fn value_set(schema: &Value, key: &str) -> Option<BTreeSet<String>> {
+    let values = schema.get(key)?.as_array()?;
+    Some(
+        values
+            .iter()
+            .map(|v| {
+                v.as_str()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| v.to_string())
+            })
+            .collect(),
+    )
+}
+
+fn join(values: &BTreeSet<String>) -> String {
+    values.iter().cloned().collect::<Vec<_>>().join(" | ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn snapshot(schema: Value) -> Snapshot {
+        let mut snapshot = Snapshot::default();
+        snapshot.add_tool(
+            "fs",
+            "search",
+            ToolSnapshot {
+                description: Some("Search files".to_string()),
+                input_schema: schema,
+            },
+        );
+        snapshot
+    }
+
+    fn summary(old: Value, new: Value) -> Vec<(String, bool, String)> {
+        diff_snapshots(&snapshot(old), &snapshot(new))
+            .into_iter()
+            .map(|c| (c.path, c.breaking, c.message))
+            .collect()
+    }
+
+    fn change(path: &str, breaking: bool, message: &str) -> (String, bool, String) {
+        (path.to_string(), breaking, message.to_string())
+    }
+
+    #[test]
+    fn test_identical_snapshots() {
+        let schema = json!({ "type": "object", "properties": { "q": { "type": "string" } } });
+        assert!(summary(schema.clone(), schema).is_empty());
+    }
+
+    #[test]
+    fn test_server_and_tool_changes() {
+        let schema = json!({ "type": "object" });
+        let mut old = snapshot(schema.clone());
+        old.add_server("db");
+        let mut new = snapshot(schema.clone());
+        new.add_server("web");
+        new.servers.get_mut("fs").unwrap().remove("search");
+        new.add_tool(
+            "fs",
+            "read",
+            ToolSnapshot {
+                description: None,
+                input_schema: schema,
+            },
+        );
+
+        let changes: Vec<(String, Option<String>, bool, String)> = diff_snapshots(&old, &new)
+            .into_iter()
+            .map(|c| (c.server, c.tool, c.breaking, c.message))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("db".into(), None, true, "server removed".into()),
+                ("fs".into(), Some("read".into()), false, "tool added".into()),
+                (
+                    "fs".into(),
+                    Some("search".into()),
+                    true,
+                    "tool removed".into()
+                ),
+                ("web".into(), None, false, "server added".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_property_changes() {
+        let old = json!({
+            "type": "object",
+            "properties": {
+                "query": { "type": "string" },
+                "limit": { "type": "integer" },
+                "legacy": { "type": "boolean" },
+                "exact": { "type": "boolean" }
+            },
+            "required": ["query", "exact"]
+        });
+        let new = json!({
+            "type": "object",
+            "properties": {
+                "query": { "type": "string" },
+                "limit": { "type": "integer" },
+                "exact": { "type": "boolean" },
+                "scope": { "type": "string" },
+                "path": { "type": "string" }
+            },
+            "required": ["query", "limit", "path"]
+        });
+        assert_eq!(
+            summary(old, new),
+            vec![
+                change("exact", false, "property became optional"),
+                change("legacy", true, "property removed"),
+                change("limit", true, "property became required"),
+                change("path", true, "required property added"),
+                change("scope", false, "optional property added"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_enum_and_bound_changes() {
+        let old = json!({
+            "type": "object",
+            "properties": {
+                "count": { "type": "integer", "maximum": 100 },
+                "id": { "type": ["string", "integer"] },
+                "mode": { "enum": ["fast", "full"] },
+                "name": { "type": "string", "minLength": 1 }
+            }
+        });
+        let new = json!({
+            "type": "object",
+            "properties": {
+                "count": { "type": "number", "maximum": 10 },
+                "id": { "type": "string" },
+                "mode": { "enum": ["fast", "auto"] },
+                "name": { "type": "string" }
+            },
+            "additionalProperties": false
+        });
+        assert_eq!(
+            summary(old, new),
+            vec![
+                change("", true, "additional properties no longer accepted"),
+                change("count", false, "type changed from integer to number"),
+                change("count", true, "maximum set to 10"),
+                change("id", true, "type changed from integer | string to string"),
+                change("mode", true, "enum value(s) removed: full"),
+                change("mode", false, "enum value(s) added: auto"),
+                change("name", false, "minLength removed"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_changes_through_refs() {
+        let old = json!({
+            "type": "object",
+            "properties": {
+                "filter": { "$ref": "#/$defs/Filter" },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            },
+            "$defs": {
+                "Filter": { "type": "object", "properties": { "tag": { "type": "string" } } }
+            }
+        });
+        let new = json!({
+            "type": "object",
+            "properties": {
+                "filter": {
+                    "type": "object",
+                    "properties": { "tag": { "type": "string" } },
+                    "required": ["tag"]
+                },
+                "tags": { "type": "array", "items": { "type": "string", "maxLength": 20 } }
+            }
+        });
+        assert_eq!(
+            summary(old, new),
+            vec![
+                change("filter.tag", true, "property became required"),
+                change("tags[]", true, "maxLength set to 20"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unrecognized_schema_edits_are_reported() {
+        let old = json!({ "type": "object", "properties": { "q": { "type": "string" } } });
+        let new = json!({
+            "type": "object",
+            "properties": { "q": { "type": "string", "description": "Query" } }
+        });
+        assert_eq!(
+            summary(old, new),
+            vec![change("", false, "inputSchema changed")]
+        );
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let snapshot = snapshot(json!({ "type": "object" }));
+        let text = serde_json::to_string(&snapshot).unwrap();
+        assert!(text.contains("\"inputSchema\""));
+        assert_eq!(serde_json::from_str::<Snapshot>(&text).unwrap(), snapshot);
+    }
+}
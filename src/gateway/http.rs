@@ -0,0 +1,501 @@
+//! Gateway over Streamable HTTP (`mcp serve --http ADDR`).
+//!
+//! Serves MCP at `/mcp` so several local agents can share one set of warm
+//! backend connections:
+//!
+//! - `initialize` opens a session and returns its `Mcp-Session-Id`; later
+//!   requests without it get 400, and unknown or deleted sessions get 404.
+//! - `allowed_tools` and `disabled_tools` query parameters on the
+//!   `initialize` request narrow the session's tools, as comma-separated
+//!   globs over `server__tool` names.
+//! - GET opens an SSE stream carrying `notifications/tools/list_changed`.
+//! - DELETE ends the session. Sessions idle for longer than the idle timeout
+//!   expire and get 404 like deleted ones; once the session limit is
+//!   reached, new `initialize` requests get 503.
+//! - With a token set, every request needs `Authorization: Bearer <token>`.
+//!
+//! Requests from browser pages on other origins are refused, so a website
+//! can't reach the gateway through DNS rebinding.
+
+use super::{
+    Gateway, INVALID_REQUEST, PARSE_ERROR, Session, ToolFilter, error_response, tools_list_changed,
+};
+use crate::error::{McpError, Result};
+use hyper::body::Bytes;
+use hyper::header::{self, HeaderValue};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Path the MCP endpoint is served at
+pub const MCP_PATH: &str = "/mcp";
+
+/// Header carrying the session ID
+const SESSION_HEADER: &str = "mcp-session-id";
+
+/// How long a session without requests or open streams is kept
+pub const DEFAULT_SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Most sessions open at once
+pub const DEFAULT_MAX_SESSIONS: usize = 256;
+
+/// A session and the SSE streams its client has open
+struct HttpSession {
+    session: Session,
+    streams: Mutex<Vec<mpsc::UnboundedSender<Value>>>,
+    last_used: Mutex<Instant>,
+}
+
+impl HttpSession {
+    fn new(filter: ToolFilter) -> Self {
+        Self {
+            session: Session::new(filter),
+            streams: Mutex::new(Vec::new()),
+            last_used: Mutex::new(Instant::now()),
+        }
+    }
+
+    fn touch(&self) {
+        *self.last_used.lock().unwrap() = Instant::now();
+    }
+
+    /// Whether the client has neither sent a request within `idle_timeout`
+    /// nor kept a stream open.
+    fn is_expired(&self, idle_timeout: Duration) -> bool {
+        let has_stream = self
+            .streams
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|stream| !stream.is_closed());
+        !has_stream && self.last_used.lock().unwrap().elapsed() > idle_timeout
+    }
+
+    /// Send a message on every open stream, dropping closed ones.
+    fn notify(&self, message: &Value) {
+        self.streams
+            .lock()
+            .unwrap()
+            .retain(|stream| stream.send(message.clone()).is_ok());
+    }
+}
+
+/// Streamable HTTP front end of a [`Gateway`]
+pub struct HttpGateway {
+    gateway: Arc<Gateway>,
+    token: Option<String>,
+    sessions: Mutex<HashMap<String, Arc<HttpSession>>>,
+    idle_timeout: Duration,
+    max_sessions: usize,
+}
+
+impl HttpGateway {
+    /// Serve `gateway` without authentication.
+    pub fn new(gateway: Arc<Gateway>) -> Self {
+        Self {
+            gateway,
+            token: None,
+            sessions: Mutex::new(HashMap::new()),
+            idle_timeout: DEFAULT_SESSION_IDLE_TIMEOUT,
+            max_sessions: DEFAULT_MAX_SESSIONS,
+        }
+    }
+
+    /// Require `Authorization: Bearer <token>` on every request.
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+
+    /// Expire sessions after `idle_timeout` without requests or open streams.
+    pub fn with_session_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Refuse new sessions while `max_sessions` are open.
+    pub fn with_max_sessions(mut self, max_sessions: usize) -> Self {
+        self.max_sessions = max_sessions;
+        self
+    }
+
+    /// Serve on `listener` until `shutdown` completes.
+    ///
+    /// # Errors
+    /// Returns McpError::IOError if the listener can't be served
+    pub async fn serve(
+        self: Arc<Self>,
+        listener: tokio::net::TcpListener,
+        shutdown: impl Future<Output = ()>,
+    ) -> Result<()> {
+        let listener = listener.into_std().map_err(McpError::io_error)?;
+        let service_gateway = Arc::clone(&self);
+        let make_svc = make_service_fn(move |_conn| {
+            let gateway = Arc::clone(&service_gateway);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req| {
+                    let gateway = Arc::clone(&gateway);
+                    async move { Ok::<_, Infallible>(gateway.handle_request(req).await) }
+                }))
+            }
+        });
+        let server = Server::from_tcp(listener)
+            .map_err(|e| McpError::io_error(std::io::Error::other(e)))?
+            .serve(make_svc);
+
+        let poller = tokio::spawn(Arc::clone(&self).poll_tool_changes());
+        let result = server.with_graceful_shutdown(shutdown).await;
+        poller.abort();
+        result.map_err(|e| McpError::io_error(std::io::Error::other(e)))
+    }
+
+    /// Notify sessions whose tools changed, re-listing backends once per
+    /// interval for all of them.
+    async fn poll_tool_changes(self: Arc<Self>) {
+        loop {
+            tokio::time::sleep(self.gateway.poll_interval()).await;
+            self.remove_expired_sessions(&mut self.sessions.lock().unwrap());
+            let sessions: Vec<_> = self.sessions.lock().unwrap().values().cloned().collect();
+            if !sessions.iter().any(|s| s.session.has_listed()) {
+                continue;
+            }
+            let tools = self.gateway.list_tools().await;
+            for session in sessions {
+                if session.session.refresh(&tools) {
+                    session.notify(&tools_list_changed());
+                }
+            }
+        }
+    }
+
+    async fn handle_request(&self, req: Request<Body>) -> Response<Body> {
+        if req.uri().path() != MCP_PATH {
+            return status_response(StatusCode::NOT_FOUND);
+        }
+        if !origin_allowed(&req) {
+            return status_response(StatusCode::FORBIDDEN);
+        }
+        if !authorized(&req, self.token.as_deref()) {
+            return unauthorized_response();
+        }
+
+        match *req.method() {
+            Method::POST => self.handle_post(req).await,
+            Method::GET => self.handle_get(&req),
+            Method::DELETE => self.handle_delete(&req),
+            _ => {
+                let mut response = status_response(StatusCode::METHOD_NOT_ALLOWED);
+                response
+                    .headers_mut()
+                    .insert(header::ALLOW, HeaderValue::from_static("GET, POST, DELETE"));
+                response
+            }
+        }
+    }
+
+    async fn handle_post(&self, req: Request<Body>) -> Response<Body> {
+        let session = self.session(&req);
+        let filter = query_filter(&req);
+        let body = match hyper::body::to_bytes(req.into_body()).await {
+            Ok(body) => body,
+            Err(_) => return status_response(StatusCode::BAD_REQUEST),
+        };
+        let message: Value = match serde_json::from_slice(&body) {
+            Ok(message) => message,
+            Err(e) => {
+                let error =
+                    error_response(Value::Null, PARSE_ERROR, &format!("Parse error: {}", e));
+                return json_response(StatusCode::BAD_REQUEST, &error);
+            }
+        };
+        if !message.is_object() {
+            let error = error_response(Value::Null, INVALID_REQUEST, "Invalid request");
+            return json_response(StatusCode::BAD_REQUEST, &error);
+        }
+
+        if message.get("method").and_then(Value::as_str) == Some("initialize") {
+            return self.initialize(message, filter).await;
+        }
+        let session = match session {
+            Ok(session) => session,
+            Err(status) => return status_response(status),
+        };
+        match self.gateway.handle_in(&session.session, message).await {
+            Some(reply) => json_response(StatusCode::OK, &reply),
+            // Notifications and responses are acknowledged without a body
+            None => status_response(StatusCode::ACCEPTED),
+        }
+    }
+
+    /// Open a session and answer its `initialize` request.
+    async fn initialize(&self, message: Value, filter: ToolFilter) -> Response<Body> {
+        let session = Arc::new(HttpSession::new(filter));
+        let session_id = hex::encode(rand::random::<[u8; 16]>());
+        {
+            // Counted and added under one lock, so parallel initializes
+            // can't open more than the limit
+            let mut sessions = self.sessions.lock().unwrap();
+            self.remove_expired_sessions(&mut sessions);
+            if sessions.len() >= self.max_sessions {
+                tracing::warn!(
+                    "Gateway: refusing session, {} already open",
+                    self.max_sessions
+                );
+                return status_response(StatusCode::SERVICE_UNAVAILABLE);
+            }
+            sessions.insert(session_id.clone(), Arc::clone(&session));
+        }
+
+        let reply = self.gateway.handle_in(&session.session, message).await;
+        if reply
+            .as_ref()
+            .is_none_or(|reply| reply.get("error").is_some())
+        {
+            self.sessions.lock().unwrap().remove(&session_id);
+        }
+        let Some(reply) = reply else {
+            return status_response(StatusCode::ACCEPTED);
+        };
+        if reply.get("error").is_some() {
+            return json_response(StatusCode::OK, &reply);
+        }
+        tracing::debug!("Gateway: opened session {}", session_id);
+
+        let mut response = json_response(StatusCode::OK, &reply);
+        if let Ok(value) = HeaderValue::from_str(&session_id) {
+            response.headers_mut().insert(SESSION_HEADER, value);
+        }
+        response
+    }
+
+    /// Open an SSE stream for server-to-client notifications.
+    fn handle_get(&self, req: &Request<Body>) -> Response<Body> {
+        let session = match self.session(req) {
+            Ok(session) => session,
+            Err(status) => return status_response(status),
+        };
+
+        let (tx, mut rx) = mpsc::unbounded_channel::<Value>();
+        session.streams.lock().unwrap().push(tx);
+        let (mut sender, body) = Body::channel();
+        tokio::spawn(async move {
+            while let Some(message) = rx.recv().await {
+                let event = format!("data: {}\n\n", message);
+                if sender.send_data(Bytes::from(event)).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "text/event-stream")
+            .header(header::CACHE_CONTROL, "no-cache")
+            .body(body)
+            .unwrap()
+    }
+
+    /// End a session, closing its streams.
+    fn handle_delete(&self, req: &Request<Body>) -> Response<Body> {
+        if let Err(status) = self.session(req) {
+            return status_response(status);
+        }
+        let session_id = session_id(req).unwrap_or_default();
+        self.sessions.lock().unwrap().remove(session_id);
+        tracing::debug!("Gateway: closed session {}", session_id);
+        status_response(StatusCode::OK)
+    }
+
+    /// Look up the request's session: 400 without the header, 404 if
+    /// unknown or expired.
+    fn session(&self, req: &Request<Body>) -> std::result::Result<Arc<HttpSession>, StatusCode> {
+        let session_id = session_id(req).ok_or(StatusCode::BAD_REQUEST)?;
+        let mut sessions = self.sessions.lock().unwrap();
+        let session = sessions.get(session_id).ok_or(StatusCode::NOT_FOUND)?;
+        if session.is_expired(self.idle_timeout) {
+            sessions.remove(session_id);
+            tracing::debug!("Gateway: session {} expired", session_id);
+            return Err(StatusCode::NOT_FOUND);
+        }
+        session.touch();
+        Ok(Arc::clone(session))
+    }
+
+    /// Drop the expired ones among `sessions`.
+    fn remove_expired_sessions(&self, sessions: &mut HashMap<String, Arc<HttpSession>>) {
+        sessions.retain(|session_id, session| {
+            let expired = session.is_expired(self.idle_timeout);
+            if expired {
+                tracing::debug!("Gateway: session {} expired", session_id);
+            }
+            !expired
+        });
+    }
+}
+
+/// Serve `gateway` over Streamable HTTP on `addr` until Ctrl+C.
+///
+/// # Errors
+/// Returns McpError::IOError if `addr` can't be bound
+pub async fn serve_http(
+    gateway: Arc<Gateway>,
+    addr: SocketAddr,
+    token: Option<String>,
+) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(McpError::io_error)?;
+    let bound = listener.local_addr().map_err(McpError::io_error)?;
+    crate::output::print_info(&format!(
+        "Gateway listening on http://{}{}",
+        bound, MCP_PATH
+    ));
+    if token.is_none() && !bound.ip().is_loopback() {
+        crate::output::print_warning(
+            "Serving on a non-loopback address without --token; anyone who can reach it can call every tool",
+        );
+    }
+
+    let gateway = Arc::new(HttpGateway::new(gateway).with_token(token));
+    gateway
+        .serve(listener, async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await
+}
+
+fn session_id(req: &Request<Body>) -> Option<&str> {
+    req.headers()
+        .get(SESSION_HEADER)
+        .and_then(|value| value.to_str().ok())
+}
+
+/// Read the session tool filter from the request's query string.
+fn query_filter(req: &Request<Body>) -> ToolFilter {
+    let mut filter = ToolFilter::default();
+    let Some(query) = req.uri().query() else {
+        return filter;
+    };
+    let Ok(url) = reqwest::Url::parse(&format!("http://localhost/?{}", query)) else {
+        return filter;
+    };
+    for (key, value) in url.query_pairs() {
+        let patterns = match key.as_ref() {
+            "allowed_tools" => &mut filter.allowed,
+            "disabled_tools" => &mut filter.disabled,
+            _ => continue,
+        };
+        patterns.extend(
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|pattern| !pattern.is_empty())
+                .map(str::to_string),
+        );
+    }
+    filter
+}
+
+/// Check the request's bearer token, if one is required.
+pub(super) fn authorized(req: &Request<Body>, token: Option<&str>) -> bool {
+    let Some(token) = token else {
+        return true;
+    };
+    req.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|presented| constant_time_eq(presented.as_bytes(), token.as_bytes()))
+}
+
+/// Allow requests without an `Origin` and those from local pages.
+pub(super) fn origin_allowed(req: &Request<Body>) -> bool {
+    let Some(origin) = req.headers().get(header::ORIGIN) else {
+        return true;
+    };
+    let host = origin
+        .to_str()
+        .ok()
+        .and_then(|origin| reqwest::Url::parse(origin).ok())
+        .and_then(|url| url.host_str().map(str::to_string));
+    matches!(host.as_deref(), Some("localhost" | "127.0.0.1" | "[::1]"))
+}
+
+/// Compare secrets without exiting early on the first differing byte.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+pub(super) fn json_response(status: StatusCode, message: &Value) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(message.to_string()))
+        .unwrap()
+}
+
+pub(super) fn status_response(status: StatusCode) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::empty())
+        .unwrap()
+}
+
+pub(super) fn unauthorized_response() -> Response<Body> {
+    let mut response = status_response(StatusCode::UNAUTHORIZED);
+    response
+        .headers_mut()
+        .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(uri: &str) -> Request<Body> {
+        Request::builder().uri(uri).body(Body::empty()).unwrap()
+    }
+
+    #[test]
+    fn test_query_filter() {
+        let filter = query_filter(&request(
+            "/mcp?allowed_tools=fs__*,github__search&disabled_tools=fs__write%2A",
+        ));
+        assert_eq!(filter.allowed, vec!["fs__*", "github__search"]);
+        assert_eq!(filter.disabled, vec!["fs__write*"]);
+
+        let filter = query_filter(&request("/mcp"));
+        assert!(filter.allowed.is_empty() && filter.disabled.is_empty());
+    }
+
+    #[test]
+    fn test_origin_allowed() {
+        let with_origin = |origin: &str| {
+            Request::builder()
+                .uri("/mcp")
+                .header("Origin", origin)
+                .body(Body::empty())
+                .unwrap()
+        };
+        assert!(origin_allowed(&request("/mcp")));
+        assert!(origin_allowed(&with_origin("http://localhost:3000")));
+        assert!(origin_allowed(&with_origin("http://127.0.0.1")));
+        assert!(!origin_allowed(&with_origin("https://evil.example")));
+        assert!(!origin_allowed(&with_origin("null")));
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+    }
+}
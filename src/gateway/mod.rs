@@ -0,0 +1,540 @@
+//! Gateway mode: serve every configured server as one MCP server.
+//!
+//! The [`Gateway`] answers MCP requests itself. `tools/list` advertises the
+//! tools of all configured servers as `server__tool`, filtered by each
+//! server's `allowed_tools`/`disabled_tools`, and `tools/call` forwards to
+//! the backend through a [`ConnectionPool`] so connections stay warm.
+//! Backends are re-listed once per poll interval by each transport's
+//! poller; `tools/list` answers from that listing.
+//!
+//! Each client is a [`Session`], which may narrow the tools further with a
+//! [`ToolFilter`] and remembers what it was last told so it can be notified
+//! when that changes.
+//!
+//! # Module Structure
+//!
+//! - [`stdio`] — Newline-delimited JSON-RPC over stdin/stdout (`mcp serve --stdio`)
+//! - [`http`] — Streamable HTTP with sessions and bearer-token auth (`mcp serve --http`)
+//! - [`rest`] — Plain HTTP endpoints per tool plus an OpenAPI document (`mcp serve --rest`)
+
+pub mod http;
+pub mod rest;
+pub mod stdio;
+
+use crate::cli::filter::tools_match_any;
+use crate::config::{Config, ServerConfig};
+use crate::daemon::pool::ConnectionPool;
+use crate::daemon::protocol::ToolInfo;
+use crate::error::{McpError, Result};
+use crate::parallel::is_tool_allowed;
+use serde_json::{Value, json};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Separator between server and tool in advertised tool names
+pub const TOOL_NAME_SEPARATOR: &str = "__";
+
+/// How often backends are re-listed to notice tool changes
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Protocol versions the gateway can speak, newest last
+const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2024-11-05", "2025-03-26", "2025-06-18"];
+
+/// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Glob patterns over advertised (`server__tool`) names narrowing what one
+/// client sees, on top of the servers' own filters
+#[derive(Debug, Clone, Default)]
+pub struct ToolFilter {
+    /// Only these tools are visible, unless empty
+    pub allowed: Vec<String>,
+    /// These tools are hidden, even if allowed
+    pub disabled: Vec<String>,
+}
+
+impl ToolFilter {
+    /// Check an advertised tool name against the filter.
+    pub fn allows(&self, exposed_name: &str) -> bool {
+        if tools_match_any(exposed_name, &self.disabled).is_some() {
+            return false;
+        }
+        self.allowed.is_empty() || tools_match_any(exposed_name, &self.allowed).is_some()
+    }
+}
+
+/// One client of the gateway
+#[derive(Debug, Default)]
+pub struct Session {
+    filter: ToolFilter,
+    /// Tool names last sent in a `tools/list` response, `None` until then
+    advertised: Mutex<Option<BTreeSet<String>>>,
+}
+
+impl Session {
+    /// Create a session that sees only the tools `filter` allows.
+    pub fn new(filter: ToolFilter) -> Self {
+        Self {
+            filter,
+            advertised: Mutex::new(None),
+        }
+    }
+
+    /// Tools this session may see and call
+    pub fn filter(&self) -> &ToolFilter {
+        &self.filter
+    }
+
+    /// Whether the client has listed tools yet.
+    pub fn has_listed(&self) -> bool {
+        self.advertised.lock().unwrap().is_some()
+    }
+
+    /// Record the tools now available and report whether the client's view
+    /// changed since it was last told.
+    ///
+    /// Always `false` before the first `tools/list`, since there is nothing
+    /// for the client to refresh yet.
+    pub fn refresh(&self, tools: &[(String, ToolInfo)]) -> bool {
+        let names = self.visible_names(tools);
+        let mut advertised = self.advertised.lock().unwrap();
+        let Some(previous) = advertised.as_ref() else {
+            return false;
+        };
+        let changed = *previous != names;
+        *advertised = Some(names);
+        changed
+    }
+
+    fn visible_names(&self, tools: &[(String, ToolInfo)]) -> BTreeSet<String> {
+        tools
+            .iter()
+            .map(|(server, tool)| exposed_name(server, &tool.name))
+            .filter(|name| self.filter.allows(name))
+            .collect()
+    }
+}
+
+/// One MCP server fronting all configured servers
+pub struct Gateway {
+    config: Arc<Config>,
+    pool: Arc<ConnectionPool>,
+    poll_interval: Duration,
+    /// Unfiltered session used by single-client transports such as stdio
+    session: Session,
+    /// Allowed tools of each server as last listed
+    tools: Mutex<HashMap<String, Vec<ToolInfo>>>,
+    /// Whether every server has been listed at least once
+    listed: AtomicBool,
+}
+
+impl Gateway {
+    /// Create a gateway with its own connection pool.
+    pub fn new(config: Arc<Config>) -> Self {
+        let pool = Arc::new(ConnectionPool::new(Arc::clone(&config)));
+        Self::with_pool(config, pool)
+    }
+
+    /// Create a gateway that forwards through an existing pool.
+    pub fn with_pool(config: Arc<Config>, pool: Arc<ConnectionPool>) -> Self {
+        Self {
+            config,
+            pool,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            session: Session::default(),
+            tools: Mutex::new(HashMap::new()),
+            listed: AtomicBool::new(false),
+        }
+    }
+
+    /// Set how often backends are re-listed to notice tool changes.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// How often backends should be re-listed to notice tool changes.
+    pub fn poll_interval(&self) -> Duration {
+        self.poll_interval
+    }
+
+    /// Servers this gateway fronts
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Pool the gateway forwards calls through
+    pub fn pool(&self) -> &ConnectionPool {
+        &self.pool
+    }
+
+    /// List the allowed tools of every configured server.
+    ///
+    /// Returns `(server, tool)` pairs in config order. A server that can't
+    /// list its tools is left out so one broken backend doesn't hide the rest.
+    /// The listing is remembered for [`Self::cached_tools`] and
+    /// [`Self::find_tool`].
+    pub async fn list_tools(&self) -> Vec<(String, ToolInfo)> {
+        let listings = self
+            .config
+            .servers
+            .iter()
+            .map(|server| async move { (server, self.list_server_tools(server).await) });
+        let listings = futures::future::join_all(listings).await;
+
+        let mut cached = self.tools.lock().unwrap();
+        let mut tools = Vec::new();
+        for (server, listing) in listings {
+            match listing {
+                Ok(server_tools) => {
+                    cached.insert(server.name.clone(), server_tools.clone());
+                    tools.extend(
+                        server_tools
+                            .into_iter()
+                            .map(|tool| (server.name.clone(), tool)),
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!("Gateway: listing tools of '{}' failed: {}", server.name, e);
+                    cached.remove(&server.name);
+                }
+            }
+        }
+        self.listed.store(true, Ordering::Release);
+        tools
+    }
+
+    /// The allowed tools of every server as last listed, in config order.
+    ///
+    /// Backends are listed only until the first full listing; after that,
+    /// keeping the cache current is left to the transport's poller.
+    pub async fn cached_tools(&self) -> Vec<(String, ToolInfo)> {
+        if !self.listed.load(Ordering::Acquire) {
+            return self.list_tools().await;
+        }
+        let cached = self.tools.lock().unwrap();
+        self.config
+            .servers
+            .iter()
+            .filter_map(|server| cached.get(&server.name).map(|tools| (server, tools)))
+            .flat_map(|(server, tools)| {
+                tools.iter().map(|tool| (server.name.clone(), tool.clone()))
+            })
+            .collect()
+    }
+
+    /// Look up an allowed tool of `server` among the tools last listed.
+    ///
+    /// The server is listed again only when the tool isn't there, so a tool
+    /// added since the last listing is still found.
+    ///
+    /// # Errors
+    /// Returns the pool's error if the server can't list its tools
+    pub async fn find_tool(&self, server: &str, tool: &str) -> Result<Option<ToolInfo>> {
+        let find = |tools: &[ToolInfo]| tools.iter().find(|info| info.name == tool).cloned();
+        if let Some(info) = self.tools.lock().unwrap().get(server).and_then(|t| find(t)) {
+            return Ok(Some(info));
+        }
+        let Some(server_config) = self.config.get_server(server) else {
+            return Ok(None);
+        };
+
+        let tools = self.list_server_tools(server_config).await?;
+        let info = find(&tools);
+        self.tools.lock().unwrap().insert(server.to_string(), tools);
+        Ok(info)
+    }
+
+    async fn list_server_tools(&self, server: &ServerConfig) -> Result<Vec<ToolInfo>> {
+        let tools = self.pool.list_tools(&server.name).await?;
+        Ok(tools
+            .into_iter()
+            .filter(|tool| is_tool_allowed(&tool.name, server))
+            .collect())
+    }
+
+    /// Re-list every backend and report whether the advertised tools changed.
+    ///
+    /// This is the poller's step for single-client transports. Always
+    /// `false` before the first `tools/list`, since there is nothing for a
+    /// client to refresh yet.
+    pub async fn tools_changed(&self) -> bool {
+        if !self.session.has_listed() {
+            return false;
+        }
+        self.session.refresh(&self.list_tools().await)
+    }
+
+    /// Handle one JSON-RPC message and return the response to send.
+    ///
+    /// Notifications and responses get no reply and return `None`.
+    pub async fn handle(&self, message: Value) -> Option<Value> {
+        self.handle_in(&self.session, message).await
+    }
+
+    /// Handle one JSON-RPC message from the client of `session`.
+    pub async fn handle_in(&self, session: &Session, message: Value) -> Option<Value> {
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            if message.get("result").is_some() || message.get("error").is_some() {
+                return None;
+            }
+            let id = message.get("id").cloned().unwrap_or(Value::Null);
+            return Some(error_response(id, INVALID_REQUEST, "Invalid request"));
+        };
+        let id = message.get("id").cloned()?;
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        let result = match method {
+            "initialize" => Ok(self.initialize(&params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(self.tools_list(session).await),
+            "tools/call" => self.tools_call(session, &params).await,
+            _ => Err((METHOD_NOT_FOUND, format!("Method not found: {}", method))),
+        };
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_response(id, code, &message),
+        })
+    }
+
+    /// Handle one line of newline-delimited JSON-RPC.
+    pub async fn handle_line(&self, line: &str) -> Option<Value> {
+        match serde_json::from_str(line) {
+            Ok(message) => self.handle(message).await,
+            Err(e) => Some(error_response(
+                Value::Null,
+                PARSE_ERROR,
+                &format!("Parse error: {}", e),
+            )),
+        }
+    }
+
+    fn initialize(&self, params: &Value) -> Value {
+        let requested = params.get("protocolVersion").and_then(Value::as_str);
+        let version = requested
+            .filter(|v| SUPPORTED_PROTOCOL_VERSIONS.contains(v))
+            .or(SUPPORTED_PROTOCOL_VERSIONS.last().copied());
+        json!({
+            "protocolVersion": version,
+            "capabilities": { "tools": { "listChanged": true } },
+            "serverInfo": {
+                "name": env!("CARGO_PKG_NAME"),
+                "version": env!("CARGO_PKG_VERSION")
+            }
+        })
+    }
+
+    async fn tools_list(&self, session: &Session) -> Value {
+        let tools = self.cached_tools().await;
+        *session.advertised.lock().unwrap() = Some(session.visible_names(&tools));
+
+        let tools: Vec<Value> = tools
+            .iter()
+            .filter_map(|(server, tool)| {
+                let name = exposed_name(server, &tool.name);
+                session.filter.allows(&name).then(|| {
+                    json!({
+                        "name": name,
+                        "description": tool.description,
+                        "inputSchema": tool.input_schema
+                    })
+                })
+            })
+            .collect();
+        json!({ "tools": tools })
+    }
+
+    async fn tools_call(
+        &self,
+        session: &Session,
+        params: &Value,
+    ) -> std::result::Result<Value, (i64, String)> {
+        let name = params.get("name").and_then(Value::as_str).unwrap_or("");
+        let unknown = || (INVALID_PARAMS, format!("Unknown tool: {}", name));
+        let (server, tool) = split_exposed_name(name).ok_or_else(unknown)?;
+        let server_config = self.config.get_server(server).ok_or_else(unknown)?;
+        if !is_tool_allowed(tool, server_config) || !session.filter.allows(name) {
+            return Err(unknown());
+        }
+
+        let arguments = params
+            .get("arguments")
+            .cloned()
+            .unwrap_or_else(|| json!({}));
+        match self.pool.execute(server, tool, arguments).await {
+            Ok(result) => Ok(result),
+            // Failures of the backend are tool errors the model can see
+            Err(e) => Ok(json!({
+                "content": [{ "type": "text", "text": e.to_string() }],
+                "isError": true
+            })),
+        }
+    }
+}
+
+/// Name a backend tool is advertised under (`server__tool`).
+pub fn exposed_name(server: &str, tool: &str) -> String {
+    format!("{}{}{}", server, TOOL_NAME_SEPARATOR, tool)
+}
+
+/// Check that every server name can be recovered from its advertised tool
+/// names.
+///
+/// Tool names are split at the first [`TOOL_NAME_SEPARATOR`], so a server
+/// whose name contains it would have its calls routed elsewhere.
+///
+/// # Errors
+/// Returns McpError::UsageError naming the first such server
+pub fn check_server_names(config: &Config) -> Result<()> {
+    match config
+        .servers
+        .iter()
+        .find(|server| server.name.contains(TOOL_NAME_SEPARATOR))
+    {
+        Some(server) => Err(McpError::usage_error(format!(
+            "Server name '{}' contains '{}', which the gateway uses to separate server and tool names; rename the server to serve it",
+            server.name, TOOL_NAME_SEPARATOR
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Split an advertised tool name into server and tool.
+pub fn split_exposed_name(name: &str) -> Option<(&str, &str)> {
+    name.split_once(TOOL_NAME_SEPARATOR)
+        .filter(|(server, tool)| !server.is_empty() && !tool.is_empty())
+}
+
+/// The `notifications/tools/list_changed` message
+pub fn tools_list_changed() -> Value {
+    json!({ "jsonrpc": "2.0", "method": "notifications/tools/list_changed" })
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gateway() -> Gateway {
+        Gateway::new(Arc::new(Config::default()))
+    }
+
+    #[test]
+    fn test_exposed_names_round_trip() {
+        assert_eq!(exposed_name("fs", "read_file"), "fs__read_file");
+        assert_eq!(
+            split_exposed_name("fs__read_file"),
+            Some(("fs", "read_file"))
+        );
+        assert_eq!(split_exposed_name("fs__my__tool"), Some(("fs", "my__tool")));
+        assert_eq!(split_exposed_name("read_file"), None);
+        assert_eq!(split_exposed_name("__read_file"), None);
+    }
+
+    #[test]
+    fn test_check_server_names_rejects_separator() {
+        let config_with = |name: &str| Config {
+            servers: vec![crate::config::ServerConfig {
+                name: name.to_string(),
+                transport: crate::config::ServerTransport::Stdio {
+                    command: "server".to_string(),
+                    args: vec![],
+                    env: Default::default(),
+                    cwd: None,
+                },
+                description: None,
+                allowed_tools: None,
+                disabled_tools: None,
+                timeout_secs: None,
+            }],
+            ..Config::default()
+        };
+
+        assert!(check_server_names(&config_with("my_fs")).is_ok());
+        assert!(matches!(
+            check_server_names(&config_with("my__fs")),
+            Err(McpError::UsageError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_tool_filter() {
+        assert!(ToolFilter::default().allows("fs__read_file"));
+
+        let filter = ToolFilter {
+            allowed: vec!["fs__*".to_string()],
+            disabled: vec!["fs__write_*".to_string()],
+        };
+        assert!(filter.allows("fs__read_file"));
+        assert!(!filter.allows("fs__write_file"));
+        assert!(!filter.allows("github__search"));
+    }
+
+    #[tokio::test]
+    async fn test_initialize_negotiates_version() {
+        let response = gateway()
+            .handle(json!({
+                "jsonrpc": "2.0", "id": 1, "method": "initialize",
+                "params": { "protocolVersion": "2024-11-05" }
+            }))
+            .await
+            .unwrap();
+        assert_eq!(response["result"]["protocolVersion"], "2024-11-05");
+        assert_eq!(
+            response["result"]["capabilities"]["tools"]["listChanged"],
+            true
+        );
+
+        let response = gateway()
+            .handle(json!({
+                "jsonrpc": "2.0", "id": 1, "method": "initialize",
+                "params": { "protocolVersion": "1999-01-01" }
+            }))
+            .await
+            .unwrap();
+        assert_eq!(response["result"]["protocolVersion"], "2025-06-18");
+    }
+
+    #[tokio::test]
+    async fn test_notifications_get_no_reply() {
+        let reply = gateway()
+            .handle(json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))
+            .await;
+        assert!(reply.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_errors() {
+        let gateway = gateway();
+        let reply = gateway.handle_line("{not json").await.unwrap();
+        assert_eq!(reply["error"]["code"], PARSE_ERROR);
+
+        let reply = gateway
+            .handle(json!({ "jsonrpc": "2.0", "id": 2, "method": "resources/list" }))
+            .await
+            .unwrap();
+        assert_eq!(reply["id"], 2);
+        assert_eq!(reply["error"]["code"], METHOD_NOT_FOUND);
+
+        let reply = gateway
+            .handle(json!({
+                "jsonrpc": "2.0", "id": 3, "method": "tools/call",
+                "params": { "name": "nowhere__tool" }
+            }))
+            .await
+            .unwrap();
+        assert_eq!(reply["error"]["code"], INVALID_PARAMS);
+    }
+}
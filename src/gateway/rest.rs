@@ -0,0 +1,589 @@
+//! REST bridge (`mcp serve --rest ADDR`).
+//!
+//! Exposes every allowed tool as a plain HTTP endpoint for services that
+//! can't speak MCP:
+//!
+//! - `POST /servers/{server}/tools/{tool}` takes the arguments as a JSON
+//!   body, checks them against the tool's `inputSchema` and returns the
+//!   tool result. Both names are percent-encoded.
+//! - `GET /openapi.json` describes those endpoints as an OpenAPI 3.1
+//!   document, with each tool's `inputSchema` as its request body schema.
+//!   Local definitions (`$defs`, `definitions`) are moved to
+//!   `components/schemas` as `{operationId}.{name}` and refs rewritten, since
+//!   `#` names the document root there.
+//!
+//! Tool schemas are cached and re-listed every poll interval; both endpoints
+//! read that cache. A tool missing from it makes its server be listed again
+//! before the call is refused.
+//!
+//! Errors are JSON objects with an `error` message. The same bearer-token
+//! and origin checks as [`super::http`] apply to every request.
+
+use super::Gateway;
+use super::http::{
+    authorized, json_response, origin_allowed, status_response, unauthorized_response,
+};
+use crate::cli::call::{disabled_tool_message, validate_arguments};
+use crate::daemon::protocol::ToolInfo;
+use crate::error::{McpError, Result};
+use crate::parallel::is_tool_allowed;
+use hyper::header::{self, HeaderValue};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, percent_decode_str, utf8_percent_encode};
+use serde_json::{Map, Value, json};
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+/// Path of the OpenAPI document
+pub const OPENAPI_PATH: &str = "/openapi.json";
+
+/// Characters percent-encoded in a path segment: all but the unreserved ones
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// REST front end of a [`Gateway`]
+pub struct RestGateway {
+    gateway: Arc<Gateway>,
+    token: Option<String>,
+}
+
+impl RestGateway {
+    /// Serve `gateway` without authentication.
+    pub fn new(gateway: Arc<Gateway>) -> Self {
+        Self {
+            gateway,
+            token: None,
+        }
+    }
+
+    /// Require `Authorization: Bearer <token>` on every request.
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+
+    /// Serve on `listener` until `shutdown` completes.
+    ///
+    /// # Errors
+    /// Returns McpError::IOError if the listener can't be served
+    pub async fn serve(
+        self: Arc<Self>,
+        listener: tokio::net::TcpListener,
+        shutdown: impl Future<Output = ()>,
+    ) -> Result<()> {
+        let listener = listener.into_std().map_err(McpError::io_error)?;
+        let gateway = Arc::clone(&self.gateway);
+        let make_svc = make_service_fn(move |_conn| {
+            let rest = Arc::clone(&self);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req| {
+                    let rest = Arc::clone(&rest);
+                    async move { Ok::<_, Infallible>(rest.handle_request(req).await) }
+                }))
+            }
+        });
+        let server = Server::from_tcp(listener)
+            .map_err(|e| McpError::io_error(std::io::Error::other(e)))?
+            .serve(make_svc);
+
+        let poller = tokio::spawn(poll_tools(gateway));
+        let result = server.with_graceful_shutdown(shutdown).await;
+        poller.abort();
+        result.map_err(|e| McpError::io_error(std::io::Error::other(e)))
+    }
+
+    async fn handle_request(&self, req: Request<Body>) -> Response<Body> {
+        if !origin_allowed(&req) {
+            return status_response(StatusCode::FORBIDDEN);
+        }
+        if !authorized(&req, self.token.as_deref()) {
+            return unauthorized_response();
+        }
+
+        let path = req.uri().path().to_string();
+        let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+        match (segments.as_slice(), req.method()) {
+            (["openapi.json"], &Method::GET) => self.openapi(&req).await,
+            (["openapi.json"], _) => method_not_allowed("GET"),
+            (["servers", server, "tools", tool], &Method::POST) => {
+                let (Ok(server), Ok(tool)) = (
+                    percent_decode_str(server).decode_utf8(),
+                    percent_decode_str(tool).decode_utf8(),
+                ) else {
+                    return error_response(StatusCode::BAD_REQUEST, "Path is not valid UTF-8");
+                };
+                self.call_tool(&server, &tool, req).await
+            }
+            (["servers", _, "tools", _], _) => method_not_allowed("POST"),
+            _ => error_response(StatusCode::NOT_FOUND, "Not found"),
+        }
+    }
+
+    async fn openapi(&self, req: &Request<Body>) -> Response<Body> {
+        let base_url = req
+            .headers()
+            .get(header::HOST)
+            .and_then(|host| host.to_str().ok())
+            .map(|host| format!("http://{}", host));
+        let tools = self.gateway.cached_tools().await;
+        let document = openapi_document(&tools, base_url.as_deref(), self.token.is_some());
+        json_response(StatusCode::OK, &document)
+    }
+
+    async fn call_tool(&self, server: &str, tool: &str, req: Request<Body>) -> Response<Body> {
+        let Some(server_config) = self.gateway.config().get_server(server) else {
+            let message = format!("Server '{}' not found", server);
+            return error_response(StatusCode::NOT_FOUND, &message);
+        };
+        if let Some(message) = disabled_tool_message(server_config, tool) {
+            return error_response(StatusCode::FORBIDDEN, &message);
+        }
+        let not_found = || {
+            let message = format!("Tool '{}' not found on server '{}'", tool, server);
+            error_response(StatusCode::NOT_FOUND, &message)
+        };
+        if !is_tool_allowed(tool, server_config) {
+            return not_found();
+        }
+
+        let info = match self.gateway.find_tool(server, tool).await {
+            Ok(Some(info)) => info,
+            Ok(None) => return not_found(),
+            Err(e) => return error_response(StatusCode::BAD_GATEWAY, &e.to_string()),
+        };
+
+        let body = match hyper::body::to_bytes(req.into_body()).await {
+            Ok(body) => body,
+            Err(e) => return error_response(StatusCode::BAD_REQUEST, &e.to_string()),
+        };
+        // An empty body calls the tool without arguments
+        let arguments = if body.iter().all(u8::is_ascii_whitespace) {
+            json!({})
+        } else {
+            match serde_json::from_slice(&body) {
+                Ok(arguments) => arguments,
+                Err(e) => {
+                    let message = format!("Request body is not valid JSON: {}", e);
+                    return error_response(StatusCode::BAD_REQUEST, &message);
+                }
+            }
+        };
+        if let Err(e) = validate_arguments(tool, &info.input_schema, &arguments) {
+            let violations = match &e {
+                McpError::InvalidArguments { violations, .. } => violations.clone(),
+                _ => Vec::new(),
+            };
+            let body = json!({ "error": e.to_string(), "violations": violations });
+            return json_response(StatusCode::BAD_REQUEST, &body);
+        }
+
+        match self.gateway.pool().execute(server, tool, arguments).await {
+            Ok(result) => json_response(StatusCode::OK, &result),
+            Err(e) => error_response(StatusCode::BAD_GATEWAY, &e.to_string()),
+        }
+    }
+}
+
+/// Keep the gateway's tool schemas current, re-listing backends once per
+/// interval.
+async fn poll_tools(gateway: Arc<Gateway>) {
+    loop {
+        gateway.list_tools().await;
+        tokio::time::sleep(gateway.poll_interval()).await;
+    }
+}
+
+/// Serve the REST bridge on `addr` until Ctrl+C.
+///
+/// # Errors
+/// Returns McpError::IOError if `addr` can't be bound
+pub async fn serve_rest(
+    gateway: Arc<Gateway>,
+    addr: SocketAddr,
+    token: Option<String>,
+) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(McpError::io_error)?;
+    let bound = listener.local_addr().map_err(McpError::io_error)?;
+    crate::output::print_info(&format!(
+        "REST bridge listening on http://{} (OpenAPI at {})",
+        bound, OPENAPI_PATH
+    ));
+    if token.is_none() && !bound.ip().is_loopback() {
+        crate::output::print_warning(
+            "Serving on a non-loopback address without --token; anyone who can reach it can call every tool",
+        );
+    }
+
+    let rest = Arc::new(RestGateway::new(gateway).with_token(token));
+    rest.serve(listener, async {
+        let _ = tokio::signal::ctrl_c().await;
+    })
+    .await
+}
+
+/// Build the OpenAPI 3.1 document for `(server, tool)` pairs.
+///
+/// # Arguments
+/// * `tools` - Tools to describe, one `POST` operation each
+/// * `base_url` - URL the bridge is reached at, listed under `servers`
+/// * `secured` - Whether requests need a bearer token
+pub fn openapi_document(
+    tools: &[(String, ToolInfo)],
+    base_url: Option<&str>,
+    secured: bool,
+) -> Value {
+    let mut paths = Map::new();
+    let mut schemas = Map::new();
+    for (server, tool) in tools {
+        let operation_id = super::exposed_name(server, &tool.name);
+        let path = format!(
+            "/servers/{}/tools/{}",
+            utf8_percent_encode(server, PATH_SEGMENT),
+            utf8_percent_encode(&tool.name, PATH_SEGMENT)
+        );
+        let pointer = format!(
+            "#/paths/{}/post/requestBody/content/application~1json/schema",
+            escape_pointer(&path).replace('%', "%25")
+        );
+        let schema = hoist_definitions(&operation_id, &tool.input_schema, &pointer, &mut schemas);
+        let mut operation = json!({
+            "operationId": operation_id,
+            "summary": tool.name,
+            "tags": [server],
+            "requestBody": {
+                "required": true,
+                "content": { "application/json": { "schema": schema } }
+            },
+            "responses": {
+                "200": {
+                    "description": "Tool result",
+                    "content": {
+                        "application/json": {
+                            "schema": { "$ref": "#/components/schemas/CallToolResult" }
+                        }
+                    }
+                },
+                "400": { "$ref": "#/components/responses/Error" },
+                "403": { "$ref": "#/components/responses/Error" },
+                "404": { "$ref": "#/components/responses/Error" },
+                "502": { "$ref": "#/components/responses/Error" }
+            }
+        });
+        if !tool.description.is_empty() {
+            operation["description"] = json!(tool.description);
+        }
+        paths.insert(path, json!({ "post": operation }));
+    }
+
+    let mut document = json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": "MCP tools",
+            "description": "Tools of the configured MCP servers, served by mcp serve --rest",
+            "version": env!("CARGO_PKG_VERSION")
+        },
+        "paths": paths,
+        "components": {
+            "schemas": {
+                "CallToolResult": {
+                    "type": "object",
+                    "properties": {
+                        "content": { "type": "array", "items": { "type": "object" } },
+                        "structuredContent": { "type": "object" },
+                        "isError": {
+                            "type": "boolean",
+                            "description": "Whether the tool reported a failure"
+                        }
+                    },
+                    "required": ["content"]
+                },
+                "Error": {
+                    "type": "object",
+                    "properties": {
+                        "error": { "type": "string" },
+                        "violations": { "type": "array", "items": { "type": "string" } }
+                    },
+                    "required": ["error"]
+                }
+            },
+            "responses": {
+                "Error": {
+                    "description": "The tool could not be called",
+                    "content": {
+                        "application/json": {
+                            "schema": { "$ref": "#/components/schemas/Error" }
+                        }
+                    }
+                }
+            }
+        }
+    });
+    if let Some(object) = document["components"]["schemas"].as_object_mut() {
+        object.extend(schemas);
+    }
+    if let Some(base_url) = base_url {
+        document["servers"] = json!([{ "url": base_url }]);
+    }
+    if secured {
+        document["components"]["securitySchemes"] =
+            json!({ "bearerAuth": { "type": "http", "scheme": "bearer" } });
+        document["security"] = json!([{ "bearerAuth": [] }]);
+    }
+    document
+}
+
+/// Keywords holding a schema's local definitions
+const DEFINITION_KEYWORDS: [&str; 2] = ["$defs", "definitions"];
+
+/// Move the local definitions of a tool's `schema` into `components`.
+///
+/// Each `$defs`/`definitions` entry `X` becomes the component
+/// `{operation_id}.X`, and `$ref`s to it are rewritten to match. Any other
+/// local ref is rewritten to start at `pointer`, where the schema itself
+/// sits in the document.
+fn hoist_definitions(
+    operation_id: &str,
+    schema: &Value,
+    pointer: &str,
+    components: &mut Map<String, Value>,
+) -> Value {
+    let mut schema = schema.clone();
+    let mut definitions = Vec::new();
+    if let Some(object) = schema.as_object_mut() {
+        for keyword in DEFINITION_KEYWORDS {
+            if let Some(Value::Object(entries)) = object.get(keyword) {
+                let entries = entries.clone();
+                object.remove(keyword);
+                definitions.extend(entries.into_iter().map(|(name, def)| (keyword, name, def)));
+            }
+        }
+    }
+
+    // Component names are limited to `[A-Za-z0-9._-]`
+    let mut names = std::collections::HashMap::new();
+    for (keyword, name, _) in &definitions {
+        let base = format!("{}.{}", component_name(operation_id), component_name(name));
+        let mut component = base.clone();
+        let mut suffix = 1;
+        while components.contains_key(&component) || names.values().any(|n| *n == component) {
+            suffix += 1;
+            component = format!("{}_{}", base, suffix);
+        }
+        names.insert((*keyword, name.clone()), component);
+    }
+
+    rewrite_refs(&mut schema, pointer, &names);
+    for (keyword, name, mut def) in definitions {
+        rewrite_refs(&mut def, pointer, &names);
+        components.insert(names[&(keyword, name)].clone(), def);
+    }
+    schema
+}
+
+/// Rewrite the local `$ref`s in `value`; see [`hoist_definitions`].
+fn rewrite_refs(
+    value: &mut Value,
+    pointer: &str,
+    names: &std::collections::HashMap<(&str, String), String>,
+) {
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::String(reference)) = object.get_mut("$ref")
+                && let Some(rewritten) = rewrite_ref(reference, pointer, names)
+            {
+                *reference = rewritten;
+            }
+            for (key, child) in object.iter_mut() {
+                if key != "$ref" {
+                    rewrite_refs(child, pointer, names);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                rewrite_refs(item, pointer, names);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn rewrite_ref(
+    reference: &str,
+    pointer: &str,
+    names: &std::collections::HashMap<(&str, String), String>,
+) -> Option<String> {
+    let fragment = reference.strip_prefix('#')?;
+    if !fragment.is_empty() && !fragment.starts_with('/') {
+        // A plain-name anchor, not a pointer
+        return None;
+    }
+    let mut segments = fragment.splitn(4, '/').skip(1);
+    if let (Some(keyword), Some(name)) = (segments.next(), segments.next()) {
+        let name = percent_decode_str(name).decode_utf8_lossy();
+        let name = name.replace("~1", "/").replace("~0", "~");
+        let component = DEFINITION_KEYWORDS
+            .iter()
+            .find(|k| **k == keyword)
+            .and_then(|k| names.get(&(*k, name)));
+        if let Some(component) = component {
+            let rest = segments.next().map(|rest| format!("/{}", rest));
+            return Some(format!(
+                "#/components/schemas/{}{}",
+                component,
+                rest.unwrap_or_default()
+            ));
+        }
+    }
+    Some(format!("{}{}", pointer, fragment))
+}
+
+/// Replace characters outside `[A-Za-z0-9._-]` with `_`.
+fn component_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Escape `/` and `~` for use in a JSON pointer.
+fn escape_pointer(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+fn method_not_allowed(allow: &'static str) -> Response<Body> {
+    let mut response = status_response(StatusCode::METHOD_NOT_ALLOWED);
+    response
+        .headers_mut()
+        .insert(header::ALLOW, HeaderValue::from_static(allow));
+    response
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response<Body> {
+    json_response(status, &json!({ "error": message }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_openapi_document() {
+        let tools = vec![(
+            "fs".to_string(),
+            ToolInfo::new(
+                "read_file",
+                "Read a file",
+                json!({
+                    "type": "object",
+                    "properties": { "path": { "type": "string" } },
+                    "required": ["path"]
+                }),
+            ),
+        )];
+        let document = openapi_document(&tools, Some("http://127.0.0.1:8080"), true);
+
+        assert_eq!(document["openapi"], "3.1.0");
+        assert_eq!(document["servers"][0]["url"], "http://127.0.0.1:8080");
+        assert_eq!(document["security"][0]["bearerAuth"], json!([]));
+
+        let operation = &document["paths"]["/servers/fs/tools/read_file"]["post"];
+        assert_eq!(operation["operationId"], "fs__read_file");
+        assert_eq!(operation["description"], "Read a file");
+        assert_eq!(
+            operation["requestBody"]["content"]["application/json"]["schema"]["required"],
+            json!(["path"])
+        );
+    }
+
+    #[test]
+    fn test_openapi_document_encodes_path_segments() {
+        let tools = vec![(
+            "my fs".to_string(),
+            ToolInfo::new("dir/{name}", "", json!({ "type": "object" })),
+        )];
+        let document = openapi_document(&tools, None, false);
+
+        let paths = document["paths"].as_object().unwrap();
+        let names: Vec<&String> = paths.keys().collect();
+        assert_eq!(names, vec!["/servers/my%20fs/tools/dir%2F%7Bname%7D"]);
+    }
+
+    #[test]
+    fn test_openapi_document_moves_definitions_to_components() {
+        let tools = vec![(
+            "geo".to_string(),
+            ToolInfo::new(
+                "route",
+                "",
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "from": { "$ref": "#/$defs/Point" },
+                        "to": { "$ref": "#/definitions/Point" },
+                        "via": { "type": "array", "items": { "$ref": "#" } }
+                    },
+                    "$defs": {
+                        "Point": {
+                            "type": "object",
+                            "properties": { "unit": { "$ref": "#/$defs/Unit" } }
+                        },
+                        "Unit": { "enum": ["m", "km"] }
+                    },
+                    "definitions": { "Point": { "type": "string" } }
+                }),
+            ),
+        )];
+        let document = openapi_document(&tools, None, false);
+
+        let schemas = &document["components"]["schemas"];
+        assert_eq!(
+            schemas["geo__route.Point"]["properties"]["unit"]["$ref"],
+            "#/components/schemas/geo__route.Unit"
+        );
+        assert_eq!(schemas["geo__route.Unit"]["enum"], json!(["m", "km"]));
+        assert_eq!(schemas["geo__route.Point_2"]["type"], "string");
+        assert!(schemas.get("CallToolResult").is_some());
+
+        let schema = &document["paths"]["/servers/geo/tools/route"]["post"]["requestBody"]["content"]
+            ["application/json"]["schema"];
+        assert!(schema.get("$defs").is_none());
+        assert!(schema.get("definitions").is_none());
+        let properties = &schema["properties"];
+        assert_eq!(
+            properties["from"]["$ref"],
+            "#/components/schemas/geo__route.Point"
+        );
+        assert_eq!(
+            properties["to"]["$ref"],
+            "#/components/schemas/geo__route.Point_2"
+        );
+        assert_eq!(
+            properties["via"]["items"]["$ref"],
+            "#/paths/~1servers~1geo~1tools~1route/post/requestBody/content/application~1json/schema"
+        );
+        let root = properties["via"]["items"]["$ref"].as_str().unwrap();
+        assert_eq!(document.pointer(&root[1..]), Some(schema));
+    }
+
+    #[test]
+    fn test_openapi_document_without_token() {
+        let document = openapi_document(&[], None, false);
+        assert!(document.get("security").is_none());
+        assert!(document.get("servers").is_none());
+        assert_eq!(document["paths"], json!({}));
+    }
+}
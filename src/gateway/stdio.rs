@@ -0,0 +1,75 @@
+//! Gateway over stdio (`mcp serve --stdio`).
+//!
+//! Reads newline-delimited JSON-RPC from stdin and writes replies to stdout.
+//! Requests are handled concurrently, so a slow tool call doesn't hold up
+//! others, and every reply and notification goes through one writer task so
+//! lines never interleave.
+
+use super::{Gateway, tools_list_changed};
+use crate::error::{McpError, Result};
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+
+/// Serve the gateway on stdin/stdout until stdin is closed.
+pub async fn serve_stdio(gateway: Arc<Gateway>) -> Result<()> {
+    serve(gateway, tokio::io::stdin(), tokio::io::stdout()).await
+}
+
+/// Serve the gateway on any line-based byte stream until `input` ends.
+///
+/// Requests still running when `input` ends are answered before returning.
+pub async fn serve(
+    gateway: Arc<Gateway>,
+    input: impl AsyncRead + Unpin,
+    mut output: impl AsyncWrite + Unpin + Send + 'static,
+) -> Result<()> {
+    let (tx, mut rx) = mpsc::unbounded_channel::<Value>();
+
+    let writer = tokio::spawn(async move {
+        while let Some(message) = rx.recv().await {
+            let mut line = message.to_string();
+            line.push('\n');
+            if let Err(e) = output.write_all(line.as_bytes()).await {
+                tracing::warn!("Gateway: writing to client failed: {}", e);
+                break;
+            }
+            let _ = output.flush().await;
+        }
+    });
+
+    // Tell the client when a backend's tools change
+    let poller = tokio::spawn({
+        let gateway = Arc::clone(&gateway);
+        let tx = tx.clone();
+        async move {
+            loop {
+                tokio::time::sleep(gateway.poll_interval()).await;
+                if gateway.tools_changed().await && tx.send(tools_list_changed()).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    let mut lines = BufReader::new(input).lines();
+    while let Some(line) = lines.next_line().await.map_err(McpError::io_error)? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let gateway = Arc::clone(&gateway);
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            if let Some(reply) = gateway.handle_line(&line).await {
+                let _ = tx.send(reply);
+            }
+        });
+    }
+
+    poller.abort();
+    drop(tx);
+    // The writer finishes once every in-flight request has replied
+    let _ = writer.await;
+    Ok(())
+}
@@ -1,11 +1,19 @@
 //! Platform-agnostic IPC abstraction for daemon communication
 
 use crate::config::Config;
+use crate::daemon::protocol::{
+    DaemonRequest, DaemonResponse, FrameRequest, Handshake, IPC_PROTOCOL_VERSION, RequestFrame,
+    ResponseFrame, receive_frame, receive_line, send_frame,
+};
 use crate::error::McpError;
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
-use tokio::io::{AsyncRead, AsyncWrite};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::io::{AsyncRead, AsyncWrite, WriteHalf};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 
 #[cfg(unix)]
 pub mod unix;
@@ -50,13 +58,281 @@ pub trait IpcClient: Send + Sync {
 
     /// Send a daemon protocol request and receive response
     ///
-    /// Generic method for NDJSON communication
+    /// Generic method for NDJSON communication. Progress frames sent before
+    /// the response are discarded.
     async fn send_request(
         &mut self,
         request: &crate::daemon::protocol::DaemonRequest,
+    ) -> Result<crate::daemon::protocol::DaemonResponse, McpError> {
+        self.send_request_with_progress(request, &mut |_| {}).await
+    }
+
+    /// Send a daemon protocol request and receive the final response,
+    /// passing any progress frames that precede it to `on_progress`
+    async fn send_request_with_progress(
+        &mut self,
+        request: &crate::daemon::protocol::DaemonRequest,
+        on_progress: ProgressCallback<'_>,
     ) -> Result<crate::daemon::protocol::DaemonResponse, McpError>;
 }
 
+/// Callback receiving progress reports for a running tool call
+pub type ProgressCallback<'a> = &'a mut (dyn FnMut(crate::daemon::protocol::ProgressUpdate) + Send);
+
+/// Senders for the responses to requests in flight, keyed by request ID.
+type PendingMap = Arc<std::sync::Mutex<HashMap<u64, mpsc::UnboundedSender<DaemonResponse>>>>;
+
+/// Write half of the connection, shared by callers and cancel guards.
+type SharedWriter = Arc<tokio::sync::Mutex<WriteHalf<Box<dyn IpcStream>>>>;
+
+/// A persistent connection to the daemon carrying any number of requests
+///
+/// Opened with a [`Handshake`]; afterwards each request gets a connection
+/// unique ID and a background reader task routes every response frame to the
+/// request with that ID, so requests can be in flight concurrently and be
+/// answered in any order. Dropping the connection stops the reader task.
+pub struct IpcConnection {
+    writer: SharedWriter,
+    pending: PendingMap,
+    next_id: AtomicU64,
+    reader: JoinHandle<()>,
+}
+
+impl IpcConnection {
+    /// Exchange handshakes over `stream` and start routing responses
+    ///
+    /// # Errors
+    /// Returns McpError::IpcVersionMismatch if the daemon speaks another
+    /// protocol version; a daemon that closes the connection or answers
+    /// without a handshake predates it and is reported as version 1
+    pub async fn open(stream: Box<dyn IpcStream>) -> Result<Self, McpError> {
+        let (reader, mut writer) = tokio::io::split(stream);
+        let mut reader = tokio::io::BufReader::new(reader);
+
+        send_frame(&mut writer, &Handshake::current())
+            .await
+            .map_err(|e| McpError::ipc_error(format!("Failed to send IPC handshake: {}", e)))?;
+        let line = receive_line(&mut reader)
+            .await
+            .map_err(|e| McpError::ipc_error(format!("Failed to receive IPC handshake: {}", e)))?;
+        let daemon_version = line
+            .and_then(|line| serde_json::from_str::<Handshake>(&line).ok())
+            .map_or(1, |handshake| handshake.protocol_version);
+        if daemon_version != IPC_PROTOCOL_VERSION {
+            return Err(McpError::IpcVersionMismatch {
+                daemon: daemon_version,
+                client: IPC_PROTOCOL_VERSION,
+            });
+        }
+
+        let pending: PendingMap = Arc::new(std::sync::Mutex::new(HashMap::new()));
+        let reader = tokio::spawn(route_responses(reader, Arc::clone(&pending)));
+        Ok(Self {
+            writer: Arc::new(tokio::sync::Mutex::new(writer)),
+            pending,
+            next_id: AtomicU64::new(1),
+            reader,
+        })
+    }
+
+    /// Send a request and wait for its final response, passing any progress
+    /// frames that precede it to `on_progress`
+    ///
+    /// If the caller stops waiting for an `ExecuteTool` response (e.g. the
+    /// future is dropped on Ctrl+C), a `FrameRequest::Cancel` with the call's ID is
+    /// sent so the daemon cancels the call on the MCP server.
+    pub async fn request(
+        &self,
+        request: &DaemonRequest,
+        on_progress: ProgressCallback<'_>,
+    ) -> Result<DaemonResponse, McpError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        self.pending_map().insert(id, tx);
+        let guard = CancelOnDrop {
+            connection: self,
+            id: Some(id),
+            // Only tool calls can be cancelled
+            cancel: matches!(request, DaemonRequest::ExecuteTool { .. }),
+        };
+
+        let frame = RequestFrame {
+            id,
+            request: request.clone().into(),
+        };
+        let sent = send_frame(&mut *self.writer.lock().await, &frame).await;
+        if let Err(e) = sent {
+            guard.finish();
+            return Err(McpError::ipc_error(format!(
+                "Failed to send IPC request: {}",
+                e
+            )));
+        }
+
+        let result = loop {
+            match rx.recv().await {
+                Some(DaemonResponse::Progress(update)) => on_progress(update),
+                Some(response) => break Ok(response),
+                None => {
+                    break Err(McpError::ipc_error(
+                        "Daemon closed the connection before responding",
+                    ));
+                }
+            }
+        };
+        guard.finish();
+        result
+    }
+
+    /// Whether the reader task has stopped (the daemon closed the connection).
+    pub fn is_closed(&self) -> bool {
+        self.reader.is_finished()
+    }
+
+    fn pending_map(
+        &self,
+    ) -> std::sync::MutexGuard<'_, HashMap<u64, mpsc::UnboundedSender<DaemonResponse>>> {
+        self.pending
+            .lock()
+            .expect("Failed to acquire pending request lock")
+    }
+}
+
+impl Drop for IpcConnection {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+/// Read response frames until the daemon closes the connection, routing
+/// each one to the request it answers.
+async fn route_responses<R>(mut reader: R, pending: PendingMap)
+where
+    R: tokio::io::AsyncBufRead + Send + Unpin,
+{
+    loop {
+        let line = match receive_line(&mut reader).await {
+            Ok(Some(line)) => line,
+            Ok(None) => break,
+            Err(e) => {
+                tracing::warn!("Failed to read from daemon: {}", e);
+                break;
+            }
+        };
+        let frame = match serde_json::from_str::<ResponseFrame>(&line) {
+            Ok(frame) => frame,
+            Err(e) => {
+                tracing::debug!("Ignoring malformed frame from daemon: {}", e);
+                continue;
+            }
+        };
+
+        let mut pending = pending
+            .lock()
+            .expect("Failed to acquire pending request lock");
+        // Progress frames leave the request waiting for its final response
+        let sender = match frame.response {
+            DaemonResponse::Progress(_) => pending.get(&frame.id).cloned(),
+            _ => pending.remove(&frame.id),
+        };
+        match sender {
+            Some(sender) => {
+                let _ = sender.send(frame.response);
+            }
+            None => tracing::debug!("Discarding response to abandoned request {}", frame.id),
+        }
+    }
+
+    // Dropping the senders fails every request still waiting for a response
+    pending
+        .lock()
+        .expect("Failed to acquire pending request lock")
+        .clear();
+}
+
+/// Removes a request from the pending map when its caller is done with it,
+/// sending `FrameRequest::Cancel` for a tool call the caller gave up on.
+struct CancelOnDrop<'a> {
+    connection: &'a IpcConnection,
+    id: Option<u64>,
+    cancel: bool,
+}
+
+impl CancelOnDrop<'_> {
+    /// The request completed (successfully or not); nothing to cancel.
+    fn finish(mut self) {
+        if let Some(id) = self.id.take() {
+            self.connection.pending_map().remove(&id);
+        }
+    }
+}
+
+impl Drop for CancelOnDrop<'_> {
+    fn drop(&mut self) {
+        let Some(id) = self.id.take() else {
+            return;
+        };
+        self.connection.pending_map().remove(&id);
+        if self.cancel {
+            let writer = Arc::clone(&self.connection.writer);
+            crate::shutdown::spawn_cleanup(async move {
+                let frame = RequestFrame {
+                    id,
+                    request: FrameRequest::Cancel,
+                };
+                if let Err(e) = send_frame(&mut *writer.lock().await, &frame).await {
+                    tracing::debug!("Failed to send cancel request to daemon: {}", e);
+                }
+            });
+        }
+    }
+}
+
+/// Connection to the daemon shared by clones of a platform client
+///
+/// Opened on first use and reopened once the daemon has closed it, e.g.
+/// after the daemon was restarted.
+#[derive(Clone, Default)]
+pub(crate) struct SharedConnection {
+    connection: Arc<tokio::sync::Mutex<Option<Arc<IpcConnection>>>>,
+}
+
+impl SharedConnection {
+    /// The open connection, or a new one over the stream `connect` returns
+    pub(crate) async fn get<F, Fut>(&self, connect: F) -> Result<Arc<IpcConnection>, McpError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<Box<dyn IpcStream>, McpError>>,
+    {
+        let mut connection = self.connection.lock().await;
+        if let Some(open) = connection.as_ref().filter(|c| !c.is_closed()) {
+            return Ok(Arc::clone(open));
+        }
+        let opened = Arc::new(IpcConnection::open(connect().await?).await?);
+        *connection = Some(Arc::clone(&opened));
+        Ok(opened)
+    }
+}
+
+/// Send one bare request the way version 1 of the protocol does and
+/// receive its response
+///
+/// Lets a current CLI shut down a daemon that predates the handshake.
+pub(crate) async fn legacy_request(
+    stream: Box<dyn IpcStream>,
+    request: &DaemonRequest,
+) -> Result<DaemonResponse, McpError> {
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut reader = tokio::io::BufReader::new(reader);
+    send_frame(&mut writer, request)
+        .await
+        .map_err(|e| McpError::ipc_error(format!("Failed to send IPC request: {}", e)))?;
+    receive_frame(&mut reader)
+        .await
+        .map_err(|e| McpError::ipc_error(format!("Failed to receive IPC response: {}", e)))?
+        .ok_or_else(|| McpError::ipc_error("Daemon closed the connection before responding"))
+}
+
 /// Wrapper struct that implements concrete protocol methods using a generic IpcClient
 ///
 /// This allows `Box<dyn IpcClient>` to have protocol-specific methods added to it
@@ -115,23 +391,74 @@ impl<T: Clone + IpcClient> IpcClientWrapper<T> {
         }
     }
 
+    /// List a single page of tools for a specific server
+    pub async fn list_tools_page(
+        &mut self,
+        server_name: &str,
+        cursor: Option<&str>,
+    ) -> Result<crate::daemon::protocol::ToolPage, McpError> {
+        let response = self
+            .client
+            .send_request(&crate::daemon::protocol::DaemonRequest::ListToolsPage {
+                server_name: server_name.to_string(),
+                cursor: cursor.map(str::to_string),
+            })
+            .await?;
+        match response {
+            crate::daemon::protocol::DaemonResponse::ToolPage(page) => Ok(page),
+            _ => Err(crate::error::McpError::InvalidProtocol {
+                message: format!(
+                    "Expected ToolPage response for '{}', got {:?}",
+                    server_name, response
+                ),
+            }),
+        }
+    }
+
     /// Execute a tool on a server
     pub async fn execute_tool(
         &mut self,
         server_name: &str,
         tool_name: &str,
         arguments: serde_json::Value,
+    ) -> Result<serde_json::Value, McpError> {
+        self.execute_tool_with_progress(server_name, tool_name, arguments, None, &mut |_| {})
+            .await
+    }
+
+    /// Execute a tool on a server, reporting progress as the daemon relays it
+    ///
+    /// The daemon gives up after `timeout_secs`, or the server's configured
+    /// timeout if `None`.
+    pub async fn execute_tool_with_progress(
+        &mut self,
+        server_name: &str,
+        tool_name: &str,
+        arguments: serde_json::Value,
+        timeout_secs: Option<u64>,
+        on_progress: ProgressCallback<'_>,
     ) -> Result<serde_json::Value, McpError> {
         let response = self
             .client
-            .send_request(&crate::daemon::protocol::DaemonRequest::ExecuteTool {
-                server_name: server_name.to_string(),
-                tool_name: tool_name.to_string(),
-                arguments,
-            })
+            .send_request_with_progress(
+                &crate::daemon::protocol::DaemonRequest::ExecuteTool {
+                    server_name: server_name.to_string(),
+                    tool_name: tool_name.to_string(),
+                    arguments,
+                    timeout_secs,
+                },
+                on_progress,
+            )
             .await?;
         match response {
             crate::daemon::protocol::DaemonResponse::ToolResult(result) => Ok(result),
+            crate::daemon::protocol::DaemonResponse::Error {
+                code: crate::daemon::protocol::TIMEOUT_ERROR_CODE,
+                ..
+            } => Err(McpError::Timeout {
+                timeout: timeout_secs
+                    .unwrap_or_else(|| self.config.server_timeout_secs(server_name)),
+            }),
             _ => Err(crate::error::McpError::InvalidProtocol {
                 message: format!(
                     "Expected ToolResult for '{}.{}', got {:?}",
@@ -140,6 +467,126 @@ impl<T: Clone + IpcClient> IpcClientWrapper<T> {
             }),
         }
     }
+
+    /// List resources for a specific server
+    pub async fn list_resources(
+        &mut self,
+        server_name: &str,
+    ) -> Result<Vec<crate::daemon::protocol::ResourceInfo>, McpError> {
+        let response = self
+            .client
+            .send_request(&crate::daemon::protocol::DaemonRequest::ListResources {
+                server_name: server_name.to_string(),
+            })
+            .await?;
+        match response {
+            crate::daemon::protocol::DaemonResponse::ResourceList(resources) => Ok(resources),
+            _ => Err(crate::error::McpError::InvalidProtocol {
+                message: format!(
+                    "Expected ResourceList response for '{}', got {:?}",
+                    server_name, response
+                ),
+            }),
+        }
+    }
+
+    /// List resource templates for a specific server
+    pub async fn list_resource_templates(
+        &mut self,
+        server_name: &str,
+    ) -> Result<Vec<crate::daemon::protocol::ResourceTemplateInfo>, McpError> {
+        let response = self
+            .client
+            .send_request(
+                &crate::daemon::protocol::DaemonRequest::ListResourceTemplates {
+                    server_name: server_name.to_string(),
+                },
+            )
+            .await?;
+        match response {
+            crate::daemon::protocol::DaemonResponse::ResourceTemplateList(templates) => {
+                Ok(templates)
+            }
+            _ => Err(crate::error::McpError::InvalidProtocol {
+                message: format!(
+                    "Expected ResourceTemplateList response for '{}', got {:?}",
+                    server_name, response
+                ),
+            }),
+        }
+    }
+
+    /// Read a resource from a server
+    pub async fn read_resource(
+        &mut self,
+        server_name: &str,
+        uri: &str,
+    ) -> Result<Vec<crate::daemon::protocol::ResourceContents>, McpError> {
+        let response = self
+            .client
+            .send_request(&crate::daemon::protocol::DaemonRequest::ReadResource {
+                server_name: server_name.to_string(),
+                uri: uri.to_string(),
+            })
+            .await?;
+        match response {
+            crate::daemon::protocol::DaemonResponse::ResourceContents(contents) => Ok(contents),
+            _ => Err(crate::error::McpError::InvalidProtocol {
+                message: format!(
+                    "Expected ResourceContents for '{}' on '{}', got {:?}",
+                    uri, server_name, response
+                ),
+            }),
+        }
+    }
+
+    /// List prompts for a specific server
+    pub async fn list_prompts(
+        &mut self,
+        server_name: &str,
+    ) -> Result<Vec<crate::daemon::protocol::PromptInfo>, McpError> {
+        let response = self
+            .client
+            .send_request(&crate::daemon::protocol::DaemonRequest::ListPrompts {
+                server_name: server_name.to_string(),
+            })
+            .await?;
+        match response {
+            crate::daemon::protocol::DaemonResponse::PromptList(prompts) => Ok(prompts),
+            _ => Err(crate::error::McpError::InvalidProtocol {
+                message: format!(
+                    "Expected PromptList response for '{}', got {:?}",
+                    server_name, response
+                ),
+            }),
+        }
+    }
+
+    /// Render a prompt on a server
+    pub async fn get_prompt(
+        &mut self,
+        server_name: &str,
+        prompt_name: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<crate::daemon::protocol::PromptResult, McpError> {
+        let response = self
+            .client
+            .send_request(&crate::daemon::protocol::DaemonRequest::GetPrompt {
+                server_name: server_name.to_string(),
+                prompt_name: prompt_name.to_string(),
+                arguments,
+            })
+            .await?;
+        match response {
+            crate::daemon::protocol::DaemonResponse::PromptResult(prompt) => Ok(prompt),
+            _ => Err(crate::error::McpError::InvalidProtocol {
+                message: format!(
+                    "Expected PromptResult for '{}.{}', got {:?}",
+                    server_name, prompt_name, response
+                ),
+            }),
+        }
+    }
 }
 
 /// Trait for protocol-specific client methods
@@ -158,12 +605,53 @@ pub trait ProtocolClient: Send + Sync {
         &mut self,
         server_name: &str,
     ) -> Result<Vec<crate::daemon::protocol::ToolInfo>, McpError>;
+    /// List a single page of tools, continuing from the server's `cursor`
+    async fn list_tools_page(
+        &mut self,
+        server_name: &str,
+        cursor: Option<&str>,
+    ) -> Result<crate::daemon::protocol::ToolPage, McpError>;
     async fn execute_tool(
         &mut self,
         server_name: &str,
         tool_name: &str,
         arguments: serde_json::Value,
     ) -> Result<serde_json::Value, McpError>;
+    /// Execute a tool, passing the server's progress reports to `on_progress`
+    ///
+    /// The call fails with `McpError::Timeout` after `timeout_secs`, or the
+    /// server's configured timeout if `None`.
+    async fn execute_tool_with_progress(
+        &mut self,
+        server_name: &str,
+        tool_name: &str,
+        arguments: serde_json::Value,
+        timeout_secs: Option<u64>,
+        on_progress: ProgressCallback<'_>,
+    ) -> Result<serde_json::Value, McpError>;
+    async fn list_resources(
+        &mut self,
+        server_name: &str,
+    ) -> Result<Vec<crate::daemon::protocol::ResourceInfo>, McpError>;
+    async fn list_resource_templates(
+        &mut self,
+        server_name: &str,
+    ) -> Result<Vec<crate::daemon::protocol::ResourceTemplateInfo>, McpError>;
+    async fn read_resource(
+        &mut self,
+        server_name: &str,
+        uri: &str,
+    ) -> Result<Vec<crate::daemon::protocol::ResourceContents>, McpError>;
+    async fn list_prompts(
+        &mut self,
+        server_name: &str,
+    ) -> Result<Vec<crate::daemon::protocol::PromptInfo>, McpError>;
+    async fn get_prompt(
+        &mut self,
+        server_name: &str,
+        prompt_name: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<crate::daemon::protocol::PromptResult, McpError>;
     async fn shutdown(&mut self) -> Result<(), McpError>;
 }
 
@@ -193,6 +681,14 @@ impl<T: IpcClient + Send + Sync + Clone> ProtocolClient for IpcClientWrapper<T>
         IpcClientWrapper::list_tools(self, server_name).await
     }
 
+    async fn list_tools_page(
+        &mut self,
+        server_name: &str,
+        cursor: Option<&str>,
+    ) -> Result<crate::daemon::protocol::ToolPage, McpError> {
+        IpcClientWrapper::list_tools_page(self, server_name, cursor).await
+    }
+
     async fn execute_tool(
         &mut self,
         server_name: &str,
@@ -203,11 +699,73 @@ impl<T: IpcClient + Send + Sync + Clone> ProtocolClient for IpcClientWrapper<T>
         IpcClientWrapper::execute_tool(self, server_name, tool_name, arguments).await
     }
 
+    async fn execute_tool_with_progress(
+        &mut self,
+        server_name: &str,
+        tool_name: &str,
+        arguments: serde_json::Value,
+        timeout_secs: Option<u64>,
+        on_progress: ProgressCallback<'_>,
+    ) -> Result<serde_json::Value, McpError> {
+        IpcClientWrapper::execute_tool_with_progress(
+            self,
+            server_name,
+            tool_name,
+            arguments,
+            timeout_secs,
+            on_progress,
+        )
+        .await
+    }
+
+    async fn list_resources(
+        &mut self,
+        server_name: &str,
+    ) -> Result<Vec<crate::daemon::protocol::ResourceInfo>, McpError> {
+        IpcClientWrapper::list_resources(self, server_name).await
+    }
+
+    async fn list_resource_templates(
+        &mut self,
+        server_name: &str,
+    ) -> Result<Vec<crate::daemon::protocol::ResourceTemplateInfo>, McpError> {
+        IpcClientWrapper::list_resource_templates(self, server_name).await
+    }
+
+    async fn read_resource(
+        &mut self,
+        server_name: &str,
+        uri: &str,
+    ) -> Result<Vec<crate::daemon::protocol::ResourceContents>, McpError> {
+        IpcClientWrapper::read_resource(self, server_name, uri).await
+    }
+
+    async fn list_prompts(
+        &mut self,
+        server_name: &str,
+    ) -> Result<Vec<crate::daemon::protocol::PromptInfo>, McpError> {
+        IpcClientWrapper::list_prompts(self, server_name).await
+    }
+
+    async fn get_prompt(
+        &mut self,
+        server_name: &str,
+        prompt_name: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<crate::daemon::protocol::PromptResult, McpError> {
+        IpcClientWrapper::get_prompt(self, server_name, prompt_name, arguments).await
+    }
+
     async fn shutdown(&mut self) -> Result<(), McpError> {
-        let response = self
-            .client
-            .send_request(&crate::daemon::protocol::DaemonRequest::Shutdown)
-            .await?;
+        let request = crate::daemon::protocol::DaemonRequest::Shutdown;
+        let response = match self.client.send_request(&request).await {
+            // A daemon predating the handshake still takes a bare request
+            Err(McpError::IpcVersionMismatch { daemon: 1, .. }) => {
+                let stream = self.client.connect(&self.config.socket_path).await?;
+                legacy_request(stream, &request).await?
+            }
+            result => result?,
+        };
         match response {
             crate::daemon::protocol::DaemonResponse::ShutdownAck => Ok(()),
             _ => Err(crate::error::McpError::InvalidProtocol {
@@ -79,16 +79,21 @@ impl IpcServer for UnixIpcServer {
 
 /// Unix socket implementation of IPC client
 ///
-/// Connects to IPC servers via Unix domain sockets on Unix-like systems
+/// Connects to IPC servers via Unix domain sockets on Unix-like systems.
+/// Requests share one persistent connection, also across clones.
 #[derive(Clone)]
 pub struct UnixIpcClient {
     config: Arc<Config>,
+    connection: crate::ipc::SharedConnection,
 }
 
 impl UnixIpcClient {
     /// Create a new UnixIpcClient with a config reference
     pub fn new(config: Arc<Config>) -> Self {
-        Self { config }
+        Self {
+            config,
+            connection: crate::ipc::SharedConnection::default(),
+        }
     }
 }
 
@@ -99,32 +104,18 @@ impl crate::ipc::IpcClient for UnixIpcClient {
         Arc::clone(&self.config)
     }
 
-    /// Send a daemon protocol request and receive response
-    async fn send_request(
+    /// Send a daemon protocol request and receive the final response
+    async fn send_request_with_progress(
         &mut self,
         request: &crate::daemon::protocol::DaemonRequest,
+        on_progress: crate::ipc::ProgressCallback<'_>,
     ) -> Result<crate::daemon::protocol::DaemonResponse, McpError> {
-        // Connect to daemon
-        let stream = self.connect(&self.config.socket_path).await?;
-
-        // Split stream for reading and writing
-        use tokio::io::BufReader;
-        let (reader, mut writer) = tokio::io::split(stream);
-        let mut buf_reader = BufReader::new(reader);
-
-        // Send request using NDJSON protocol
-        crate::daemon::protocol::send_request(&mut writer, request)
-            .await
-            .map_err(|e| McpError::IpcError {
-                message: format!("Failed to send IPC request: {}", e),
-            })?;
-
-        // Receive response using NDJSON protocol
-        crate::daemon::protocol::receive_response(&mut buf_reader)
-            .await
-            .map_err(|e| McpError::IpcError {
-                message: format!("Failed to receive IPC response: {}", e),
-            })
+        // Connect to daemon unless already connected
+        let connection = self
+            .connection
+            .get(|| self.connect(&self.config.socket_path))
+            .await?;
+        connection.request(request, on_progress).await
     }
 
     /// Connect to an IPC server at the given path
@@ -135,16 +135,21 @@ impl IpcServer for NamedPipeIpcServer {
 /// Connection attempts to remote pipes will fail due to server-side
 /// `reject_remote_clients(true)` security policy (see XP-02 above).
 ///
-/// Connects to IPC servers via named pipes on Windows systems
+/// Connects to IPC servers via named pipes on Windows systems.
+/// Requests share one persistent connection, also across clones.
 #[derive(Clone)]
 pub struct NamedPipeIpcClient {
     config: Arc<Config>,
+    connection: crate::ipc::SharedConnection,
 }
 
 impl NamedPipeIpcClient {
     /// Create a new NamedPipeIpcClient with a config reference (convenience method)
     pub fn with_config(config: Arc<Config>) -> Self {
-        Self { config }
+        Self {
+            config,
+            connection: crate::ipc::SharedConnection::default(),
+        }
     }
 }
 
@@ -155,40 +160,27 @@ impl crate::ipc::IpcClient for NamedPipeIpcClient {
         Arc::clone(&self.config)
     }
 
-    /// Send a daemon protocol request and receive response
-    async fn send_request(
+    /// Send a daemon protocol request and receive the final response
+    async fn send_request_with_progress(
         &mut self,
         request: &crate::daemon::protocol::DaemonRequest,
+        on_progress: crate::ipc::ProgressCallback<'_>,
     ) -> Result<crate::daemon::protocol::DaemonResponse, McpError> {
         let path_str = self.config.socket_path.to_string_lossy().to_string();
-        tracing::debug!("IPC: Connecting to pipe at {:?}", path_str);
 
-        // Connect to daemon
+        // Connect to daemon unless already connected
         let path = Path::new(&path_str);
-        let stream = self.connect(path).await?;
-        tracing::debug!("IPC: Connected to pipe");
-
-        // Split stream for reading and writing
-        use tokio::io::BufReader;
-        let (reader, mut writer) = tokio::io::split(stream);
-        let mut buf_reader = BufReader::new(reader);
-
-        // Send request using NDJSON protocol
+        let connection = self
+            .connection
+            .get(|| async {
+                tracing::debug!("IPC: Connecting to pipe at {:?}", path_str);
+                self.connect(path).await
+            })
+            .await?;
+
+        // Exchange request and response using NDJSON protocol
         tracing::debug!("IPC: Sending request: {:?}", request);
-        crate::daemon::protocol::send_request(&mut writer, request)
-            .await
-            .map_err(|e| McpError::IpcError {
-                message: format!("Failed to send IPC request: {}", e),
-            })?;
-        tracing::debug!("IPC: Request sent");
-
-        // Receive response using NDJSON protocol
-        tracing::debug!("IPC: Waiting for response...");
-        let result = crate::daemon::protocol::receive_response(&mut buf_reader)
-            .await
-            .map_err(|e| McpError::IpcError {
-                message: format!("Failed to receive IPC response: {}", e),
-            });
+        let result = connection.request(request, on_progress).await;
         tracing::debug!("IPC: Got response: {:?}", result);
         result
     }
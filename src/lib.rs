@@ -27,6 +27,9 @@ pub use daemon::{DaemonState, run_daemon};
 pub mod ipc;
 pub use ipc::{create_ipc_server, get_socket_path};
 
+// Gateway mode - all configured servers served as one MCP server
+pub mod gateway;
+
 // Parallel discovery module - used by tests
 pub mod parallel;
 
@@ -34,5 +37,5 @@ pub mod parallel;
 pub mod retry;
 
 // Internal modules - not needed by external users
-mod shutdown;
 mod pool;
+mod shutdown;
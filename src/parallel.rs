@@ -68,42 +68,24 @@ impl Default for ParallelExecutor {
 /// let tools = list_tools_parallel(..., &config).await?;
 /// ```
 pub fn filter_tools(tools: Vec<ToolInfo>, server_config: &ServerConfig) -> Vec<ToolInfo> {
+    tools
+        .into_iter()
+        .filter(|tool| is_tool_allowed(&tool.name, server_config))
+        .collect()
+}
+
+/// Check one tool name against a server's `allowed_tools`/`disabled_tools`.
+///
+/// Disabled patterns take precedence over allowed ones; with neither set,
+/// every tool is allowed.
+pub fn is_tool_allowed(tool_name: &str, server_config: &ServerConfig) -> bool {
     let disabled_patterns = server_config.disabled_tools.as_deref().unwrap_or_default();
     let allowed_patterns = server_config.allowed_tools.as_deref().unwrap_or_default();
 
-    // If no filtering rules, return all tools (backward compatible)
-    if disabled_patterns.is_empty() && allowed_patterns.is_empty() {
-        return tools;
+    if tools_match_any(tool_name, disabled_patterns).is_some() {
+        return false;
     }
-
-    tools
-        .iter()
-        .filter(|tool| {
-            // Precedence: disabled > allowed
-            if !disabled_patterns.is_empty() {
-                // Check if tool matches disabled patterns first
-                match tools_match_any(&tool.name, disabled_patterns) {
-                    Some(_) => false, // Disabled (precedes allowed)
-                    None => {
-                        // Not disabled, check allowed patterns
-                        if !allowed_patterns.is_empty() {
-                            tools_match_any(&tool.name, allowed_patterns).is_some()
-                        } else {
-                            true // No allowed patterns, so it's allowed
-                        }
-                    }
-                }
-            } else {
-                // No disabled patterns, check allowed patterns
-                if !allowed_patterns.is_empty() {
-                    tools_match_any(&tool.name, allowed_patterns).is_some()
-                } else {
-                    true // Both empty, return all tools
-                }
-            }
-        })
-        .cloned()
-        .collect()
+    allowed_patterns.is_empty() || tools_match_any(tool_name, allowed_patterns).is_some()
 }
 
 /// List tools from multiple servers in parallel.
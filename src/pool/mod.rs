@@ -19,6 +19,7 @@ use crate::client::ToolInfo;
 use async_trait::async_trait;
 
 /// Connection pool trait
+#[allow(dead_code)] // Only exercised by the unit tests
 #[async_trait]
 pub trait ConnectionPoolInterface: Send + Sync {
     /// Execute a tool on a server
@@ -43,10 +44,12 @@ pub trait ConnectionPoolInterface: Send + Sync {
 }
 
 /// Dummy connection pool for stub implementation
+#[allow(dead_code)] // Only exercised by the unit tests
 #[derive(Default)]
 pub struct DummyConnectionPool;
 
 impl DummyConnectionPool {
+    #[allow(dead_code)] // Only exercised by the unit tests
     pub fn new() -> Self {
         Self
     }
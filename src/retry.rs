@@ -79,11 +79,9 @@ where
         match result {
             Ok(value) => return Ok(value),
             Err(error) => {
-                // Check if this is a timeout error
-                let is_timeout = matches!(error, McpError::Timeout { .. });
-
-                if is_timeout {
-                    return Err(McpError::OperationCancelled { timeout: 30 });
+                // Timeouts already waited the full deadline - don't retry
+                if matches!(error, McpError::Timeout { .. }) {
+                    return Err(error);
                 }
 
                 // For other errors, check if they're transient
@@ -145,11 +143,9 @@ where
         match result {
             Ok(value) => return Ok(value),
             Err(error) => {
-                // Check if this is a timeout error
-                let is_timeout = matches!(error, McpError::Timeout { .. });
-
-                if is_timeout {
-                    return Err(McpError::OperationCancelled { timeout: 30 });
+                // Timeouts already waited the full deadline - don't retry
+                if matches!(error, McpError::Timeout { .. }) {
+                    return Err(error);
                 }
 
                 // For other errors, check if they're transient
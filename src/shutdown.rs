@@ -3,8 +3,53 @@
 //! Provides cross-platform signal handling for SIGINT/SIGTERM (Unix)
 //! and Ctrl+C (Windows). Implements CLI-04.
 
+use std::sync::Mutex;
+use std::time::Duration;
+#[cfg(windows)]
 use tokio::signal;
 use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+/// Maximum time to wait for cleanup tasks after a shutdown signal.
+pub const CLEANUP_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Cleanup tasks spawned via [`spawn_cleanup`] that may still be running.
+static CLEANUP_TASKS: Mutex<Vec<JoinHandle<()>>> = Mutex::new(Vec::new());
+
+/// Spawn a task that should complete before the process exits.
+///
+/// Used for work that must outlive an operation dropped on shutdown, such as
+/// telling the daemon to cancel a tool call. [`run_with_graceful_shutdown`]
+/// waits for these tasks (up to [`CLEANUP_TIMEOUT`]) before returning.
+/// Does nothing outside a Tokio runtime.
+pub fn spawn_cleanup<F>(task: F)
+where
+    F: std::future::Future<Output = ()> + Send + 'static,
+{
+    let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+        return;
+    };
+    let mut tasks = CLEANUP_TASKS
+        .lock()
+        .expect("Failed to acquire cleanup task lock");
+    tasks.retain(|task| !task.is_finished());
+    tasks.push(runtime.spawn(task));
+}
+
+/// Wait for outstanding cleanup tasks, giving up after [`CLEANUP_TIMEOUT`].
+pub async fn wait_for_cleanup() {
+    let tasks = std::mem::take(
+        &mut *CLEANUP_TASKS
+            .lock()
+            .expect("Failed to acquire cleanup task lock"),
+    );
+    if tokio::time::timeout(CLEANUP_TIMEOUT, futures::future::join_all(tasks))
+        .await
+        .is_err()
+    {
+        tracing::warn!("Cleanup did not finish within {:?}", CLEANUP_TIMEOUT);
+    }
+}
 
 /// Graceful shutdown handler for signals.
 ///
@@ -15,6 +60,7 @@ pub struct GracefulShutdown {
     shutdown_tx: broadcast::Sender<bool>,
 
     /// Shutdown receiver.
+    #[allow(dead_code)] // Only read by is_shutdown_requested
     shutdown_rx: broadcast::Receiver<bool>,
 }
 
@@ -80,6 +126,7 @@ impl GracefulShutdown {
     /// Check if shutdown was requested.
     ///
     /// Returns true if shutdown signal was sent.
+    #[allow(dead_code)] // Only exercised by the unit tests
     pub fn is_shutdown_requested(&mut self) -> bool {
         self.shutdown_rx.try_recv().unwrap_or_default()
     }
@@ -94,6 +141,8 @@ impl Default for GracefulShutdown {
 /// Run an async operation with graceful shutdown support.
 ///
 /// Automatically spawns signal listener and cancels operation on shutdown.
+/// After the operation is dropped, cleanup it scheduled with
+/// [`spawn_cleanup`] is given a chance to finish.
 ///
 /// # Arguments
 /// * `op` - Async operation to run
@@ -123,15 +172,19 @@ where
     Fut: std::future::Future<Output = crate::error::Result<T>>,
 {
     tokio::select! {
-        result = op() => result,
-        _ = shutdown_rx.recv() => {
-            println!("Shutting down gracefully...");
-            // Return shutdown error on termination
-            Err(crate::error::McpError::io_error(
-                std::io::Error::new(std::io::ErrorKind::Interrupted, "Shutdown requested")
-            ))
-        }
+        result = op() => return result,
+        _ = shutdown_rx.recv() => {}
     }
+
+    // The operation has been dropped; let its cleanup run
+    println!("Shutting down gracefully...");
+    wait_for_cleanup().await;
+
+    // Return shutdown error on termination
+    Err(crate::error::McpError::io_error(std::io::Error::new(
+        std::io::ErrorKind::Interrupted,
+        "Shutdown requested",
+    )))
 }
 
 #[cfg(test)]
@@ -154,4 +207,50 @@ mod tests {
         let _ = shutdown.shutdown_tx.send(true);
         assert!(shutdown.is_shutdown_requested());
     }
+
+    #[tokio::test]
+    async fn test_shutdown_waits_for_cleanup() {
+        let shutdown = GracefulShutdown::new();
+        let shutdown_rx = shutdown.subscribe();
+        let (done_tx, mut done_rx) = tokio::sync::oneshot::channel();
+        let done_tx = std::sync::Mutex::new(Some(done_tx));
+
+        let _ = shutdown.shutdown_tx.send(true);
+        let result = run_with_graceful_shutdown(
+            || {
+                // Cleanup scheduled when the pending operation is dropped
+                let done_tx = done_tx.lock().unwrap().take();
+                let on_drop = scopeguard(move || {
+                    spawn_cleanup(async move {
+                        tokio::time::sleep(Duration::from_millis(20)).await;
+                        if let Some(tx) = done_tx {
+                            let _ = tx.send(());
+                        }
+                    })
+                });
+                async move {
+                    let _on_drop = on_drop;
+                    std::future::pending::<crate::error::Result<()>>().await
+                }
+            },
+            shutdown_rx,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(done_rx.try_recv().is_ok());
+    }
+
+    /// Runs a closure when dropped
+    fn scopeguard(f: impl FnOnce()) -> impl Drop {
+        struct Guard<F: FnOnce()>(Option<F>);
+        impl<F: FnOnce()> Drop for Guard<F> {
+            fn drop(&mut self) {
+                if let Some(f) = self.0.take() {
+                    f();
+                }
+            }
+        }
+        Guard(Some(f))
+    }
 }
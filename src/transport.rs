@@ -6,8 +6,9 @@
 
 use async_trait::async_trait;
 use serde_json::Value;
+use std::time::Duration;
 
-use crate::error::Result;
+use crate::error::{McpError, Result};
 
 /// Transport protocol for MCP server communication.
 ///
@@ -45,6 +46,72 @@ pub trait Transport: Send + Sync {
     ///
     /// Returns a string identifying the transport type (e.g., "stdio", "http").
     fn transport_type(&self) -> &str;
+
+    /// Whether the connection is known to be dead, e.g. the server closed
+    /// its stream.
+    ///
+    /// Checked before reusing a pooled connection, so it must not do I/O.
+    /// The default returns false; transports without a persistent stream
+    /// can't tell before the next request.
+    fn is_closed(&self) -> bool {
+        false
+    }
+
+    /// Send a JSON-RPC request, passing notifications that arrive while it
+    /// is outstanding to `on_notification` instead of queueing them.
+    ///
+    /// Used to follow `notifications/progress` for long-running requests.
+    /// The default just sends the request.
+    async fn send_with_notifications(
+        &mut self,
+        request: Value,
+        on_notification: NotificationCallback<'_>,
+    ) -> Result<Value> {
+        let _ = on_notification;
+        self.send(request).await
+    }
+
+    /// Close the connection, releasing any server-side session.
+    ///
+    /// The default does nothing; transports with explicit session teardown
+    /// (e.g. Streamable HTTP's DELETE) override it.
+    async fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Send `notifications/cancelled` for requests that were abandoned, i.e.
+    /// whose `send` future was dropped before the response arrived.
+    ///
+    /// Lets the server stop work nobody is waiting for, leaving the
+    /// connection usable for further requests. Transports that already sent
+    /// the cancellation when the request was dropped just wait for it to be
+    /// written, and may use their own `reason`. The default does nothing.
+    async fn cancel_abandoned(&mut self, reason: &str) -> Result<()> {
+        let _ = reason;
+        Ok(())
+    }
+
+    /// Set how long to wait for the response to each request.
+    ///
+    /// A request that times out fails with `McpError::Timeout` and counts as
+    /// abandoned (see [`Self::cancel_abandoned`]). The default does nothing.
+    fn set_request_timeout(&mut self, timeout: Duration) {
+        let _ = timeout;
+    }
+}
+
+/// Callback receiving server notifications during a request.
+pub type NotificationCallback<'a> = &'a mut (dyn FnMut(Value) + Send);
+
+/// Build a `notifications/cancelled` message for the request with `request_id`.
+///
+/// `request_id` must be the id the request carried on the wire.
+pub fn cancelled_notification(request_id: Value, reason: &str) -> Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "notifications/cancelled",
+        "params": { "requestId": request_id, "reason": reason }
+    })
 }
 
 /// Trait extension for transport factory methods.
@@ -69,13 +136,364 @@ pub trait TransportFactory: Send + Sync {
 /// This simplifies the signature of methods that return BoxedTransport.
 pub type BoxedTransport = Box<dyn Transport + Send + Sync>;
 
+/// Protocol version requested in `initialize`: the current Streamable HTTP
+/// revision of the MCP specification.
+pub const LATEST_PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// Protocol version requested again when a server rejects the latest one.
+pub const FALLBACK_PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Build an `initialize` request asking for `protocol_version`.
+fn initialize_request(id: u64, protocol_version: &str) -> Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": protocol_version,
+            "capabilities": {
+                "roots": {},
+                "sampling": {},
+                "tools": {}
+            },
+            "clientInfo": {
+                "name": "mcp-cli-rs",
+                "version": env!("CARGO_PKG_VERSION")
+            }
+        }
+    })
+}
+
+/// Send `initialize` with [`LATEST_PROTOCOL_VERSION`], retrying once with
+/// [`FALLBACK_PROTOCOL_VERSION`] if the server answers with an error.
+///
+/// Servers that only implement the older revision reject unknown versions
+/// rather than offering their own. Returns the JSON-RPC response of the last
+/// attempt; an error response is left for the caller to report.
+pub async fn send_initialize(transport: &mut BoxedTransport, id: u64) -> Result<Value> {
+    let response = transport
+        .send(initialize_request(id, LATEST_PROTOCOL_VERSION))
+        .await?;
+    if response.get("error").is_none() {
+        return Ok(response);
+    }
+
+    tracing::debug!(
+        "Server rejected protocol {}, retrying with {}",
+        LATEST_PROTOCOL_VERSION,
+        FALLBACK_PROTOCOL_VERSION
+    );
+    transport
+        .send(initialize_request(id, FALLBACK_PROTOCOL_VERSION))
+        .await
+}
+
+/// Maximum number of pages fetched when following `nextCursor`.
+///
+/// Protects against servers that keep returning cursors indefinitely.
+pub const MAX_LIST_PAGES: usize = 100;
+
+/// Send a paginated MCP list request and merge every page into one result.
+///
+/// Follows `nextCursor` until the server stops returning one, returns a cursor
+/// it already sent, or [`MAX_LIST_PAGES`] is reached. The returned value has the
+/// same shape as a single page: `{ key: [...all items...] }`.
+///
+/// # Arguments
+/// * `transport` - Initialized transport to send requests on
+/// * `method` - List method, e.g. "tools/list"
+/// * `key` - Result member holding the items, e.g. "tools"
+/// * `first_id` - JSON-RPC id of the first page request; later pages increment it
+///
+/// # Errors
+/// Returns McpError::InvalidProtocol if the server returns an error or a malformed page
+pub async fn request_all_pages(
+    transport: &mut BoxedTransport,
+    method: &str,
+    key: &str,
+    first_id: u64,
+) -> Result<Value> {
+    let mut items = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    for page in 0..MAX_LIST_PAGES {
+        let params = match cursor {
+            Some(ref c) => serde_json::json!({ "cursor": c }),
+            None => serde_json::json!({}),
+        };
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": first_id + page as u64,
+            "method": method,
+            "params": params
+        });
+
+        let response = transport.send(request).await?;
+        let result = match (response.get("result"), response.get("error")) {
+            (Some(result), _) => result,
+            (None, Some(error)) => {
+                let msg = error
+                    .get("message")
+                    .and_then(|m| m.as_str())
+                    .unwrap_or("Unknown error");
+                return Err(McpError::InvalidProtocol {
+                    message: format!("{} failed: {}", method, msg),
+                });
+            }
+            (None, None) => {
+                return Err(McpError::InvalidProtocol {
+                    message: "Invalid MCP response format".to_string(),
+                });
+            }
+        };
+
+        match result.get(key) {
+            Some(Value::Array(page_items)) => items.extend(page_items.iter().cloned()),
+            Some(_) => {
+                return Err(McpError::InvalidProtocol {
+                    message: format!("Invalid '{}' in {} result: expected an array", key, method),
+                });
+            }
+            None => {}
+        }
+
+        match result.get("nextCursor").and_then(|c| c.as_str()) {
+            Some(next) if cursor.as_deref() != Some(next) => cursor = Some(next.to_string()),
+            _ => return Ok(serde_json::json!({ key: items })),
+        }
+    }
+
+    tracing::warn!(
+        "{} returned more than {} pages; remaining items were not fetched",
+        method,
+        MAX_LIST_PAGES
+    );
+    Ok(serde_json::json!({ key: items }))
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
     #[test]
     fn test_transport_type() {
         // This is a placeholder test - actual transport implementations will be in stdio.rs and http.rs
         println!("Transport trait implemented");
     }
+
+    /// Transport serving `total` items in pages of `page_size`, using offsets as cursors
+    struct PagedTransport {
+        total: usize,
+        page_size: usize,
+        requests: Arc<Mutex<Vec<Value>>>,
+        /// Always return this cursor instead of advancing (misbehaving server)
+        stuck_cursor: Option<String>,
+    }
+
+    #[async_trait]
+    impl Transport for PagedTransport {
+        async fn send(&mut self, request: Value) -> Result<Value> {
+            let offset: usize = request["params"]["cursor"]
+                .as_str()
+                .map(|c| c.parse().unwrap())
+                .unwrap_or(0);
+            self.requests.lock().unwrap().push(request.clone());
+
+            let end = (offset + self.page_size).min(self.total);
+            let items: Vec<Value> = (offset..end)
+                .map(|i| serde_json::json!({ "n": i }))
+                .collect();
+            let mut result = serde_json::json!({ "items": items });
+            if let Some(ref stuck) = self.stuck_cursor {
+                result["nextCursor"] = Value::String(stuck.clone());
+            } else if end < self.total {
+                result["nextCursor"] = Value::String(end.to_string());
+            }
+            Ok(serde_json::json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+        }
+
+        async fn send_notification(&mut self, _notification: Value) -> Result<()> {
+            Ok(())
+        }
+
+        async fn receive_notification(&mut self) -> Result<Value> {
+            Ok(Value::Null)
+        }
+
+        async fn ping(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn transport_type(&self) -> &str {
+            "paged"
+        }
+    }
+
+    fn paged(total: usize, page_size: usize) -> PagedTransport {
+        PagedTransport {
+            total,
+            page_size,
+            requests: Arc::new(Mutex::new(Vec::new())),
+            stuck_cursor: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_all_pages_follows_cursors() {
+        let mut transport: BoxedTransport = Box::new(paged(5, 2));
+
+        let result = request_all_pages(&mut transport, "items/list", "items", 1)
+            .await
+            .unwrap();
+
+        let items = result["items"].as_array().unwrap();
+        assert_eq!(items.len(), 5);
+        assert_eq!(items[4]["n"], 4);
+    }
+
+    #[tokio::test]
+    async fn test_request_all_pages_sends_cursor_and_ids() {
+        let transport = paged(3, 2);
+        let requests = Arc::clone(&transport.requests);
+        let mut transport: BoxedTransport = Box::new(transport);
+
+        request_all_pages(&mut transport, "items/list", "items", 7)
+            .await
+            .unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0]["id"], 7);
+        assert_eq!(requests[0]["params"], serde_json::json!({}));
+        assert_eq!(requests[1]["id"], 8);
+        assert_eq!(requests[1]["params"]["cursor"], "2");
+    }
+
+    #[tokio::test]
+    async fn test_request_all_pages_stops_on_repeated_cursor() {
+        let mut transport: BoxedTransport = Box::new(PagedTransport {
+            stuck_cursor: Some("0".to_string()),
+            ..paged(4, 2)
+        });
+
+        let result = request_all_pages(&mut transport, "items/list", "items", 1)
+            .await
+            .unwrap();
+
+        // First page (no cursor) then one page at cursor "0" which repeats itself
+        assert_eq!(result["items"].as_array().unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_request_all_pages_caps_page_count() {
+        // One item per page and more items than the cap allows
+        let mut transport: BoxedTransport = Box::new(paged(MAX_LIST_PAGES + 10, 1));
+
+        let result = request_all_pages(&mut transport, "items/list", "items", 1)
+            .await
+            .unwrap();
+
+        assert_eq!(result["items"].as_array().unwrap().len(), MAX_LIST_PAGES);
+    }
+
+    #[tokio::test]
+    async fn test_request_all_pages_missing_key_is_empty() {
+        let mut transport: BoxedTransport = Box::new(paged(0, 1));
+
+        let result = request_all_pages(&mut transport, "items/list", "other", 1)
+            .await
+            .unwrap();
+
+        assert_eq!(result, serde_json::json!({ "other": [] }));
+    }
+
+    /// Transport for a server that accepts only `supported` protocol versions
+    struct VersionedTransport {
+        supported: &'static [&'static str],
+        requested: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Transport for VersionedTransport {
+        async fn send(&mut self, request: Value) -> Result<Value> {
+            let version = request["params"]["protocolVersion"].as_str().unwrap();
+            self.requested.lock().unwrap().push(version.to_string());
+            if self.supported.contains(&version) {
+                Ok(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "result": { "protocolVersion": version }
+                }))
+            } else {
+                Ok(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "error": { "code": -32602, "message": "Unsupported protocol version" }
+                }))
+            }
+        }
+
+        async fn send_notification(&mut self, _notification: Value) -> Result<()> {
+            Ok(())
+        }
+
+        async fn receive_notification(&mut self) -> Result<Value> {
+            Ok(Value::Null)
+        }
+
+        async fn ping(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn transport_type(&self) -> &str {
+            "versioned"
+        }
+    }
+
+    async fn initialize_with(supported: &'static [&'static str]) -> (Value, Vec<String>) {
+        let requested = Arc::new(Mutex::new(Vec::new()));
+        let mut transport: BoxedTransport = Box::new(VersionedTransport {
+            supported,
+            requested: Arc::clone(&requested),
+        });
+        let response = send_initialize(&mut transport, 0).await.unwrap();
+        let requested = requested.lock().unwrap().clone();
+        (response, requested)
+    }
+
+    #[tokio::test]
+    async fn test_send_initialize_requests_latest_version() {
+        let (response, requested) = initialize_with(&[LATEST_PROTOCOL_VERSION]).await;
+
+        assert_eq!(
+            response["result"]["protocolVersion"],
+            LATEST_PROTOCOL_VERSION
+        );
+        assert_eq!(requested, vec![LATEST_PROTOCOL_VERSION]);
+    }
+
+    #[tokio::test]
+    async fn test_send_initialize_falls_back_to_old_version() {
+        let (response, requested) = initialize_with(&[FALLBACK_PROTOCOL_VERSION]).await;
+
+        assert_eq!(
+            response["result"]["protocolVersion"],
+            FALLBACK_PROTOCOL_VERSION
+        );
+        assert_eq!(
+            requested,
+            vec![LATEST_PROTOCOL_VERSION, FALLBACK_PROTOCOL_VERSION]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_initialize_returns_final_rejection() {
+        let (response, requested) = initialize_with(&[]).await;
+
+        assert!(response.get("error").is_some());
+        assert_eq!(requested.len(), 2);
+    }
 }
 
 // TransportFactory trait is already defined above, no re-export needed
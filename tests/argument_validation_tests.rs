@@ -0,0 +1,101 @@
+//! Integration tests for checking `mcp call` arguments against the tool's
+//! inputSchema before they are sent
+//!
+//! The mock server's `add` tool requires numbers `a` and `b`. Flag-style
+//! arguments are converted to those types before they are checked.
+
+use std::path::Path;
+use std::process::Output;
+
+const MOCK_SERVER: &str = env!("CARGO_BIN_EXE_mock-mcp-server");
+
+/// Run `mcp --no-daemon call mock/add <args>` against the mock server
+fn call_add(dir: &Path, flags: &[&str], args: &[&str]) -> Output {
+    let config_path = dir.join("mcp_servers.toml");
+    let config = format!(
+        "[[servers]]\nname = \"mock\"\n\n[servers.transport]\ntype = \"stdio\"\ncommand = '{}'\n",
+        MOCK_SERVER
+    );
+    std::fs::write(&config_path, config).unwrap();
+
+    std::process::Command::new(env!("CARGO_BIN_EXE_mcp-cli-rs"))
+        .arg("--config")
+        .arg(&config_path)
+        .args(["--no-daemon", "call", "mock/add"])
+        .args(flags)
+        .arg("--")
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_invalid_arguments_are_rejected_with_paths() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let output = call_add(dir.path(), &[], &[r#"{"a": "one"}"#]);
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Invalid arguments for tool 'add'"),
+        "{}",
+        stderr
+    );
+    assert!(
+        stderr.contains("/b: required property is missing"),
+        "{}",
+        stderr
+    );
+    assert!(
+        stderr.contains("/a: expected number, got string"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_valid_arguments_are_sent() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let output = call_add(dir.path(), &[], &[r#"{"a": 1, "b": 2}"#]);
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_no_validate_sends_invalid_arguments() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let output = call_add(dir.path(), &["--no-validate"], &[r#"{"a": "one"}"#]);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("Invalid arguments"), "{}", stderr);
+}
+
+#[test]
+fn test_flag_values_are_coerced_to_schema_types() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let output = call_add(dir.path(), &[], &["--a", "1", "--b", "2.5"]);
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_flag_type_mismatch_is_usage_error() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let output = call_add(dir.path(), &[], &["--a", "one", "--b", "2"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("--a expects a number, got 'one'"),
+        "{}",
+        stderr
+    );
+}
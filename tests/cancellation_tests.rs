@@ -0,0 +1,108 @@
+//! Integration tests for tool call cancellation
+//!
+//! Runs the mock MCP server with a long MOCK_DELAY_MS so tool calls are still
+//! running when they are cancelled. The server logs each call stopped by
+//! `notifications/cancelled` to MOCK_CANCEL_LOG, which only happens when the
+//! notification carries the id the call was sent with.
+
+use mcp_cli_rs::config::Config;
+use mcp_cli_rs::daemon::pool::ConnectionPool;
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+mod fixtures {
+    pub mod daemon_test_helper;
+}
+
+mod common {
+    pub mod mock_server;
+}
+
+use common::mock_server::mock_server_config;
+
+/// Build a config with a single mock server whose tool calls take a minute
+fn slow_config(cancel_log: &Path) -> Config {
+    Config {
+        servers: vec![mock_server_config(
+            "mock",
+            &[
+                ("MOCK_DELAY_MS", "60000"),
+                ("MOCK_CANCEL_LOG", cancel_log.to_str().unwrap()),
+            ],
+        )],
+        ..Config::default()
+    }
+}
+
+/// Wait until the mock server has logged `count` cancelled calls
+async fn wait_for_cancellations(cancel_log: &Path, count: usize) -> Vec<String> {
+    for _ in 0..100 {
+        if let Ok(log) = std::fs::read_to_string(cancel_log) {
+            let ids: Vec<String> = log.lines().map(str::to_string).collect();
+            if ids.len() >= count {
+                return ids;
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("server did not record {} cancelled call(s)", count);
+}
+
+#[tokio::test]
+async fn test_pool_cancel_notifies_server_and_keeps_connection() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let cancel_log = dir.path().join("cancelled.log");
+    let pool = ConnectionPool::new(Arc::new(slow_config(&cancel_log)));
+
+    let result = pool
+        .execute_cancellable(
+            "mock",
+            "echo",
+            json!({ "message": "hi" }),
+            None,
+            &mut |_| {},
+            tokio::time::sleep(Duration::from_millis(300)),
+        )
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(wait_for_cancellations(&cancel_log, 1).await.len(), 1);
+
+    // The connection went back to the pool and the server is free again
+    assert_eq!(pool.count(), 1);
+    let tools = tokio::time::timeout(Duration::from_secs(5), pool.list_tools("mock"))
+        .await
+        .expect("server still busy with the cancelled call")
+        .unwrap();
+    assert!(!tools.is_empty());
+}
+
+#[tokio::test]
+async fn test_dropped_daemon_call_is_cancelled() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let cancel_log = dir.path().join("cancelled.log");
+    let daemon = fixtures::daemon_test_helper::spawn_test_daemon(slow_config(&cancel_log))
+        .await
+        .unwrap();
+    let mut client = daemon.client().unwrap();
+
+    // Giving up on the call (as Ctrl+C does) sends CancelRequest to the daemon
+    let call = client.execute_tool("mock", "echo", json!({ "message": "hi" }));
+    assert!(
+        tokio::time::timeout(Duration::from_millis(500), call)
+            .await
+            .is_err()
+    );
+    assert_eq!(wait_for_cancellations(&cancel_log, 1).await.len(), 1);
+
+    // The daemon's pooled connection is still usable
+    let tools = tokio::time::timeout(Duration::from_secs(5), client.list_tools("mock"))
+        .await
+        .expect("server still busy with the cancelled call")
+        .unwrap();
+    assert!(!tools.is_empty());
+
+    daemon.shutdown().await.unwrap();
+}
@@ -269,7 +269,7 @@ fn test_parameter_model_conversion() {
 
     assert_eq!(info.name, "test_param");
     assert_eq!(info.param_type, "number");
-    assert_eq!(info.required, true);
+    assert!(info.required);
     assert_eq!(info.description, Some("A test parameter".into()));
 }
 
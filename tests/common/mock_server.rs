@@ -0,0 +1,67 @@
+//! The mock MCP server as a configured server
+//!
+//! Tests that talk to `mock-mcp-server` through the pool, a client or a
+//! daemon describe it with [`mock_server_config`], or take a whole config
+//! holding just that server from [`single_server_config`]; tests of the
+//! `mcp` binary run it against the mock server with [`run_mcp`].
+
+// Shared by several test crates; not every crate uses every helper
+#![allow(dead_code)]
+
+use mcp_cli_rs::config::{Config, ServerConfig, ServerTransport};
+use std::path::Path;
+use std::process::Output;
+
+pub const MOCK_SERVER: &str = env!("CARGO_BIN_EXE_mock-mcp-server");
+
+/// A stdio server named `name` backed by the mock MCP server
+///
+/// `env` sets the MOCK_* variables that select the server's behaviour.
+pub fn mock_server_config(name: &str, env: &[(&str, &str)]) -> ServerConfig {
+    ServerConfig {
+        name: name.to_string(),
+        transport: ServerTransport::Stdio {
+            command: MOCK_SERVER.to_string(),
+            args: vec![],
+            env: env
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+            cwd: None,
+        },
+        description: None,
+        allowed_tools: None,
+        disabled_tools: None,
+        timeout_secs: None,
+    }
+}
+
+/// A config whose only server is the mock MCP server, named `name`
+pub fn single_server_config(name: &str) -> Config {
+    Config {
+        servers: vec![mock_server_config(name, &[])],
+        ..Config::default()
+    }
+}
+
+/// Run `mcp --no-daemon <args>` with a config in `dir` holding the mock
+/// server as `mock`
+///
+/// `server_toml` adds keys to the server's table, e.g.
+/// `disabled_tools = ["fail"]`.
+pub fn run_mcp(dir: &Path, server_toml: &str, args: &[&str]) -> Output {
+    let config_path = dir.join("mcp_servers.toml");
+    let config = format!(
+        "[[servers]]\nname = \"mock\"\n{}\n\n[servers.transport]\ntype = \"stdio\"\ncommand = '{}'\n",
+        server_toml, MOCK_SERVER
+    );
+    std::fs::write(&config_path, config).unwrap();
+
+    std::process::Command::new(env!("CARGO_BIN_EXE_mcp-cli-rs"))
+        .arg("--config")
+        .arg(&config_path)
+        .arg("--no-daemon")
+        .args(args)
+        .output()
+        .unwrap()
+}
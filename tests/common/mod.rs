@@ -2,13 +2,13 @@
 //!
 //! Provides utilities and patterns used by both Unix and Windows tests
 
-use crate::helpers;
+// Shared by several test crates; not every crate uses every helper
+#![allow(dead_code)]
+
 use mcp_cli_rs::daemon::protocol::{DaemonRequest, DaemonResponse};
 use mcp_cli_rs::ipc;
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::io::BufReader;
 use tokio::time::timeout;
 
 /// Test helper: IPC roundtrip with timeout
@@ -22,33 +22,30 @@ pub async fn test_ipc_roundtrip_with_timeout(
     // Implementation using helpers and common patterns
     let server = ipc::create_ipc_server(&socket_path).await?;
     let expected_response_clone = expected_response.clone();
+    let expected_request = request.clone();
     let server_handle = tokio::spawn(async move {
         let result = timeout(
             std::time::Duration::from_secs(5),
             server.accept()
         ).await;
-        let (mut stream, _addr) = match result {
+        let (stream, _addr) = match result {
             Ok(Ok(stream)) => stream,
             Ok(Err(e)) => panic!("Server accept failed: {}", e),
             Err(e) => panic!("Server accept timed out: {}", e),
         };
 
         let mut buf_reader = tokio::io::BufReader::new(stream);
-        let req = mcp_cli_rs::daemon::protocol::receive_request(&mut buf_reader)
-            .await
-            .expect("Failed to receive request");
+        let (id, req) = crate::helpers::receive_first_request(&mut buf_reader).await;
 
-        assert!(matches!(req, request));
+        assert_eq!(req, expected_request);
 
-        mcp_cli_rs::daemon::protocol::send_response(&mut buf_reader, &expected_response_clone)
-            .await
-            .expect("Failed to send response");
+        crate::helpers::send_response_frame(&mut buf_reader, id, expected_response_clone).await;
     });
 
     tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
 
     let config = Arc::new(mcp_cli_rs::config::Config::with_socket_path(socket_path.clone()));
-    let mut client = ipc::create_ipc_client(&*config)?;
+    let mut client = ipc::create_ipc_client(&config)?;
 
     let response = client.send_request(&request).await?;
     assert!(
@@ -0,0 +1,53 @@
+//! Integration tests for `mcp completions` and the hidden `mcp __complete`
+//! helper the completion scripts call
+
+use std::process::Output;
+
+mod common {
+    pub mod mock_server;
+}
+
+use common::mock_server::run_mcp;
+
+fn candidates(output: Output) -> Vec<String> {
+    assert!(output.status.success());
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn test_complete_server_then_tool() {
+    let dir = tempfile::TempDir::new().unwrap();
+
+    let servers = candidates(run_mcp(dir.path(), "", &["__complete", "tool", "mo"]));
+    assert_eq!(servers, vec!["mock/"]);
+
+    let tools = candidates(run_mcp(dir.path(), "", &["__complete", "tool", "mock/ec"]));
+    assert_eq!(tools, vec!["mock/echo"]);
+
+    let servers = candidates(run_mcp(dir.path(), "", &["__complete", "server"]));
+    assert_eq!(servers, vec!["mock"]);
+}
+
+#[test]
+fn test_complete_unknown_server_is_empty() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let tools = candidates(run_mcp(
+        dir.path(),
+        "",
+        &["__complete", "tool", "nowhere/x"],
+    ));
+    assert!(tools.is_empty());
+}
+
+#[test]
+fn test_completions_script_is_printed() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let output = run_mcp(dir.path(), "", &["completions", "bash"]);
+
+    assert!(output.status.success());
+    let script = String::from_utf8_lossy(&output.stdout);
+    assert!(script.contains("_mcp_with_names"));
+}
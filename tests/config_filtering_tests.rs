@@ -150,6 +150,7 @@ args = ["-m", "server2"]
                 description: None,
                 allowed_tools: Some(vec!["list_*".to_string(), "search_*".to_string()]),
                 disabled_tools: Some(vec!["password_*".to_string()]),
+                timeout_secs: None,
             }],
             ..Config::default()
         };
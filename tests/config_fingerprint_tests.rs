@@ -115,20 +115,22 @@ fn test_config_fingerprint_integration_with_daemon_logic() {
 
     // Create daemon state similar to how daemon/mod.rs creates it
     let lifecycle = DaemonLifecycle::new(60);
-    let connection_pool = Arc::new(mcp_cli_rs::daemon::pool::ConnectionPool::new(Arc::new(
-        config1.clone(),
-    )));
+    let config = mcp_cli_rs::daemon::reload::SharedConfig::new(config1.clone());
+    let connection_pool = Arc::new(mcp_cli_rs::daemon::pool::ConnectionPool::new(
+        config.clone(),
+    ));
 
     let state = mcp_cli_rs::daemon::DaemonState {
-        config: Arc::new(config1.clone()),
-        config_fingerprint: fp1.clone(),
+        config,
+        config_path: None,
         lifecycle: Arc::new(Mutex::new(lifecycle)),
         connection_pool,
     };
 
     // If config hash matches, no change
     assert_eq!(
-        state.config_fingerprint, fp1,
+        state.config.fingerprint(),
+        fp1,
         "State should store initial fingerprint"
     );
 
@@ -138,6 +140,6 @@ fn test_config_fingerprint_integration_with_daemon_logic() {
     assert!(fp2 != fp1, "New config should have different hash");
 
     // Integration: config hash should detect change
-    assert!(config_hash_changed(&config1, &state.config));
-    assert!(state.config_fingerprint != fp2);
+    assert!(config_hash_changed(&config1, &state.config.get()));
+    assert!(state.config.fingerprint() != fp2);
 }
@@ -0,0 +1,111 @@
+//! Integration tests for reloading the daemon config in place
+//!
+//! Exercises `ConnectionPool::reload` and the config file watcher against the
+//! mock MCP server.
+
+use mcp_cli_rs::config::{Config, ServerConfig};
+use mcp_cli_rs::daemon::pool::ConnectionPool;
+use mcp_cli_rs::daemon::reload::watch_config;
+use std::time::{Duration, Instant};
+
+mod common {
+    pub mod mock_server;
+}
+
+use common::mock_server::mock_server_config;
+
+fn config(servers: Vec<ServerConfig>) -> Config {
+    Config {
+        servers,
+        ..Config::default()
+    }
+}
+
+#[tokio::test]
+async fn test_reload_keeps_connections_to_unchanged_servers() {
+    let pool = ConnectionPool::new(config(vec![
+        mock_server_config("kept", &[]),
+        mock_server_config("edited", &[]),
+        mock_server_config("removed", &[]),
+    ]));
+    for server in ["kept", "edited", "removed"] {
+        pool.list_tools(server).await.unwrap();
+    }
+    assert_eq!(pool.count(), 3);
+    let reloaded_at = Instant::now();
+
+    let changes = pool.reload(config(vec![
+        mock_server_config("kept", &[]),
+        mock_server_config("edited", &[("MOCK_DELAY_MS", "1")]),
+        mock_server_config("added", &[]),
+    ]));
+
+    assert_eq!(changes.added, vec!["added"]);
+    assert_eq!(changes.removed, vec!["removed"]);
+    assert_eq!(changes.changed, vec!["edited"]);
+    assert_eq!(pool.count(), 1);
+    let kept = pool.take("kept").await.unwrap().unwrap();
+    assert!(kept.created_at < reloaded_at);
+    pool.put_back(kept);
+
+    assert!(pool.list_tools("added").await.is_ok());
+    assert!(pool.list_tools("edited").await.is_ok());
+    assert!(pool.list_tools("removed").await.is_err());
+}
+
+#[tokio::test]
+async fn test_reload_lets_in_flight_call_finish_on_old_connection() {
+    let pool = ConnectionPool::new(config(vec![mock_server_config(
+        "mock",
+        &[("MOCK_DELAY_MS", "500")],
+    )]));
+
+    let call = pool.execute("mock", "echo", serde_json::json!({ "message": "hi" }));
+    let reload = async {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        pool.reload(config(vec![mock_server_config("mock", &[])]))
+    };
+    let (result, changes) = tokio::join!(call, reload);
+
+    assert_eq!(changes.changed, vec!["mock"]);
+    assert_eq!(result.unwrap()["content"][0]["text"], "Echo: hi");
+    // The connection made with the old config isn't pooled again
+    assert_eq!(pool.count(), 0);
+}
+
+#[tokio::test]
+async fn test_watcher_reloads_changed_config_file() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("mcp_servers.toml");
+    let server = |name: &str| {
+        format!(
+            "[[servers]]\nname = \"{}\"\n\n[servers.transport]\ntype = \"stdio\"\ncommand = '{}'\n\n",
+            name,
+            env!("CARGO_BIN_EXE_mock-mcp-server")
+        )
+    };
+    std::fs::write(&path, server("mock")).unwrap();
+    let pool = ConnectionPool::new(
+        mcp_cli_rs::config::loader::load_config(&path)
+            .await
+            .unwrap(),
+    );
+    let watcher = tokio::spawn(watch_config(path.clone(), pool.clone()));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    std::fs::write(&path, server("mock") + &server("mock2")).unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while pool.config().servers.len() < 2 {
+        assert!(Instant::now() < deadline, "config was not reloaded");
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(pool.config().servers[1].name, "mock2");
+
+    // A broken edit keeps the last good config
+    std::fs::write(&path, "[[servers]\n").unwrap();
+    tokio::time::sleep(Duration::from_millis(2500)).await;
+    assert_eq!(pool.config().servers.len(), 2);
+
+    watcher.abort();
+}
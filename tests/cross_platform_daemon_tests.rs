@@ -7,7 +7,7 @@
 //! - tests/windows/tests.rs (Named pipe tests for Windows)
 //! - tests/common/mod.rs (Shared test patterns)
 
-use mcp_cli_rs::daemon::protocol::{DaemonRequest, DaemonResponse};
+use mcp_cli_rs::daemon::protocol::DaemonRequest;
 use mcp_cli_rs::ipc::IpcClient;
 
 #[cfg(test)]
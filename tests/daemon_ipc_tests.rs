@@ -81,6 +81,7 @@ async fn test_daemon_protocol_roundtrip() -> Result<()> {
             server_name: "mock-server".to_string(),
             tool_name: "echo".to_string(),
             arguments: serde_json::json!({"message": "hello"}),
+            timeout_secs: None,
         })
         .await?;
     // Response received - IPC is working
@@ -128,6 +129,7 @@ async fn test_concurrent_tool_calls() -> Result<()> {
             server_name: "mock-server".to_string(),
             tool_name: "echo".to_string(),
             arguments: serde_json::json!({"message": format!("test_{}", i)}),
+            timeout_secs: None,
         };
 
         match timeout(Duration::from_secs(10), client.send_request(&request)).await {
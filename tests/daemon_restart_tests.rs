@@ -0,0 +1,214 @@
+//! Integration tests for reloading or replacing a daemon whose config is stale
+//!
+//! The daemon socket lives under the working directory, so each test runs
+//! the binary inside its own temp directory. A daemon reloads its own config
+//! file in place; one reading another file is left alone, and one without a
+//! config file is replaced. A replaced daemon finishes the calls it is
+//! running first, however long they take within their timeout.
+
+#![cfg(unix)]
+
+use mcp_cli_rs::config::Config;
+use mcp_cli_rs::daemon::protocol::{DaemonRequest, DaemonResponse};
+use serde_json::{Value, json};
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use std::time::{Duration, Instant};
+
+mod fixtures {
+    pub mod daemon_test_helper;
+}
+
+mod common {
+    pub mod mock_server;
+}
+
+use common::mock_server::{MOCK_SERVER, mock_server_config};
+
+/// Write `file` in `dir` with one slow mock server per name
+fn write_config(dir: &Path, file: &str, servers: &[&str]) {
+    let config: String = servers
+        .iter()
+        .map(|name| {
+            format!(
+                "[[servers]]\nname = \"{}\"\n\n[servers.transport]\ntype = \"stdio\"\ncommand = '{}'\nenv = {{ MOCK_DELAY_MS = \"2000\" }}\n\n",
+                name, MOCK_SERVER
+            )
+        })
+        .collect();
+    std::fs::write(dir.join(file), config).unwrap();
+}
+
+/// `mcp --config <file> <args>`, run in `dir`
+fn mcp(dir: &Path, file: &str, args: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_mcp-cli-rs"));
+    command
+        .current_dir(dir)
+        .args(["--config", file])
+        .args(args)
+        .env_remove("MCP_CONFIG_PATH");
+    command
+}
+
+fn server_names(output: &Output) -> Vec<String> {
+    let list: Value = serde_json::from_slice(&output.stdout).unwrap();
+    list["servers"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|server| server["name"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_daemon_reloads_its_edited_config() {
+    let dir = tempfile::TempDir::new().unwrap();
+    write_config(dir.path(), "a.toml", &["mock"]);
+    let output = mcp(dir.path(), "a.toml", &["list"]).output().unwrap();
+    assert!(output.status.success());
+
+    write_config(dir.path(), "a.toml", &["mock", "mock2"]);
+    let output = mcp(
+        dir.path(),
+        "a.toml",
+        &["--require-daemon", "--json", "list"],
+    )
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+    assert_eq!(server_names(&output), vec!["mock", "mock2"]);
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("different config"));
+
+    let output = mcp(dir.path(), "a.toml", &["shutdown"]).output().unwrap();
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_daemon_reading_another_config_is_not_replaced() {
+    let dir = tempfile::TempDir::new().unwrap();
+    write_config(dir.path(), "a.toml", &["mock"]);
+    write_config(dir.path(), "b.toml", &["mock", "mock2"]);
+
+    let output = mcp(dir.path(), "a.toml", &["list"]).output().unwrap();
+    assert!(output.status.success());
+
+    // A call from another client is running on the daemon...
+    let call = mcp(
+        dir.path(),
+        "a.toml",
+        &["call", "mock/echo", "--", "--message", "in flight"],
+    )
+    .stdout(Stdio::piped())
+    .spawn()
+    .unwrap();
+    std::thread::sleep(Duration::from_millis(500));
+
+    // ...when a command using another config file finds it
+    let output = mcp(dir.path(), "b.toml", &["list"]).output().unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("is serving config"));
+
+    let call = call.wait_with_output().unwrap();
+    assert!(call.status.success());
+    assert!(String::from_utf8_lossy(&call.stdout).contains("in flight"));
+
+    let output = mcp(
+        dir.path(),
+        "a.toml",
+        &["--require-daemon", "--json", "list"],
+    )
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+    assert_eq!(server_names(&output), vec!["mock"]);
+
+    let output = mcp(dir.path(), "a.toml", &["shutdown"]).output().unwrap();
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_daemon_without_config_is_replaced() {
+    let dir = tempfile::TempDir::new().unwrap();
+    write_config(dir.path(), "b.toml", &["mock", "mock2"]);
+
+    // No config file in any default location
+    let mut daemon = Command::new(env!("CARGO_BIN_EXE_mcp-cli-rs"))
+        .current_dir(dir.path())
+        .arg("daemon")
+        .env_remove("MCP_CONFIG_PATH")
+        .env("HOME", dir.path())
+        .env("XDG_CONFIG_HOME", dir.path().join(".config"))
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    let socket = dir.path().join(".mcp-cli").join("daemon.sock");
+    for _ in 0..100 {
+        if socket.exists() {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    assert!(socket.exists());
+
+    let output = mcp(dir.path(), "b.toml", &["--json", "list"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(server_names(&output), vec!["mock", "mock2"]);
+    assert!(daemon.wait().unwrap().success());
+
+    let output = mcp(dir.path(), "b.toml", &["shutdown"]).output().unwrap();
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_require_daemon_warns_about_stale_config() {
+    let dir = tempfile::TempDir::new().unwrap();
+    write_config(dir.path(), "a.toml", &["mock"]);
+    write_config(dir.path(), "b.toml", &["mock", "mock2"]);
+    let output = mcp(dir.path(), "a.toml", &["list"]).output().unwrap();
+    assert!(output.status.success());
+
+    let output = mcp(dir.path(), "b.toml", &["--require-daemon", "list"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("different config"));
+
+    let output = mcp(dir.path(), "a.toml", &["shutdown"]).output().unwrap();
+    assert!(output.status.success());
+}
+
+#[tokio::test]
+async fn test_call_outlives_the_replaced_daemon_by_over_a_minute() {
+    let config = Config {
+        servers: vec![mock_server_config("mock", &[("MOCK_DELAY_MS", "65000")])],
+        ..Config::default()
+    };
+    let daemon = fixtures::daemon_test_helper::spawn_test_daemon(config)
+        .await
+        .unwrap();
+    let mut client = daemon.client().unwrap();
+    let call = tokio::spawn(async move {
+        client
+            .execute_tool("mock", "echo", json!({ "message": "in flight" }))
+            .await
+    });
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    // A client with another config retires the daemon the same way
+    let mut other = daemon.client().unwrap();
+    let response = other.send_request(&DaemonRequest::Shutdown).await.unwrap();
+    assert_eq!(response, DaemonResponse::ShutdownAck);
+    let retired = Instant::now();
+    while daemon.client().unwrap().list_servers().await.is_ok() {
+        assert!(retired.elapsed() < Duration::from_secs(10));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    let result = call.await.unwrap().unwrap();
+    assert!(result.to_string().contains("in flight"));
+    assert!(retired.elapsed() > Duration::from_secs(60));
+
+    daemon.shutdown().await.unwrap();
+}
@@ -0,0 +1,57 @@
+//! Integration tests for `mcp docs`
+
+mod common {
+    pub mod mock_server;
+}
+
+use common::mock_server::run_mcp;
+
+/// Mock server settings: a description and its `fail` tool disabled
+const SERVER_TOML: &str = "description = \"Mock server\"\ndisabled_tools = [\"fail\"]";
+
+#[test]
+fn test_docs_markdown() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let out = dir.path().join("docs");
+    let output = run_mcp(
+        dir.path(),
+        SERVER_TOML,
+        &["docs", "--out", out.to_str().unwrap()],
+    );
+    assert!(output.status.success());
+
+    let index = std::fs::read_to_string(out.join("index.md")).unwrap();
+    assert!(index.contains("| [mock](mock.md) | 2 | Mock server |"));
+
+    let page = std::fs::read_to_string(out.join("mock.md")).unwrap();
+    assert!(page.contains("## echo"));
+    assert!(page.contains("| `message` | string | yes |"));
+    assert!(page.contains("mcp call mock/echo -- '{\"message\":\"<message>\"}'"));
+    assert!(!page.contains("## fail"));
+
+    // Regenerating produces the same pages
+    let output = run_mcp(
+        dir.path(),
+        SERVER_TOML,
+        &["docs", "--out", out.to_str().unwrap()],
+    );
+    assert!(output.status.success());
+    assert_eq!(std::fs::read_to_string(out.join("mock.md")).unwrap(), page);
+}
+
+#[test]
+fn test_docs_html() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let out = dir.path().join("site");
+    let output = run_mcp(
+        dir.path(),
+        SERVER_TOML,
+        &["docs", "--out", out.to_str().unwrap(), "--format", "html"],
+    );
+    assert!(output.status.success());
+
+    let index = std::fs::read_to_string(out.join("index.html")).unwrap();
+    assert!(index.contains("<a href=\"mock.html\">mock</a>"));
+    let page = std::fs::read_to_string(out.join("mock.html")).unwrap();
+    assert!(page.contains("<h2 id=\"tool-add\">add</h2>"));
+}
@@ -0,0 +1,74 @@
+//! Integration tests for `mcp export`
+
+use serde_json::Value;
+
+mod common {
+    pub mod mock_server;
+}
+
+use common::mock_server::run_mcp;
+
+/// Mock server settings: its `fail` tool disabled
+const SERVER_TOML: &str = "disabled_tools = [\"fail\"]";
+
+#[test]
+fn test_export_openai_with_mapping() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let mapping_path = dir.path().join("names.json");
+    let output = run_mcp(
+        dir.path(),
+        SERVER_TOML,
+        &[
+            "export",
+            "--format",
+            "openai",
+            "--mapping",
+            mapping_path.to_str().unwrap(),
+        ],
+    );
+    assert!(output.status.success());
+
+    let tools: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let names: Vec<&str> = tools
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tool| tool["function"]["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["mock__echo", "mock__add"]);
+    assert_eq!(
+        tools[0]["function"]["parameters"]["required"],
+        serde_json::json!(["message"])
+    );
+
+    let mapping: Value =
+        serde_json::from_str(&std::fs::read_to_string(&mapping_path).unwrap()).unwrap();
+    assert_eq!(mapping["mock__echo"], "mock/echo");
+    assert_eq!(mapping["mock__add"], "mock/add");
+}
+
+#[test]
+fn test_export_gemini_to_file() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let output_path = dir.path().join("tools.json");
+    let output = run_mcp(
+        dir.path(),
+        SERVER_TOML,
+        &[
+            "export",
+            "--format",
+            "gemini",
+            "-o",
+            output_path.to_str().unwrap(),
+        ],
+    );
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+
+    let tools: Value =
+        serde_json::from_str(&std::fs::read_to_string(&output_path).unwrap()).unwrap();
+    let declarations = tools[0]["functionDeclarations"].as_array().unwrap();
+    assert_eq!(declarations.len(), 2);
+    assert_eq!(declarations[1]["name"], "mock__add");
+    assert_eq!(declarations[1]["parameters"]["type"], "object");
+}
@@ -6,6 +6,9 @@
 //! - Creating IPC clients connected to test daemon
 //! - Configuring mock MCP servers for daemon tests
 
+// Shared by several test crates; not every crate uses every helper
+#![allow(dead_code)]
+
 use anyhow::Result;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -117,6 +120,15 @@ impl Drop for TestDaemon {
 /// daemon.shutdown().await?;
 /// ```
 pub async fn spawn_test_daemon(config: Config) -> Result<TestDaemon> {
+    // 5 minute idle timeout for tests
+    spawn_test_daemon_with_lifecycle(config, DaemonLifecycle::new(300)).await
+}
+
+/// Spawn a test daemon that shuts down as `lifecycle` says
+pub async fn spawn_test_daemon_with_lifecycle(
+    config: Config,
+    lifecycle: DaemonLifecycle,
+) -> Result<TestDaemon> {
     // Create unique socket path in temp directory
     let temp_dir = TempDir::new()?;
     let socket_path = get_daemon_socket_path(&temp_dir);
@@ -127,9 +139,6 @@ pub async fn spawn_test_daemon(config: Config) -> Result<TestDaemon> {
 
     let config = Arc::new(config);
 
-    // Create lifecycle manager with short idle timeout for tests
-    let lifecycle = DaemonLifecycle::new(300); // 5 minute timeout for tests
-
     // Create shutdown channel
     let (shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
 
@@ -142,6 +151,7 @@ pub async fn spawn_test_daemon(config: Config) -> Result<TestDaemon> {
         // Run daemon with shutdown signal
         let daemon_future = mcp_cli_rs::daemon::run_daemon(
             (*daemon_config).clone(),
+            None,
             daemon_socket,
             lifecycle,
         );
@@ -258,6 +268,7 @@ pub async fn create_test_config() -> Result<Config> {
         description: None,
         allowed_tools: None,
         disabled_tools: None,
+        timeout_secs: None,
     };
 
     let config = Config {
@@ -267,6 +278,7 @@ pub async fn create_test_config() -> Result<Config> {
         retry_delay_ms: 1000,
         timeout_secs: 1800,
         daemon_ttl: 60,
+        max_connections_per_server: 4,
         socket_path: temp_dir.path().join("daemon.sock"),
     };
 
@@ -325,6 +337,7 @@ pub async fn create_test_config_with_tools(tool_count: usize) -> Result<Config>
         description: None,
         allowed_tools: None,
         disabled_tools: None,
+        timeout_secs: None,
     };
 
     let config = Config {
@@ -334,6 +347,7 @@ pub async fn create_test_config_with_tools(tool_count: usize) -> Result<Config>
         retry_delay_ms: 1000,
         timeout_secs: 1800,
         daemon_ttl: 60,
+        max_connections_per_server: 4,
         socket_path: temp_dir.path().join("daemon.sock"),
     };
 
@@ -370,6 +384,7 @@ pub fn create_test_config_with_mock_data(
         description: None,
         allowed_tools: None,
         disabled_tools: None,
+        timeout_secs: None,
     };
 
     let config = Config {
@@ -379,6 +394,7 @@ pub fn create_test_config_with_mock_data(
         retry_delay_ms: 1000,
         timeout_secs: 1800,
         daemon_ttl: 60,
+        max_connections_per_server: 4,
         socket_path: temp_dir.path().join("daemon.sock"),
     };
 
@@ -443,12 +459,11 @@ pub async fn wait_for_daemon_ready(daemon: &TestDaemon, max_wait: Duration) -> R
     let start = tokio::time::Instant::now();
 
     while start.elapsed() < max_wait {
-        if let Ok(mut client) = daemon.client() {
-            if let Ok(response) = client.send_request(&DaemonRequest::Ping).await {
-                if matches!(response, DaemonResponse::Pong) {
-                    return Ok(());
-                }
-            }
+        if let Ok(mut client) = daemon.client()
+            && let Ok(response) = client.send_request(&DaemonRequest::Ping).await
+            && matches!(response, DaemonResponse::Pong)
+        {
+            return Ok(());
         }
         tokio::time::sleep(Duration::from_millis(50)).await;
     }
@@ -10,15 +10,18 @@
 //! server.shutdown().await;
 //! ```
 
+// Shared by several test crates; not every crate uses every helper
+#![allow(dead_code)]
+
 use hyper::service::{make_service_fn, service_fn};
 use hyper::{Body, Request, Response, Server, StatusCode};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::convert::Infallible;
 use std::net::SocketAddr;
-use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use tokio::sync::{oneshot, RwLock};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::{RwLock, oneshot};
 
 /// Server state tracking failures
 struct FailingServerState {
@@ -67,7 +70,9 @@ impl MockFailingServer {
 
         // Bind to localhost with random port
         let addr = SocketAddr::from(([127, 0, 0, 1], 0));
-        let listener = tokio::net::TcpListener::bind(&addr).await.expect("Failed to bind");
+        let listener = tokio::net::TcpListener::bind(&addr)
+            .await
+            .expect("Failed to bind");
         let bound_addr = listener.local_addr().expect("Failed to get local addr");
 
         let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
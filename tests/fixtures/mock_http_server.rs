@@ -10,6 +10,9 @@
 //! server.shutdown().await;
 //! ```
 
+// Shared by several test crates; not every crate uses every helper
+#![allow(dead_code)]
+
 use hyper::service::{make_service_fn, service_fn};
 use hyper::{Body, Method, Request, Response, Server, StatusCode};
 use serde::{Deserialize, Serialize};
@@ -18,7 +21,7 @@ use std::collections::HashMap;
 use std::convert::Infallible;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::sync::{oneshot, RwLock};
+use tokio::sync::{RwLock, oneshot};
 
 /// Tool definition matching MCP protocol
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -156,7 +159,7 @@ impl MockServerState {
 }
 
 /// Configuration for the mock HTTP server
-/// 
+///
 /// This struct holds all configuration needed to set up a mock server instance.
 /// Pass it directly to `MockHttpServer::start()` instead of using environment variables
 /// to avoid race conditions when running tests in parallel.
@@ -229,7 +232,9 @@ impl MockHttpServer {
 
         // Bind to localhost with random port (let OS assign: 127.0.0.1:0)
         let addr = SocketAddr::from(([127, 0, 0, 1], 0));
-        let listener = tokio::net::TcpListener::bind(&addr).await.expect("Failed to bind");
+        let listener = tokio::net::TcpListener::bind(&addr)
+            .await
+            .expect("Failed to bind");
         let bound_addr = listener.local_addr().expect("Failed to get local addr");
 
         let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
@@ -468,7 +473,7 @@ async fn handle_tools_list(
             serde_json::json!({
                 "name": tool.name,
                 "description": tool.description,
-                "input_schema": tool.input_schema
+                "inputSchema": tool.input_schema
             })
         })
         .collect();
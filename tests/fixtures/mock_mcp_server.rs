@@ -2,12 +2,29 @@
 //!
 //! This binary implements a minimal MCP server that communicates via stdin/stdout
 //! using newline-delimited JSON. It supports the full MCP protocol initialization
-//! handshake and responds to tools/list, tools/call, and ping methods.
+//! handshake and responds to tools/list, tools/call, resources/list,
+//! resources/templates/list, resources/read, prompts/list, prompts/get,
+//! and ping methods.
 //!
 //! Configuration via environment variables:
 //! - MOCK_TOOLS: JSON array of ToolDefinition objects defining available tools
 //! - MOCK_RESPONSES: JSON object mapping tool_name -> response content
 //! - MOCK_ERRORS: JSON object mapping tool_name -> error message
+//! - MOCK_NOISY: when set, precede every response with a non-JSON log line,
+//!   a notifications/message notification and a server→client ping request
+//! - MOCK_PAGE_SIZE: serve tools/list and resources/list in pages of this size,
+//!   using the item offset as `nextCursor`
+//! - MOCK_PROGRESS: number of `notifications/progress` to send before answering
+//!   a tools/call that carries `_meta.progressToken`
+//! - MOCK_CANCEL_LOG: file to append the id of each tools/call stopped by a
+//!   matching `notifications/cancelled` (cancelled calls are never answered)
+//! - MOCK_EXTRA_TOOL_FILE: while this file exists, tools/list also reports an
+//!   `extra` tool (lets tests change the tool list of a running server)
+//! - MOCK_EXIT_AFTER_CALLS: exit after answering this many tools/call requests
+//! - MOCK_START_LOG: file to append the server's process id to when it starts
+//! - MOCK_LIST_DELAY_MS: delay before answering tools/list
+//!
+//! Like a real server, it rejects a second `initialize` on the same connection.
 //!
 //! Usage:
 //!   cargo run --bin mock-mcp-server
@@ -15,7 +32,8 @@
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::io::Write;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
 use tokio::time::{sleep, Duration};
 
@@ -70,6 +88,10 @@ struct MockServerState {
     responses: HashMap<String, MockResponse>,
     errors: HashMap<String, String>,
     delay_ms: u64,
+    list_delay_ms: u64,
+    page_size: Option<usize>,
+    progress_steps: u32,
+    noisy: bool,
     initialized: bool,
 }
 
@@ -79,12 +101,26 @@ impl MockServerState {
         let responses = Self::load_responses_from_env();
         let errors = Self::load_errors_from_env();
         let delay_ms = Self::load_delay_from_env();
+        let list_delay_ms = std::env::var("MOCK_LIST_DELAY_MS")
+            .ok()
+            .and_then(|delay| delay.parse().ok())
+            .unwrap_or(0);
+        let page_size = Self::load_page_size_from_env();
+        let progress_steps = std::env::var("MOCK_PROGRESS")
+            .ok()
+            .and_then(|steps| steps.parse().ok())
+            .unwrap_or(0);
+        let noisy = std::env::var("MOCK_NOISY").is_ok();
 
         Self {
             tools,
             responses,
             errors,
             delay_ms,
+            list_delay_ms,
+            page_size,
+            progress_steps,
+            noisy,
             initialized: false,
         }
     }
@@ -97,6 +133,13 @@ impl MockServerState {
         }
     }
 
+    fn load_page_size_from_env() -> Option<usize> {
+        std::env::var("MOCK_PAGE_SIZE")
+            .ok()
+            .and_then(|size| size.parse().ok())
+            .filter(|&size| size > 0)
+    }
+
     fn load_tools_from_env() -> Vec<ToolDefinition> {
         if let Ok(tools_json) = std::env::var("MOCK_TOOLS") {
             serde_json::from_str(&tools_json).unwrap_or_else(|_| Self::default_tools())
@@ -198,17 +241,79 @@ async fn main() {
     let mut lines = reader.lines();
 
     tracing::info!("Mock MCP server started, awaiting requests...");
+    if let Ok(path) = std::env::var("MOCK_START_LOG") {
+        let mut log = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        let _ = writeln!(log, "{}", std::process::id());
+    }
 
-    while let Ok(Some(line)) = lines.next_line().await {
+    // Lines read while waiting for a cancellation, not yet processed
+    let mut deferred: VecDeque<String> = VecDeque::new();
+
+    let exit_after_calls: Option<u32> = std::env::var("MOCK_EXIT_AFTER_CALLS")
+        .ok()
+        .and_then(|calls| calls.parse().ok());
+    let mut calls = 0;
+
+    loop {
+        let line = match deferred.pop_front() {
+            Some(line) => line,
+            None => match lines.next_line().await {
+                Ok(Some(line)) => line,
+                _ => break,
+            },
+        };
         let line = line.trim();
         if line.is_empty() {
             continue;
         }
 
+        // Responses from the client (to our server→client requests) need no answer
+        if let Ok(message) = serde_json::from_str::<Value>(line)
+            && message.get("method").is_none()
+            && (message.get("result").is_some() || message.get("error").is_some())
+        {
+            tracing::info!("Received client response: {}", message);
+            continue;
+        }
+
         match serde_json::from_str::<JsonRpcRequest>(line) {
+            // Notifications carry no id and must not be answered
+            Ok(request) if request.id.is_null() && request.method.starts_with("notifications/") => {
+                tracing::info!("Received notification: {}", request.method);
+            }
             Ok(request) => {
-                let response = handle_request(&request, &mut state).await;
-                let response_json = serde_json::to_string(&response).unwrap();
+                if let Some(progress) = progress_lines(&request, state.progress_steps) {
+                    let _ = stdout.write_all(progress.as_bytes()).await;
+                    let _ = stdout.flush().await;
+                }
+                let response = if request.method == "tools/call" {
+                    // Tool calls run until done or cancelled by the client
+                    tokio::select! {
+                        response = handle_request(&request, &mut state) => response,
+                        _ = wait_for_cancel(&mut lines, &mut deferred, &request.id) => {
+                            tracing::info!("tools/call {} cancelled", request.id);
+                            if let Ok(path) = std::env::var("MOCK_CANCEL_LOG") {
+                                let mut log = std::fs::OpenOptions::new()
+                                    .create(true)
+                                    .append(true)
+                                    .open(path)
+                                    .unwrap();
+                                let _ = writeln!(log, "{}", request.id);
+                            }
+                            continue;
+                        }
+                    }
+                } else {
+                    handle_request(&request, &mut state).await
+                };
+                let mut response_json = serde_json::to_string(&response).unwrap();
+                if state.noisy {
+                    response_json = format!("{}\n{}", noise_lines(&request), response_json);
+                }
                 if let Err(e) = stdout.write_all(response_json.as_bytes()).await {
                     tracing::error!("Failed to write response: {}", e);
                     break;
@@ -221,6 +326,14 @@ async fn main() {
                     tracing::error!("Failed to flush stdout: {}", e);
                     break;
                 }
+                if request.method == "tools/call" {
+                    calls += 1;
+                    if exit_after_calls.is_some_and(|limit| calls >= limit) {
+                        tracing::info!("Exiting after {} tool call(s)", calls);
+                        // Returning from main would wait for the blocked stdin read
+                        std::process::exit(0);
+                    }
+                }
             }
             Err(e) => {
                 tracing::error!("Failed to parse JSON-RPC request: {}", e);
@@ -273,6 +386,11 @@ async fn handle_request(request: &JsonRpcRequest, state: &mut MockServerState) -
         }
         "tools/list" => handle_tools_list(request, state).await,
         "tools/call" => handle_tools_call(request, state).await,
+        "resources/list" => handle_resources_list(request, state).await,
+        "resources/templates/list" => handle_resource_templates_list(request).await,
+        "resources/read" => handle_resources_read(request).await,
+        "prompts/list" => handle_prompts_list(request).await,
+        "prompts/get" => handle_prompts_get(request).await,
         "ping" => handle_ping(request).await,
         _ => JsonRpcResponse {
             jsonrpc: "2.0".to_string(),
@@ -310,6 +428,18 @@ async fn handle_initialize(request: &JsonRpcRequest, state: &mut MockServerState
         };
     }
 
+    if state.initialized {
+        return JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(JsonRpcError {
+                code: -32600,
+                message: "Server already initialized".to_string(),
+                data: None,
+            }),
+            id: request.id.clone(),
+        };
+    }
     state.initialized = true;
 
     let result = serde_json::json!({
@@ -319,7 +449,9 @@ async fn handle_initialize(request: &JsonRpcRequest, state: &mut MockServerState
             "version": "0.1.0"
         },
         "capabilities": {
-            "tools": {}
+            "tools": {},
+            "resources": {},
+            "prompts": {}
         }
     });
 
@@ -334,6 +466,10 @@ async fn handle_initialize(request: &JsonRpcRequest, state: &mut MockServerState
 async fn handle_tools_list(request: &JsonRpcRequest, state: &MockServerState) -> JsonRpcResponse {
     tracing::info!("Handling tools/list request");
 
+    if state.list_delay_ms > 0 {
+        sleep(Duration::from_millis(state.list_delay_ms)).await;
+    }
+
     if !state.initialized {
         return JsonRpcResponse {
             jsonrpc: "2.0".to_string(),
@@ -347,19 +483,27 @@ async fn handle_tools_list(request: &JsonRpcRequest, state: &MockServerState) ->
         };
     }
 
-    let tools: Vec<Value> = state
+    let mut tools: Vec<Value> = state
         .tools
         .iter()
         .map(|tool| {
             serde_json::json!({
                 "name": tool.name,
                 "description": tool.description,
-                "input_schema": tool.input_schema
+                "inputSchema": tool.input_schema
             })
         })
         .collect();
+    if std::env::var("MOCK_EXTRA_TOOL_FILE").is_ok_and(|path| std::path::Path::new(&path).exists())
+    {
+        tools.push(serde_json::json!({
+            "name": "extra",
+            "description": "Appears while MOCK_EXTRA_TOOL_FILE exists",
+            "inputSchema": {"type": "object"}
+        }));
+    }
 
-    let result = serde_json::json!({ "tools": tools });
+    let result = paginate("tools", tools, request, state.page_size);
 
     JsonRpcResponse {
         jsonrpc: "2.0".to_string(),
@@ -470,6 +614,283 @@ async fn handle_tools_call(request: &JsonRpcRequest, state: &MockServerState) ->
     }
 }
 
+/// Read client messages until `notifications/cancelled` arrives for `id`
+///
+/// Other messages are queued on `deferred` for the main loop. Never
+/// resolves if stdin closes first.
+async fn wait_for_cancel<R>(
+    lines: &mut tokio::io::Lines<R>,
+    deferred: &mut VecDeque<String>,
+    id: &Value,
+) where
+    R: tokio::io::AsyncBufRead + Unpin,
+{
+    while let Ok(Some(line)) = lines.next_line().await {
+        if let Ok(message) = serde_json::from_str::<Value>(&line)
+            && message["method"] == "notifications/cancelled"
+            && message["params"]["requestId"] == *id
+        {
+            return;
+        }
+        deferred.push_back(line);
+    }
+    std::future::pending().await
+}
+
+/// Unsolicited output emitted before each response in MOCK_NOISY mode
+fn noise_lines(request: &JsonRpcRequest) -> String {
+    let notification = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "notifications/message",
+        "params": { "level": "info", "data": format!("handling {}", request.method) }
+    });
+    let server_request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": format!("server-ping-{}", request.id),
+        "method": "ping"
+    });
+    format!(
+        "mock server log: handling {}\n{}\n{}",
+        request.method, notification, server_request
+    )
+}
+
+/// Progress notifications sent ahead of a tools/call response in MOCK_PROGRESS mode
+///
+/// Returns None unless the request asked for progress with a token.
+fn progress_lines(request: &JsonRpcRequest, steps: u32) -> Option<String> {
+    if request.method != "tools/call" || steps == 0 {
+        return None;
+    }
+    let token = request.params.pointer("/_meta/progressToken")?;
+    let lines: String = (1..=steps)
+        .map(|step| {
+            let notification = serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "notifications/progress",
+                "params": {
+                    "progressToken": token,
+                    "progress": step,
+                    "total": steps,
+                    "message": format!("step {}", step)
+                }
+            });
+            format!("{}\n", notification)
+        })
+        .collect();
+    Some(lines)
+}
+
+/// Build a list result, slicing `items` into a page when pagination is enabled
+///
+/// The cursor is the offset of the first item on the page.
+fn paginate(
+    key: &str,
+    items: Vec<Value>,
+    request: &JsonRpcRequest,
+    page_size: Option<usize>,
+) -> Value {
+    let Some(page_size) = page_size else {
+        return serde_json::json!({ key: items });
+    };
+
+    let offset = request
+        .params
+        .get("cursor")
+        .and_then(|c| c.as_str())
+        .and_then(|c| c.parse::<usize>().ok())
+        .unwrap_or(0)
+        .min(items.len());
+    let end = (offset + page_size).min(items.len());
+
+    let mut result = serde_json::json!({ key: items[offset..end] });
+    if end < items.len() {
+        result["nextCursor"] = Value::String(end.to_string());
+    }
+    result
+}
+
+/// Fixed resources served by the mock: one text file and one binary blob
+fn mock_resources() -> Vec<Value> {
+    vec![
+        serde_json::json!({
+            "uri": "file:///mock/readme.txt",
+            "name": "readme.txt",
+            "description": "Mock text resource",
+            "mimeType": "text/plain"
+        }),
+        serde_json::json!({
+            "uri": "file:///mock/logo.bin",
+            "name": "logo.bin",
+            "mimeType": "application/octet-stream"
+        }),
+    ]
+}
+
+async fn handle_resources_list(
+    request: &JsonRpcRequest,
+    state: &MockServerState,
+) -> JsonRpcResponse {
+    tracing::info!("Handling resources/list request");
+
+    JsonRpcResponse {
+        jsonrpc: "2.0".to_string(),
+        result: Some(paginate(
+            "resources",
+            mock_resources(),
+            request,
+            state.page_size,
+        )),
+        error: None,
+        id: request.id.clone(),
+    }
+}
+
+async fn handle_resource_templates_list(request: &JsonRpcRequest) -> JsonRpcResponse {
+    tracing::info!("Handling resources/templates/list request");
+
+    let result = serde_json::json!({
+        "resourceTemplates": [{
+            "uriTemplate": "file:///mock/{path}",
+            "name": "mock-file",
+            "description": "Any file under /mock"
+        }]
+    });
+
+    JsonRpcResponse {
+        jsonrpc: "2.0".to_string(),
+        result: Some(result),
+        error: None,
+        id: request.id.clone(),
+    }
+}
+
+async fn handle_resources_read(request: &JsonRpcRequest) -> JsonRpcResponse {
+    tracing::info!("Handling resources/read request");
+
+    let uri = request
+        .params
+        .get("uri")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+
+    let content = match uri {
+        "file:///mock/readme.txt" => serde_json::json!({
+            "uri": uri,
+            "mimeType": "text/plain",
+            "text": "Hello from the mock server"
+        }),
+        // Bytes 0x00 0x01 0x02 0xff
+        "file:///mock/logo.bin" => serde_json::json!({
+            "uri": uri,
+            "mimeType": "application/octet-stream",
+            "blob": "AAEC/w=="
+        }),
+        _ => {
+            return JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(JsonRpcError {
+                    code: -32002,
+                    message: format!("Resource not found: {}", uri),
+                    data: None,
+                }),
+                id: request.id.clone(),
+            };
+        }
+    };
+
+    JsonRpcResponse {
+        jsonrpc: "2.0".to_string(),
+        result: Some(serde_json::json!({ "contents": [content] })),
+        error: None,
+        id: request.id.clone(),
+    }
+}
+
+async fn handle_prompts_list(request: &JsonRpcRequest) -> JsonRpcResponse {
+    tracing::info!("Handling prompts/list request");
+
+    let result = serde_json::json!({
+        "prompts": [{
+            "name": "greeting",
+            "description": "Greet someone by name",
+            "arguments": [
+                {"name": "name", "description": "Who to greet", "required": true},
+                {"name": "tone", "description": "Greeting tone"}
+            ]
+        }]
+    });
+
+    JsonRpcResponse {
+        jsonrpc: "2.0".to_string(),
+        result: Some(result),
+        error: None,
+        id: request.id.clone(),
+    }
+}
+
+async fn handle_prompts_get(request: &JsonRpcRequest) -> JsonRpcResponse {
+    tracing::info!("Handling prompts/get request");
+
+    let prompt_name = request
+        .params
+        .get("name")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    let name = request
+        .params
+        .get("arguments")
+        .and_then(|a| a.get("name"))
+        .and_then(|v| v.as_str());
+
+    let error = match (prompt_name, name) {
+        ("greeting", Some(_)) => None,
+        ("greeting", None) => Some((-32602, "Missing required argument: name".to_string())),
+        _ => Some((-32602, format!("Prompt not found: {}", prompt_name))),
+    };
+    if let Some((code, message)) = error {
+        return JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(JsonRpcError {
+                code,
+                message,
+                data: None,
+            }),
+            id: request.id.clone(),
+        };
+    }
+
+    let tone = request
+        .params
+        .get("arguments")
+        .and_then(|a| a.get("tone"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("friendly");
+
+    let result = serde_json::json!({
+        "description": "Greeting prompt",
+        "messages": [
+            {
+                "role": "user",
+                "content": {"type": "text", "text": format!("Say hello to {} in a {} tone", name.unwrap_or_default(), tone)}
+            },
+            {
+                "role": "assistant",
+                "content": {"type": "text", "text": format!("Hello, {}!", name.unwrap_or_default())}
+            }
+        ]
+    });
+
+    JsonRpcResponse {
+        jsonrpc: "2.0".to_string(),
+        result: Some(result),
+        error: None,
+        id: request.id.clone(),
+    }
+}
+
 async fn handle_ping(request: &JsonRpcRequest) -> JsonRpcResponse {
     tracing::info!("Handling ping request");
 
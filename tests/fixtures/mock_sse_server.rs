@@ -0,0 +1,233 @@
+//! Mock MCP server speaking the legacy HTTP+SSE transport (2024-11-05)
+//!
+//! - `GET /sse` opens an event stream whose first event is `endpoint`,
+//!   announcing `/messages?sessionId=N`.
+//! - `POST /messages?sessionId=N` accepts a client message with 202; the
+//!   response is delivered as a pretty-printed `message` event on the stream.
+//!   `tools/list` is preceded by a `notifications/message` notification.
+//! - `POST /sse` is rejected with 405, like a server that doesn't implement
+//!   Streamable HTTP, so clients can exercise their fallback.
+//!
+//! Usage:
+//! ```rust
+//! let (server, url) = MockSseServer::start().await;
+//! // url points at the /sse stream
+//! ```
+
+// Shared by several test crates; not every crate uses every helper
+#![allow(dead_code)]
+
+use hyper::body::{Bytes, Sender};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::{Mutex, oneshot};
+
+/// Shared server state
+#[derive(Default)]
+struct SseState {
+    next_session: AtomicUsize,
+    /// Open event streams by session ID
+    streams: Mutex<HashMap<String, Sender>>,
+}
+
+/// Mock legacy SSE MCP server
+pub struct MockSseServer {
+    addr: SocketAddr,
+    state: Arc<SseState>,
+    shutdown_tx: Option<oneshot::Sender<()>>,
+}
+
+impl MockSseServer {
+    /// Start the server on a random port
+    ///
+    /// # Returns
+    /// * `(MockSseServer, String)` - Server handle and URL of the SSE stream
+    pub async fn start() -> (Self, String) {
+        let state = Arc::new(SseState::default());
+
+        let addr = SocketAddr::from(([127, 0, 0, 1], 0));
+        let listener = tokio::net::TcpListener::bind(&addr)
+            .await
+            .expect("Failed to bind");
+        let bound_addr = listener.local_addr().expect("Failed to get local addr");
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+
+        let service_state = state.clone();
+        let make_svc = make_service_fn(move |_conn| {
+            let state = service_state.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req| {
+                    let state = state.clone();
+                    handle_request(req, state)
+                }))
+            }
+        });
+
+        let server = Server::from_tcp(listener.into_std().expect("Failed to convert listener"))
+            .expect("Failed to create server from listener")
+            .serve(make_svc);
+        let graceful = server.with_graceful_shutdown(async {
+            let _ = shutdown_rx.await;
+        });
+
+        tokio::spawn(async move {
+            if let Err(e) = graceful.await {
+                eprintln!("Server error: {}", e);
+            }
+        });
+
+        let url = format!("http://{}/sse", bound_addr);
+        (
+            MockSseServer {
+                addr: bound_addr,
+                state,
+                shutdown_tx: Some(shutdown_tx),
+            },
+            url,
+        )
+    }
+
+    /// Get the URL of the SSE stream
+    pub fn url(&self) -> String {
+        format!("http://{}/sse", self.addr)
+    }
+
+    /// Number of event streams opened so far
+    pub fn sessions_opened(&self) -> usize {
+        self.state.next_session.load(Ordering::SeqCst)
+    }
+
+    /// Shutdown the server gracefully
+    pub async fn shutdown(mut self) {
+        self.state.streams.lock().await.clear();
+        if let Some(tx) = self.shutdown_tx.take() {
+            let _ = tx.send(());
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+}
+
+fn status_response(status: StatusCode) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::empty())
+        .unwrap()
+}
+
+/// Format a `message` event, splitting multi-line data over `data` fields
+fn message_event(message: &Value) -> Bytes {
+    let pretty = serde_json::to_string_pretty(message).unwrap();
+    let data: String = pretty
+        .lines()
+        .map(|line| format!("data: {}\n", line))
+        .collect();
+    Bytes::from(format!("event: message\n{}\n", data))
+}
+
+/// Handle HTTP requests
+async fn handle_request(
+    req: Request<Body>,
+    state: Arc<SseState>,
+) -> Result<Response<Body>, Infallible> {
+    let response = match (req.method(), req.uri().path()) {
+        (&Method::GET, "/sse") => open_stream(state).await,
+        (&Method::POST, "/messages") => handle_message(req, state).await,
+        _ => status_response(StatusCode::METHOD_NOT_ALLOWED),
+    };
+    Ok(response)
+}
+
+async fn open_stream(state: Arc<SseState>) -> Response<Body> {
+    let session_id = (state.next_session.fetch_add(1, Ordering::SeqCst) + 1).to_string();
+    let (mut sender, body) = Body::channel();
+
+    let endpoint = format!(
+        "event: endpoint\ndata: /messages?sessionId={}\n\n",
+        session_id
+    );
+    // The body is only polled once the response is returned, so send from a
+    // task; holding the lock keeps POSTs waiting until the stream is registered
+    tokio::spawn(async move {
+        let mut streams = state.streams.lock().await;
+        if sender.send_data(Bytes::from(endpoint)).await.is_ok() {
+            streams.insert(session_id, sender);
+        }
+    });
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("Content-Type", "text/event-stream")
+        .body(body)
+        .unwrap()
+}
+
+async fn handle_message(req: Request<Body>, state: Arc<SseState>) -> Response<Body> {
+    let session_id = req
+        .uri()
+        .query()
+        .and_then(|q| q.strip_prefix("sessionId="))
+        .map(str::to_string);
+    let Some(session_id) = session_id else {
+        return status_response(StatusCode::BAD_REQUEST);
+    };
+
+    let body = hyper::body::to_bytes(req.into_body())
+        .await
+        .unwrap_or_default();
+    let Ok(message) = serde_json::from_slice::<Value>(&body) else {
+        return status_response(StatusCode::BAD_REQUEST);
+    };
+
+    let mut streams = state.streams.lock().await;
+    let Some(stream) = streams.get_mut(&session_id) else {
+        return status_response(StatusCode::NOT_FOUND);
+    };
+
+    let method = message.get("method").and_then(|m| m.as_str()).unwrap_or("");
+    if let Some(id) = message.get("id").filter(|_| !method.is_empty()) {
+        let result = match method {
+            "initialize" => json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": { "tools": {} },
+                "serverInfo": { "name": "mock-sse", "version": "0.1.0" }
+            }),
+            "tools/list" => {
+                let notification = json!({
+                    "jsonrpc": "2.0",
+                    "method": "notifications/message",
+                    "params": { "level": "info", "data": "listing tools" }
+                });
+                let _ = stream.send_data(message_event(&notification)).await;
+                json!({
+                    "tools": [{
+                        "name": "echo",
+                        "description": "Echo back the input message",
+                        "inputSchema": {
+                            "type": "object",
+                            "properties": { "message": { "type": "string" } }
+                        }
+                    }]
+                })
+            }
+            "tools/call" => {
+                let text = message
+                    .pointer("/params/arguments/message")
+                    .and_then(|m| m.as_str())
+                    .unwrap_or("");
+                json!({ "content": [{ "type": "text", "text": format!("Echo: {}", text) }] })
+            }
+            _ => json!({}),
+        };
+        let response = json!({ "jsonrpc": "2.0", "id": id, "result": result });
+        let _ = stream.send_data(message_event(&response)).await;
+    }
+
+    status_response(StatusCode::ACCEPTED)
+}
@@ -0,0 +1,401 @@
+//! Mock MCP server speaking the Streamable HTTP transport
+//!
+//! Unlike `mock_http_server`, which answers every POST with a single JSON
+//! body, this server exercises the parts of the Streamable HTTP spec that
+//! need session and SSE handling:
+//!
+//! - `initialize` assigns an `Mcp-Session-Id`; later requests without it get
+//!   400, and requests with an unknown or terminated session get 404.
+//! - `tools/list` is answered over SSE, with a notification and a server→client
+//!   `ping` request sent before the response.
+//! - `tools/call` for the `resume` tool sends one event and closes the stream;
+//!   the response is replayed on a GET carrying `Last-Event-ID`.
+//! - A GET without `Last-Event-ID` opens a long-lived stream that delivers a
+//!   `notifications/tools/list_changed` notification.
+//! - DELETE terminates the session.
+//!
+//! Usage:
+//! ```rust
+//! let (server, url) = MockStreamableHttpServer::start().await;
+//! // Use url in HTTP transport config
+//! assert_eq!(server.deleted_sessions(), 0);
+//! ```
+
+// Shared by several test crates; not every crate uses every helper
+#![allow(dead_code)]
+
+use hyper::body::{Bytes, Sender};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use serde_json::{Value, json};
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+
+/// Protocol version the mock negotiates
+pub const PROTOCOL_VERSION: &str = "2025-03-26";
+
+/// Event ID of the partial `resume` response stream
+const RESUME_EVENT_ID: &str = "resume-1";
+
+/// Shared server state
+#[derive(Default)]
+struct StreamableState {
+    next_session: u64,
+    active_sessions: HashSet<String>,
+    deleted_sessions: usize,
+    /// Every POSTed message with the protocol version header it carried
+    requests: Vec<(Value, Option<String>)>,
+    /// Responses the client sent to server→client requests
+    client_responses: Vec<Value>,
+    /// Open GET streams, kept so they stay open until shutdown
+    streams: Vec<Sender>,
+}
+
+/// Mock Streamable HTTP MCP server
+pub struct MockStreamableHttpServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<StreamableState>>,
+    shutdown_tx: Option<oneshot::Sender<()>>,
+}
+
+impl MockStreamableHttpServer {
+    /// Start the server on a random port
+    ///
+    /// # Returns
+    /// * `(MockStreamableHttpServer, String)` - Server handle and base URL
+    pub async fn start() -> (Self, String) {
+        let state = Arc::new(Mutex::new(StreamableState::default()));
+
+        let addr = SocketAddr::from(([127, 0, 0, 1], 0));
+        let listener = tokio::net::TcpListener::bind(&addr)
+            .await
+            .expect("Failed to bind");
+        let bound_addr = listener.local_addr().expect("Failed to get local addr");
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+
+        let service_state = state.clone();
+        let make_svc = make_service_fn(move |_conn| {
+            let state = service_state.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req| {
+                    let state = state.clone();
+                    handle_request(req, state)
+                }))
+            }
+        });
+
+        let server = Server::from_tcp(listener.into_std().expect("Failed to convert listener"))
+            .expect("Failed to create server from listener")
+            .serve(make_svc);
+        let graceful = server.with_graceful_shutdown(async {
+            let _ = shutdown_rx.await;
+        });
+
+        tokio::spawn(async move {
+            if let Err(e) = graceful.await {
+                eprintln!("Server error: {}", e);
+            }
+        });
+
+        let url = format!("http://{}/mcp", bound_addr);
+        (
+            MockStreamableHttpServer {
+                addr: bound_addr,
+                state,
+                shutdown_tx: Some(shutdown_tx),
+            },
+            url,
+        )
+    }
+
+    /// Get the server URL
+    pub fn url(&self) -> String {
+        format!("http://{}/mcp", self.addr)
+    }
+
+    /// Number of sessions terminated with DELETE
+    pub fn deleted_sessions(&self) -> usize {
+        self.state.lock().unwrap().deleted_sessions
+    }
+
+    /// Methods of POSTed requests, with the `MCP-Protocol-Version` header sent
+    pub fn requests(&self) -> Vec<(String, Option<String>)> {
+        self.state
+            .lock()
+            .unwrap()
+            .requests
+            .iter()
+            .filter_map(|(message, version)| {
+                let method = message.get("method")?.as_str()?.to_string();
+                Some((method, version.clone()))
+            })
+            .collect()
+    }
+
+    /// Responses the client sent to server→client requests
+    pub fn client_responses(&self) -> Vec<Value> {
+        self.state.lock().unwrap().client_responses.clone()
+    }
+
+    /// Forget all sessions, as a restarted server would
+    pub fn expire_sessions(&self) {
+        self.state.lock().unwrap().active_sessions.clear();
+    }
+
+    /// Shutdown the server gracefully
+    pub async fn shutdown(mut self) {
+        self.state.lock().unwrap().streams.clear();
+        if let Some(tx) = self.shutdown_tx.take() {
+            let _ = tx.send(());
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+}
+
+/// Format one SSE event
+fn sse_event(id: &str, message: &Value) -> String {
+    format!("id: {}\ndata: {}\n\n", id, message)
+}
+
+fn sse_response(body: Body) -> Response<Body> {
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("Content-Type", "text/event-stream")
+        .body(body)
+        .unwrap()
+}
+
+fn json_response(message: &Value) -> Response<Body> {
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("Content-Type", "application/json")
+        .body(Body::from(message.to_string()))
+        .unwrap()
+}
+
+fn status_response(status: StatusCode) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn tools_list_result() -> Value {
+    json!({
+        "tools": [{
+            "name": "echo",
+            "description": "Echo back the input message",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "message": { "type": "string" } },
+                "required": ["message"]
+            }
+        }]
+    })
+}
+
+fn resume_response(id: &Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "result": { "content": [{ "type": "text", "text": "resumed" }] }
+    })
+}
+
+/// Check the session header; returns the error response to send if invalid
+fn check_session(req: &Request<Body>, state: &Mutex<StreamableState>) -> Option<Response<Body>> {
+    let session = req
+        .headers()
+        .get("mcp-session-id")
+        .and_then(|v| v.to_str().ok());
+    match session {
+        None => Some(status_response(StatusCode::BAD_REQUEST)),
+        Some(id) if !state.lock().unwrap().active_sessions.contains(id) => {
+            Some(status_response(StatusCode::NOT_FOUND))
+        }
+        Some(_) => None,
+    }
+}
+
+/// Handle HTTP requests
+async fn handle_request(
+    req: Request<Body>,
+    state: Arc<Mutex<StreamableState>>,
+) -> Result<Response<Body>, Infallible> {
+    let response = match *req.method() {
+        Method::POST => handle_post(req, state).await,
+        Method::GET => handle_get(req, state),
+        Method::DELETE => handle_delete(req, state),
+        _ => status_response(StatusCode::METHOD_NOT_ALLOWED),
+    };
+    Ok(response)
+}
+
+async fn handle_post(req: Request<Body>, state: Arc<Mutex<StreamableState>>) -> Response<Body> {
+    let protocol_version = req
+        .headers()
+        .get("mcp-protocol-version")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let session_error = check_session(&req, &state);
+
+    let body = hyper::body::to_bytes(req.into_body())
+        .await
+        .unwrap_or_default();
+    let Ok(message) = serde_json::from_slice::<Value>(&body) else {
+        return status_response(StatusCode::BAD_REQUEST);
+    };
+    let method = message
+        .get("method")
+        .and_then(|m| m.as_str())
+        .map(str::to_string);
+    let id = message.get("id").cloned();
+
+    if method.as_deref() == Some("initialize") {
+        let session_id = {
+            let mut state = state.lock().unwrap();
+            state.next_session += 1;
+            let session_id = format!("session-{}", state.next_session);
+            state.active_sessions.insert(session_id.clone());
+            state.requests.push((message.clone(), protocol_version));
+            session_id
+        };
+        let response = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "result": {
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": { "tools": {} },
+                "serverInfo": { "name": "mock-streamable-http", "version": "0.1.0" }
+            }
+        });
+        let mut response = json_response(&response);
+        response
+            .headers_mut()
+            .insert("Mcp-Session-Id", session_id.parse().unwrap());
+        return response;
+    }
+
+    if let Some(error) = session_error {
+        return error;
+    }
+
+    {
+        let mut state = state.lock().unwrap();
+        state.requests.push((message.clone(), protocol_version));
+        if method.is_none() {
+            state.client_responses.push(message.clone());
+        }
+    }
+
+    // Notifications and responses are acknowledged without a body
+    let (Some(method), Some(id)) = (method, id) else {
+        return status_response(StatusCode::ACCEPTED);
+    };
+
+    match method.as_str() {
+        "tools/list" => {
+            let events = [
+                sse_event(
+                    "1",
+                    &json!({
+                        "jsonrpc": "2.0",
+                        "method": "notifications/message",
+                        "params": { "level": "info", "data": "listing tools" }
+                    }),
+                ),
+                sse_event(
+                    "2",
+                    &json!({ "jsonrpc": "2.0", "id": "server-ping-1", "method": "ping" }),
+                ),
+                sse_event(
+                    "3",
+                    &json!({ "jsonrpc": "2.0", "id": id, "result": tools_list_result() }),
+                ),
+            ];
+            sse_response(Body::from(events.concat()))
+        }
+        "tools/call" => {
+            let params = message.get("params").cloned().unwrap_or(Value::Null);
+            let tool = params.get("name").and_then(|n| n.as_str()).unwrap_or("");
+            if tool == "resume" {
+                // Send only a priming event; the response follows on resumption
+                let event = format!("id: {}\ndata:\n\n", RESUME_EVENT_ID);
+                return sse_response(Body::from(event));
+            }
+            let text = params
+                .pointer("/arguments/message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("");
+            json_response(&json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "result": { "content": [{ "type": "text", "text": format!("Echo: {}", text) }] }
+            }))
+        }
+        "ping" => json_response(&json!({ "jsonrpc": "2.0", "id": id, "result": {} })),
+        _ => json_response(&json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": -32601, "message": format!("Method not found: {}", method) }
+        })),
+    }
+}
+
+fn handle_get(req: Request<Body>, state: Arc<Mutex<StreamableState>>) -> Response<Body> {
+    if let Some(error) = check_session(&req, &state) {
+        return error;
+    }
+
+    let last_event_id = req
+        .headers()
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok());
+    if let Some(last_event_id) = last_event_id {
+        if last_event_id != RESUME_EVENT_ID {
+            return status_response(StatusCode::NOT_FOUND);
+        }
+        // Replay the response to the interrupted `resume` call
+        let id = state
+            .lock()
+            .unwrap()
+            .requests
+            .iter()
+            .rev()
+            .find(|(m, _)| m.pointer("/params/name").and_then(|n| n.as_str()) == Some("resume"))
+            .and_then(|(m, _)| m.get("id").cloned())
+            .unwrap_or(Value::Null);
+        return sse_response(Body::from(sse_event("resume-2", &resume_response(&id))));
+    }
+
+    let (mut sender, body) = Body::channel();
+    tokio::spawn(async move {
+        let event = sse_event(
+            "stream-1",
+            &json!({ "jsonrpc": "2.0", "method": "notifications/tools/list_changed" }),
+        );
+        if sender.send_data(Bytes::from(event)).await.is_ok() {
+            state.lock().unwrap().streams.push(sender);
+        }
+    });
+    sse_response(body)
+}
+
+fn handle_delete(req: Request<Body>, state: Arc<Mutex<StreamableState>>) -> Response<Body> {
+    if let Some(error) = check_session(&req, &state) {
+        return error;
+    }
+    let session_id = req.headers()["mcp-session-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+
+    let mut state = state.lock().unwrap();
+    state.active_sessions.remove(&session_id);
+    state.deleted_sessions += 1;
+    status_response(StatusCode::OK)
+}
@@ -22,26 +22,37 @@
 //! let (child, stdin, stdout) = start_mock_stdio().await;
 //! ```
 
+// Shared by several test crates; not every crate uses every helper
+#![allow(dead_code)]
+
 // Re-export mock HTTP server
 pub mod mock_http_server;
 
 // Re-export mock failing server for retry tests
 pub mod mock_failing_server;
 
+// Mock server for the Streamable HTTP transport (sessions, SSE responses)
+pub mod mock_streamable_http_server;
+
+// Mock server for the legacy HTTP+SSE transport
+pub mod mock_sse_server;
+
 // Re-export types from HTTP server for convenience
-pub use mock_http_server::{MockHttpServer, MockResponse, ToolDefinition};
+#[allow(unused_imports)]
 pub use mock_failing_server::{MockFailingServer, spawn_failing_server};
+#[allow(unused_imports)]
+pub use mock_http_server::{MockHttpServer, MockResponse, ToolDefinition};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::Stdio;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::BufReader;
 use tokio::process::{Child, ChildStdin, ChildStdout, Command};
 
 /// Mock server configuration for tests
-/// 
+///
 /// This version is for stdio tests that need to convert to environment variables.
 /// HTTP tests should use `mock_http_server::MockServerConfig` directly.
 #[derive(Debug, Clone)]
@@ -155,7 +166,9 @@ pub async fn start_mock_stdio() -> anyhow::Result<(Child, ChildStdin, BufReader<
         } else if debug_path_unix.exists() {
             debug_path_unix
         } else {
-            anyhow::bail!("mock-mcp-server binary not found. Run: cargo build --bin mock-mcp-server");
+            anyhow::bail!(
+                "mock-mcp-server binary not found. Run: cargo build --bin mock-mcp-server"
+            );
         }
     };
 
@@ -216,7 +229,9 @@ pub async fn start_mock_stdio_with_config(
         } else if debug_path_unix.exists() {
             debug_path_unix
         } else {
-            anyhow::bail!("mock-mcp-server binary not found. Run: cargo build --bin mock-mcp-server");
+            anyhow::bail!(
+                "mock-mcp-server binary not found. Run: cargo build --bin mock-mcp-server"
+            );
         }
     };
 
@@ -0,0 +1,608 @@
+//! Integration tests for gateway mode (`mcp serve`)
+//!
+//! The gateway fronts the mock MCP server, which reports an extra tool while
+//! MOCK_EXTRA_TOOL_FILE exists so tests can change its tool list.
+
+use mcp_cli_rs::config::{Config, ServerConfig};
+use mcp_cli_rs::gateway::Gateway;
+use mcp_cli_rs::gateway::http::HttpGateway;
+use mcp_cli_rs::gateway::rest::RestGateway;
+use serde_json::{Value, json};
+use std::io::Write;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::oneshot;
+
+mod common {
+    pub mod mock_server;
+}
+
+use common::mock_server::{MOCK_SERVER, mock_server_config};
+
+/// Config with the mock server, its `fail` tool disabled
+fn mock_config(extra_tool_file: &Path) -> Config {
+    Config {
+        servers: vec![ServerConfig {
+            disabled_tools: Some(vec!["fail".to_string()]),
+            ..mock_server_config(
+                "mock",
+                &[("MOCK_EXTRA_TOOL_FILE", extra_tool_file.to_str().unwrap())],
+            )
+        }],
+        ..Config::default()
+    }
+}
+
+fn request(id: u64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+fn tool_names(response: &Value) -> Vec<&str> {
+    response["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tool| tool["name"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_gateway_lists_filtered_tools_and_forwards_calls() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let gateway = Gateway::new(Arc::new(mock_config(&dir.path().join("extra"))));
+
+    let response = gateway
+        .handle(request(1, "tools/list", json!({})))
+        .await
+        .unwrap();
+    let names = tool_names(&response);
+    assert!(names.contains(&"mock__echo"), "{:?}", names);
+    assert!(names.contains(&"mock__add"), "{:?}", names);
+    assert!(!names.contains(&"mock__fail"), "{:?}", names);
+
+    let response = gateway
+        .handle(request(
+            2,
+            "tools/call",
+            json!({ "name": "mock__echo", "arguments": { "message": "hi" } }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response["id"], 2);
+    assert_eq!(response["result"]["content"][0]["text"], "Echo: hi");
+
+    // Disabled tools can't be called by name either
+    let response = gateway
+        .handle(request(3, "tools/call", json!({ "name": "mock__fail" })))
+        .await
+        .unwrap();
+    assert_eq!(response["error"]["code"], -32602);
+}
+
+#[tokio::test]
+async fn test_gateway_notices_tool_changes() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let extra = dir.path().join("extra");
+    let gateway = Gateway::new(Arc::new(mock_config(&extra)));
+
+    // Nothing to refresh before the client has listed tools
+    assert!(!gateway.tools_changed().await);
+
+    gateway
+        .handle(request(1, "tools/list", json!({})))
+        .await
+        .unwrap();
+    assert!(!gateway.tools_changed().await);
+
+    std::fs::write(&extra, "").unwrap();
+    assert!(gateway.tools_changed().await);
+    assert!(!gateway.tools_changed().await);
+}
+
+#[tokio::test]
+async fn test_gateway_serves_tools_list_from_last_listing() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let extra = dir.path().join("extra");
+    let gateway = Gateway::new(Arc::new(mock_config(&extra)));
+
+    // The first tools/list lists the backends
+    let response = gateway
+        .handle(request(1, "tools/list", json!({})))
+        .await
+        .unwrap();
+    assert!(!tool_names(&response).contains(&"mock__extra"));
+
+    // Later ones answer from that listing until the poller re-lists
+    std::fs::write(&extra, "").unwrap();
+    let response = gateway
+        .handle(request(2, "tools/list", json!({})))
+        .await
+        .unwrap();
+    assert!(!tool_names(&response).contains(&"mock__extra"));
+
+    assert!(gateway.tools_changed().await);
+    let response = gateway
+        .handle(request(3, "tools/list", json!({})))
+        .await
+        .unwrap();
+    assert!(tool_names(&response).contains(&"mock__extra"));
+}
+
+#[tokio::test]
+async fn test_gateway_finds_tools_in_last_listing() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let extra = dir.path().join("extra");
+    let gateway = Gateway::new(Arc::new(mock_config(&extra)));
+
+    // Not listed yet, so the server is listed on demand
+    std::fs::write(&extra, "").unwrap();
+    assert!(gateway.find_tool("mock", "extra").await.unwrap().is_some());
+
+    // Found in the cache without listing again
+    std::fs::remove_file(&extra).unwrap();
+    assert!(gateway.find_tool("mock", "extra").await.unwrap().is_some());
+
+    // A tool missing from the cache re-lists the server, refreshing it
+    assert!(gateway.find_tool("mock", "nope").await.unwrap().is_none());
+    assert!(gateway.find_tool("mock", "extra").await.unwrap().is_none());
+
+    // Disabled tools and unknown servers are never found
+    assert!(gateway.find_tool("mock", "fail").await.unwrap().is_none());
+    assert!(gateway.find_tool("other", "echo").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_stdio_gateway_sends_list_changed() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let extra = dir.path().join("extra");
+    let gateway = Arc::new(
+        Gateway::new(Arc::new(mock_config(&extra))).with_poll_interval(Duration::from_millis(100)),
+    );
+
+    let (client, server) = tokio::io::duplex(64 * 1024);
+    let (server_read, server_write) = tokio::io::split(server);
+    let serving = tokio::spawn(mcp_cli_rs::gateway::stdio::serve(
+        gateway,
+        server_read,
+        server_write,
+    ));
+    let (client_read, mut client_write) = tokio::io::split(client);
+    let mut lines = BufReader::new(client_read).lines();
+
+    let list = format!("{}\n", request(1, "tools/list", json!({})));
+    client_write.write_all(list.as_bytes()).await.unwrap();
+    let reply: Value = serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+    assert!(!tool_names(&reply).contains(&"mock__extra"));
+
+    std::fs::write(&extra, "").unwrap();
+    let notification = tokio::time::timeout(Duration::from_secs(10), lines.next_line())
+        .await
+        .expect("no list_changed notification")
+        .unwrap()
+        .unwrap();
+    let notification: Value = serde_json::from_str(&notification).unwrap();
+    assert_eq!(notification["method"], "notifications/tools/list_changed");
+
+    drop(client_write);
+    drop(lines);
+    serving.await.unwrap().unwrap();
+}
+
+#[test]
+fn test_serve_stdio_command() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let config_path = dir.path().join("mcp_servers.toml");
+    let config = format!(
+        "[[servers]]\nname = \"mock\"\n\n[servers.transport]\ntype = \"stdio\"\ncommand = '{}'\n",
+        MOCK_SERVER
+    );
+    std::fs::write(&config_path, config).unwrap();
+
+    let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_mcp-cli-rs"))
+        .arg("--config")
+        .arg(&config_path)
+        .args(["serve", "--stdio"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    for message in [
+        request(1, "initialize", json!({ "protocolVersion": "2025-06-18" })),
+        json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+        request(
+            2,
+            "tools/call",
+            json!({ "name": "mock__add", "arguments": { "a": 2, "b": 3 } }),
+        ),
+    ] {
+        writeln!(stdin, "{}", message).unwrap();
+    }
+    drop(stdin);
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let mut replies: Vec<Value> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    replies.sort_by_key(|reply| reply["id"].as_u64());
+
+    assert_eq!(replies.len(), 2);
+    assert_eq!(replies[0]["result"]["serverInfo"]["name"], "mcp-cli-rs");
+    assert_eq!(replies[1]["result"]["isError"], false);
+}
+
+/// Serve `gateway` over HTTP on a random port; returns the endpoint URL and
+/// a sender that shuts the server down
+async fn start_http(gateway: Gateway, token: Option<&str>) -> (String, oneshot::Sender<()>) {
+    serve_http(HttpGateway::new(Arc::new(gateway)).with_token(token.map(str::to_string))).await
+}
+
+/// Serve an already configured HTTP gateway on a random port
+async fn serve_http(http: HttpGateway) -> (String, oneshot::Sender<()>) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/mcp", listener.local_addr().unwrap());
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let http = Arc::new(http);
+    tokio::spawn(http.serve(listener, async {
+        let _ = shutdown_rx.await;
+    }));
+    (url, shutdown_tx)
+}
+
+/// POST one message, with the bearer token and session if given
+async fn post(
+    url: &str,
+    token: Option<&str>,
+    session: Option<&str>,
+    message: &Value,
+) -> reqwest::Response {
+    let mut request = reqwest::Client::new().post(url).json(message);
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    if let Some(session) = session {
+        request = request.header("Mcp-Session-Id", session);
+    }
+    request.send().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_http_gateway_sessions_and_auth() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let gateway = Gateway::new(Arc::new(mock_config(&dir.path().join("extra"))));
+    let (url, shutdown) = start_http(gateway, Some("s3cret")).await;
+    let token = Some("s3cret");
+    let initialize = request(1, "initialize", json!({ "protocolVersion": "2025-06-18" }));
+
+    let response = post(&url, None, None, &initialize).await;
+    assert_eq!(response.status(), 401);
+    let response = post(&url, Some("wrong"), None, &initialize).await;
+    assert_eq!(response.status(), 401);
+
+    // This session hides the add tool on top of the server's own filter
+    let filtered_url = format!("{}?disabled_tools=mock__add", url);
+    let response = post(&filtered_url, token, None, &initialize).await;
+    assert_eq!(response.status(), 200);
+    let session_id = response.headers()["mcp-session-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let session = Some(session_id.as_str());
+
+    let list = request(2, "tools/list", json!({}));
+    assert_eq!(post(&url, token, None, &list).await.status(), 400);
+    assert_eq!(post(&url, token, Some("nope"), &list).await.status(), 404);
+
+    let reply: Value = post(&url, token, session, &list)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(tool_names(&reply), vec!["mock__echo"]);
+
+    let call = request(3, "tools/call", json!({ "name": "mock__add" }));
+    let reply: Value = post(&url, token, session, &call)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(reply["error"]["code"], -32602);
+
+    let call = request(
+        4,
+        "tools/call",
+        json!({ "name": "mock__echo", "arguments": { "message": "hi" } }),
+    );
+    let reply: Value = post(&url, token, session, &call)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(reply["result"]["content"][0]["text"], "Echo: hi");
+
+    let initialized = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+    assert_eq!(post(&url, token, session, &initialized).await.status(), 202);
+
+    // A second session without a filter sees everything the server allows
+    let response = post(&url, token, None, &initialize).await;
+    let other = response.headers()["mcp-session-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let reply: Value = post(&url, token, Some(&other), &list)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(tool_names(&reply), vec!["mock__echo", "mock__add"]);
+
+    let response = reqwest::Client::new()
+        .delete(&url)
+        .bearer_auth("s3cret")
+        .header("Mcp-Session-Id", &session_id)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(post(&url, token, session, &list).await.status(), 404);
+
+    let _ = shutdown.send(());
+}
+
+#[tokio::test]
+async fn test_http_gateway_expires_and_caps_sessions() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let gateway = Gateway::new(Arc::new(mock_config(&dir.path().join("extra"))));
+    let http = HttpGateway::new(Arc::new(gateway))
+        .with_session_idle_timeout(Duration::from_secs(1))
+        .with_max_sessions(2);
+    let (url, shutdown) = serve_http(http).await;
+    let initialize = request(1, "initialize", json!({ "protocolVersion": "2025-06-18" }));
+    let open_session = || async {
+        let response = post(&url, None, None, &initialize).await;
+        assert_eq!(response.status(), 200);
+        response.headers()["mcp-session-id"]
+            .to_str()
+            .unwrap()
+            .to_string()
+    };
+    let ping = request(2, "ping", json!({}));
+
+    let first = open_session().await;
+    let second = open_session().await;
+    assert_eq!(post(&url, None, None, &initialize).await.status(), 503);
+
+    // Requests keep a session alive; the idle one expires
+    for _ in 0..4 {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(post(&url, None, Some(&first), &ping).await.status(), 200);
+    }
+    assert_eq!(post(&url, None, Some(&second), &ping).await.status(), 404);
+
+    // Its place is free for a new session
+    open_session().await;
+
+    let _ = shutdown.send(());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_http_gateway_caps_parallel_sessions() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let gateway = Gateway::new(Arc::new(mock_config(&dir.path().join("extra"))));
+    let http = HttpGateway::new(Arc::new(gateway)).with_max_sessions(2);
+    let (url, shutdown) = serve_http(http).await;
+    let initialize = request(1, "initialize", json!({ "protocolVersion": "2025-06-18" }));
+
+    let responses =
+        futures::future::join_all((0..10).map(|_| post(&url, None, None, &initialize))).await;
+    let opened = responses
+        .iter()
+        .filter(|response| response.status() == 200)
+        .count();
+    assert_eq!(opened, 2);
+
+    let _ = shutdown.send(());
+}
+
+#[tokio::test]
+async fn test_http_gateway_streams_list_changed() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let extra = dir.path().join("extra");
+    let gateway =
+        Gateway::new(Arc::new(mock_config(&extra))).with_poll_interval(Duration::from_millis(100));
+    let (url, shutdown) = start_http(gateway, None).await;
+
+    let initialize = request(1, "initialize", json!({ "protocolVersion": "2025-06-18" }));
+    let response = post(&url, None, None, &initialize).await;
+    let session = response.headers()["mcp-session-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+
+    let mut stream = reqwest::Client::new()
+        .get(&url)
+        .header("Accept", "text/event-stream")
+        .header("Mcp-Session-Id", &session)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(stream.headers()["content-type"], "text/event-stream");
+
+    let list = request(2, "tools/list", json!({}));
+    post(&url, None, Some(&session), &list).await;
+    std::fs::write(&extra, "").unwrap();
+
+    let chunk = tokio::time::timeout(Duration::from_secs(10), stream.chunk())
+        .await
+        .expect("no list_changed event")
+        .unwrap()
+        .unwrap();
+    let event = String::from_utf8_lossy(&chunk);
+    assert!(event.starts_with("data: "), "{}", event);
+    assert!(
+        event.contains("notifications/tools/list_changed"),
+        "{}",
+        event
+    );
+
+    let _ = shutdown.send(());
+}
+
+/// Serve `gateway` as the REST bridge on a random port; returns the base URL
+/// and a sender that shuts the server down
+async fn start_rest(gateway: Gateway, token: Option<&str>) -> (String, oneshot::Sender<()>) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let rest = Arc::new(RestGateway::new(Arc::new(gateway)).with_token(token.map(str::to_string)));
+    tokio::spawn(rest.serve(listener, async {
+        let _ = shutdown_rx.await;
+    }));
+    (url, shutdown_tx)
+}
+
+#[tokio::test]
+async fn test_rest_bridge_calls_tools() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let gateway = Gateway::new(Arc::new(mock_config(&dir.path().join("extra"))));
+    let (url, shutdown) = start_rest(gateway, None).await;
+    let client = reqwest::Client::new();
+    let call = |tool: &str, body: Value| {
+        client
+            .post(format!("{}/servers/mock/tools/{}", url, tool))
+            .json(&body)
+            .send()
+    };
+
+    let response = call("echo", json!({ "message": "hi" })).await.unwrap();
+    assert_eq!(response.status(), 200);
+    let result: Value = response.json().await.unwrap();
+    assert_eq!(result["content"][0]["text"], "Echo: hi");
+
+    // Arguments are checked against the inputSchema before the call
+    let response = call("add", json!({ "a": "two" })).await.unwrap();
+    assert_eq!(response.status(), 400);
+    let error: Value = response.json().await.unwrap();
+    assert!(
+        !error["violations"].as_array().unwrap().is_empty(),
+        "{}",
+        error
+    );
+
+    let response = call("fail", json!({})).await.unwrap();
+    assert_eq!(response.status(), 403);
+    let error: Value = response.json().await.unwrap();
+    assert!(error["error"].as_str().unwrap().contains("disabled"));
+
+    assert_eq!(call("nope", json!({})).await.unwrap().status(), 404);
+    let response = client
+        .post(format!("{}/servers/nowhere/tools/echo", url))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+    let response = client
+        .get(format!("{}/servers/mock/tools/echo", url))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 405);
+
+    let _ = shutdown.send(());
+}
+
+#[tokio::test]
+async fn test_rest_bridge_decodes_names_in_paths() {
+    let config = Config {
+        servers: vec![mock_server_config("my mock%", &[])],
+        ..Config::default()
+    };
+    let (url, shutdown) = start_rest(Gateway::new(Arc::new(config)), None).await;
+    let client = reqwest::Client::new();
+
+    let document: Value = client
+        .get(format!("{}/openapi.json", url))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let echo = "/servers/my%20mock%25/tools/echo";
+    assert!(
+        document["paths"].get(echo).is_some(),
+        "{}",
+        document["paths"]
+    );
+
+    let response = client
+        .post(format!("{}{}", url, echo))
+        .json(&json!({ "message": "hi" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let result: Value = response.json().await.unwrap();
+    assert_eq!(result["content"][0]["text"], "Echo: hi");
+
+    let _ = shutdown.send(());
+}
+
+#[tokio::test]
+async fn test_rest_bridge_openapi_document() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let extra = dir.path().join("extra");
+    let gateway = Gateway::new(Arc::new(mock_config(&extra)));
+    let (url, shutdown) = start_rest(gateway, Some("s3cret")).await;
+    let client = reqwest::Client::new();
+    let openapi_url = format!("{}/openapi.json", url);
+
+    let response = client.get(&openapi_url).send().await.unwrap();
+    assert_eq!(response.status(), 401);
+
+    let document: Value = client
+        .get(&openapi_url)
+        .bearer_auth("s3cret")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(document["openapi"], "3.1.0");
+    assert_eq!(document["servers"][0]["url"], url);
+
+    let paths = document["paths"].as_object().unwrap();
+    let mut names: Vec<&str> = paths.keys().map(String::as_str).collect();
+    names.sort();
+    assert_eq!(
+        names,
+        vec!["/servers/mock/tools/add", "/servers/mock/tools/echo"]
+    );
+    let schema = &paths["/servers/mock/tools/echo"]["post"]["requestBody"]["content"]["application/json"]
+        ["schema"];
+    assert_eq!(schema["required"], json!(["message"]));
+
+    // Later documents come from the cached listing, not a fresh one; give
+    // the poller's first listing time to finish before the tools change
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    std::fs::write(&extra, "").unwrap();
+    let document: Value = client
+        .get(&openapi_url)
+        .bearer_auth("s3cret")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(document["paths"].get("/servers/mock/tools/extra").is_none());
+
+    let _ = shutdown.send(());
+}
@@ -6,6 +6,9 @@
 //! - IPC server/client roundtrip patterns
 //! - Test configuration factories
 
+// Shared by several test crates; not every crate uses every helper
+#![allow(dead_code)]
+
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
@@ -15,7 +18,9 @@ use tokio::io::BufReader;
 use tokio::time::timeout;
 
 use mcp_cli_rs::config::Config;
-use mcp_cli_rs::daemon::protocol::{DaemonRequest, DaemonResponse};
+use mcp_cli_rs::daemon::protocol::{
+    self, DaemonRequest, DaemonResponse, FrameRequest, Handshake, RequestFrame, ResponseFrame,
+};
 use mcp_cli_rs::ipc;
 
 /// Thread-safe counter for generating unique socket paths
@@ -95,10 +100,11 @@ pub fn cleanup_all_test_sockets() {
     let temp_dir = std::env::temp_dir();
     if let Ok(entries) = std::fs::read_dir(&temp_dir) {
         for entry in entries.flatten() {
-            if let Ok(name) = entry.file_name().into_string() {
-                if name.starts_with("mcp-test-") && name.ends_with(".sock") {
-                    let _ = std::fs::remove_file(entry.path());
-                }
+            if let Ok(name) = entry.file_name().into_string()
+                && name.starts_with("mcp-test-")
+                && name.ends_with(".sock")
+            {
+                let _ = std::fs::remove_file(entry.path());
             }
         }
     }
@@ -110,31 +116,25 @@ pub fn cleanup_all_test_sockets() {
 /// This pattern is repeated across 10+ tests in the codebase.
 pub async fn run_ping_pong_roundtrip(socket_path: PathBuf) -> anyhow::Result<()> {
     // Create IPC server
-    let mut server = ipc::create_ipc_server(&socket_path).await?;
+    let server = ipc::create_ipc_server(&socket_path).await?;
 
     // Spawn server task
     let server_handle = tokio::spawn(async move {
-        let (mut stream, _addr) =
-            match timeout(Duration::from_secs(5), server.accept()).await {
-                Ok(Ok(stream)) => stream,
-                Ok(Err(e)) => panic!("Server accept failed: {}", e),
-                Err(e) => panic!("Server accept timed out: {}", e),
-            };
+        let (stream, _addr) = match timeout(Duration::from_secs(5), server.accept()).await {
+            Ok(Ok(stream)) => stream,
+            Ok(Err(e)) => panic!("Server accept failed: {}", e),
+            Err(e) => panic!("Server accept timed out: {}", e),
+        };
 
         // Read request
         let mut buf_reader = BufReader::new(stream);
-        let request = mcp_cli_rs::daemon::protocol::receive_request(&mut buf_reader)
-            .await
-            .expect("Failed to receive request");
+        let (id, request) = receive_first_request(&mut buf_reader).await;
 
         // Verify Ping request
         assert!(matches!(request, DaemonRequest::Ping));
 
         // Send Pong response
-        let response = DaemonResponse::Pong;
-        mcp_cli_rs::daemon::protocol::send_response(&mut buf_reader, &response)
-            .await
-            .expect("Failed to send response");
+        send_response_frame(&mut buf_reader, id, DaemonResponse::Pong).await;
     });
 
     // Give server time to start
@@ -171,39 +171,77 @@ pub async fn spawn_single_response_server(
     expected_request: DaemonRequest,
     response: DaemonResponse,
 ) -> tokio::task::JoinHandle<()> {
-    let mut server = ipc::create_ipc_server(&socket_path)
+    let server = ipc::create_ipc_server(&socket_path)
         .await
         .expect("Failed to create IPC server");
 
     tokio::spawn(async move {
-        let (mut stream, _addr) =
-            match timeout(Duration::from_secs(5), server.accept()).await {
-                Ok(Ok(stream)) => stream,
-                Ok(Err(e)) => panic!("Server accept failed: {}", e),
-                Err(e) => panic!("Server accept timed out: {}", e),
-            };
+        let (stream, _addr) = match timeout(Duration::from_secs(5), server.accept()).await {
+            Ok(Ok(stream)) => stream,
+            Ok(Err(e)) => panic!("Server accept failed: {}", e),
+            Err(e) => panic!("Server accept timed out: {}", e),
+        };
 
         let mut buf_reader = BufReader::new(stream);
-        let request = mcp_cli_rs::daemon::protocol::receive_request(&mut buf_reader)
-            .await
-            .expect("Failed to receive request");
+        let (id, request) = receive_first_request(&mut buf_reader).await;
 
         assert!(
             std::mem::discriminant(&request) == std::mem::discriminant(&expected_request),
             "Request type mismatch"
         );
 
-        mcp_cli_rs::daemon::protocol::send_response(&mut buf_reader, &response)
-            .await
-            .expect("Failed to send response");
+        send_response_frame(&mut buf_reader, id, response).await;
     })
 }
 
+/// Answer the client's handshake and read its first request, the way the
+/// daemon does on a new connection
+///
+/// Returns the request with the ID its response must carry.
+pub async fn receive_first_request<S>(stream: &mut BufReader<S>) -> (u64, DaemonRequest)
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    let handshake: Handshake = protocol::receive_frame(stream)
+        .await
+        .expect("Failed to receive handshake")
+        .expect("Client closed the connection before the handshake");
+    assert_eq!(handshake, Handshake::current());
+    protocol::send_frame(stream, &Handshake::current())
+        .await
+        .expect("Failed to send handshake");
+
+    let frame: RequestFrame = protocol::receive_frame(stream)
+        .await
+        .expect("Failed to receive request")
+        .expect("Client closed the connection before sending a request");
+    match frame.request {
+        FrameRequest::Serve(request) => (frame.id, request),
+        FrameRequest::Cancel => panic!("Client cancelled request {} before sending it", frame.id),
+    }
+}
+
+/// Send the response to the request with `id`
+pub async fn send_response_frame<S>(stream: &mut BufReader<S>, id: u64, response: DaemonResponse)
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    protocol::send_frame(stream, &ResponseFrame { id, response })
+        .await
+        .expect("Failed to send response");
+}
+
 /// Test environment with temporary directory cleanup
 pub struct TestEnvironment {
     pub temp_dir: TempDir,
 }
 
+impl Default for TestEnvironment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TestEnvironment {
     pub fn new() -> Self {
         Self {
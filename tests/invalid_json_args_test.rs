@@ -427,7 +427,7 @@ async fn test_nested_json_validation() {
         .expect("Failed to initialize");
 
     // Valid nested structure
-    let result = client
+    let _result = client
         .call_tool("nested", serde_json::json!({
             "data": {"value": "test"}
         }))
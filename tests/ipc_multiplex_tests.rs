@@ -0,0 +1,299 @@
+//! Integration tests for persistent IPC connections between CLI and daemon
+//!
+//! One connection carries many requests, each tagged with an ID and answered
+//! as soon as it completes. Connections open with a protocol version
+//! handshake so CLIs and daemons from before it fail with a clear error.
+
+use mcp_cli_rs::config::Config;
+use mcp_cli_rs::daemon::protocol::{
+    self, DaemonRequest, DaemonResponse, Handshake, IPC_PROTOCOL_VERSION, RequestFrame,
+    ResponseFrame,
+};
+use mcp_cli_rs::error::McpError;
+use mcp_cli_rs::ipc::{self, IpcClient, IpcConnection, IpcStream};
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::BufReader;
+
+mod fixtures {
+    pub mod daemon_test_helper;
+}
+
+mod common {
+    pub mod mock_server;
+}
+
+mod helpers;
+
+use common::mock_server::mock_server_config;
+use fixtures::daemon_test_helper::{TestDaemon, spawn_test_daemon};
+
+/// A config with one mock server whose tool calls take `delay_ms`
+fn slow_config(delay_ms: u64, cancel_log: &Path) -> Config {
+    Config {
+        servers: vec![mock_server_config(
+            "mock",
+            &[
+                ("MOCK_DELAY_MS", &delay_ms.to_string()),
+                ("MOCK_CANCEL_LOG", cancel_log.to_str().unwrap()),
+            ],
+        )],
+        ..Config::default()
+    }
+}
+
+/// Open a raw stream to the daemon
+async fn connect(daemon: &TestDaemon) -> Box<dyn IpcStream> {
+    #[cfg(unix)]
+    let client = ipc::UnixIpcClient::new(Arc::clone(daemon.config()));
+    #[cfg(windows)]
+    let client = ipc::NamedPipeIpcClient::with_config(Arc::clone(daemon.config()));
+    client.connect(daemon.socket_path()).await.unwrap()
+}
+
+fn echo(message: &str) -> DaemonRequest {
+    DaemonRequest::ExecuteTool {
+        server_name: "mock".to_string(),
+        tool_name: "echo".to_string(),
+        arguments: json!({ "message": message }),
+        timeout_secs: None,
+    }
+}
+
+#[tokio::test]
+async fn test_requests_on_one_connection_are_answered_as_they_complete() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let daemon = spawn_test_daemon(slow_config(1000, &dir.path().join("cancelled.log")))
+        .await
+        .unwrap();
+    let connection = IpcConnection::open(connect(&daemon).await).await.unwrap();
+
+    let started = Instant::now();
+    let slow = async {
+        let response = connection.request(&echo("slow"), &mut |_| {}).await;
+        (response, started.elapsed())
+    };
+    let fast = async {
+        // Sent after the tool call, on the same connection
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let response = connection
+            .request(&DaemonRequest::ListServers, &mut |_| {})
+            .await;
+        (response, started.elapsed())
+    };
+    let ((slow, slow_done), (fast, fast_done)) = tokio::join!(slow, fast);
+
+    assert_eq!(
+        fast.unwrap(),
+        DaemonResponse::ServerList(vec!["mock".to_string()])
+    );
+    match slow.unwrap() {
+        DaemonResponse::ToolResult(result) => {
+            assert_eq!(result["content"][0]["text"], "Echo: slow")
+        }
+        other => panic!("unexpected response: {:?}", other),
+    }
+    assert!(
+        fast_done < slow_done && fast_done < Duration::from_millis(900),
+        "server list waited for the tool call: {:?} vs {:?}",
+        fast_done,
+        slow_done
+    );
+
+    // The connection stays open for further requests
+    assert!(!connection.is_closed());
+    let response = connection
+        .request(&DaemonRequest::Ping, &mut |_| {})
+        .await
+        .unwrap();
+    assert_eq!(response, DaemonResponse::Pong);
+
+    drop(connection);
+    daemon.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_cancel_stops_only_the_call_with_its_id() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let cancel_log = dir.path().join("cancelled.log");
+    let daemon = spawn_test_daemon(slow_config(1500, &cancel_log))
+        .await
+        .unwrap();
+    let connection = IpcConnection::open(connect(&daemon).await).await.unwrap();
+
+    let abandoned = async {
+        // Giving up on the call (as Ctrl+C does) sends CancelRequest with its ID
+        let request = echo("abandoned");
+        let mut ignore_progress = |_| {};
+        let call = connection.request(&request, &mut ignore_progress);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(300), call)
+                .await
+                .is_err()
+        );
+    };
+    let kept = async { connection.request(&echo("kept"), &mut |_| {}).await };
+    let ((), kept) = tokio::join!(abandoned, kept);
+
+    match kept.unwrap() {
+        DaemonResponse::ToolResult(result) => {
+            assert_eq!(result["content"][0]["text"], "Echo: kept")
+        }
+        other => panic!("unexpected response: {:?}", other),
+    }
+    let cancelled = std::fs::read_to_string(&cancel_log).unwrap_or_default();
+    assert_eq!(cancelled.lines().count(), 1);
+
+    drop(connection);
+    daemon.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_request_reusing_an_id_in_flight_is_rejected() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let daemon = spawn_test_daemon(slow_config(500, &dir.path().join("cancelled.log")))
+        .await
+        .unwrap();
+    let mut stream = BufReader::new(connect(&daemon).await);
+    protocol::send_frame(&mut stream, &Handshake::current())
+        .await
+        .unwrap();
+    let _: Option<Handshake> = protocol::receive_frame(&mut stream).await.unwrap();
+
+    for request in [echo("first"), DaemonRequest::Ping] {
+        protocol::send_frame(
+            &mut stream,
+            &RequestFrame {
+                id: 7,
+                request: request.into(),
+            },
+        )
+        .await
+        .unwrap();
+    }
+    let rejected: Option<ResponseFrame> = protocol::receive_frame(&mut stream).await.unwrap();
+    match rejected.map(|frame| (frame.id, frame.response)) {
+        Some((7, DaemonResponse::Error { message, .. })) => {
+            assert!(message.contains("already in use"), "{}", message)
+        }
+        other => panic!("unexpected response: {:?}", other),
+    }
+    let first: Option<ResponseFrame> = protocol::receive_frame(&mut stream).await.unwrap();
+    match first.map(|frame| (frame.id, frame.response)) {
+        Some((7, DaemonResponse::ToolResult(result))) => {
+            assert_eq!(result["content"][0]["text"], "Echo: first")
+        }
+        other => panic!("unexpected response: {:?}", other),
+    }
+
+    // Once answered, the ID is free again
+    protocol::send_frame(
+        &mut stream,
+        &RequestFrame {
+            id: 7,
+            request: DaemonRequest::Ping.into(),
+        },
+    )
+    .await
+    .unwrap();
+    let pong: Option<ResponseFrame> = protocol::receive_frame(&mut stream).await.unwrap();
+    assert_eq!(pong.map(|frame| frame.response), Some(DaemonResponse::Pong));
+
+    drop(stream);
+    daemon.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_client_without_handshake_is_told_it_is_outdated() {
+    let daemon = spawn_test_daemon(Config::default()).await.unwrap();
+    let mut stream = BufReader::new(connect(&daemon).await);
+
+    // A CLI from before the handshake sends a bare request
+    protocol::send_frame(&mut stream, &DaemonRequest::Ping)
+        .await
+        .unwrap();
+    let response: Option<DaemonResponse> = protocol::receive_frame(&mut stream).await.unwrap();
+    match response {
+        Some(DaemonResponse::Error { message, .. }) => {
+            assert!(
+                message.contains("older than the running daemon"),
+                "{}",
+                message
+            )
+        }
+        other => panic!("unexpected response: {:?}", other),
+    }
+    let closed: Option<DaemonResponse> = protocol::receive_frame(&mut stream).await.unwrap();
+    assert!(closed.is_none());
+
+    daemon.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_daemon_answers_and_closes_on_version_mismatch() {
+    let daemon = spawn_test_daemon(Config::default()).await.unwrap();
+    let mut stream = BufReader::new(connect(&daemon).await);
+
+    let newer = Handshake {
+        protocol_version: IPC_PROTOCOL_VERSION + 1,
+    };
+    protocol::send_frame(&mut stream, &newer).await.unwrap();
+    let answer: Option<Handshake> = protocol::receive_frame(&mut stream).await.unwrap();
+    assert_eq!(answer, Some(Handshake::current()));
+    let closed: Option<Handshake> = protocol::receive_frame(&mut stream).await.unwrap();
+    assert!(closed.is_none());
+
+    daemon.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_legacy_daemon_is_reported_and_can_be_shut_down() {
+    let socket_path = helpers::get_test_socket_path_with_suffix("legacy");
+    let server = ipc::create_ipc_server(&socket_path).await.unwrap();
+
+    // A daemon from before the handshake reads one bare request per
+    // connection and closes connections it can't parse
+    let legacy_daemon = tokio::spawn(async move {
+        let mut requests = Vec::new();
+        loop {
+            let (stream, _) = server.accept().await.unwrap();
+            let mut stream = BufReader::new(stream);
+            match protocol::receive_frame::<_, DaemonRequest>(&mut stream).await {
+                Ok(Some(DaemonRequest::Shutdown)) => {
+                    protocol::send_frame(&mut stream, &DaemonResponse::ShutdownAck)
+                        .await
+                        .unwrap();
+                    requests.push(DaemonRequest::Shutdown);
+                    return requests;
+                }
+                Ok(Some(request)) => requests.push(request),
+                Ok(None) | Err(_) => {}
+            }
+        }
+    });
+
+    let config = Config::with_socket_path(socket_path.clone());
+    let mut client = ipc::create_ipc_client(&config).unwrap();
+    match client.list_servers().await {
+        Err(McpError::IpcVersionMismatch { daemon, client }) => {
+            assert_eq!(daemon, 1);
+            assert_eq!(client, IPC_PROTOCOL_VERSION);
+        }
+        other => panic!("expected a version mismatch, got {:?}", other),
+    }
+
+    // Shutdown falls back to the bare request the old daemon understands
+    client.shutdown().await.unwrap();
+    let requests = tokio::time::timeout(Duration::from_secs(5), legacy_daemon)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(requests, vec![DaemonRequest::Shutdown]);
+
+    #[cfg(unix)]
+    {
+        let _ = std::fs::remove_file(&socket_path);
+    }
+}
@@ -33,18 +33,14 @@ mod ipc_tests {
 
             // Read request - wrap in BufReader for AsyncBufRead requirement
             let mut buf_reader = tokio::io::BufReader::new(stream);
-            let request = mcp_cli_rs::daemon::protocol::receive_request(&mut buf_reader)
-                .await
-                .expect("Failed to receive request");
+            let (id, request) = crate::helpers::receive_first_request(&mut buf_reader).await;
 
             // Verify we got a Ping request
             assert!(matches!(request, DaemonRequest::Ping));
 
             // Send response
             let response = DaemonResponse::Pong;
-            mcp_cli_rs::daemon::protocol::send_response(&mut buf_reader, &response)
-                .await
-                .expect("Failed to send response");
+            crate::helpers::send_response_frame(&mut buf_reader, id, response).await;
         });
 
         // Give the server time to start accepting connections (Windows named pipes need more time)
@@ -98,18 +94,14 @@ mod ipc_tests {
 
                 // Read request - wrap in BufReader for AsyncBufRead requirement
                 let mut buf_reader = tokio::io::BufReader::new(stream);
-                let request = mcp_cli_rs::daemon::protocol::receive_request(&mut buf_reader)
-                    .await
-                    .expect("Failed to receive request");
+                let (id, request) = crate::helpers::receive_first_request(&mut buf_reader).await;
 
                 // Verify Ping request
                 assert!(matches!(request, DaemonRequest::Ping));
 
                 // Send response
                 let response = DaemonResponse::Pong;
-                mcp_cli_rs::daemon::protocol::send_response(&mut buf_reader, &response)
-                    .await
-                    .expect("Failed to send response");
+                crate::helpers::send_response_frame(&mut buf_reader, id, response).await;
             }
         });
 
@@ -171,15 +163,11 @@ mod ipc_tests {
 
             // Read request - wrap in BufReader for AsyncBufRead requirement
             let mut buf_reader = tokio::io::BufReader::new(stream);
-            let _request = mcp_cli_rs::daemon::protocol::receive_request(&mut buf_reader)
-                .await
-                .expect("Failed to receive request");
+            let (id, _request) = crate::helpers::receive_first_request(&mut buf_reader).await;
 
             // Send large response
             let response = DaemonResponse::ToolResult(server_content);
-            mcp_cli_rs::daemon::protocol::send_response(&mut buf_reader, &response)
-                .await
-                .expect("Failed to send response");
+            crate::helpers::send_response_frame(&mut buf_reader, id, response).await;
         });
 
         // Give the server time to start accepting connections (Windows named pipes need more time)
@@ -0,0 +1,108 @@
+//! Integration tests for the legacy HTTP+SSE transport
+//!
+//! Runs `SseTransport` against an in-process server implementing the
+//! 2024-11-05 transport, and checks that `HttpTransport` with SSE fallback
+//! switches to it when the server rejects Streamable HTTP.
+
+use mcp_cli_rs::cli::daemon_lifecycle::DirectProtocolClient;
+use mcp_cli_rs::client::McpClient;
+use mcp_cli_rs::client::http::HttpTransport;
+use mcp_cli_rs::client::legacy_sse::SseTransport;
+use mcp_cli_rs::config::{Config, ServerConfig, ServerTransport};
+use mcp_cli_rs::ipc::ProtocolClient;
+use mcp_cli_rs::transport::Transport;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod fixtures;
+use fixtures::mock_sse_server::MockSseServer;
+
+#[tokio::test]
+async fn test_sse_list_and_call_tools() {
+    let (server, url) = MockSseServer::start().await;
+    let transport = SseTransport::new(&url, HashMap::new());
+    let mut client = McpClient::new("mock".to_string(), Box::new(transport));
+
+    let tools = client.list_tools().await.unwrap();
+    assert_eq!(tools.len(), 1);
+    assert_eq!(tools[0].name, "echo");
+
+    let result = client
+        .call_tool("echo", json!({ "message": "hi" }))
+        .await
+        .unwrap();
+    assert_eq!(result["content"][0]["text"], "Echo: hi");
+
+    // Both requests shared one event stream
+    assert_eq!(server.sessions_opened(), 1);
+    server.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_sse_notifications_and_ping() {
+    let (server, url) = MockSseServer::start().await;
+    let mut transport = SseTransport::new(&url, HashMap::new());
+
+    transport
+        .send(json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list", "params": {} }))
+        .await
+        .unwrap();
+    let notification = transport.receive_notification().await.unwrap();
+    assert_eq!(notification["method"], "notifications/message");
+
+    transport.ping().await.unwrap();
+    assert_eq!(transport.transport_type(), "sse");
+    server.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_http_falls_back_to_sse() {
+    let (server, url) = MockSseServer::start().await;
+    let transport = HttpTransport::new(&url, HashMap::new()).with_sse_fallback();
+    let mut client = McpClient::new("mock".to_string(), Box::new(transport));
+
+    let tools = client.list_tools().await.unwrap();
+
+    assert_eq!(tools[0].name, "echo");
+    assert_eq!(server.sessions_opened(), 1);
+    server.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_http_without_fallback_fails() {
+    let (server, url) = MockSseServer::start().await;
+    let transport = HttpTransport::new(&url, HashMap::new());
+    let mut client = McpClient::new("mock".to_string(), Box::new(transport));
+
+    let err = client.list_tools().await.unwrap_err();
+
+    assert!(err.to_string().contains("405"), "unexpected error: {}", err);
+    assert_eq!(server.sessions_opened(), 0);
+    server.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_sse_server_from_config() {
+    let (server, url) = MockSseServer::start().await;
+    let config = Config {
+        servers: vec![ServerConfig {
+            name: "legacy".to_string(),
+            transport: ServerTransport::Sse {
+                url,
+                headers: HashMap::new(),
+            },
+            description: None,
+            allowed_tools: None,
+            disabled_tools: None,
+            timeout_secs: None,
+        }],
+        ..Config::default()
+    };
+    let mut client = DirectProtocolClient::new(Arc::new(config));
+
+    let tools = client.list_tools("legacy").await.unwrap();
+
+    assert_eq!(tools.len(), 1);
+    server.shutdown().await;
+}
@@ -473,4 +473,21 @@ async fn test_activity_expiration_verification() {
     // Verify activity expired
     let elapsed = lifecycle.lock().await.elapsed_since_last_activity().await;
     assert!(elapsed >= std::time::Duration::from_secs(1));
-}
\ No newline at end of file
+}
+/// Running requests keep the daemon from going idle until their deadline
+#[tokio::test]
+async fn test_running_requests_delay_idle_shutdown() {
+    let lifecycle = DaemonLifecycle::new(0);
+    tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    assert!(lifecycle.should_shutdown().await);
+
+    let running = lifecycle.begin_request(std::time::Duration::from_secs(60));
+    assert!(!lifecycle.should_shutdown().await);
+    let overdue = lifecycle.begin_request(std::time::Duration::ZERO);
+    assert!(!lifecycle.should_shutdown().await);
+
+    drop(running);
+    assert!(lifecycle.should_shutdown().await);
+    drop(overdue);
+    assert!(lifecycle.latest_deadline().is_none());
+}
@@ -10,16 +10,12 @@ use std::path::PathBuf;
 use mcp_cli_rs::config::Config;
 use mcp_cli_rs::daemon::orphan::{
     cleanup_orphaned_daemon, get_fingerprint_file_path, get_pid_file_path, is_daemon_running,
-    kill_daemon_process, write_daemon_pid,
+    kill_daemon_process,
 };
 
 #[cfg(test)]
 mod helpers;
 
-#[cfg(unix)]
-use nix::sys::signal::{Signal, kill};
-#[cfg(unix)]
-use nix::unistd::Pid;
 
 #[tokio::test]
 async fn test_orphan_socket_cleanup_unix() {
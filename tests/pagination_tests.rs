@@ -0,0 +1,177 @@
+//! Integration tests for MCP list pagination
+//!
+//! Runs the mock MCP server with MOCK_PAGE_SIZE set so that tools/list and
+//! resources/list are split into pages, and checks that every client follows
+//! `nextCursor` to completion.
+
+use mcp_cli_rs::cli::daemon_lifecycle::DirectProtocolClient;
+use mcp_cli_rs::client::McpClient;
+use mcp_cli_rs::client::stdio::StdioTransport;
+use mcp_cli_rs::config::Config;
+use mcp_cli_rs::daemon::pool::ConnectionPool;
+use mcp_cli_rs::ipc::ProtocolClient;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod common {
+    pub mod mock_server;
+}
+
+use common::mock_server::{MOCK_SERVER, mock_server_config};
+
+/// Build a config with a single mock server serving lists one item per page
+fn paginated_config() -> Config {
+    Config {
+        servers: vec![mock_server_config("mock", &[("MOCK_PAGE_SIZE", "1")])],
+        ..Config::default()
+    }
+}
+
+#[tokio::test]
+async fn test_direct_list_tools_follows_cursors() {
+    let mut client = DirectProtocolClient::new(Arc::new(paginated_config()));
+
+    let tools = client.list_tools("mock").await.unwrap();
+
+    let names: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["echo", "add", "fail"]);
+}
+
+#[tokio::test]
+async fn test_direct_list_resources_follows_cursors() {
+    let mut client = DirectProtocolClient::new(Arc::new(paginated_config()));
+
+    let resources = client.list_resources("mock").await.unwrap();
+
+    assert_eq!(resources.len(), 2);
+    assert_eq!(resources[1].uri, "file:///mock/logo.bin");
+}
+
+#[tokio::test]
+async fn test_pool_list_tools_follows_cursors() {
+    let pool = ConnectionPool::new(Arc::new(paginated_config()));
+
+    let tools = pool.list_tools("mock").await.unwrap();
+    assert_eq!(tools.len(), 3);
+
+    // The connection survives the multi-page exchange and is reused
+    let resources = pool.list_resources("mock").await.unwrap();
+    assert_eq!(resources.len(), 2);
+    assert_eq!(pool.count(), 1);
+}
+
+#[tokio::test]
+async fn test_mcp_client_list_tools_follows_cursors() {
+    let env = HashMap::from([("MOCK_PAGE_SIZE".to_string(), "2".to_string())]);
+    let transport = StdioTransport::new(MOCK_SERVER, &[], &env, None).unwrap();
+    let mut client = McpClient::new("mock".to_string(), Box::new(transport));
+
+    let tools = client.list_tools().await.unwrap();
+
+    assert_eq!(tools.len(), 3);
+    assert_eq!(tools[2].name, "fail");
+}
+
+#[tokio::test]
+async fn test_direct_list_tools_page_returns_server_cursor() {
+    let mut client = DirectProtocolClient::new(Arc::new(paginated_config()));
+
+    // One request per page, each returning the server's own cursor
+    let mut names = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = client
+            .list_tools_page("mock", cursor.as_deref())
+            .await
+            .unwrap();
+        assert_eq!(page.tools.len(), 1);
+        names.push(page.tools[0].name.clone());
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    assert_eq!(names, vec!["echo", "add", "fail"]);
+}
+
+#[tokio::test]
+async fn test_pool_list_tools_page_returns_server_cursor() {
+    let pool = ConnectionPool::new(Arc::new(paginated_config()));
+
+    let first = pool.list_tools_page("mock", None).await.unwrap();
+    assert_eq!(first.tools.len(), 1);
+    let cursor = first.next_cursor.expect("more pages");
+
+    let second = pool.list_tools_page("mock", Some(&cursor)).await.unwrap();
+    assert_eq!(second.tools[0].name, "add");
+    assert!(second.next_cursor.is_some());
+}
+
+/// Run `mcp list --json --page-size` over all pages, with the mock server
+/// serving `server_page_size` tools per `tools/list` page
+fn list_all_pages(page_size: &str, server_page_size: &str) -> Vec<Vec<String>> {
+    let dir = tempfile::TempDir::new().unwrap();
+    let config_path = dir.path().join("mcp_servers.toml");
+    let config = format!(
+        "[[servers]]\nname = \"mock\"\n\n[servers.transport]\ntype = \"stdio\"\ncommand = '{}'\n",
+        MOCK_SERVER
+    );
+    std::fs::write(&config_path, config).unwrap();
+
+    let mut pages = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut command = std::process::Command::new(env!("CARGO_BIN_EXE_mcp-cli-rs"));
+        command
+            .arg("--config")
+            .arg(&config_path)
+            .arg("--no-daemon")
+            .args(["list", "--json", "--page-size", page_size])
+            .env("MOCK_PAGE_SIZE", server_page_size);
+        if let Some(ref cursor) = cursor {
+            command.args(["--cursor", cursor]);
+        }
+        let output = command.output().unwrap();
+        assert!(output.status.success(), "{:?}", output);
+
+        let page: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        pages.push(
+            page["tools"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|t| {
+                    format!(
+                        "{}/{}",
+                        t["server"].as_str().unwrap(),
+                        t["name"].as_str().unwrap()
+                    )
+                })
+                .collect(),
+        );
+        match page["next_cursor"].as_str() {
+            Some(next) => cursor = Some(next.to_string()),
+            None => break,
+        }
+    }
+    pages
+}
+
+#[test]
+fn test_list_page_size_buffers_server_pages() {
+    // Two tools per page, gathered from one-tool server pages
+    assert_eq!(
+        list_all_pages("2", "1"),
+        vec![vec!["mock/echo", "mock/add"], vec!["mock/fail"]]
+    );
+}
+
+#[test]
+fn test_list_page_size_splits_server_pages() {
+    // One tool per page, resuming inside two-tool server pages
+    assert_eq!(
+        list_all_pages("1", "2"),
+        vec![vec!["mock/echo"], vec!["mock/add"], vec!["mock/fail"]]
+    );
+}
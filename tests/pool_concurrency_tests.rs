@@ -0,0 +1,284 @@
+//! Integration tests for concurrent requests to one server
+//!
+//! The pool opens up to `max_connections_per_server` connections per server;
+//! further requests wait their turn. The mock server's MOCK_START_LOG counts
+//! the server processes that were started.
+
+use mcp_cli_rs::config::{Config, ServerConfig};
+use mcp_cli_rs::daemon::pool::{ConnectionPool, ConnectionPoolInterface};
+use mcp_cli_rs::error::McpError;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+mod common {
+    pub mod mock_server;
+}
+
+use common::mock_server::{MOCK_SERVER, mock_server_config};
+
+/// A mock server that takes `delay_ms` per call and logs its starts to `log`
+fn slow_server(delay_ms: u64, log: &Path) -> ServerConfig {
+    mock_server_config(
+        "mock",
+        &[
+            ("MOCK_DELAY_MS", &delay_ms.to_string()),
+            ("MOCK_START_LOG", log.to_str().unwrap()),
+        ],
+    )
+}
+
+fn pool(server: ServerConfig, max_connections_per_server: usize) -> ConnectionPool {
+    ConnectionPool::new(Config {
+        servers: vec![server],
+        max_connections_per_server,
+        ..Config::default()
+    })
+}
+
+/// Number of server processes started, per the mock's start log
+fn starts(log: &Path) -> usize {
+    std::fs::read_to_string(log)
+        .map(|log| log.lines().count())
+        .unwrap_or(0)
+}
+
+fn echo(message: &str) -> serde_json::Value {
+    serde_json::json!({ "message": message })
+}
+
+#[tokio::test]
+async fn test_concurrent_calls_run_on_separate_connections() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let log = dir.path().join("starts.log");
+    let pool = pool(slow_server(500, &log), 3);
+
+    let started = Instant::now();
+    let (a, b, c) = tokio::join!(
+        pool.execute("mock", "echo", echo("a")),
+        pool.execute("mock", "echo", echo("b")),
+        pool.execute("mock", "echo", echo("c")),
+    );
+    let elapsed = started.elapsed();
+
+    assert_eq!(a.unwrap()["content"][0]["text"], "Echo: a");
+    assert_eq!(b.unwrap()["content"][0]["text"], "Echo: b");
+    assert_eq!(c.unwrap()["content"][0]["text"], "Echo: c");
+    assert!(
+        elapsed < Duration::from_millis(1400),
+        "calls ran one at a time: {:?}",
+        elapsed
+    );
+    assert_eq!(starts(&log), 3);
+    assert_eq!(pool.count(), 3);
+
+    // Later calls reuse the idle connections
+    pool.execute("mock", "echo", echo("d")).await.unwrap();
+    assert_eq!(starts(&log), 3);
+}
+
+#[tokio::test]
+async fn test_calls_beyond_limit_wait_in_arrival_order() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let log = dir.path().join("starts.log");
+    let pool = pool(slow_server(200, &log), 1);
+    let finished = Arc::new(Mutex::new(Vec::new()));
+
+    let mut calls = Vec::new();
+    for i in 0..4 {
+        let pool = pool.clone();
+        let finished = Arc::clone(&finished);
+        calls.push(tokio::spawn(async move {
+            let result = pool.execute("mock", "echo", echo(&i.to_string())).await;
+            finished.lock().unwrap().push(i);
+            result
+        }));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    for (i, call) in calls.into_iter().enumerate() {
+        let result = call.await.unwrap().unwrap();
+        assert_eq!(result["content"][0]["text"], format!("Echo: {}", i));
+    }
+
+    assert_eq!(*finished.lock().unwrap(), vec![0, 1, 2, 3]);
+    assert_eq!(starts(&log), 1);
+    assert_eq!(pool.count(), 1);
+}
+
+#[tokio::test]
+async fn test_failed_and_cancelled_calls_free_their_place() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let log = dir.path().join("starts.log");
+    let pool = pool(slow_server(300, &log), 1);
+
+    let result = pool.execute("mock", "missing", serde_json::json!({})).await;
+    assert!(result.is_err());
+    let result = pool
+        .execute_cancellable(
+            "mock",
+            "echo",
+            echo("cancelled"),
+            None,
+            &mut |_| {},
+            tokio::time::sleep(Duration::from_millis(50)),
+        )
+        .await;
+    assert!(result.is_err());
+
+    // Neither failure left the only place taken
+    let result = tokio::time::timeout(
+        Duration::from_secs(5),
+        pool.execute("mock", "echo", echo("after")),
+    )
+    .await
+    .expect("call waited for a connection that was never returned");
+    assert_eq!(result.unwrap()["content"][0]["text"], "Echo: after");
+}
+
+#[tokio::test]
+async fn test_calls_waiting_for_a_connection_time_out_and_cancel() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let log = dir.path().join("starts.log");
+    let pool = pool(slow_server(0, &log), 1);
+    let held = pool.take("mock").await.unwrap().unwrap();
+
+    // The wait for the only place counts against the call's timeout
+    let started = Instant::now();
+    let result = pool
+        .execute_cancellable(
+            "mock",
+            "echo",
+            echo("queued"),
+            Some(Duration::from_millis(200)),
+            &mut |_| {},
+            std::future::pending(),
+        )
+        .await;
+    assert!(
+        matches!(result, Err(McpError::Timeout { timeout: 1 })),
+        "{:?}",
+        result
+    );
+    assert!(started.elapsed() < Duration::from_secs(2));
+
+    // Cancellation ends the wait without a place freeing up
+    let result = tokio::time::timeout(
+        Duration::from_secs(2),
+        pool.execute_cancellable(
+            "mock",
+            "echo",
+            echo("queued"),
+            None,
+            &mut |_| {},
+            tokio::time::sleep(Duration::from_millis(100)),
+        ),
+    )
+    .await
+    .expect("cancelled call kept waiting for a connection");
+    assert!(result.is_err());
+
+    // Neither abandoned wait took the place once it was returned
+    pool.put_back(held);
+    let result = pool.execute("mock", "echo", echo("after")).await;
+    assert_eq!(result.unwrap()["content"][0]["text"], "Echo: after");
+}
+
+/// Whether a connection to "mock" can be taken within a short wait
+async fn can_take(pool: &ConnectionPool) -> bool {
+    tokio::time::timeout(Duration::from_millis(200), pool.take("mock"))
+        .await
+        .is_ok()
+}
+
+#[tokio::test]
+async fn test_lowered_limit_counts_connections_in_use() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let log = dir.path().join("starts.log");
+    let pool = pool(slow_server(0, &log), 2);
+
+    let first = pool.take("mock").await.unwrap().unwrap();
+    let second = pool.take("mock").await.unwrap().unwrap();
+    pool.reload(Config {
+        servers: vec![slow_server(0, &log)],
+        max_connections_per_server: 1,
+        ..Config::default()
+    });
+
+    // The first returned connection pays for the lowered limit
+    pool.put_back(first);
+    assert!(!can_take(&pool).await, "pool went over the lowered limit");
+
+    pool.put_back(second);
+    assert!(can_take(&pool).await);
+}
+
+#[tokio::test]
+async fn test_connection_from_get_keeps_its_place() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let log = dir.path().join("starts.log");
+    let pool = pool(slow_server(0, &log), 1);
+
+    let transport = ConnectionPoolInterface::get(&pool, "mock").await.unwrap();
+    assert!(!can_take(&pool).await, "connection in use lost its place");
+
+    drop(transport);
+    assert!(can_take(&pool).await);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_parallel_mcp_calls_share_bounded_connections() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let log = dir.path().join("starts.log");
+    std::fs::write(
+        dir.path().join("mcp.toml"),
+        format!(
+            "max_connections_per_server = 2\n\n[[servers]]\nname = \"mock\"\n\n[servers.transport]\ntype = \"stdio\"\ncommand = '{}'\nenv = {{ MOCK_DELAY_MS = \"1000\", MOCK_START_LOG = '{}' }}\n",
+            MOCK_SERVER,
+            log.display()
+        ),
+    )
+    .unwrap();
+    let mcp = |args: &[&str]| {
+        let mut command = std::process::Command::new(env!("CARGO_BIN_EXE_mcp-cli-rs"));
+        command
+            .current_dir(dir.path())
+            .args(["--config", "mcp.toml"])
+            .args(args)
+            .env_remove("MCP_CONFIG_PATH")
+            .stdout(std::process::Stdio::piped());
+        command
+    };
+    assert!(mcp(&["list"]).status().unwrap().success());
+
+    let started = Instant::now();
+    let calls: Vec<_> = (0..4)
+        .map(|i| {
+            let message = format!("call {}", i);
+            mcp(&["call", "mock/echo", "--", "--message", &message])
+                .spawn()
+                .unwrap()
+        })
+        .collect();
+    for (i, call) in calls.into_iter().enumerate() {
+        let output = call.wait_with_output().unwrap();
+        assert!(output.status.success());
+        assert!(String::from_utf8_lossy(&output.stdout).contains(&format!("call {}", i)));
+    }
+    let elapsed = started.elapsed();
+
+    // Two at a time: slower than one round, faster than one call after another
+    assert!(
+        elapsed >= Duration::from_secs(2),
+        "limit not applied: {:?}",
+        elapsed
+    );
+    assert!(
+        elapsed < Duration::from_millis(3500),
+        "calls ran one at a time: {:?}",
+        elapsed
+    );
+    assert_eq!(starts(&log), 2);
+
+    assert!(mcp(&["shutdown"]).status().unwrap().success());
+}
@@ -0,0 +1,80 @@
+//! Integration tests for reusing pooled connections
+//!
+//! The mock MCP server rejects a second `initialize`, so these tests fail if
+//! the pool repeats the handshake on a connection it hands out again.
+
+use mcp_cli_rs::config::{Config, ServerConfig};
+use mcp_cli_rs::daemon::pool::ConnectionPool;
+use std::time::{Duration, Instant};
+
+mod common {
+    pub mod mock_server;
+}
+
+use common::mock_server::mock_server_config;
+
+fn pool(server: ServerConfig) -> ConnectionPool {
+    ConnectionPool::new(Config {
+        servers: vec![server],
+        ..Config::default()
+    })
+}
+
+fn echo(message: &str) -> serde_json::Value {
+    serde_json::json!({ "message": message })
+}
+
+#[tokio::test]
+async fn test_reused_connection_is_initialized_once() {
+    let pool = pool(mock_server_config("mock", &[]));
+
+    pool.list_tools("mock").await.unwrap();
+    let first = pool.take("mock").await.unwrap().unwrap();
+    let created_at = first.created_at;
+    let init = first
+        .initialized
+        .clone()
+        .expect("connection was initialized");
+    assert_eq!(init.server_info["name"], "mock-mcp-server");
+    assert!(init.protocol_version.is_some());
+    pool.put_back(first);
+
+    for message in ["one", "two", "three"] {
+        let result = pool.execute("mock", "echo", echo(message)).await.unwrap();
+        assert_eq!(result["content"][0]["text"], format!("Echo: {}", message));
+    }
+    pool.list_tools("mock").await.unwrap();
+
+    let conn = pool.take("mock").await.unwrap().unwrap();
+    assert_eq!(conn.created_at, created_at);
+    assert_eq!(conn.initialized, Some(init));
+}
+
+#[tokio::test]
+async fn test_dead_transport_is_replaced() {
+    let pool = pool(mock_server_config(
+        "mock",
+        &[("MOCK_EXIT_AFTER_CALLS", "1")],
+    ));
+
+    let result = pool.execute("mock", "echo", echo("first")).await.unwrap();
+    assert_eq!(result["content"][0]["text"], "Echo: first");
+
+    // Once the server has exited after its one call, the pool replaces
+    // the connection instead of handing it out. The replacement is the
+    // first connection taken that hasn't been initialized yet.
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        let conn = pool.take("mock").await.unwrap().unwrap();
+        let replaced = conn.initialized.is_none();
+        pool.put_back(conn);
+        if replaced {
+            break;
+        }
+        assert!(Instant::now() < deadline, "server did not exit");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    let result = pool.execute("mock", "echo", echo("second")).await.unwrap();
+    assert_eq!(result["content"][0]["text"], "Echo: second");
+}
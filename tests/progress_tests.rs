@@ -0,0 +1,143 @@
+//! Integration tests for tool call progress reporting
+//!
+//! Runs the mock MCP server with MOCK_PROGRESS set, so it sends
+//! `notifications/progress` for the request's progress token before each
+//! tools/call response, and checks that every client path surfaces them.
+
+use mcp_cli_rs::cli::daemon_lifecycle::DirectProtocolClient;
+use mcp_cli_rs::config::Config;
+use mcp_cli_rs::daemon::pool::ConnectionPool;
+use mcp_cli_rs::daemon::protocol::ProgressUpdate;
+use mcp_cli_rs::ipc::ProtocolClient;
+use serde_json::json;
+use std::sync::Arc;
+
+mod fixtures {
+    pub mod daemon_test_helper;
+}
+
+mod common {
+    pub mod mock_server;
+}
+
+use common::mock_server::{MOCK_SERVER, mock_server_config};
+
+/// Build a config with a single mock server reporting three progress steps
+fn progress_config() -> Config {
+    Config {
+        servers: vec![mock_server_config("mock", &[("MOCK_PROGRESS", "3")])],
+        ..Config::default()
+    }
+}
+
+fn assert_three_steps(updates: &[ProgressUpdate]) {
+    let progress: Vec<f64> = updates.iter().map(|u| u.progress).collect();
+    assert_eq!(progress, vec![1.0, 2.0, 3.0]);
+    assert!(updates.iter().all(|u| u.total == Some(3.0)));
+    assert_eq!(updates[2].message.as_deref(), Some("step 3"));
+}
+
+#[tokio::test]
+async fn test_pool_reports_progress() {
+    let pool = ConnectionPool::new(Arc::new(progress_config()));
+
+    let mut updates = Vec::new();
+    let result = pool
+        .execute_with_progress("mock", "echo", json!({ "message": "hi" }), &mut |u| {
+            updates.push(u)
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(result["content"][0]["text"], "Echo: hi");
+    assert_three_steps(&updates);
+}
+
+#[tokio::test]
+async fn test_direct_client_reports_progress() {
+    let mut client = DirectProtocolClient::new(Arc::new(progress_config()));
+
+    let mut updates = Vec::new();
+    let result = client
+        .execute_tool_with_progress("mock", "echo", json!({ "message": "hi" }), None, &mut |u| {
+            updates.push(u)
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(result["content"][0]["text"], "Echo: hi");
+    assert_three_steps(&updates);
+}
+
+#[tokio::test]
+async fn test_daemon_relays_progress() {
+    let daemon = fixtures::daemon_test_helper::spawn_test_daemon(progress_config())
+        .await
+        .unwrap();
+    let mut client = daemon.client().unwrap();
+
+    let mut updates = Vec::new();
+    let result = client
+        .execute_tool_with_progress("mock", "echo", json!({ "message": "hi" }), None, &mut |u| {
+            updates.push(u)
+        })
+        .await
+        .unwrap();
+    assert_eq!(result["content"][0]["text"], "Echo: hi");
+    assert_three_steps(&updates);
+
+    // Clients that don't ask for progress still get just the result
+    let result = client
+        .execute_tool("mock", "echo", json!({ "message": "again" }))
+        .await
+        .unwrap();
+    assert_eq!(result["content"][0]["text"], "Echo: again");
+
+    daemon.shutdown().await.unwrap();
+}
+
+#[test]
+fn test_cli_json_mode_writes_progress_events_to_stderr() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let config_path = dir.path().join("mcp_servers.toml");
+    let config = format!(
+        "[[servers]]\nname = \"mock\"\n\n[servers.transport]\ntype = \"stdio\"\ncommand = '{}'\nenv = {{ MOCK_PROGRESS = \"2\" }}\n",
+        MOCK_SERVER
+    );
+    std::fs::write(&config_path, config).unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_mcp-cli-rs"))
+        .arg("--config")
+        .arg(&config_path)
+        .args([
+            "--no-daemon",
+            "--json",
+            "call",
+            "mock/echo",
+            "--",
+            "--message",
+            "hi",
+        ])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // stdout carries only the result document
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["success"], true);
+
+    let events: Vec<serde_json::Value> = String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .filter(|event: &serde_json::Value| event["event"] == "progress")
+        .collect();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[1]["server_name"], "mock");
+    assert_eq!(events[1]["tool_name"], "echo");
+    assert_eq!(events[1]["progress"], 2.0);
+    assert_eq!(events[1]["total"], 2.0);
+}
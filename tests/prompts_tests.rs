@@ -0,0 +1,82 @@
+//! Integration tests for MCP prompts support
+//!
+//! Exercises prompts/list and prompts/get against the mock MCP server, both
+//! through the direct (no-daemon) client and through the daemon connection pool.
+
+use mcp_cli_rs::cli::daemon_lifecycle::DirectProtocolClient;
+use mcp_cli_rs::daemon::pool::ConnectionPool;
+use mcp_cli_rs::ipc::ProtocolClient;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+mod common {
+    pub mod mock_server;
+}
+
+use common::mock_server::single_server_config;
+
+#[tokio::test]
+async fn test_direct_list_prompts() {
+    let mut client = DirectProtocolClient::new(Arc::new(single_server_config("mock")));
+
+    let prompts = client.list_prompts("mock").await.unwrap();
+
+    assert_eq!(prompts.len(), 1);
+    assert_eq!(prompts[0].name, "greeting");
+    assert_eq!(prompts[0].arguments.len(), 2);
+    assert!(prompts[0].arguments[0].required);
+}
+
+#[tokio::test]
+async fn test_direct_get_prompt_renders_messages() {
+    let mut client = DirectProtocolClient::new(Arc::new(single_server_config("mock")));
+    let arguments = HashMap::from([("name".to_string(), "Ada".to_string())]);
+
+    let prompt = client
+        .get_prompt("mock", "greeting", arguments)
+        .await
+        .unwrap();
+
+    assert_eq!(prompt.description.as_deref(), Some("Greeting prompt"));
+    assert_eq!(prompt.messages.len(), 2);
+    assert_eq!(prompt.messages[0].role, "user");
+    assert_eq!(prompt.messages[1].role, "assistant");
+    assert_eq!(prompt.messages[1].content["text"], "Hello, Ada!");
+}
+
+#[tokio::test]
+async fn test_direct_get_prompt_missing_argument_fails() {
+    let mut client = DirectProtocolClient::new(Arc::new(single_server_config("mock")));
+
+    let result = client.get_prompt("mock", "greeting", HashMap::new()).await;
+
+    assert!(result.is_err());
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("Missing required argument")
+    );
+}
+
+#[tokio::test]
+async fn test_pool_get_prompt() {
+    let pool = ConnectionPool::new(Arc::new(single_server_config("mock")));
+    let arguments = HashMap::from([
+        ("name".to_string(), "Grace".to_string()),
+        ("tone".to_string(), "formal".to_string()),
+    ]);
+
+    let prompt = pool
+        .get_prompt("mock", "greeting", &arguments)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        prompt.messages[0].content["text"],
+        "Say hello to Grace in a formal tone"
+    );
+
+    let prompts = pool.list_prompts("mock").await.unwrap();
+    assert_eq!(prompts.len(), 1);
+}
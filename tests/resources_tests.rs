@@ -0,0 +1,87 @@
+//! Integration tests for MCP resources support
+//!
+//! Exercises resources/list, resources/templates/list and resources/read
+//! against the mock MCP server, both through the direct (no-daemon) client
+//! and through the daemon connection pool.
+
+use mcp_cli_rs::cli::daemon_lifecycle::DirectProtocolClient;
+use mcp_cli_rs::daemon::pool::ConnectionPool;
+use mcp_cli_rs::ipc::ProtocolClient;
+use std::sync::Arc;
+
+mod common {
+    pub mod mock_server;
+}
+
+use common::mock_server::single_server_config;
+
+#[tokio::test]
+async fn test_direct_list_resources() {
+    let mut client = DirectProtocolClient::new(Arc::new(single_server_config("mock")));
+
+    let resources = client.list_resources("mock").await.unwrap();
+
+    assert_eq!(resources.len(), 2);
+    assert_eq!(resources[0].uri, "file:///mock/readme.txt");
+    assert_eq!(resources[0].mime_type.as_deref(), Some("text/plain"));
+}
+
+#[tokio::test]
+async fn test_direct_list_resource_templates() {
+    let mut client = DirectProtocolClient::new(Arc::new(single_server_config("mock")));
+
+    let templates = client.list_resource_templates("mock").await.unwrap();
+
+    assert_eq!(templates.len(), 1);
+    assert_eq!(templates[0].uri_template, "file:///mock/{path}");
+}
+
+#[tokio::test]
+async fn test_direct_read_text_resource() {
+    let mut client = DirectProtocolClient::new(Arc::new(single_server_config("mock")));
+
+    let contents = client
+        .read_resource("mock", "file:///mock/readme.txt")
+        .await
+        .unwrap();
+
+    assert_eq!(contents.len(), 1);
+    assert_eq!(
+        contents[0].text.as_deref(),
+        Some("Hello from the mock server")
+    );
+    assert!(contents[0].blob.is_none());
+}
+
+#[tokio::test]
+async fn test_direct_read_unknown_resource_fails() {
+    let mut client = DirectProtocolClient::new(Arc::new(single_server_config("mock")));
+
+    let result = client.read_resource("mock", "file:///mock/missing").await;
+
+    assert!(result.is_err());
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("Resource not found")
+    );
+}
+
+#[tokio::test]
+async fn test_pool_read_blob_resource() {
+    let pool = ConnectionPool::new(Arc::new(single_server_config("mock")));
+
+    let contents = pool
+        .read_resource("mock", "file:///mock/logo.bin")
+        .await
+        .unwrap();
+
+    assert_eq!(contents.len(), 1);
+    assert_eq!(contents[0].blob.as_deref(), Some("AAEC/w=="));
+
+    // Connection is returned to the pool and reusable for a second request
+    let resources = pool.list_resources("mock").await.unwrap();
+    assert_eq!(resources.len(), 2);
+    assert_eq!(pool.count(), 1);
+}
@@ -7,13 +7,13 @@
 
 mod fixtures;
 
+use fixtures::spawn_failing_server;
+use futures_util::FutureExt;
 use mcp_cli_rs::error::McpError;
 use mcp_cli_rs::retry::{RetryConfig, retry_with_backoff};
-use fixtures::spawn_failing_server;
-use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::{Duration, Instant};
-use futures_util::FutureExt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
 
 /// TEST-06: Verify exponential backoff produces measurable delays
 ///
@@ -52,7 +52,7 @@ async fn test_exponential_backoff() {
                 .await
                 .map_err(|e| McpError::ConnectionError {
                     server: url.clone(),
-                    source: std::io::Error::new(std::io::ErrorKind::Other, e),
+                    source: std::io::Error::other(e),
                 })?;
 
             // Check response status
@@ -76,10 +76,7 @@ async fn test_exponential_backoff() {
             if body.get("error").is_some() {
                 return Err(McpError::ConnectionError {
                     server: url.clone(),
-                    source: std::io::Error::new(
-                        std::io::ErrorKind::Other,
-                        "Server returned error",
-                    ),
+                    source: std::io::Error::other("Server returned error"),
                 });
             }
 
@@ -228,7 +225,7 @@ async fn test_retry_delay_increases() {
                 .await
                 .map_err(|e| McpError::ConnectionError {
                     server: url.clone(),
-                    source: std::io::Error::new(std::io::ErrorKind::Other, e),
+                    source: std::io::Error::other(e),
                 })?;
 
             let status = response.status();
@@ -305,7 +302,7 @@ async fn test_retry_delay_increases() {
 
     // First delay should be around base_delay_ms (100ms) plus operation time
     assert!(
-        first_delay_ms >= 80 && first_delay_ms <= 300,
+        (80..=300).contains(&first_delay_ms),
         "First delay should be in range [80ms, 300ms] (base: {}ms)",
         config.base_delay_ms
     );
@@ -91,6 +91,7 @@ async fn spawn_mock_server_with_delay(
 }
 
 /// Spawn the mock MCP server with custom configuration (no delay)
+#[allow(dead_code)]
 async fn spawn_mock_server(
     tools: Vec<ToolDefinition>,
     responses: HashMap<String, MockResponse>,
@@ -0,0 +1,76 @@
+//! Integration tests for `mcp shell`
+//!
+//! Pipes a session into the shell against the mock MCP server. Without a
+//! terminal the shell reads plain lines, so the same commands a person would
+//! type can be scripted.
+
+use std::io::Write;
+use std::path::Path;
+use std::process::{Output, Stdio};
+
+const MOCK_SERVER: &str = env!("CARGO_BIN_EXE_mock-mcp-server");
+
+/// Run `mcp --no-daemon shell` with `input` on stdin
+fn run_shell(dir: &Path, input: &str) -> Output {
+    let config_path = dir.join("mcp_servers.toml");
+    let config = format!(
+        "[[servers]]\nname = \"mock\"\n\n[servers.transport]\ntype = \"stdio\"\ncommand = '{}'\n",
+        MOCK_SERVER
+    );
+    std::fs::write(&config_path, config).unwrap();
+
+    let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_mcp-cli-rs"))
+        .arg("--config")
+        .arg(&config_path)
+        .args(["--no-daemon", "shell"])
+        .env("XDG_CACHE_HOME", dir.join("cache"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn test_shell_runs_commands_with_default_server() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let output = run_shell(
+        dir.path(),
+        "search ech*\nuse mock\ncall echo -- --message 'hello shell'\nexit\n",
+    );
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("echo"), "{}", stdout);
+    assert!(stdout.contains("hello shell"), "{}", stdout);
+}
+
+#[test]
+fn test_shell_keeps_going_after_errors() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let output = run_shell(
+        dir.path(),
+        "use nowhere\nbogus\ndaemon\ncall mock/echo -- --message after\n",
+    );
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Server 'nowhere' not found"), "{}", stderr);
+    assert!(
+        stderr.contains("'daemon' is not available in the shell"),
+        "{}",
+        stderr
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("after"));
+}
@@ -0,0 +1,93 @@
+//! Integration tests for `mcp snapshot` and `mcp diff`
+
+use serde_json::{Value, json};
+use std::path::Path;
+
+mod common {
+    pub mod mock_server;
+}
+
+use common::mock_server::run_mcp;
+
+/// Mock server settings: its `fail` tool disabled
+const SERVER_TOML: &str = "disabled_tools = [\"fail\"]";
+
+/// Take a snapshot, save it as `tools.lock.json` and return it
+fn snapshot(dir: &Path) -> Value {
+    let output = run_mcp(dir, SERVER_TOML, &["snapshot"]);
+    assert!(output.status.success());
+    std::fs::write(dir.join("tools.lock.json"), &output.stdout).unwrap();
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_snapshot_then_unchanged_diff() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let lock = snapshot(dir.path());
+    assert_eq!(lock["version"], 1);
+    let tools = lock["servers"]["mock"].as_object().unwrap();
+    assert_eq!(tools.keys().collect::<Vec<_>>(), vec!["add", "echo"]);
+    assert_eq!(tools["echo"]["inputSchema"]["required"], json!(["message"]));
+
+    let lock_path = dir.path().join("tools.lock.json");
+    let output = run_mcp(
+        dir.path(),
+        SERVER_TOML,
+        &["diff", lock_path.to_str().unwrap()],
+    );
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("No changes"));
+}
+
+#[test]
+fn test_diff_reports_breaking_changes() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let mut lock = snapshot(dir.path());
+    // Pretend `message` used to be optional and a tool has since been removed
+    lock["servers"]["mock"]["echo"]["inputSchema"]["required"] = json!([]);
+    lock["servers"]["mock"]["gone"] = json!({ "inputSchema": { "type": "object" } });
+    let lock_path = dir.path().join("tools.lock.json");
+    std::fs::write(&lock_path, lock.to_string()).unwrap();
+
+    let output = run_mcp(
+        dir.path(),
+        SERVER_TOML,
+        &["--json", "diff", lock_path.to_str().unwrap()],
+    );
+    assert_eq!(output.status.code(), Some(4));
+
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["breaking"], 2);
+    assert_eq!(
+        report["changes"],
+        json!([
+            {
+                "server": "mock",
+                "tool": "echo",
+                "path": "message",
+                "breaking": true,
+                "message": "property became required"
+            },
+            {
+                "server": "mock",
+                "tool": "gone",
+                "breaking": true,
+                "message": "tool removed"
+            }
+        ])
+    );
+}
+
+#[test]
+fn test_diff_rejects_non_snapshot_file() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let lock_path = dir.path().join("tools.lock.json");
+    std::fs::write(&lock_path, "[]").unwrap();
+    let output = run_mcp(
+        dir.path(),
+        SERVER_TOML,
+        &["diff", lock_path.to_str().unwrap()],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("is not a tool snapshot"));
+}
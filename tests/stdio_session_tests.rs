@@ -0,0 +1,90 @@
+//! Integration tests for JSON-RPC message demultiplexing on stdio
+//!
+//! Runs the mock MCP server in MOCK_NOISY mode, where every response is
+//! preceded by a log line, a notification and a server→client ping request,
+//! and checks that responses are still matched to the right requests.
+
+use mcp_cli_rs::client::McpClient;
+use mcp_cli_rs::client::stdio::StdioTransport;
+use mcp_cli_rs::transport::Transport;
+use std::collections::HashMap;
+
+const MOCK_SERVER: &str = env!("CARGO_BIN_EXE_mock-mcp-server");
+
+fn noisy_transport() -> StdioTransport {
+    let env = HashMap::from([("MOCK_NOISY".to_string(), "1".to_string())]);
+    StdioTransport::new(MOCK_SERVER, &[], &env, None).unwrap()
+}
+
+fn initialize_request() -> serde_json::Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 0,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2024-11-05",
+            "capabilities": {},
+            "clientInfo": { "name": "test", "version": "0.0.0" }
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_noisy_server_responses_are_matched() {
+    let transport = noisy_transport();
+    let mut client = McpClient::new("mock".to_string(), Box::new(transport));
+
+    let tools = client.list_tools().await.unwrap();
+    assert_eq!(tools.len(), 3);
+
+    let result = client
+        .call_tool("echo", serde_json::json!({ "message": "hi" }))
+        .await
+        .unwrap();
+    assert!(result.get("content").is_some());
+}
+
+#[tokio::test]
+async fn test_notifications_are_delivered_separately() {
+    let mut transport = noisy_transport();
+
+    let response = transport.send(initialize_request()).await.unwrap();
+    assert_eq!(response["id"], 0);
+    assert!(response.get("result").is_some());
+
+    let notification = transport.receive_notification().await.unwrap();
+    assert_eq!(notification["method"], "notifications/message");
+}
+
+#[tokio::test]
+async fn test_concurrent_requests_on_one_connection() {
+    let mut transport = noisy_transport();
+    transport.send(initialize_request()).await.unwrap();
+
+    let session = transport.session();
+    let calls = (0..8).map(|i| {
+        let session = session.clone();
+        async move {
+            let response = session
+                .request(
+                    "tools/call",
+                    serde_json::json!({ "name": "echo", "arguments": { "message": format!("call-{}", i) } }),
+                )
+                .await
+                .unwrap();
+            (i, response)
+        }
+    });
+
+    for (i, response) in futures::future::join_all(calls).await {
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        assert_eq!(text, format!("Echo: call-{}", i));
+    }
+    assert_eq!(session.pending_count(), 0);
+}
+
+#[tokio::test]
+async fn test_stdio_ping() {
+    let transport = noisy_transport();
+    transport.ping().await.unwrap();
+}
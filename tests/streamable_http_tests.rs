@@ -0,0 +1,182 @@
+//! Integration tests for the Streamable HTTP transport
+//!
+//! Runs `HttpTransport` against an in-process server that assigns session
+//! IDs, answers some requests over SSE, interrupts streams to force
+//! `Last-Event-ID` resumption and offers a GET stream for server-initiated
+//! messages.
+
+use mcp_cli_rs::client::McpClient;
+use mcp_cli_rs::client::http::HttpTransport;
+use mcp_cli_rs::transport::Transport;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+mod fixtures;
+use fixtures::mock_streamable_http_server::{MockStreamableHttpServer, PROTOCOL_VERSION};
+
+fn request(id: u64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+/// Connect and initialize, returning a transport with an active session
+async fn initialized_transport(url: &str) -> HttpTransport {
+    let mut transport = HttpTransport::new(url, HashMap::new());
+    let params = json!({
+        "protocolVersion": PROTOCOL_VERSION,
+        "capabilities": {},
+        "clientInfo": { "name": "test", "version": "0.0.0" }
+    });
+    let response = transport
+        .send(request(0, "initialize", params))
+        .await
+        .unwrap();
+    assert!(response.get("result").is_some());
+    transport
+        .send_notification(json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))
+        .await
+        .unwrap();
+    transport
+}
+
+#[tokio::test]
+async fn test_session_id_and_protocol_version_are_sent() {
+    let (server, url) = MockStreamableHttpServer::start().await;
+    let mut transport = initialized_transport(&url).await;
+    assert_eq!(transport.session_id().as_deref(), Some("session-1"));
+
+    let response = transport
+        .send(request(
+            1,
+            "tools/call",
+            json!({ "name": "echo", "arguments": { "message": "hi" } }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response["result"]["content"][0]["text"], "Echo: hi");
+
+    // Without the session header the server would have answered 400
+    let requests = server.requests();
+    assert_eq!(requests[0], ("initialize".to_string(), None));
+    assert!(
+        requests[1..]
+            .iter()
+            .all(|(_, version)| version.as_deref() == Some(PROTOCOL_VERSION))
+    );
+    server.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_sse_response_with_interleaved_messages() {
+    let (server, url) = MockStreamableHttpServer::start().await;
+    let mut client = McpClient::new(
+        "mock".to_string(),
+        Box::new(HttpTransport::new(&url, HashMap::new())),
+    );
+
+    let tools = client.list_tools().await.unwrap();
+    assert_eq!(tools.len(), 1);
+    assert_eq!(tools[0].name, "echo");
+
+    // The server's ping request on the stream was answered by POST
+    let responses = server.client_responses();
+    assert_eq!(responses.len(), 1);
+    assert_eq!(responses[0]["id"], "server-ping-1");
+    assert_eq!(responses[0]["result"], json!({}));
+    server.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_stream_notifications_are_queued() {
+    let (server, url) = MockStreamableHttpServer::start().await;
+    let mut transport = initialized_transport(&url).await;
+
+    transport
+        .send(request(1, "tools/list", json!({})))
+        .await
+        .unwrap();
+
+    // One notification arrives on the response stream, one on the GET stream
+    let mut methods = vec![
+        transport.receive_notification().await.unwrap()["method"].clone(),
+        transport.receive_notification().await.unwrap()["method"].clone(),
+    ];
+    methods.sort_by_key(|m| m.to_string());
+    assert_eq!(
+        methods,
+        vec!["notifications/message", "notifications/tools/list_changed"]
+    );
+    server.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_get_stream_delivers_server_notifications() {
+    let (server, url) = MockStreamableHttpServer::start().await;
+    let mut transport = initialized_transport(&url).await;
+
+    let notification = transport.receive_notification().await.unwrap();
+    assert_eq!(notification["method"], "notifications/tools/list_changed");
+    server.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_interrupted_stream_is_resumed() {
+    let (server, url) = MockStreamableHttpServer::start().await;
+    let mut transport = initialized_transport(&url).await;
+
+    let response = transport
+        .send(request(
+            7,
+            "tools/call",
+            json!({ "name": "resume", "arguments": {} }),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response["id"], 7);
+    assert_eq!(response["result"]["content"][0]["text"], "resumed");
+    server.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_ping_over_http() {
+    let (server, url) = MockStreamableHttpServer::start().await;
+    let transport = initialized_transport(&url).await;
+
+    transport.ping().await.unwrap();
+    server.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_close_terminates_session() {
+    let (server, url) = MockStreamableHttpServer::start().await;
+    let mut transport = initialized_transport(&url).await;
+
+    transport.close().await.unwrap();
+    assert_eq!(server.deleted_sessions(), 1);
+    assert!(transport.session_id().is_none());
+
+    // Closing twice doesn't send another DELETE
+    transport.close().await.unwrap();
+    assert_eq!(server.deleted_sessions(), 1);
+    server.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_expired_session_is_reported() {
+    let (server, url) = MockStreamableHttpServer::start().await;
+    let mut transport = initialized_transport(&url).await;
+
+    server.expire_sessions();
+    let err = transport
+        .send(request(1, "tools/list", json!({})))
+        .await
+        .unwrap_err();
+
+    assert!(
+        err.to_string().contains("session expired"),
+        "unexpected error: {}",
+        err
+    );
+    assert!(transport.session_id().is_none());
+    server.shutdown().await;
+}
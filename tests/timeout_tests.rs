@@ -0,0 +1,202 @@
+//! Integration tests for tool call timeouts
+//!
+//! Runs the mock MCP server with a long MOCK_DELAY_MS so tool calls outlive
+//! their timeout. The server logs each call stopped by
+//! `notifications/cancelled` to MOCK_CANCEL_LOG.
+
+use mcp_cli_rs::cli::daemon_lifecycle::DirectProtocolClient;
+use mcp_cli_rs::config::{Config, ServerConfig};
+use mcp_cli_rs::daemon::lifecycle::DaemonLifecycle;
+use mcp_cli_rs::daemon::pool::ConnectionPool;
+use mcp_cli_rs::error::McpError;
+use mcp_cli_rs::ipc::ProtocolClient;
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+mod fixtures {
+    pub mod daemon_test_helper;
+}
+
+mod common {
+    pub mod mock_server;
+}
+
+use common::mock_server::{MOCK_SERVER, mock_server_config};
+
+/// Build a config with a single mock server whose tool calls take a minute
+fn slow_config(cancel_log: &Path, timeout_secs: Option<u64>) -> Config {
+    Config {
+        servers: vec![ServerConfig {
+            timeout_secs,
+            ..mock_server_config(
+                "mock",
+                &[
+                    ("MOCK_DELAY_MS", "60000"),
+                    ("MOCK_CANCEL_LOG", cancel_log.to_str().unwrap()),
+                ],
+            )
+        }],
+        ..Config::default()
+    }
+}
+
+/// Wait until the mock server has logged a cancelled call
+async fn wait_for_cancellation(cancel_log: &Path) {
+    for _ in 0..100 {
+        if std::fs::read_to_string(cancel_log).is_ok_and(|log| !log.is_empty()) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("server did not record a cancelled call");
+}
+
+#[tokio::test]
+async fn test_pool_enforces_server_timeout() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let cancel_log = dir.path().join("cancelled.log");
+    let pool = ConnectionPool::new(Arc::new(slow_config(&cancel_log, Some(1))));
+
+    let result = pool
+        .execute("mock", "echo", json!({ "message": "hi" }))
+        .await;
+
+    assert!(matches!(result, Err(McpError::Timeout { timeout: 1 })));
+    wait_for_cancellation(&cancel_log).await;
+
+    // The connection went back to the pool and the server is free again
+    assert_eq!(pool.count(), 1);
+    let tools = tokio::time::timeout(Duration::from_secs(5), pool.list_tools("mock"))
+        .await
+        .expect("server still busy with the timed-out call")
+        .unwrap();
+    assert!(!tools.is_empty());
+}
+
+#[tokio::test]
+async fn test_daemon_enforces_call_timeout() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let cancel_log = dir.path().join("cancelled.log");
+    let daemon = fixtures::daemon_test_helper::spawn_test_daemon(slow_config(&cancel_log, None))
+        .await
+        .unwrap();
+    let mut client = daemon.client().unwrap();
+
+    let result = tokio::time::timeout(
+        Duration::from_secs(10),
+        client.execute_tool_with_progress(
+            "mock",
+            "echo",
+            json!({ "message": "hi" }),
+            Some(1),
+            &mut |_| {},
+        ),
+    )
+    .await
+    .expect("daemon ignored the call timeout");
+
+    assert!(matches!(result, Err(McpError::Timeout { timeout: 1 })));
+    wait_for_cancellation(&cancel_log).await;
+
+    daemon.shutdown().await.unwrap();
+}
+
+#[test]
+fn test_cli_timeout_flag_reports_real_value() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let config_path = dir.path().join("mcp_servers.toml");
+    let config = format!(
+        "[[servers]]\nname = \"mock\"\ntimeout_secs = 600\n\n[servers.transport]\ntype = \"stdio\"\ncommand = '{}'\nenv = {{ MOCK_DELAY_MS = \"60000\" }}\n",
+        MOCK_SERVER
+    );
+    std::fs::write(&config_path, config).unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_mcp-cli-rs"))
+        .arg("--config")
+        .arg(&config_path)
+        .args([
+            "--no-daemon",
+            "--json",
+            "call",
+            "mock/echo",
+            "--timeout",
+            "1",
+        ])
+        .args(["--", "--message", "hi"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(3));
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"], "Tool execution timed out after 1s");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("(1s timeout)"));
+}
+
+/// Build a config with a mock server whose tools/list outlasts its 1s
+/// tool-call timeout
+fn slow_list_config() -> Config {
+    Config {
+        servers: vec![ServerConfig {
+            timeout_secs: Some(1),
+            ..mock_server_config("mock", &[("MOCK_LIST_DELAY_MS", "1500")])
+        }],
+        ..Config::default()
+    }
+}
+
+#[tokio::test]
+async fn test_pool_server_timeout_applies_only_to_tool_calls() {
+    let pool = ConnectionPool::new(Arc::new(slow_list_config()));
+
+    let tools = pool.list_tools("mock").await.unwrap();
+    assert!(!tools.is_empty());
+
+    // A tool call in between doesn't leave its timeout on the connection
+    pool.execute("mock", "echo", json!({ "message": "hi" }))
+        .await
+        .unwrap();
+    assert!(pool.list_tools("mock").await.is_ok());
+    assert_eq!(pool.count(), 1);
+}
+
+#[tokio::test]
+async fn test_direct_server_timeout_applies_only_to_tool_calls() {
+    let mut client = DirectProtocolClient::new(Arc::new(slow_list_config()));
+
+    let tools = client.list_tools("mock").await.unwrap();
+
+    assert!(!tools.is_empty());
+}
+
+#[tokio::test]
+async fn test_daemon_stays_up_while_a_call_outlasts_its_ttl() {
+    let config = Config {
+        servers: vec![mock_server_config("mock", &[("MOCK_DELAY_MS", "4000")])],
+        ..Config::default()
+    };
+    let daemon = fixtures::daemon_test_helper::spawn_test_daemon_with_lifecycle(
+        config,
+        DaemonLifecycle::new(1),
+    )
+    .await
+    .unwrap();
+    let mut client = daemon.client().unwrap();
+    let call = tokio::spawn(async move {
+        client
+            .execute_tool("mock", "echo", json!({ "message": "still here" }))
+            .await
+    });
+
+    // Well past the daemon's TTL, the running call keeps it up
+    tokio::time::sleep(Duration::from_millis(2500)).await;
+    let mut other = daemon.client().unwrap();
+    assert!(other.list_servers().await.is_ok());
+
+    let result = call.await.unwrap().unwrap();
+    assert!(result.to_string().contains("still here"));
+
+    daemon.shutdown().await.unwrap();
+}
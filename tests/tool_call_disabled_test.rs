@@ -25,6 +25,7 @@ mod tests {
                 description: None,
                 allowed_tools: None,
                 disabled_tools: Some(vec!["password_*".to_string()]),
+                timeout_secs: None,
             }],
             ..Config::default()
         };
@@ -56,6 +57,7 @@ mod tests {
                 description: None,
                 allowed_tools: Some(vec!["list_*".to_string(), "search_*".to_string()]),
                 disabled_tools: None,
+                timeout_secs: None,
             }],
             ..Config::default()
         };
@@ -86,6 +88,7 @@ mod tests {
                 description: None,
                 allowed_tools: Some(vec!["*".to_string()]), // Allow all tools
                 disabled_tools: Some(vec!["password_*".to_string(), "sudo_*".to_string()]),
+                timeout_secs: None,
             }],
             ..Config::default()
         };
@@ -126,6 +129,7 @@ mod tests {
                 description: None,
                 allowed_tools: None,
                 disabled_tools: Some(vec!["sensitive_*".to_string(), "password_*".to_string()]),
+                timeout_secs: None,
             }],
             ..Config::default()
         };
@@ -8,7 +8,6 @@
 
 use mcp_cli_rs::client::McpClient;
 use mcp_cli_rs::client::http::HttpTransport;
-use serde_json::Value;
 use std::collections::HashMap;
 use std::future::Future;
 
@@ -24,6 +24,7 @@ mod tests {
                 description: None,
                 allowed_tools: None,
                 disabled_tools: Some(vec!["password_*".to_string(), "sudo_*".to_string()]),
+                timeout_secs: None,
             }],
             ..Config::default()
         };
@@ -80,6 +81,7 @@ mod tests {
                 description: None,
                 allowed_tools: Some(vec!["list_*".to_string(), "search_*".to_string()]),
                 disabled_tools: None,
+                timeout_secs: None,
             }],
             ..Config::default()
         };
@@ -136,6 +138,7 @@ mod tests {
                 description: None,
                 allowed_tools: Some(vec!["*".to_string()]), // Allow all
                 disabled_tools: Some(vec!["password_*".to_string()]), // But block password tools
+                timeout_secs: None,
             }],
             ..Config::default()
         };
@@ -191,6 +194,7 @@ mod tests {
                 description: None,
                 allowed_tools: None,
                 disabled_tools: None,
+                timeout_secs: None,
             }],
             ..Config::default()
         };
@@ -588,7 +588,7 @@ async fn test_filter_by_description() {
     // Filter by description containing "file"
     let file_related: Vec<_> = all_tools
         .iter()
-        .filter(|t| t.description.as_ref().map_or(false, |d| d.contains("file")))
+        .filter(|t| t.description.as_ref().is_some_and(|d| d.contains("file")))
         .collect();
 
     println!("File-related tools: {:?}", file_related);
@@ -599,7 +599,7 @@ async fn test_filter_by_description() {
     // Filter by description containing "HTTP"
     let http_related: Vec<_> = all_tools
         .iter()
-        .filter(|t| t.description.as_ref().map_or(false, |d| d.contains("HTTP")))
+        .filter(|t| t.description.as_ref().is_some_and(|d| d.contains("HTTP")))
         .collect();
 
     assert_eq!(http_related.len(), 1);
@@ -2,9 +2,7 @@
 //!
 //! XP-04: Validates Unix socket (Linux/macOS) IPC implementation
 
-use crate::helpers;
 use mcp_cli_rs::daemon::protocol::{DaemonRequest, DaemonResponse};
-use mcp_cli_rs::ipc;
 use std::time::Duration;
 use tokio::time::timeout;
 
@@ -50,13 +48,14 @@ async fn test_unix_socket_multiple_concurrent_connections() {
     let socket_path = crate::helpers::get_test_socket_path();
 
     // Create IPC server
-    let mut server =
-        mcp_cli_rs::ipc::create_ipc_server(&socket_path).await.expect("Failed to create IPC server");
+    let server = mcp_cli_rs::ipc::create_ipc_server(&socket_path)
+        .await
+        .expect("Failed to create IPC server");
 
     // Spawn server task handling 3 concurrent connections
     let server_handle = tokio::spawn(async move {
-        for i in 0..3 {
-            let (mut stream, _addr) = match timeout(Duration::from_secs(5), server.accept()).await {
+        for _ in 0..3 {
+            let (stream, _addr) = match timeout(Duration::from_secs(5), server.accept()).await {
                 Ok(result) => match result {
                     Ok(stream) => stream,
                     Err(e) => panic!("Server accept failed: {}", e),
@@ -66,9 +65,7 @@ async fn test_unix_socket_multiple_concurrent_connections() {
 
             // Read request - wrap in BufReader for AsyncBufRead requirement
             let mut buf_reader = tokio::io::BufReader::new(stream);
-            let request = mcp_cli_rs::daemon::protocol::receive_request(&mut buf_reader)
-                .await
-                .expect("Failed to receive request");
+            let (id, request) = crate::helpers::receive_first_request(&mut buf_reader).await;
 
             // Verify Ping request
             assert!(
@@ -79,9 +76,7 @@ async fn test_unix_socket_multiple_concurrent_connections() {
 
             // Send response
             let response = DaemonResponse::Pong;
-            mcp_cli_rs::daemon::protocol::send_response(&mut buf_reader, &response)
-                .await
-                .expect("Failed to send response");
+            crate::helpers::send_response_frame(&mut buf_reader, id, response).await;
             }
     });
 
@@ -90,8 +85,8 @@ async fn test_unix_socket_multiple_concurrent_connections() {
     for _ in 0..3 {
         let config = crate::helpers::create_test_config_with_socket(socket_path.clone());
         let handle = tokio::spawn(async move {
-            let mut client = mcp_cli_rs::ipc::create_ipc_client(&*config)
-                .expect("Failed to create IPC client");
+            let mut client =
+                mcp_cli_rs::ipc::create_ipc_client(&config).expect("Failed to create IPC client");
             let request = DaemonRequest::Ping;
             let response = client
                 .send_request(&request)
@@ -123,8 +118,9 @@ async fn test_unix_socket_large_message_transfer() {
     let socket_path = crate::helpers::get_test_socket_path();
 
     // Create IPC server
-    let mut server =
-        mcp_cli_rs::ipc::create_ipc_server(&socket_path).await.expect("Failed to create IPC server");
+    let server = mcp_cli_rs::ipc::create_ipc_server(&socket_path)
+        .await
+        .expect("Failed to create IPC server");
 
     // Create large JSON object (100KB text as in plan)
     let large_content = serde_json::json!({
@@ -135,7 +131,7 @@ async fn test_unix_socket_large_message_transfer() {
 
     // Spawn server task
     let server_handle = tokio::spawn(async move {
-        let (mut stream, _addr) = match timeout(Duration::from_secs(10), server.accept()).await {
+        let (stream, _addr) = match timeout(Duration::from_secs(10), server.accept()).await {
             Ok(result) => match result {
                 Ok(stream) => stream,
                 Err(e) => panic!("Server accept failed: {}", e),
@@ -145,21 +141,17 @@ async fn test_unix_socket_large_message_transfer() {
 
         // Read request - wrap in BufReader for AsyncBufRead requirement
         let mut buf_reader = tokio::io::BufReader::new(stream);
-        let _request = mcp_cli_rs::daemon::protocol::receive_request(&mut buf_reader)
-            .await
-            .expect("Failed to receive request");
+        let (id, _request) = crate::helpers::receive_first_request(&mut buf_reader).await;
 
         // Send large response
         let response = DaemonResponse::ToolResult(server_content);
-        mcp_cli_rs::daemon::protocol::send_response(&mut buf_reader, &response)
-            .await
-            .expect("Failed to send response");
+        crate::helpers::send_response_frame(&mut buf_reader, id, response).await;
     });
 
     // Create IPC client using the same socket path as the server
     let config = crate::helpers::create_test_config_with_socket(socket_path.clone());
-    let mut client = mcp_cli_rs::ipc::create_ipc_client(&*config)
-        .expect("Failed to create IPC client");
+    let mut client =
+        mcp_cli_rs::ipc::create_ipc_client(&config).expect("Failed to create IPC client");
 
     // Send ping request
     let request = DaemonRequest::Ping;
@@ -82,9 +82,7 @@ async fn test_windows_named_pipe_multiple_concurrent_connections() {
 
             // Read request - wrap in BufReader for AsyncBufRead requirement
             let mut buf_reader = tokio::io::BufReader::new(server_instance);
-            let request = mcp_cli_rs::daemon::protocol::receive_request(&mut buf_reader)
-                .await
-                .expect("Failed to receive request");
+            let (id, request) = crate::helpers::receive_first_request(&mut buf_reader).await;
 
             // Verify Ping request
             assert!(
@@ -95,9 +93,7 @@ async fn test_windows_named_pipe_multiple_concurrent_connections() {
 
             // Send response
             let response = DaemonResponse::Pong;
-            mcp_cli_rs::daemon::protocol::send_response(&mut buf_reader, &response)
-                .await
-                .expect("Failed to send response");
+            crate::helpers::send_response_frame(&mut buf_reader, id, response).await;
         }
     });
 
@@ -160,15 +156,11 @@ async fn test_windows_named_pipe_large_message_transfer() {
 
         // Read request - wrap in BufReader for AsyncBufRead requirement
         let mut buf_reader = tokio::io::BufReader::new(server_instance);
-        let _request = mcp_cli_rs::daemon::protocol::receive_request(&mut buf_reader)
-            .await
-            .expect("Failed to receive request");
+        let (id, _request) = crate::helpers::receive_first_request(&mut buf_reader).await;
 
         // Send large response
         let response = DaemonResponse::ToolResult(server_content);
-        mcp_cli_rs::daemon::protocol::send_response(&mut buf_reader, &response)
-            .await
-            .expect("Failed to send response");
+        crate::helpers::send_response_frame(&mut buf_reader, id, response).await;
     });
 
     // Give server time to create the named pipe before client connects
@@ -228,15 +220,11 @@ async fn test_windows_named_pipe_security_flags() {
 
         // Read request and send response (proper protocol handling)
         let mut buf_reader = tokio::io::BufReader::new(server_instance);
-        let _request = mcp_cli_rs::daemon::protocol::receive_request(&mut buf_reader)
-            .await
-            .expect("Failed to receive request");
+        let (id, _request) = crate::helpers::receive_first_request(&mut buf_reader).await;
 
         // Send response back to client
         let response = DaemonResponse::Pong;
-        mcp_cli_rs::daemon::protocol::send_response(&mut buf_reader, &response)
-            .await
-            .expect("Failed to send response");
+        crate::helpers::send_response_frame(&mut buf_reader, id, response).await;
     });
 
     // Give server time to create the named pipe before client connects